@@ -7,9 +7,9 @@ use plotters::{
     series::LineSeries,
     style::{IntoFont, WHITE},
 };
+use rowifi_core::repos::AnalyticsRepo;
 use rowifi_framework::{arguments::ArgumentError, prelude::*};
 use rowifi_models::{
-    analytics::AnalyticsGroup,
     discord::{
         application::interaction::application_command::{CommandDataOption, CommandOptionValue},
         http::{
@@ -57,8 +57,8 @@ pub async fn analytics_view_func(
     args: ViewArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, registered_groups FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::Kind, GuildColumn::RegisteredGroups],
             ctx.guild_id,
         )
         .await?;
@@ -86,14 +86,9 @@ pub async fn analytics_view_func(
         .unwrap_or_else(|| ViewDuration(Duration::from_secs(60 * 60 * 24 * 7)))
         .0;
     let start_time = Utc::now() - duration;
-    let mut group_data = bot
-        .database
-        .query::<AnalyticsGroup>(
-            "SELECT * FROM group_analytics WHERE group_id = $1 and timestamp > $2",
-            &[&args.group_id, &start_time],
-        )
+    let group_data = AnalyticsRepo::new(&bot.database)
+        .group_history(args.group_id, start_time)
         .await?;
-    group_data.sort_unstable_by_key(|k| k.timestamp);
 
     if group_data.len() <= 2 {
         let message = "There is not enough usable data for the given timeframe. Please give the bot 24 hours to collect enough data or use another timeframe";
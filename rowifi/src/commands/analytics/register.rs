@@ -1,3 +1,4 @@
+use rowifi_core::repos::GuildRepo;
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     discord::{
@@ -35,8 +36,12 @@ pub async fn analytics_register_func(
     args: RegisterArguments,
 ) -> CommandResult {
     let mut guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, registered_groups FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[
+                GuildColumn::Kind,
+                GuildColumn::RegisteredGroups,
+                GuildColumn::ConfigVersion,
+            ],
             ctx.guild_id,
         )
         .await?;
@@ -49,12 +54,12 @@ pub async fn analytics_register_func(
 
     if !guild.registered_groups.contains(&args.group_id) {
         guild.registered_groups.push(args.group_id);
-        bot.database
-            .execute(
-                "UPDATE guilds SET registered_groups = $2 WHERE guild_id = $1",
-                &[&ctx.guild_id, &guild.registered_groups],
-            )
+        let updated = GuildRepo::new(&bot.database)
+            .set_registered_groups(ctx.guild_id, &guild.registered_groups, guild.config_version)
             .await?;
+        if !updated {
+            return Err(RoError::conflict());
+        }
     }
 
     let embed = EmbedBuilder::new()
@@ -90,8 +95,12 @@ pub async fn analytics_unregister_func(
     args: RegisterArguments,
 ) -> CommandResult {
     let mut guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, registered_groups FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[
+                GuildColumn::Kind,
+                GuildColumn::RegisteredGroups,
+                GuildColumn::ConfigVersion,
+            ],
             ctx.guild_id,
         )
         .await?;
@@ -108,12 +117,12 @@ pub async fn analytics_unregister_func(
         .position(|g| *g == args.group_id);
     if let Some(position) = position {
         guild.registered_groups.remove(position);
-        bot.database
-            .execute(
-                "UPDATE guilds SET registered_groups = $2 WHERE guild_id = $1",
-                &[&ctx.guild_id, &guild.registered_groups],
-            )
+        let updated = GuildRepo::new(&bot.database)
+            .set_registered_groups(ctx.guild_id, &guild.registered_groups, guild.config_version)
             .await?;
+        if !updated {
+            return Err(RoError::conflict());
+        }
     }
 
     let embed = EmbedBuilder::new()
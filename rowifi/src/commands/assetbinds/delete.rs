@@ -1,4 +1,4 @@
-use rowifi_core::assetbinds::delete::delete_assetbinds;
+use rowifi_core::assetbinds::{delete::delete_assetbinds, guild_assetbinds};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     discord::{
@@ -36,15 +36,17 @@ pub async fn delete_assetbind_func(
     args: AssetbindRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, assetbinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let assetbinds = guild_assetbinds(&bot.database, ctx.guild_id).await?;
 
     let res = delete_assetbinds(
         &bot.database,
-        &guild.assetbinds,
+        &assetbinds,
+        guild.config_version,
         ctx.guild_id,
         ctx.author_id,
         vec![AssetId(args.asset_id)],
@@ -2,12 +2,13 @@ mod delete;
 mod new;
 
 use itertools::Itertools;
+use rowifi_core::assetbinds::{asset_infos, guild_assetbinds};
 use rowifi_framework::{prelude::*, utils::paginate_embeds};
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
     util::Timestamp,
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use twilight_mention::Mention;
 use twilight_standby::Standby;
 
@@ -37,16 +38,18 @@ pub async fn view_assetbinds_func(
     standby: Arc<Standby>,
     ctx: &CommandContext,
 ) -> CommandResult {
-    let guild = bot
-        .get_guild(
-            "SELECT guild_id, assetbinds FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
-        .await?;
+    let assetbinds = guild_assetbinds(&bot.database, ctx.guild_id).await?;
 
     let mut pages = Vec::new();
     let mut page_count = 0usize;
-    let assetbinds = guild.assetbinds;
+    // The binds are still worth showing without the names and icons if Roblox is having issues.
+    let infos = match asset_infos(&bot.roblox, &assetbinds).await {
+        Ok(infos) => infos,
+        Err(err) => {
+            tracing::warn!("failed to look up the assetbind items: {}", err);
+            HashMap::new()
+        }
+    };
     for abs in &assetbinds.into_iter().chunks(12) {
         let mut embed = EmbedBuilder::new()
             .color(DARK_GREEN)
@@ -55,8 +58,12 @@ pub async fn view_assetbinds_func(
             .title("Assetbinds")
             .description(format!("Page {}", page_count + 1));
         for ab in abs {
-            let name = format!("ID: {}", ab.asset_id);
-            let desc = format!(
+            let info = infos.get(&ab.asset_id);
+            let name = match info {
+                Some(info) => format!("{} ({})", info.name, ab.asset_id),
+                None => format!("ID: {}", ab.asset_id),
+            };
+            let mut desc = format!(
                 "Type: `{}`\nTemplate: `{}`\nPriority: {}\n Roles: {}",
                 ab.asset_type,
                 ab.template,
@@ -66,6 +73,9 @@ pub async fn view_assetbinds_func(
                     .map(|r| r.0.mention().to_string())
                     .collect::<String>()
             );
+            if let Some(icon_url) = info.and_then(|i| i.icon_url.as_ref()) {
+                desc.push_str(&format!("\n[Icon]({icon_url})"));
+            }
             embed = embed.field(EmbedFieldBuilder::new(name, desc).inline().build());
         }
         pages.push(embed.build());
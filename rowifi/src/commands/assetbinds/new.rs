@@ -1,7 +1,10 @@
-use rowifi_core::assetbinds::add::{add_assetbind, AddAssetbindError, AssetbindArguments};
+use rowifi_core::assetbinds::{
+    add::{add_assetbind, AddAssetbindError, AssetbindArguments},
+    guild_assetbinds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
-    bind::{AssetType, Template},
+    bind::{AssetType, Assetbind, Template},
     discord::{
         http::interaction::{InteractionResponse, InteractionResponseType},
         util::Timestamp,
@@ -45,11 +48,12 @@ pub async fn new_assetbind_func(
 ) -> CommandResult {
     tracing::debug!("assetbinds new invoked");
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, assetbinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let assetbinds = guild_assetbinds(&bot.database, ctx.guild_id).await?;
     let server = bot.server(ctx.guild_id).await?;
     let server_roles = bot
         .cache
@@ -60,10 +64,12 @@ pub async fn new_assetbind_func(
         .collect::<HashMap<_, _>>();
 
     let res = match add_assetbind(
+        &bot.roblox,
         &bot.database,
         ctx.guild_id,
         ctx.author_id,
-        guild.assetbinds,
+        &assetbinds,
+        guild.config_version,
         &server_roles,
         AssetbindArguments {
             kind: args.option,
@@ -77,24 +83,22 @@ pub async fn new_assetbind_func(
     {
         Ok(res) => res,
         Err(AddAssetbindError::Generic(err)) => return Err(err),
+        Err(err) => {
+            let message = invalid_asset_message(&err, args.option, args.asset_id);
+            ctx.respond(bot).content(&message).unwrap().await?;
+            return Ok(());
+        }
     };
 
     let mut description: String = String::new();
     if res.modified {
         description.push_str(":warning: Bind already exists. Modified it to:\n\n");
     }
-    description.push_str(&format!("**Asset Id: {}**\n", res.bind.asset_id));
     description.push_str(&format!(
-        "Type: {}\nTemplate: {}\nPriority: {}\n Roles: {}",
-        res.bind.asset_type,
-        res.bind.template,
-        res.bind.priority,
-        res.bind
-            .discord_roles
-            .iter()
-            .map(|r| r.0.mention().to_string())
-            .collect::<String>()
+        "**{}** (ID: {})\n",
+        res.info.name, res.bind.asset_id
     ));
+    description.push_str(&bind_details(&res.bind));
 
     if !res.ignored_roles.is_empty() {
         let ignored_roles_str = res
@@ -105,13 +109,18 @@ pub async fn new_assetbind_func(
         description.push_str(&format!("\n\n🚫 Invalid Roles: {ignored_roles_str}"));
     }
 
-    let embed = EmbedBuilder::new()
+    let mut embed = EmbedBuilder::new()
         .color(DARK_GREEN)
         .footer(EmbedFooterBuilder::new("RoWifi").build())
         .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
         .title("Bind Addition Successful")
-        .description(description)
-        .build();
+        .description(description);
+    if let Some(icon_url) = &res.info.icon_url {
+        if let Ok(thumbnail) = ImageSource::url(icon_url) {
+            embed = embed.thumbnail(thumbnail);
+        }
+    }
+    let embed = embed.build();
     ctx.respond(bot).embeds(&[embed]).unwrap().await?;
 
     if let Some(log_channel) = guild.log_channel {
@@ -122,18 +131,8 @@ pub async fn new_assetbind_func(
             .title(format!("Action by <@{}>", ctx.author_id))
             .description("Assetbind Added")
             .field(EmbedFieldBuilder::new(
-                format!("**Asset Id: {}**\n", res.bind.asset_id),
-                format!(
-                    "Type: {}\nTemplate: {}\nPriority: {}\n Roles: {}",
-                    res.bind.asset_type,
-                    res.bind.template,
-                    res.bind.priority,
-                    res.bind
-                        .discord_roles
-                        .iter()
-                        .map(|r| r.0.mention().to_string())
-                        .collect::<String>()
-                ),
+                format!("**{}** (ID: {})\n", res.info.name, res.bind.asset_id),
+                bind_details(&res.bind),
             ))
             .build();
         let _ = bot
@@ -145,3 +144,27 @@ pub async fn new_assetbind_func(
 
     Ok(())
 }
+
+fn bind_details(bind: &Assetbind) -> String {
+    format!(
+        "Type: {}\nTemplate: {}\nPriority: {}\n Roles: {}",
+        bind.asset_type,
+        bind.template,
+        bind.priority,
+        bind.discord_roles
+            .iter()
+            .map(|r| r.0.mention().to_string())
+            .collect::<String>()
+    )
+}
+
+fn invalid_asset_message(err: &AddAssetbindError, kind: AssetType, asset_id: u64) -> String {
+    let kind = kind.to_string().to_lowercase();
+    match err {
+        AddAssetbindError::WrongType { actual } => format!(
+            "Oh no! {asset_id} is the ID of a {}, not a {kind}. Please create the bind with the `{actual}` option instead.",
+            actual.to_string().to_lowercase()
+        ),
+        _ => format!("Oh no! There is no {kind} with the ID {asset_id}."),
+    }
+}
@@ -1,7 +1,7 @@
-use rowifi_database::postgres::types::ToSql;
+use rowifi_core::repos::{AuditLogFilter, AuditLogRepo};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
-    audit_log::{AuditLog, AuditLogData, AuditLogKind},
+    audit_log::{AuditLogData, AuditLogKind},
     discord::{
         http::interaction::{InteractionResponse, InteractionResponseType},
         util::Timestamp,
@@ -39,35 +39,14 @@ pub async fn audit_logs_func(
     ctx: &CommandContext,
     args: AuditLogArguments,
 ) -> CommandResult {
-    let items = args.page.saturating_sub(1) * 100;
-    let (statement, params): (_, Vec<&(dyn ToSql + Sync)>) = match (&args.user, &args.action) {
-        (Some(user), Some(action)) => (
-            format!(
-                "SELECT * FROM audit_logs WHERE guild_id = $1 AND user_id = $2 AND kind = $3 ORDER BY timestamp LIMIT 100 OFFSET {items}"
-            ),
-            vec![&ctx.guild_id, user, action]
-        ),
-        (Some(user), None) => (
-            format!(
-                "SELECT * FROM audit_logs WHERE guild_id = $1 AND user_id = $2 ORDER BY timestamp LIMIT 100 OFFSET {items}"
-            ),
-            vec![&ctx.guild_id, user]
-        ),
-        (None, Some(action)) => (
-            format!(
-                "SELECT * FROM audit_logs WHERE guild_id = $1 AND action = $2 ORDER BY timestamp LIMIT 100 OFFSET {items}"
-            ),
-            vec![&ctx.guild_id, action]
-        ),
-        (None, None) => (
-            format!(
-                "SELECT * FROM audit_logs WHERE guild_id = $1 ORDER BY timestamp LIMIT 100 OFFSET {items}"
-            ),
-            vec![&ctx.guild_id]
-        )
+    let filter = AuditLogFilter {
+        user_id: args.user,
+        kind: args.action,
     };
-
-    let audit_logs = bot.database.query::<AuditLog>(&statement, &params).await?;
+    let offset = i64::from(args.page.saturating_sub(1)) * 100;
+    let audit_logs = AuditLogRepo::new(&bot.database)
+        .list(ctx.guild_id, filter, 100, offset)
+        .await?;
 
     let user_ids = audit_logs.iter().filter_map(|a| a.user_id);
     let members = bot
@@ -90,6 +69,8 @@ pub async fn audit_logs_func(
         AuditLogData::XPRemove(xp) => Some(xp.target_roblox_user),
         AuditLogData::XPSet(xp) => Some(xp.target_roblox_user),
         AuditLogData::SetRank(setrank) => Some(setrank.target_roblox_user),
+        AuditLogData::GameBan(ban) => Some(ban.target_roblox_user),
+        AuditLogData::GameUnban(unban) => Some(unban.target_roblox_user),
         _ => None,
     });
     let roblox_users = bot
@@ -231,6 +212,24 @@ pub async fn audit_logs_func(
                     user, command.name
                 ));
             }
+            AuditLogData::GameBan(ban) => {
+                let target_user = roblox_users
+                    .get(&ban.target_roblox_user)
+                    .map_or_else(|| ban.target_roblox_user.to_string(), |u| u.name.clone());
+                description.push_str(&format!(
+                    "- {} banned {} from {}. Reason: {}",
+                    user, target_user, ban.universe_id, ban.reason
+                ));
+            }
+            AuditLogData::GameUnban(unban) => {
+                let target_user = roblox_users
+                    .get(&unban.target_roblox_user)
+                    .map_or_else(|| unban.target_roblox_user.to_string(), |u| u.name.clone());
+                description.push_str(&format!(
+                    "- {} unbanned {} from {}",
+                    user, target_user, unban.universe_id
+                ));
+            }
         }
         description.push('\n');
     }
@@ -7,16 +7,13 @@ pub use new::backup_new;
 pub use restore::backup_restore;
 
 use itertools::Itertools;
+use rowifi_core::repos::BackupRepo;
 use rowifi_framework::prelude::*;
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
     util::Timestamp,
 };
 
-pub struct BackupRow {
-    pub name: String,
-}
-
 pub async fn backup_view(bot: Extension<BotContext>, command: Command<()>) -> impl IntoResponse {
     tokio::spawn(async move {
         if let Err(err) = backup_view_func(&bot, &command.ctx).await {
@@ -31,13 +28,7 @@ pub async fn backup_view(bot: Extension<BotContext>, command: Command<()>) -> im
 }
 
 pub async fn backup_view_func(bot: &BotContext, ctx: &CommandContext) -> CommandResult {
-    let backups = bot
-        .database
-        .query::<BackupRow>(
-            "SELECT name FROM backups WHERE user_id = $1",
-            &[&ctx.author_id],
-        )
-        .await?;
+    let backups = BackupRepo::new(&bot.database).names(ctx.author_id).await?;
 
     let embed = EmbedBuilder::new()
         .color(BLUE)
@@ -48,20 +39,10 @@ pub async fn backup_view_func(bot: &BotContext, ctx: &CommandContext) -> Command
             backups
                 .into_iter()
                 .enumerate()
-                .map(|(i, b)| format!("{}: {}", i + 1, b.name))
+                .map(|(i, b)| format!("{}: {}", i + 1, b))
                 .join("\n"),
         );
 
     ctx.respond(bot).embeds(&[embed.build()]).unwrap().await?;
     Ok(())
 }
-
-impl TryFrom<rowifi_database::postgres::Row> for BackupRow {
-    type Error = rowifi_database::postgres::Error;
-
-    fn try_from(row: rowifi_database::postgres::Row) -> Result<Self, Self::Error> {
-        let name = row.try_get("name")?;
-
-        Ok(Self { name })
-    }
-}
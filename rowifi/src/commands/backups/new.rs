@@ -1,4 +1,7 @@
-use rowifi_core::backups::new::{create_backup, BackupArguments};
+use rowifi_core::{
+    backups::new::{create_backup, BackupArguments},
+    guild::load_binds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::discord::http::interaction::{InteractionResponse, InteractionResponseType};
 
@@ -28,14 +31,14 @@ pub async fn backup_new_func(
     ctx: &CommandContext,
     args: BackupRouteArguments,
 ) -> CommandResult {
-    let guild = bot
-        .get_guild("SELECT * FROM guilds WHERE guild_id = $1", ctx.guild_id)
-        .await?;
+    let guild = bot.get_guild_config(GuildColumn::ALL, ctx.guild_id).await?;
+    let binds = load_binds(&bot.database, guild.guild_id).await?;
 
     create_backup(
         &bot.database,
         &bot.cache,
         guild,
+        binds,
         BackupArguments {
             name: args.name.clone(),
             author: ctx.author_id,
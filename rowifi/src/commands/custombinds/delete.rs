@@ -1,4 +1,7 @@
-use rowifi_core::custombinds::delete::{delete_custombinds, CustombindArguments};
+use rowifi_core::custombinds::{
+    delete::{delete_custombinds, CustombindArguments},
+    guild_custombinds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
@@ -33,15 +36,17 @@ pub async fn delete_custombind_func(
     args: CustombindRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, custombinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let custombinds = guild_custombinds(&bot.database, ctx.guild_id).await?;
 
     let res = delete_custombinds(
         &bot.database,
-        &guild.custombinds,
+        &custombinds,
+        guild.config_version,
         ctx.guild_id,
         ctx.author_id,
         vec![CustombindArguments {
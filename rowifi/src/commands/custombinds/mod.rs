@@ -2,6 +2,7 @@ mod delete;
 mod new;
 
 use itertools::Itertools;
+use rowifi_core::custombinds::guild_custombinds;
 use rowifi_framework::{prelude::*, utils::paginate_embeds};
 use std::sync::Arc;
 
@@ -37,16 +38,10 @@ pub async fn view_custombinds_func(
     standby: Arc<Standby>,
     ctx: &CommandContext,
 ) -> CommandResult {
-    let guild = bot
-        .get_guild(
-            "SELECT guild_id, custombinds FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
-        .await?;
+    let custombinds = guild_custombinds(&bot.database, ctx.guild_id).await?;
 
     let mut pages = Vec::new();
     let mut page_count = 0usize;
-    let custombinds = guild.custombinds;
     for cbs in &custombinds.into_iter().chunks(12) {
         let mut embed = EmbedBuilder::new()
             .color(DARK_GREEN)
@@ -1,4 +1,7 @@
-use rowifi_core::custombinds::add::{add_custombind, AddCustombindError, CustombindArguments};
+use rowifi_core::custombinds::{
+    add::{add_custombind, AddCustombindError, CustombindArguments},
+    guild_custombinds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     bind::Template,
@@ -42,11 +45,12 @@ async fn new_custombind_func(
     args: CustombindRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, custombinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let custombinds = guild_custombinds(&bot.database, ctx.guild_id).await?;
     let server = bot.server(ctx.guild_id).await?;
 
     let server_roles = bot
@@ -61,7 +65,8 @@ async fn new_custombind_func(
         &bot.database,
         ctx.guild_id,
         ctx.author_id,
-        &guild.custombinds,
+        &custombinds,
+        guild.config_version,
         &server_roles,
         CustombindArguments {
             code: args.code,
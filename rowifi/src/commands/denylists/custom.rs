@@ -1,4 +1,7 @@
-use rowifi_core::denylists::add::{add_denylist, AddDenylistError, DenylistArguments};
+use rowifi_core::denylists::{
+    add::{add_denylist, AddDenylistError, DenylistArguments},
+    guild_deny_lists,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     deny_list::{DenyListActionType, DenyListType},
@@ -38,11 +41,12 @@ pub async fn add_custom_denylist_func(
     args: DenylistRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, deny_lists, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let deny_lists = guild_deny_lists(&bot.database, ctx.guild_id).await?;
 
     let reason = args.reason.unwrap_or_else(|| "N/A".into());
 
@@ -50,7 +54,8 @@ pub async fn add_custom_denylist_func(
         &bot.database,
         ctx.guild_id,
         ctx.author_id,
-        guild.deny_lists,
+        &deny_lists,
+        guild.config_version,
         DenylistArguments {
             kind: DenyListType::Custom,
             action: args.action,
@@ -1,4 +1,4 @@
-use rowifi_core::denylists::delete::delete_denylists;
+use rowifi_core::denylists::{delete::delete_denylists, guild_deny_lists};
 use rowifi_framework::prelude::*;
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
@@ -33,15 +33,17 @@ pub async fn delete_denylist_func(
     args: DenylistRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, deny_lists, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let deny_lists = guild_deny_lists(&bot.database, ctx.guild_id).await?;
 
     let res = delete_denylists(
         &bot.database,
-        &guild.deny_lists,
+        &deny_lists,
+        guild.config_version,
         guild.guild_id,
         ctx.author_id,
         vec![args.id],
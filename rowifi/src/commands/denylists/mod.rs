@@ -4,6 +4,7 @@ mod group;
 mod user;
 
 use itertools::Itertools;
+use rowifi_core::denylists::guild_deny_lists;
 use rowifi_framework::{prelude::*, utils::paginate_embeds};
 use rowifi_models::{
     deny_list::DenyListData,
@@ -43,16 +44,10 @@ pub async fn view_denylists_func(
     standby: Arc<Standby>,
     ctx: &CommandContext,
 ) -> CommandResult {
-    let guild = bot
-        .get_guild(
-            "SELECT guild_id, deny_lists FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
-        .await?;
+    let denylists = guild_deny_lists(&bot.database, ctx.guild_id).await?;
 
     let mut pages = Vec::new();
     let mut page_count = 0usize;
-    let denylists = guild.deny_lists;
     for denylist_chunk in &denylists.into_iter().chunks(12) {
         let mut embed = EmbedBuilder::new()
             .color(DARK_GREEN)
@@ -1,4 +1,7 @@
-use rowifi_core::events::new::{log_event, EventLogArguments, EventLogError};
+use rowifi_core::{
+    events::new::{log_event, EventLogArguments, EventLogError},
+    repos::UserRepo,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     discord::{
@@ -6,7 +9,6 @@ use rowifi_models::{
         util::Timestamp,
     },
     guild::GuildType,
-    user::RoUser,
 };
 
 #[derive(Arguments, Debug)]
@@ -38,8 +40,12 @@ pub async fn new_event_func(
     args: EventArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, event_types, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[
+                GuildColumn::Kind,
+                GuildColumn::EventTypes,
+                GuildColumn::LogChannel,
+            ],
             ctx.guild_id,
         )
         .await?;
@@ -52,14 +58,7 @@ pub async fn new_event_func(
         return Ok(());
     }
 
-    let Some(user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&ctx.author_id],
-        )
-        .await?
-    else {
+    let Some(user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
         let message = "Only verified users may log events.";
         ctx.respond(bot).content(message).unwrap().await?;
         return Ok(());
@@ -37,10 +37,7 @@ pub async fn view_event_types_func(
     ctx: &CommandContext,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, event_types FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
+        .get_guild_config(&[GuildColumn::Kind, GuildColumn::EventTypes], ctx.guild_id)
         .await?;
 
     // Check for Gamma Tier
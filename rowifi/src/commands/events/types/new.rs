@@ -36,8 +36,12 @@ pub async fn new_event_type_func(
     args: AddEventTypeArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, event_types FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[
+                GuildColumn::Kind,
+                GuildColumn::EventTypes,
+                GuildColumn::ConfigVersion,
+            ],
             ctx.guild_id,
         )
         .await?;
@@ -55,6 +59,7 @@ pub async fn new_event_type_func(
         ctx.guild_id,
         ctx.author_id,
         guild.event_types,
+        guild.config_version,
         EventTypeArguments {
             id: args.id,
             name: args.name,
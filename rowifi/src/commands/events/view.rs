@@ -1,17 +1,16 @@
 use std::sync::Arc;
 
 use itertools::Itertools;
+use rowifi_core::repos::{EventRepo, UserRepo};
 use rowifi_framework::{prelude::*, utils::paginate_embeds};
 use rowifi_models::{
     discord::{
         http::interaction::{InteractionResponse, InteractionResponseType},
         util::Timestamp,
     },
-    events::EventLog,
     guild::GuildType,
-    user::RoUser,
+    roblox::thumbnail::{AvatarThumbnailKind, ThumbnailOptions},
 };
-use rowifi_roblox::error::ErrorKind;
 use twilight_standby::Standby;
 
 #[derive(Arguments, Debug)]
@@ -84,10 +83,7 @@ pub async fn view_attendee_events_func(
     args: EventViewArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, event_types FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
+        .get_guild_config(&[GuildColumn::Kind, GuildColumn::EventTypes], ctx.guild_id)
         .await?;
 
     // Check for Gamma Tier
@@ -114,14 +110,7 @@ pub async fn view_attendee_events_func(
             return Ok(());
         }
     } else {
-        let Some(user) = bot
-            .database
-            .query_opt::<RoUser>(
-                "SELECT * FROM roblox_users WHERE user_id = $1",
-                &[&ctx.author_id],
-            )
-            .await?
-        else {
+        let Some(user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
             let message = "Only verified users may log events.";
             ctx.respond(bot).content(message).unwrap().await?;
             return Ok(());
@@ -132,12 +121,13 @@ pub async fn view_attendee_events_func(
             .unwrap_or(user.default_account_id)
     };
 
-    let events = bot
-        .database
-        .query::<EventLog>(
-            "SELECT * FROM events WHERE guild_id = $1 AND $2 = ANY(attendees)",
-            &[&ctx.guild_id, &roblox_id],
-        )
+    let events = EventRepo::new(&bot.database)
+        .attended_by(ctx.guild_id, roblox_id)
+        .await?;
+
+    let hosts = bot
+        .roblox
+        .resolve_users(events.iter().map(|e| e.host_id), None)
         .await?;
 
     let mut pages = Vec::new();
@@ -160,29 +150,12 @@ pub async fn view_attendee_events_func(
                 .iter()
                 .find(|e| e.id == event.event_type as u32)
                 .unwrap();
-            let host = match bot.roblox.get_user(event.host_id).await {
-                Ok(u) => Some(u),
-                Err(err) => {
-                    if let ErrorKind::Response {
-                        route: _,
-                        status,
-                        bytes: _,
-                    } = err.kind()
-                    {
-                        if status.as_u16() == 404 {
-                            None
-                        } else {
-                            return Err(err.into());
-                        }
-                    } else {
-                        return Err(err.into());
-                    }
-                }
-            };
             let desc = format!(
                 "Event Type: {}\nHost: {}\nTimestamp: <t:{}:f>",
                 event_type.name,
-                host.map(|h| h.name).unwrap_or(event.host_id.to_string()),
+                hosts
+                    .get(&event.host_id)
+                    .map_or_else(|| event.host_id.to_string(), |h| h.user.name.clone()),
                 event.timestamp.timestamp()
             );
 
@@ -213,10 +186,7 @@ pub async fn view_host_events_func(
     args: EventViewArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, event_types FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
+        .get_guild_config(&[GuildColumn::Kind, GuildColumn::EventTypes], ctx.guild_id)
         .await?;
 
     // Check for Gamma Tier
@@ -243,14 +213,7 @@ pub async fn view_host_events_func(
             return Ok(());
         }
     } else {
-        let Some(user) = bot
-            .database
-            .query_opt::<RoUser>(
-                "SELECT * FROM roblox_users WHERE user_id = $1",
-                &[&ctx.author_id],
-            )
-            .await?
-        else {
+        let Some(user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
             let message = "Only verified users may log events.";
             ctx.respond(bot).content(message).unwrap().await?;
             return Ok(());
@@ -261,12 +224,8 @@ pub async fn view_host_events_func(
             .unwrap_or(user.default_account_id)
     };
 
-    let events = bot
-        .database
-        .query::<EventLog>(
-            "SELECT * FROM events WHERE guild_id = $1 AND host_id = $2",
-            &[&ctx.guild_id, &roblox_id],
-        )
+    let events = EventRepo::new(&bot.database)
+        .hosted_by(ctx.guild_id, roblox_id)
         .await?;
 
     let mut pages = Vec::new();
@@ -275,17 +234,8 @@ pub async fn view_host_events_func(
     let host = match bot.roblox.get_user(roblox_id).await {
         Ok(u) => Some(u),
         Err(err) => {
-            if let ErrorKind::Response {
-                route: _,
-                status,
-                bytes: _,
-            } = err.kind()
-            {
-                if status.as_u16() == 404 {
-                    None
-                } else {
-                    return Err(err.into());
-                }
+            if err.is_not_found() {
+                None
             } else {
                 return Err(err.into());
             }
@@ -342,10 +292,7 @@ pub async fn view_event_func(
     args: EventViewIdArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, kind, event_types FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
+        .get_guild_config(&[GuildColumn::Kind, GuildColumn::EventTypes], ctx.guild_id)
         .await?;
 
     // Check for Gamma Tier
@@ -357,12 +304,8 @@ pub async fn view_event_func(
     }
 
     #[allow(clippy::cast_possible_wrap)]
-    let Some(event) = bot
-        .database
-        .query_opt::<EventLog>(
-            "SELECT * FROM events WHERE guild_id = $1 AND guild_event_id = $2",
-            &[&ctx.guild_id, &(args.event_id as i64)],
-        )
+    let Some(event) = EventRepo::new(&bot.database)
+        .get(ctx.guild_id, args.event_id as i64)
         .await?
     else {
         let message = format!("There is no event with the ID {}", args.event_id);
@@ -376,48 +319,23 @@ pub async fn view_event_func(
         .iter()
         .find(|e| e.id == event.event_type as u32)
         .unwrap();
-    let host = match bot.roblox.get_user(event.host_id).await {
-        Ok(u) => Some(u),
-        Err(err) => {
-            if let ErrorKind::Response {
-                route: _,
-                status,
-                bytes: _,
-            } = err.kind()
-            {
-                if status.as_u16() == 404 {
-                    None
-                } else {
-                    return Err(err.into());
-                }
-            } else {
-                return Err(err.into());
-            }
-        }
-    };
-    let mut attendees = Vec::new();
-    for attendee in event.attendees {
-        let user = match bot.roblox.get_user(attendee).await {
-            Ok(u) => Some(u),
-            Err(err) => {
-                if let ErrorKind::Response {
-                    route: _,
-                    status,
-                    bytes: _,
-                } = err.kind()
-                {
-                    if status.as_u16() == 404 {
-                        None
-                    } else {
-                        return Err(err.into());
-                    }
-                } else {
-                    return Err(err.into());
-                }
-            }
-        };
-        attendees.push(user.map_or_else(|| attendee.to_string(), |u| u.name));
-    }
+    let users = bot
+        .roblox
+        .resolve_users(
+            std::iter::once(event.host_id).chain(event.attendees.iter().copied()),
+            Some((AvatarThumbnailKind::Headshot, ThumbnailOptions::default())),
+        )
+        .await?;
+    let host = users.get(&event.host_id);
+    let attendees = event
+        .attendees
+        .iter()
+        .map(|a| {
+            users
+                .get(a)
+                .map_or_else(|| a.to_string(), |u| u.user.name.clone())
+        })
+        .collect::<Vec<_>>();
 
     let mut embed = EmbedBuilder::new()
         .color(DARK_GREEN)
@@ -429,10 +347,16 @@ pub async fn view_event_func(
         ))
         .field(EmbedFieldBuilder::new(
             "Host",
-            host.map_or_else(|| event.host_id.to_string(), |h| h.name),
+            host.map_or_else(|| event.host_id.to_string(), |h| h.user.name.clone()),
         ))
         .timestamp(Timestamp::from_secs(event.timestamp.timestamp()).unwrap());
 
+    if let Some(thumbnail) = host.and_then(|h| h.thumbnail.as_deref()) {
+        if let Ok(thumbnail) = ImageSource::url(thumbnail) {
+            embed = embed.thumbnail(thumbnail);
+        }
+    }
+
     if attendees.is_empty() {
         embed = embed.field(EmbedFieldBuilder::new("Attendees", "None"));
     } else {
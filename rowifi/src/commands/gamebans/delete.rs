@@ -0,0 +1,107 @@
+use rowifi_core::gamebans::delete::delete_gameban as delete_gameban_op;
+use rowifi_framework::prelude::*;
+use rowifi_models::discord::{
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    util::Timestamp,
+};
+
+use super::NO_LINKED_UNIVERSE;
+
+#[derive(Arguments, Debug)]
+pub struct GamebanRouteArguments {
+    pub username: String,
+}
+
+pub async fn delete_gameban(
+    bot: Extension<BotContext>,
+    command: Command<GamebanRouteArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = delete_gameban_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all, fields(args = ?args))]
+pub async fn delete_gameban_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: GamebanRouteArguments,
+) -> CommandResult {
+    let guild = bot
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::LinkedUniverse],
+            ctx.guild_id,
+        )
+        .await?;
+    let Some(universe_id) = guild.linked_universe else {
+        ctx.respond(bot)
+            .content(NO_LINKED_UNIVERSE)
+            .unwrap()
+            .await?;
+        return Ok(());
+    };
+
+    let Some(user) = bot
+        .roblox
+        .get_users_from_usernames([args.username.as_str()].into_iter())
+        .await?
+        .into_iter()
+        .next()
+    else {
+        let message = format!(
+            r"
+Oh no! A user with the name `{}` does not exist.
+        ",
+            args.username
+        );
+        ctx.respond(bot).content(&message).unwrap().await?;
+        return Ok(());
+    };
+
+    delete_gameban_op(
+        &bot.roblox,
+        &bot.database,
+        ctx.guild_id,
+        ctx.author_id,
+        universe_id,
+        user.id,
+    )
+    .await?;
+
+    let name = format!("User: {}", user.name);
+    let desc = format!("User Id: {}\nUniverse Id: {}", user.id, universe_id);
+
+    let embed = EmbedBuilder::new()
+        .color(DARK_GREEN)
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title("Game Unban Successful")
+        .field(EmbedFieldBuilder::new(&name, &desc))
+        .build();
+    ctx.respond(bot).embeds(&[embed])?.await?;
+
+    if let Some(log_channel) = guild.log_channel {
+        let embed = EmbedBuilder::new()
+            .color(BLUE)
+            .footer(EmbedFooterBuilder::new("RoWifi").build())
+            .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+            .title(format!("Action by <@{}>", ctx.author_id))
+            .description("Game Ban Removed")
+            .field(EmbedFieldBuilder::new(&name, &desc))
+            .build();
+        let _ = bot
+            .http
+            .create_message(log_channel.0)
+            .embeds(&[embed])
+            .await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+mod delete;
+mod new;
+mod universe;
+
+use futures_util::{StreamExt, TryStreamExt};
+use itertools::Itertools;
+use rowifi_framework::{prelude::*, utils::paginate_embeds};
+use rowifi_models::{
+    discord::{
+        http::interaction::{InteractionResponse, InteractionResponseType},
+        util::Timestamp,
+    },
+    roblox::user_restriction::UserRestriction,
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use twilight_standby::Standby;
+
+pub use delete::delete_gameban;
+pub use new::new_gameban;
+pub use universe::link_universe;
+
+/// The most bans shown by `/gameban view`.
+const MAX_VIEWED_BANS: usize = 120;
+
+pub async fn view_gamebans(
+    bot: Extension<BotContext>,
+    standby: Extension<Arc<Standby>>,
+    command: Command<()>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = view_gamebans_func(&bot, standby.0, &command.ctx).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn view_gamebans_func(
+    bot: &BotContext,
+    standby: Arc<Standby>,
+    ctx: &CommandContext,
+) -> CommandResult {
+    let guild = bot
+        .get_guild_config(&[GuildColumn::LinkedUniverse], ctx.guild_id)
+        .await?;
+    let Some(universe_id) = guild.linked_universe else {
+        ctx.respond(bot)
+            .content(NO_LINKED_UNIVERSE)
+            .unwrap()
+            .await?;
+        return Ok(());
+    };
+
+    let restrictions = bot
+        .roblox
+        .stream_user_restrictions(universe_id, None, 100)
+        .try_filter(|r| std::future::ready(r.game_join_restriction.active))
+        .take(MAX_VIEWED_BANS)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let users = bot
+        .roblox
+        .get_users(restrictions.iter().map(UserRestriction::user_id))
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect::<HashMap<_, _>>();
+
+    let mut pages = Vec::new();
+    let mut page_count = 0usize;
+    for chunk in &restrictions.into_iter().chunks(12) {
+        let mut embed = EmbedBuilder::new()
+            .color(DARK_GREEN)
+            .footer(EmbedFooterBuilder::new("RoWifi").build())
+            .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+            .title("Game Bans")
+            .description(format!("Page {}", page_count + 1));
+        for restriction in chunk {
+            let user_id = restriction.user_id();
+            let name = users
+                .get(&user_id)
+                .map_or_else(|| user_id.to_string(), |u| u.name.clone());
+            let ban = &restriction.game_join_restriction;
+            let duration = ban
+                .duration_secs()
+                .map_or_else(|| "Permanent".to_string(), format_duration);
+            let desc = format!(
+                "User Id: {}\nReason: {}\nDuration: {}",
+                user_id, ban.private_reason, duration
+            );
+            embed = embed.field(EmbedFieldBuilder::new(name, desc).inline().build());
+        }
+        pages.push(embed.build());
+        page_count += 1;
+    }
+
+    paginate_embeds(
+        ctx,
+        bot,
+        &standby,
+        pages,
+        page_count,
+        "There is no one banned from the linked universe.",
+    )
+    .await?;
+
+    Ok(())
+}
+
+const NO_LINKED_UNIVERSE: &str = r"
+This server does not have a linked universe. Link one with `/gameban universe` first.
+";
+
+/// Parses durations such as `30m`, `12h`, `7d` or `2w`.
+fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let unit = input.chars().last()?;
+    let amount = input[..input.len() - unit.len_utf8()].parse::<u64>().ok()?;
+    let seconds = match unit.to_ascii_lowercase() {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    amount.checked_mul(seconds).map(Duration::from_secs)
+}
+
+fn format_duration(seconds: u64) -> String {
+    match seconds {
+        s if s % (24 * 60 * 60) == 0 => format!("{} day(s)", s / (24 * 60 * 60)),
+        s if s % (60 * 60) == 0 => format!("{} hour(s)", s / (60 * 60)),
+        s if s % 60 == 0 => format!("{} minute(s)", s / 60),
+        s => format!("{s} second(s)"),
+    }
+}
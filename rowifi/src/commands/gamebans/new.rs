@@ -0,0 +1,132 @@
+use rowifi_core::gamebans::add::{add_gameban, GamebanArguments};
+use rowifi_framework::prelude::*;
+use rowifi_models::discord::{
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    util::Timestamp,
+};
+
+use super::{format_duration, parse_duration, NO_LINKED_UNIVERSE};
+
+#[derive(Arguments, Debug)]
+pub struct GamebanRouteArguments {
+    pub username: String,
+    pub reason: Option<String>,
+    pub duration: Option<String>,
+}
+
+pub async fn new_gameban(
+    bot: Extension<BotContext>,
+    command: Command<GamebanRouteArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = new_gameban_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all, fields(args = ?args))]
+pub async fn new_gameban_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: GamebanRouteArguments,
+) -> CommandResult {
+    let guild = bot
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::LinkedUniverse],
+            ctx.guild_id,
+        )
+        .await?;
+    let Some(universe_id) = guild.linked_universe else {
+        ctx.respond(bot)
+            .content(NO_LINKED_UNIVERSE)
+            .unwrap()
+            .await?;
+        return Ok(());
+    };
+
+    let duration = match args.duration.as_deref().map(parse_duration) {
+        Some(Some(duration)) => Some(duration),
+        Some(None) => {
+            let message = r"
+Oops, the duration must be a number followed by `m`, `h`, `d` or `w`. For example, `7d` bans the user for 7 days.
+            ";
+            ctx.respond(bot).content(message).unwrap().await?;
+            return Ok(());
+        }
+        None => None,
+    };
+
+    let Some(user) = bot
+        .roblox
+        .get_users_from_usernames([args.username.as_str()].into_iter())
+        .await?
+        .into_iter()
+        .next()
+    else {
+        let message = format!(
+            r"
+Oh no! A user with the name `{}` does not exist.
+        ",
+            args.username
+        );
+        ctx.respond(bot).content(&message).unwrap().await?;
+        return Ok(());
+    };
+
+    let reason = args.reason.unwrap_or_else(|| "N/A".into());
+    add_gameban(
+        &bot.roblox,
+        &bot.database,
+        ctx.guild_id,
+        Some(ctx.author_id),
+        universe_id,
+        GamebanArguments {
+            user_id: user.id,
+            reason: reason.clone(),
+            duration,
+        },
+    )
+    .await?;
+
+    let name = format!("User: {}", user.name);
+    let desc = format!(
+        "User Id: {}\nUniverse Id: {}\nReason: {}\nDuration: {}",
+        user.id,
+        universe_id,
+        reason,
+        duration.map_or_else(|| "Permanent".into(), |d| format_duration(d.as_secs()))
+    );
+
+    let embed = EmbedBuilder::new()
+        .color(DARK_GREEN)
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title("Game Ban Successful")
+        .field(EmbedFieldBuilder::new(&name, &desc))
+        .build();
+    ctx.respond(bot).embeds(&[embed])?.await?;
+
+    if let Some(log_channel) = guild.log_channel {
+        let embed = EmbedBuilder::new()
+            .color(BLUE)
+            .footer(EmbedFooterBuilder::new("RoWifi").build())
+            .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+            .title(format!("Action by <@{}>", ctx.author_id))
+            .description("Game Ban Added")
+            .field(EmbedFieldBuilder::new(&name, &desc))
+            .build();
+        let _ = bot
+            .http
+            .create_message(log_channel.0)
+            .embeds(&[embed])
+            .await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use rowifi_core::gamebans::universe::{link_universe as link_universe_op, LinkUniverseError};
+use rowifi_framework::prelude::*;
+use rowifi_models::{
+    discord::{
+        http::interaction::{InteractionResponse, InteractionResponseType},
+        util::Timestamp,
+    },
+    roblox::id::UniverseId,
+};
+
+#[derive(Arguments, Debug)]
+pub struct LinkUniverseArguments {
+    pub universe_id: Option<u64>,
+}
+
+pub async fn link_universe(
+    bot: Extension<BotContext>,
+    command: Command<LinkUniverseArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = link_universe_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all, fields(args = ?args))]
+pub async fn link_universe_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: LinkUniverseArguments,
+) -> CommandResult {
+    let universe_id = args.universe_id.map(UniverseId);
+    let universe = match link_universe_op(
+        &bot.roblox,
+        &bot.database,
+        ctx.guild_id,
+        ctx.author_id,
+        universe_id,
+    )
+    .await
+    {
+        Ok(universe) => universe,
+        Err(LinkUniverseError::InvalidUniverse) => {
+            let message = format!(
+                r"
+Oh no! The universe with the id `{}` does not exist or cannot be accessed by RoWifi.
+            ",
+                args.universe_id.unwrap_or_default()
+            );
+            ctx.respond(bot).content(&message).unwrap().await?;
+            return Ok(());
+        }
+        Err(LinkUniverseError::Generic(err)) => return Err(err),
+    };
+
+    let description = match (universe_id, universe) {
+        (Some(universe_id), Some(universe)) => format!(
+            "Denylists with the ban action and `/gameban` now apply to **{}** ({universe_id}).",
+            universe.display_name
+        ),
+        _ => "This server no longer has a linked universe.".to_string(),
+    };
+    let embed = EmbedBuilder::new()
+        .color(DARK_GREEN)
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title("Settings Modification Successful")
+        .description(description)
+        .build();
+    ctx.respond(bot).embeds(&[embed])?.await?;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use rowifi_core::groupbinds::delete::delete_groupbinds;
+use rowifi_core::groupbinds::{delete::delete_groupbinds, guild_groupbinds};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     discord::{
@@ -36,15 +36,17 @@ pub async fn delete_groupbind_func(
     args: GroupbindRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, groupbinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let groupbinds = guild_groupbinds(&bot.database, ctx.guild_id).await?;
 
     let res = delete_groupbinds(
         &bot.database,
-        &guild.groupbinds,
+        &groupbinds,
+        guild.config_version,
         ctx.guild_id,
         ctx.author_id,
         vec![GroupId(args.group_id)],
@@ -2,6 +2,7 @@ mod delete;
 mod new;
 
 use itertools::Itertools;
+use rowifi_core::groupbinds::guild_groupbinds;
 use rowifi_framework::{prelude::*, utils::paginate_embeds};
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
@@ -37,16 +38,10 @@ pub async fn view_groupbinds_func(
     standby: Arc<Standby>,
     ctx: &CommandContext,
 ) -> CommandResult {
-    let guild = bot
-        .get_guild(
-            "SELECT guild_id, groupbinds FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
-        .await?;
+    let groupbinds = guild_groupbinds(&bot.database, ctx.guild_id).await?;
 
     let mut pages = Vec::new();
     let mut page_count = 0usize;
-    let groupbinds = guild.groupbinds;
     for gbs in &groupbinds.into_iter().chunks(12) {
         let mut embed = EmbedBuilder::new()
             .color(DARK_GREEN)
@@ -1,4 +1,7 @@
-use rowifi_core::groupbinds::add::{add_groupbind, AddGroupbindError, GroupbindArguments};
+use rowifi_core::groupbinds::{
+    add::{add_groupbind, AddGroupbindError, GroupbindArguments},
+    guild_groupbinds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     bind::Template,
@@ -43,11 +46,12 @@ pub async fn new_groupbind_func(
     args: GroupbindRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, groupbinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let groupbinds = guild_groupbinds(&bot.database, ctx.guild_id).await?;
     let server = bot.server(ctx.guild_id).await?;
     let server_roles = bot
         .cache
@@ -62,7 +66,8 @@ pub async fn new_groupbind_func(
         &bot.database,
         ctx.guild_id,
         ctx.author_id,
-        guild.groupbinds,
+        &groupbinds,
+        guild.config_version,
         &server_roles,
         GroupbindArguments {
             group_id: GroupId(args.group_id),
@@ -5,7 +5,9 @@ pub mod backups;
 pub mod custombinds;
 pub mod denylists;
 pub mod events;
+pub mod gamebans;
 pub mod groupbinds;
 pub mod rankbinds;
 pub mod server;
+pub mod staff;
 pub mod user;
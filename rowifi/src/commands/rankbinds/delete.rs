@@ -1,4 +1,7 @@
-use rowifi_core::rankbinds::delete::{delete_rankbinds, RankbindArguments};
+use rowifi_core::rankbinds::{
+    delete::{delete_rankbinds, RankbindArguments},
+    guild_rankbinds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     discord::{
@@ -37,15 +40,14 @@ pub async fn delete_rankbind_func(
     args: RankbindRouteArguments,
 ) -> CommandResult {
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, rankbinds FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
+        .get_guild_config(&[GuildColumn::ConfigVersion], ctx.guild_id)
         .await?;
+    let rankbinds = guild_rankbinds(&bot.database, ctx.guild_id).await?;
 
     let res = delete_rankbinds(
         &bot.database,
-        &guild.rankbinds,
+        &rankbinds,
+        guild.config_version,
         ctx.guild_id,
         ctx.author_id,
         vec![RankbindArguments {
@@ -2,6 +2,7 @@ mod delete;
 mod new;
 
 use itertools::Itertools;
+use rowifi_core::rankbinds::guild_rankbinds;
 use rowifi_framework::{prelude::*, utils::paginate_embeds};
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
@@ -37,16 +38,10 @@ pub async fn view_rankbinds_func(
     standby: Extension<Arc<Standby>>,
     ctx: &CommandContext,
 ) -> CommandResult {
-    let guild = bot
-        .get_guild(
-            "SELECT guild_id, rankbinds FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
-        .await?;
+    let rankbinds = guild_rankbinds(&bot.database, ctx.guild_id).await?;
 
     let mut pages = Vec::new();
     let mut page_count = 0usize;
-    let rankbinds = guild.rankbinds;
     for group in &rankbinds
         .into_iter()
         .sorted_by_key(|r| r.group_id)
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use rowifi_core::rankbinds::add::{add_rankbind, AddRankbindError, RankbindArguments};
+use rowifi_core::rankbinds::{
+    add::{add_rankbind, AddRankbindError, RankbindArguments},
+    guild_rankbinds,
+};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     bind::Template,
@@ -46,11 +49,12 @@ async fn new_rankbind_func(
 ) -> CommandResult {
     tracing::debug!("rankbinds new invoked");
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, rankbinds, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
             ctx.guild_id,
         )
         .await?;
+    let rankbinds = guild_rankbinds(&bot.database, ctx.guild_id).await?;
     let server = bot.server(ctx.guild_id).await?;
 
     let server_roles = bot
@@ -66,7 +70,8 @@ async fn new_rankbind_func(
         &bot.database,
         ctx.guild_id,
         ctx.author_id,
-        guild.rankbinds,
+        &rankbinds,
+        guild.config_version,
         &server_roles,
         RankbindArguments {
             group_id: GroupId(args.group_id),
@@ -0,0 +1,104 @@
+use itertools::Itertools;
+use rowifi_core::api_keys::set::{set_api_key, SetApiKeyError, REQUIRED_SCOPES};
+use rowifi_framework::prelude::*;
+use rowifi_models::discord::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
+    util::Timestamp,
+};
+
+#[derive(Arguments)]
+pub struct ApiKeyArguments {
+    pub api_key: Option<String>,
+}
+
+pub async fn api_key(
+    bot: Extension<BotContext>,
+    command: Command<ApiKeyArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = api_key_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    // The reply is ephemeral since the key is sensitive.
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: Some(InteractionResponseData {
+            flags: Some(MessageFlags::EPHEMERAL),
+            ..Default::default()
+        }),
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn api_key_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: ApiKeyArguments,
+) -> CommandResult {
+    let Some(cipher) = &bot.api_keys else {
+        let message = r"
+Registering API keys is not enabled on this instance of RoWifi. Workflows and the datastore commands use RoWifi's API key.
+        ";
+        ctx.respond(bot).content(message).unwrap().await?;
+        return Ok(());
+    };
+
+    let info = match set_api_key(
+        &bot.roblox,
+        &bot.database,
+        cipher,
+        ctx.guild_id,
+        ctx.author_id,
+        args.api_key.as_deref(),
+    )
+    .await
+    {
+        Ok(info) => info,
+        Err(SetApiKeyError::InvalidKey) => {
+            let message = r"
+Oh no! Roblox did not accept this API key. Please make sure that it is enabled and has not expired.
+            ";
+            ctx.respond(bot).content(message).unwrap().await?;
+            return Ok(());
+        }
+        Err(SetApiKeyError::MissingScopes(missing)) => {
+            let message = format!(
+                r"
+Oh no! This API key is missing the following permissions:
+{}
+
+RoWifi needs {} to run workflows and the datastore commands.
+            ",
+                missing.iter().map(|s| format!("- `{s}`")).join("\n"),
+                REQUIRED_SCOPES
+                    .iter()
+                    .map(|(scope, operation)| format!("`{scope}:{operation}`"))
+                    .join(", ")
+            );
+            ctx.respond(bot).content(&message).unwrap().await?;
+            return Ok(());
+        }
+        Err(SetApiKeyError::Generic(err)) => return Err(err),
+    };
+
+    let description = match info {
+        Some(info) => format!(
+            "Workflows and the datastore commands now use the API key **{}**.",
+            info.name
+        ),
+        None => "Workflows and the datastore commands now use RoWifi's API key.".to_string(),
+    };
+    let embed = EmbedBuilder::new()
+        .color(DARK_GREEN)
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title("Settings Modification Successful")
+        .description(description)
+        .build();
+    ctx.respond(bot).embeds(&[embed])?.await?;
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use rowifi_core::{
+    channels::InvalidChannel,
+    log_channel::set::{set_log_channel, SetLogChannelError},
+};
+use rowifi_framework::prelude::*;
+use rowifi_models::{
+    discord::{
+        http::interaction::{InteractionResponse, InteractionResponseType},
+        util::Timestamp,
+    },
+    id::{ChannelId, UserId},
+};
+
+#[derive(Arguments, Debug)]
+pub struct LogChannelArguments {
+    pub channel: Option<ChannelId>,
+}
+
+pub async fn log_channel(
+    bot: Extension<BotContext>,
+    command: Command<LogChannelArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = log_channel_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all, fields(args = ?args))]
+pub async fn log_channel_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: LogChannelArguments,
+) -> CommandResult {
+    // Makes sure the bot's own member is in the cache so that its permissions can be computed.
+    let bot_id = UserId::new(bot.application_id.get());
+    bot.member(ctx.guild_id, bot_id).await?;
+
+    match set_log_channel(
+        &bot.cache,
+        &bot.database,
+        ctx.guild_id,
+        ctx.author_id,
+        bot_id,
+        args.channel,
+    )
+    .await
+    {
+        Ok(()) => {}
+        Err(SetLogChannelError::InvalidChannel(reason)) => {
+            let message = match reason {
+                InvalidChannel::NotFound => "Oh no! This channel does not exist in this server.",
+                InvalidChannel::NotMessageable => {
+                    "Oh no! Messages cannot be sent in categories, forums or locked threads. Please pick another channel."
+                }
+                InvalidChannel::MissingPermissions => {
+                    "Oh no! RoWifi needs the `View Channel` and `Send Messages` permissions in this channel to send logs to it."
+                }
+            };
+            ctx.respond(bot).content(message).unwrap().await?;
+            return Ok(());
+        }
+        Err(SetLogChannelError::Generic(err)) => return Err(err),
+    }
+
+    let description = match args.channel {
+        Some(channel) => format!("Logs will now be sent to <#{channel}>."),
+        None => "Logs will no longer be sent to a channel.".to_string(),
+    };
+    let embed = EmbedBuilder::new()
+        .color(DARK_GREEN)
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title("Settings Modification Successful")
+        .description(description)
+        .build();
+    ctx.respond(bot).embeds(&[embed])?.await?;
+
+    Ok(())
+}
@@ -1,5 +1,9 @@
+mod api_key;
+mod log_channel;
 mod mass_update;
 mod serverinfo;
 
+pub use api_key::api_key;
+pub use log_channel::log_channel;
 pub use mass_update::{update_all, update_role};
 pub use serverinfo::serverinfo;
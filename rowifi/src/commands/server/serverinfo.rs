@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use rowifi_core::guild::load_binds;
 use rowifi_framework::prelude::*;
 use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
@@ -20,9 +21,8 @@ pub async fn serverinfo(bot: Extension<BotContext>, command: Command<()>) -> imp
 }
 
 pub async fn serverinfo_func(bot: &BotContext, ctx: &CommandContext) -> CommandResult {
-    let guild = bot
-        .get_guild("SELECT * FROM guilds WHERE guild_id = $1", ctx.guild_id)
-        .await?;
+    let guild = bot.get_guild_config(GuildColumn::ALL, ctx.guild_id).await?;
+    let binds = load_binds(&bot.database, guild.guild_id).await?;
     let server = bot.server(ctx.guild_id).await?;
 
     let unverified_roles = if guild.unverified_roles.is_empty() {
@@ -44,10 +44,11 @@ pub async fn serverinfo_func(bot: &BotContext, ctx: &CommandContext) -> CommandR
             .join(" ")
     };
     let settings = format!(
-        "**Auto Detection**: {}\n**Sync XP on `/setrank`**: {}\n**Update On Join**: {}",
+        "**Auto Detection**: {}\n**Sync XP on `/setrank`**: {}\n**Update On Join**: {}\n**Linked Universe**: {}",
         guild.auto_detection.unwrap_or_default(),
         guild.sync_xp_on_setrank.unwrap_or_default(),
-        guild.update_on_join.unwrap_or_default()
+        guild.update_on_join.unwrap_or_default(),
+        guild.linked_universe.map_or_else(|| "None".into(), |u| u.to_string())
     );
 
     let embed = EmbedBuilder::new()
@@ -56,15 +57,16 @@ pub async fn serverinfo_func(bot: &BotContext, ctx: &CommandContext) -> CommandR
         .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
         .title(server.name)
         .field(EmbedFieldBuilder::new("Guild Id", ctx.guild_id.to_string()).inline())
+        .field(EmbedFieldBuilder::new("Members", server.member_count.to_string()).inline())
         .field(
             EmbedFieldBuilder::new("Tier", format!("{}", guild.kind.unwrap_or_default())).inline(),
         )
         .field(EmbedFieldBuilder::new("Unverified Roles", unverified_roles).inline())
         .field(EmbedFieldBuilder::new("Verified Roles", verified_roles).inline())
-        .field(EmbedFieldBuilder::new("Rankbinds", guild.rankbinds.len().to_string()).inline())
-        .field(EmbedFieldBuilder::new("Groupbinds", guild.groupbinds.len().to_string()).inline())
-        .field(EmbedFieldBuilder::new("Custombinds", guild.custombinds.len().to_string()).inline())
-        .field(EmbedFieldBuilder::new("Assetbinds", guild.assetbinds.len().to_string()).inline())
+        .field(EmbedFieldBuilder::new("Rankbinds", binds.rankbinds.len().to_string()).inline())
+        .field(EmbedFieldBuilder::new("Groupbinds", binds.groupbinds.len().to_string()).inline())
+        .field(EmbedFieldBuilder::new("Custombinds", binds.custombinds.len().to_string()).inline())
+        .field(EmbedFieldBuilder::new("Assetbinds", binds.assetbinds.len().to_string()).inline())
         .field(EmbedFieldBuilder::new("XP Binds", guild.xp_binds.len().to_string()).inline())
         .field(
             EmbedFieldBuilder::new("Bypass Roles", guild.bypass_roles.len().to_string()).inline(),
@@ -0,0 +1,245 @@
+use itertools::Itertools;
+use rowifi_core::cache_repair::{fetch_guild, fetch_member, guild_drift, member_drift, Drift};
+use rowifi_framework::prelude::*;
+use rowifi_models::{
+    discord::{
+        http::interaction::{InteractionResponse, InteractionResponseType},
+        util::Timestamp,
+    },
+    id::{GuildId, UserId},
+};
+
+use super::is_staff;
+
+/// Embed field values are capped at 1024 characters.
+const FIELD_LIMIT: usize = 1000;
+
+#[derive(Arguments, Debug)]
+pub struct CacheGuildArguments {
+    pub guild: GuildId,
+    pub reseed: Option<bool>,
+}
+
+#[derive(Arguments, Debug)]
+pub struct CacheMemberArguments {
+    pub guild: GuildId,
+    pub user: UserId,
+    pub reseed: Option<bool>,
+}
+
+pub async fn staff_cache_guild(
+    bot: Extension<BotContext>,
+    command: Command<CacheGuildArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = staff_cache_guild_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all, fields(args = ?args))]
+pub async fn staff_cache_guild_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: CacheGuildArguments,
+) -> CommandResult {
+    if !is_staff(bot, ctx.author_id).await? {
+        return not_staff(bot, ctx).await;
+    }
+
+    let Some(live) = fetch_guild(&bot.http, args.guild).await? else {
+        let message = format!("RoWifi is not in the server with the id {}.", args.guild);
+        ctx.respond(bot).content(&message).unwrap().await?;
+        return Ok(());
+    };
+
+    let cached = bot.cache.guild(args.guild).await?;
+    let cached_roles = match &cached {
+        Some(cached) => {
+            let mut roles = bot.cache.guild_roles(cached.roles.iter().copied()).await?;
+            roles.sort_by_key(|r| std::cmp::Reverse(r.position));
+            roles
+        }
+        None => Vec::new(),
+    };
+    let drift = guild_drift(cached.as_ref(), &cached_roles, &live);
+
+    let guild_info = match &cached {
+        Some(cached) => format!(
+            "**Name:** {}\n**Owner:** <@{}>\n**Members:** {}\n**Roles:** {}\n**Channels:** {}",
+            cached.name,
+            cached.owner_id,
+            cached.member_count,
+            cached.roles.len(),
+            cached.channels.len()
+        ),
+        None => "Not cached".into(),
+    };
+    let roles = truncate_lines(cached_roles.iter().map(|r| {
+        format!(
+            "{} - `{}` - `{}`",
+            r.name.trim_start_matches('@'),
+            r.id,
+            r.permissions.bits()
+        )
+    }));
+    let mut channels = Vec::new();
+    if let Some(cached) = &cached {
+        for channel_id in cached.channels.iter().sorted() {
+            let line = match bot.cache.channel(*channel_id).await? {
+                Some(channel) => format!("<#{channel_id}> - {:?}", channel.kind()),
+                None => format!("`{channel_id}` - not cached"),
+            };
+            channels.push(line);
+        }
+    }
+    let channels = truncate_lines(channels.into_iter());
+
+    let mut embed = EmbedBuilder::new()
+        .color(if drift.is_empty() { DARK_GREEN } else { RED })
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title(format!("Cached Server {}", args.guild))
+        .field(EmbedFieldBuilder::new("Server", guild_info))
+        .field(EmbedFieldBuilder::new("Roles", roles))
+        .field(EmbedFieldBuilder::new("Channels", channels))
+        .field(EmbedFieldBuilder::new("Drift", drift_lines(&drift)));
+    if args.reseed.unwrap_or_default() {
+        bot.cache.cache_guild(live).await?;
+        embed = embed.field(EmbedFieldBuilder::new(
+            "Re-seeded",
+            "The server, its roles and channels were cached again from Discord.",
+        ));
+    }
+    ctx.respond(bot).embeds(&[embed.build()])?.await?;
+
+    Ok(())
+}
+
+pub async fn staff_cache_member(
+    bot: Extension<BotContext>,
+    command: Command<CacheMemberArguments>,
+) -> impl IntoResponse {
+    tokio::spawn(async move {
+        if let Err(err) = staff_cache_member_func(&bot, &command.ctx, command.args).await {
+            handle_error(bot.0, command.ctx, err).await;
+        }
+    });
+
+    Json(InteractionResponse {
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+        data: None,
+    })
+}
+
+#[tracing::instrument(skip_all, fields(args = ?args))]
+pub async fn staff_cache_member_func(
+    bot: &BotContext,
+    ctx: &CommandContext,
+    args: CacheMemberArguments,
+) -> CommandResult {
+    if !is_staff(bot, ctx.author_id).await? {
+        return not_staff(bot, ctx).await;
+    }
+
+    let Some(live) = fetch_member(&bot.http, args.guild, args.user).await? else {
+        let message = format!(
+            "<@{}> is not a member of the server with the id {}.",
+            args.user, args.guild
+        );
+        ctx.respond(bot).content(&message).unwrap().await?;
+        return Ok(());
+    };
+
+    let cached = bot.cache.guild_member(args.guild, args.user).await?;
+    let cached_user = bot.cache.user(args.user).await?;
+    let drift = member_drift(cached.as_ref(), &live);
+
+    let member_info = match (&cached, &cached_user) {
+        (Some(member), user) => format!(
+            "**Username:** {}\n**Nickname:** {}\n**Roles:** {}",
+            user.as_ref().map_or("Not cached", |u| u.username.as_str()),
+            member.nickname.as_deref().unwrap_or("None"),
+            if member.roles.is_empty() {
+                "None".into()
+            } else {
+                member.roles.iter().map(|r| format!("<@&{r}>")).join(" ")
+            }
+        ),
+        (None, _) => "Not cached".into(),
+    };
+
+    let mut embed = EmbedBuilder::new()
+        .color(if drift.is_empty() { DARK_GREEN } else { RED })
+        .footer(EmbedFooterBuilder::new("RoWifi").build())
+        .timestamp(Timestamp::from_secs(Utc::now().timestamp()).unwrap())
+        .title(format!("Cached Member {} in {}", args.user, args.guild))
+        .field(EmbedFieldBuilder::new("Member", truncate(member_info)))
+        .field(EmbedFieldBuilder::new("Drift", drift_lines(&drift)));
+    if args.reseed.unwrap_or_default() {
+        bot.cache.cache_member(args.guild, &live).await?;
+        embed = embed.field(EmbedFieldBuilder::new(
+            "Re-seeded",
+            "The member was cached again from Discord.",
+        ));
+    }
+    ctx.respond(bot).embeds(&[embed.build()])?.await?;
+
+    Ok(())
+}
+
+async fn not_staff(bot: &BotContext, ctx: &CommandContext) -> CommandResult {
+    ctx.respond(bot)
+        .content("This command is only available to RoWifi staff.")
+        .unwrap()
+        .await?;
+    Ok(())
+}
+
+fn drift_lines(drift: &[Drift]) -> String {
+    if drift.is_empty() {
+        "The cache matches Discord.".into()
+    } else {
+        truncate_lines(drift.iter().map(ToString::to_string))
+    }
+}
+
+/// Joins the lines, leaving out the ones that don't fit in an embed field.
+fn truncate_lines(lines: impl ExactSizeIterator<Item = String>) -> String {
+    let total = lines.len();
+    if total == 0 {
+        return "None".into();
+    }
+
+    let mut value = String::new();
+    let mut shown = 0;
+    for line in lines {
+        if value.len() + line.len() + 1 > FIELD_LIMIT {
+            break;
+        }
+        value.push_str(&line);
+        value.push('\n');
+        shown += 1;
+    }
+    if shown < total {
+        value.push_str(&format!("...and {} more", total - shown));
+    }
+    value
+}
+
+fn truncate(mut value: String) -> String {
+    if value.len() > FIELD_LIMIT {
+        let mut end = FIELD_LIMIT;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+    }
+    value
+}
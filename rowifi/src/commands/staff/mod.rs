@@ -0,0 +1,13 @@
+mod cache;
+
+use rowifi_core::repos::UserRepo;
+use rowifi_framework::prelude::*;
+use rowifi_models::{id::UserId, user::UserFlags};
+
+pub use cache::{staff_cache_guild, staff_cache_member};
+
+/// Whether the user is flagged as RoWifi staff in the database.
+async fn is_staff(bot: &BotContext, user_id: UserId) -> Result<bool, RoError> {
+    let user = UserRepo::new(&bot.database).get(user_id).await?;
+    Ok(user.is_some_and(|u| u.flags.contains(UserFlags::STAFF)))
+}
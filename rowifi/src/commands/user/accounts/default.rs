@@ -1,8 +1,6 @@
+use rowifi_core::repos::UserRepo;
 use rowifi_framework::prelude::*;
-use rowifi_models::{
-    discord::http::interaction::{InteractionResponse, InteractionResponseType},
-    user::RoUser,
-};
+use rowifi_models::discord::http::interaction::{InteractionResponse, InteractionResponseType};
 
 #[derive(Arguments, Debug)]
 pub struct AccountRouteArguments {
@@ -31,14 +29,7 @@ pub async fn account_default_func(
     ctx: &CommandContext,
     args: AccountRouteArguments,
 ) -> CommandResult {
-    let Some(mut user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&ctx.author_id],
-        )
-        .await?
-    else {
+    let Some(mut user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
         tracing::debug!("user is not in the database");
         let message = r"
 Hey there, it looks like you're not verified with us. Please run `/verify` to register with RoWifi.
@@ -78,11 +69,8 @@ Oh no! An account with the name `{}` does not seem to exist. Ensure you have spe
     user.other_accounts.retain(|a| *a != roblox_user.id);
     user.other_accounts.push(user.default_account_id);
 
-    bot.database
-        .execute(
-            "UPDATE roblox_users SET default_account_id = $2, other_accounts = $3 WHERE user_id = $1",
-            &[&ctx.author_id, &roblox_user.id, &user.other_accounts],
-        )
+    UserRepo::new(&bot.database)
+        .set_default_account(ctx.author_id, roblox_user.id, &user.other_accounts)
         .await?;
 
     let message = format!(
@@ -1,10 +1,6 @@
-use std::collections::HashMap;
-
+use rowifi_core::repos::UserRepo;
 use rowifi_framework::prelude::*;
-use rowifi_models::{
-    discord::http::interaction::{InteractionResponse, InteractionResponseType},
-    user::RoUser,
-};
+use rowifi_models::discord::http::interaction::{InteractionResponse, InteractionResponseType};
 
 #[derive(Arguments, Debug)]
 pub struct AccountRouteArguments {
@@ -33,14 +29,7 @@ pub async fn account_delete_func(
     ctx: &CommandContext,
     args: AccountRouteArguments,
 ) -> CommandResult {
-    let Some(mut user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&ctx.author_id],
-        )
-        .await?
-    else {
+    let Some(mut user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
         tracing::debug!("user is not in the database");
         let message = r"
 Hey there, it looks like you're not verified with us. Please run `/verify` to register with RoWifi.
@@ -88,16 +77,8 @@ You cannot delete your default linked account.
     user.other_accounts.retain(|a| *a != roblox_user.id);
     user.linked_accounts.retain(|_, v| *v != roblox_user.id);
 
-    let linked_accounts = user
-        .linked_accounts
-        .into_iter()
-        .map(|(k, v)| (k.to_string(), Some(v.0.to_string())))
-        .collect::<HashMap<_, _>>();
-    bot.database
-        .execute(
-            "UPDATE roblox_users SET linked_accounts = $2, other_accounts = $3 WHERE user_id = $1",
-            &[&ctx.author_id, &linked_accounts, &user.other_accounts],
-        )
+    UserRepo::new(&bot.database)
+        .set_accounts(ctx.author_id, &user.linked_accounts, &user.other_accounts)
         .await?;
 
     let message = format!(
@@ -2,18 +2,15 @@ mod default;
 mod delete;
 mod switch;
 
+use rowifi_core::repos::UserRepo;
 use rowifi_framework::prelude::*;
-use rowifi_models::{
-    discord::{
-        http::interaction::{InteractionResponse, InteractionResponseType},
-        util::Timestamp,
-    },
-    user::RoUser,
+use rowifi_models::discord::{
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    util::Timestamp,
 };
 
 pub use default::account_default;
 pub use delete::account_delete;
-use rowifi_roblox::error::ErrorKind;
 pub use switch::account_switch;
 
 pub async fn account_view(bot: Extension<BotContext>, command: Command<()>) -> impl IntoResponse {
@@ -31,14 +28,7 @@ pub async fn account_view(bot: Extension<BotContext>, command: Command<()>) -> i
 
 #[tracing::instrument(skip_all)]
 pub async fn account_view_func(bot: &BotContext, ctx: &CommandContext) -> CommandResult {
-    let Some(user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&ctx.author_id],
-        )
-        .await?
-    else {
+    let Some(user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
         tracing::debug!("user is not in the database");
         let message = r"
 Hey there, it looks like you're not verified with us. Please run `/verify` to register with RoWifi.
@@ -58,17 +48,8 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
     let main_user = match bot.roblox.get_user(user.default_account_id).await {
         Ok(u) => Some(u),
         Err(err) => {
-            if let ErrorKind::Response {
-                route: _,
-                status,
-                bytes: _,
-            } = err.kind()
-            {
-                if status.as_u16() == 404 {
-                    None
-                } else {
-                    return Err(err.into());
-                }
+            if err.is_not_found() {
+                None
             } else {
                 return Err(err.into());
             }
@@ -97,17 +78,8 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
         let alt_user = match bot.roblox.get_user(user.default_account_id).await {
             Ok(u) => Some(u),
             Err(err) => {
-                if let ErrorKind::Response {
-                    route: _,
-                    status,
-                    bytes: _,
-                } = err.kind()
-                {
-                    if status.as_u16() == 404 {
-                        None
-                    } else {
-                        return Err(err.into());
-                    }
+                if err.is_not_found() {
+                    None
                 } else {
                     return Err(err.into());
                 }
@@ -1,9 +1,6 @@
+use rowifi_core::repos::UserRepo;
 use rowifi_framework::prelude::*;
-use rowifi_models::{
-    discord::http::interaction::{InteractionResponse, InteractionResponseType},
-    user::RoUser,
-};
-use std::collections::HashMap;
+use rowifi_models::discord::http::interaction::{InteractionResponse, InteractionResponseType};
 
 #[derive(Arguments, Debug)]
 pub struct AccountRouteArguments {
@@ -32,14 +29,7 @@ pub async fn account_switch_func(
     ctx: &CommandContext,
     args: AccountRouteArguments,
 ) -> CommandResult {
-    let Some(user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&ctx.author_id],
-        )
-        .await?
-    else {
+    let Some(user) = UserRepo::new(&bot.database).get(ctx.author_id).await? else {
         tracing::debug!("user is not in the database");
         let message = r"
 Hey there, it looks like you're not verified with us. Please run `/verify` to register with RoWifi.
@@ -78,18 +68,9 @@ Oh no! An account with the name `{}` does not seem to exist. Ensure you have spe
 
     let guild = bot.server(ctx.guild_id).await?;
 
-    let mut map = HashMap::new();
-    map.insert(ctx.guild_id.to_string(), Some(roblox_user.id.0.to_string()));
-    bot.database
-        .execute(
-            "UPDATE roblox_users SET linked_accounts = linked_accounts || $2 WHERE user_id = $1",
-            &[&ctx.author_id, &map],
-        )
+    UserRepo::new(&bot.database)
+        .link_account(ctx.author_id, ctx.guild_id, roblox_user.id)
         .await?;
-    bot.database.execute(
-        "INSERT INTO linked_users(roblox_id, user_id, guild_id) VALUES($1, $2, $3) ON CONFLICT(guild_id, user_id) DO UPDATE SET roblox_id = $1", 
-        &[&roblox_user.id, &user.user_id, &ctx.guild_id]
-    ).await?;
 
     let message = format!(
         r"
@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use rowifi_core::{groupbinds::guild_groupbinds, rankbinds::guild_rankbinds, repos::UserRepo};
 use rowifi_framework::prelude::*;
 use rowifi_models::{
     discord::{
@@ -6,9 +7,8 @@ use rowifi_models::{
         util::Timestamp,
     },
     id::UserId,
-    user::{RoUser, UserFlags},
+    user::UserFlags,
 };
-use rowifi_roblox::error::ErrorKind;
 use std::collections::HashSet;
 
 #[derive(Arguments, Debug)]
@@ -53,18 +53,10 @@ pub async fn userinfo_func(
         ctx.respond(bot).content(&message).unwrap().await?;
         return Ok(());
     };
-    let guild = bot
-        .get_guild(
-            "SELECT guild_id, rankbinds, groupbinds FROM guilds WHERE guild_id = $1",
-            ctx.guild_id,
-        )
-        .await?;
+    let rankbinds = guild_rankbinds(&bot.database, ctx.guild_id).await?;
+    let groupbinds = guild_groupbinds(&bot.database, ctx.guild_id).await?;
 
-    let Some(database_user) = bot
-        .database
-        .query_opt::<RoUser>("SELECT * FROM roblox_users WHERE user_id = $1", &[&user_id])
-        .await?
-    else {
+    let Some(database_user) = UserRepo::new(&bot.database).get(user_id).await? else {
         tracing::debug!("user is not in the database");
         let message = if args.user.is_some() {
             format!(
@@ -89,17 +81,10 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
     let roblox_user = match bot.roblox.get_user(*roblox_id).await {
         Ok(u) => u,
         Err(err) => {
-            if let ErrorKind::Response {
-                route: _,
-                status,
-                bytes: _,
-            } = err.kind()
-            {
-                if status.as_u16() == 404 {
-                    let message = format!("Your selected Roblox account for this server is [this](https://www.roblox.com/users/{user_id}/profile). It seems that Roblox has banned or suspended this account. If this is not the case, please contact the RoWifi support server.");
-                    ctx.respond(bot).content(&message).unwrap().await?;
-                    return Ok(());
-                }
+            if err.is_not_found() {
+                let message = format!("Your selected Roblox account for this server is [this](https://www.roblox.com/users/{user_id}/profile). It seems that Roblox has banned or suspended this account. If this is not the case, please contact the RoWifi support server.");
+                ctx.respond(bot).content(&message).unwrap().await?;
+                return Ok(());
             }
             return Err(err.into());
         }
@@ -108,8 +93,8 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
     let thumbnail = bot.roblox.get_user_thumbnail(*roblox_id).await?;
 
     let mut group_ids = HashSet::new();
-    group_ids.extend(guild.rankbinds.iter().map(|r| r.group_id));
-    group_ids.extend(guild.groupbinds.iter().map(|g| g.group_id));
+    group_ids.extend(rankbinds.iter().map(|r| r.group_id));
+    group_ids.extend(groupbinds.iter().map(|g| g.group_id));
     let mut ranks_info = String::new();
     for rank in ranks {
         if group_ids.contains(&rank.group.id) {
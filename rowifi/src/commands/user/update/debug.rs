@@ -1,7 +1,11 @@
 use itertools::Itertools;
-use rowifi_core::custombinds::{
-    evaluate::{evaluate, EvaluationContext, EvaluationResult},
-    parser::parser,
+use rowifi_core::{
+    custombinds::{
+        evaluate::{evaluate, EvaluationContext, EvaluationResult},
+        parser::parser,
+    },
+    guild::load_binds,
+    repos::UserRepo,
 };
 use rowifi_framework::prelude::*;
 use rowifi_models::{
@@ -11,9 +15,8 @@ use rowifi_models::{
     guild::BypassRoleKind,
     id::{RoleId, UserId},
     roblox::inventory::InventoryItem,
-    user::RoUser,
 };
-use rowifi_roblox::{error::ErrorKind, filter::AssetFilterBuilder};
+use rowifi_roblox::filter::AssetFilterBuilder;
 use std::{
     collections::{HashMap, HashSet},
     fmt::Write,
@@ -77,14 +80,7 @@ pub async fn debug_update_func(
         return Ok(());
     };
 
-    let Some(user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&discord_member.id],
-        )
-        .await?
-    else {
+    let Some(user) = UserRepo::new(&bot.database).get(discord_member.id).await? else {
         let message = if args.user_id.is_some() {
             "This user is not verified with RoWifi."
         } else {
@@ -101,11 +97,18 @@ pub async fn debug_update_func(
     }
 
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, bypass_roles, unverified_roles, verified_roles, rankbinds, groupbinds, custombinds, assetbinds, deny_lists, default_template, sticky_roles FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[
+                GuildColumn::BypassRoles,
+                GuildColumn::UnverifiedRoles,
+                GuildColumn::VerifiedRoles,
+                GuildColumn::DefaultTemplate,
+                GuildColumn::StickyRoles,
+            ],
             server.id,
         )
         .await?;
+    let binds = load_binds(&bot.database, guild.guild_id).await?;
 
     let mut active_bypass_role = None;
     for bypass_role in &guild.bypass_roles {
@@ -121,25 +124,25 @@ pub async fn debug_update_func(
         success_checks.push(Checks::BypassRole);
     }
 
-    let mut all_roles = guild
+    let mut all_roles = binds
         .rankbinds
         .iter()
         .flat_map(|b| b.discord_roles.clone())
         .collect::<Vec<_>>();
     all_roles.extend(
-        guild
+        binds
             .groupbinds
             .iter()
             .flat_map(|b| b.discord_roles.clone()),
     );
     all_roles.extend(
-        guild
+        binds
             .custombinds
             .iter()
             .flat_map(|b| b.discord_roles.clone()),
     );
     all_roles.extend(
-        guild
+        binds
             .assetbinds
             .iter()
             .flat_map(|b| b.discord_roles.clone()),
@@ -164,17 +167,10 @@ pub async fn debug_update_func(
     let roblox_user = match bot.roblox.get_user(*user_id).await {
         Ok(u) => u,
         Err(err) => {
-            if let ErrorKind::Response {
-                route: _,
-                status,
-                bytes: _,
-            } = err.kind()
-            {
-                if status.as_u16() == 404 {
-                    let message = format!("Your selected Roblox account for this server is [this](https://www.roblox.com/users/{user_id}/profile). It seems that Roblox has banned or suspended this account. If this is not the case, please contact the RoWifi support server.");
-                    ctx.respond(bot).content(&message).unwrap().await?;
-                    return Ok(());
-                }
+            if err.is_not_found() {
+                let message = format!("Your selected Roblox account for this server is [this](https://www.roblox.com/users/{user_id}/profile). It seems that Roblox has banned or suspended this account. If this is not the case, please contact the RoWifi support server.");
+                ctx.respond(bot).content(&message).unwrap().await?;
+                return Ok(());
             }
             return Err(err.into());
         }
@@ -183,7 +179,7 @@ pub async fn debug_update_func(
     let mut active_deny_lists = Vec::new();
     let mut evaluation_failed = Vec::new();
     let mut parsing_failed = Vec::new();
-    for denylist in &guild.deny_lists {
+    for denylist in &binds.deny_lists {
         let success = match &denylist.data {
             DenyListData::User(u) => *u == roblox_user.id,
             DenyListData::Group(g) => user_ranks.contains_key(g),
@@ -234,7 +230,7 @@ pub async fn debug_update_func(
     }
 
     let mut asset_filter = AssetFilterBuilder::new();
-    for assetbind in &guild.assetbinds {
+    for assetbind in &binds.assetbinds {
         match assetbind.asset_type {
             AssetType::Asset => asset_filter = asset_filter.asset(assetbind.asset_id),
             AssetType::Badge => asset_filter = asset_filter.badge(assetbind.asset_id),
@@ -264,7 +260,7 @@ pub async fn debug_update_func(
         }
     }
 
-    for rankbind in &guild.rankbinds {
+    for rankbind in &binds.rankbinds {
         // Check if the user's rank in the group is the same as the rankbind
         // or check if the bind is for the Guest role and the user is not in
         // the group
@@ -289,7 +285,7 @@ pub async fn debug_update_func(
         }
     }
 
-    for groupbind in &guild.groupbinds {
+    for groupbind in &binds.groupbinds {
         if user_ranks.contains_key(&groupbind.group_id) {
             if let Some(ref highest) = nickname_bind {
                 if highest.priority() < groupbind.priority {
@@ -309,7 +305,7 @@ pub async fn debug_update_func(
 
     let mut custombind_evaluation_failed = Vec::new();
     let mut custombind_parsing_failed = Vec::new();
-    for custombind in &guild.custombinds {
+    for custombind in &binds.custombinds {
         let exp = match parser(&custombind.code) {
             Ok(e) => e,
             Err(err) => {
@@ -352,7 +348,7 @@ pub async fn debug_update_func(
         }
     }
 
-    for assetbind in &guild.assetbinds {
+    for assetbind in &binds.assetbinds {
         if inventory_items.contains(&assetbind.asset_id.0.to_string()) {
             if let Some(ref highest) = nickname_bind {
                 if highest.priority() < assetbind.priority {
@@ -2,7 +2,12 @@ mod debug;
 
 use futures_util::FutureExt;
 use itertools::Itertools;
-use rowifi_core::user::update::{UpdateUser, UpdateUserError};
+use rowifi_core::{
+    gamebans::add::{add_gameban, GamebanArguments},
+    guild::load_binds,
+    repos::UserRepo,
+    user::update::{UpdateUser, UpdateUserError},
+};
 use rowifi_framework::{prelude::*, Interaction};
 use rowifi_models::{
     deny_list::{DenyList, DenyListActionType},
@@ -12,7 +17,6 @@ use rowifi_models::{
     },
     guild::BypassRoleKind,
     id::UserId,
-    user::RoUser,
 };
 use std::{error::Error, fmt::Write};
 use twilight_http::error::{Error as DiscordHttpError, ErrorType as DiscordErrorType};
@@ -94,11 +98,20 @@ pub async fn update_func(
     }
 
     let guild = bot
-        .get_guild(
-            "SELECT guild_id, bypass_roles, unverified_roles, verified_roles, rankbinds, groupbinds, custombinds, assetbinds, deny_lists, default_template, sticky_roles, log_channel FROM guilds WHERE guild_id = $1",
+        .get_guild_config(
+            &[
+                GuildColumn::BypassRoles,
+                GuildColumn::UnverifiedRoles,
+                GuildColumn::VerifiedRoles,
+                GuildColumn::DefaultTemplate,
+                GuildColumn::StickyRoles,
+                GuildColumn::LogChannel,
+                GuildColumn::LinkedUniverse,
+            ],
             server.id,
         )
         .await?;
+    let binds = load_binds(&bot.database, guild.guild_id).await?;
     tracing::trace!(guild = ?guild);
 
     // Check if the user has a bypass role for both (roles & nickname)
@@ -120,14 +133,7 @@ You have a role (<@&{}>) which has been marked as a bypass role.
         }
     }
 
-    let Some(user) = bot
-        .database
-        .query_opt::<RoUser>(
-            "SELECT * FROM roblox_users WHERE user_id = $1",
-            &[&discord_member.id],
-        )
-        .await?
-    else {
+    let Some(user) = UserRepo::new(&bot.database).get(discord_member.id).await? else {
         tracing::debug!("user is not in the database");
         let message = if args.user_id.is_some() {
             format!(
@@ -147,25 +153,32 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
     };
     tracing::trace!(user = ?user);
 
-    let mut all_roles = guild
+    // An explicit update should always see the latest group ranks of the user.
+    let roblox_id = user
+        .linked_accounts
+        .get(&guild.guild_id)
+        .unwrap_or(&user.default_account_id);
+    bot.roblox.invalidate_user_roles(*roblox_id).await;
+
+    let mut all_roles = binds
         .rankbinds
         .iter()
         .flat_map(|b| b.discord_roles.clone())
         .collect::<Vec<_>>();
     all_roles.extend(
-        guild
+        binds
             .groupbinds
             .iter()
             .flat_map(|b| b.discord_roles.clone()),
     );
     all_roles.extend(
-        guild
+        binds
             .custombinds
             .iter()
             .flat_map(|b| b.discord_roles.clone()),
     );
     all_roles.extend(
-        guild
+        binds
             .assetbinds
             .iter()
             .flat_map(|b| b.discord_roles.clone()),
@@ -182,6 +195,7 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
         user: &user,
         server: &server,
         guild: &guild,
+        binds: &binds,
         all_roles: &all_roles,
     };
     let (added_roles, removed_roles, nickname) = match update_user.execute().await {
@@ -251,6 +265,39 @@ Hey there, it looks like you're not verified with us. Please run `/verify` to re
                                     .await;
                             })
                             .await;
+
+                        if let Some(universe_id) = guild.linked_universe {
+                            // Deny lists are checked on every update, so skip users who are
+                            // already banned instead of banning and logging them again.
+                            let already_banned = bot
+                                .roblox
+                                .get_user_restriction(universe_id, None, *roblox_id)
+                                .await
+                                .is_ok_and(|r| r.game_join_restriction.active);
+                            if already_banned {
+                                tracing::trace!("already banned from universe {universe_id}");
+                            } else {
+                                tracing::trace!("banning them from universe {universe_id}");
+                                // The ban comes from the deny list rather than from whoever ran
+                                // the update, so it is logged as the bot's own action.
+                                if let Err(err) = add_gameban(
+                                    &bot.roblox,
+                                    &bot.database,
+                                    ctx.guild_id,
+                                    None,
+                                    universe_id,
+                                    GamebanArguments {
+                                        user_id: *roblox_id,
+                                        reason: deny_list.reason.clone(),
+                                        duration: None,
+                                    },
+                                )
+                                .await
+                                {
+                                    tracing::error!("failed to apply the game ban: {}", err);
+                                }
+                            }
+                        }
                     }
                 }
 
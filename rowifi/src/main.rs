@@ -10,16 +10,21 @@ mod commands;
 use axum::{
     body::Body,
     extract::Request,
-    http::{StatusCode, Uri},
+    http::{header::CONTENT_TYPE, StatusCode, Uri},
     middleware::map_request,
-    response::Response,
-    routing::post,
+    response::{IntoResponse, Response},
+    routing::{get, post},
     Extension, Json, Router, ServiceExt,
 };
 use ed25519_dalek::{Verifier, VerifyingKey, PUBLIC_KEY_LENGTH};
 use hex::FromHex;
-use rowifi_cache::Cache;
-use rowifi_database::Database;
+use prometheus::{Registry, TextEncoder};
+use rowifi_cache::{
+    roblox::{CachedRobloxClient, RobloxCacheConfig},
+    Cache, LocalCacheConfig,
+};
+use rowifi_core::api_keys::ApiKeyCipher;
+use rowifi_database::{ClientCert, Database, DatabaseConfig, TlsConfig};
 use rowifi_framework::context::BotContext;
 use rowifi_models::discord::{
     application::{
@@ -30,8 +35,8 @@ use rowifi_models::discord::{
     http::interaction::{InteractionResponse, InteractionResponseType},
     id::{marker::ApplicationMarker, Id},
 };
-use rowifi_roblox::RobloxClient;
-use std::{error::Error, future::Future, pin::Pin, sync::Arc};
+use rowifi_roblox::{RobloxClient, RobloxMetrics};
+use std::{error::Error, future::Future, pin::Pin, sync::Arc, time::Duration};
 use tokio::net::TcpListener;
 use tower::Layer as _;
 use tower_http::{
@@ -55,9 +60,11 @@ use crate::commands::{
         new_event, new_event_type, view_attendee_events, view_event, view_event_types,
         view_host_events,
     },
+    gamebans::{delete_gameban, link_universe, new_gameban, view_gamebans},
     groupbinds::{delete_groupbind, new_groupbind, view_groupbinds},
     rankbinds::{delete_rankbind, new_rankbind, view_rankbinds},
-    server::{serverinfo, update_all, update_role},
+    server::{api_key, log_channel, serverinfo, update_all, update_role},
+    staff::{staff_cache_guild, staff_cache_member},
     user::{
         account_default, account_delete, account_switch, account_view, debug_update, update_route,
         userinfo, verify_route,
@@ -71,6 +78,11 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         .with(tracing_subscriber::fmt::layer().with_filter(EnvFilter::from_default_env()))
         .init();
 
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) == Some("migrate") {
+        return migrate(args.iter().any(|a| a == "--baseline")).await;
+    }
+
     let application_id = std::env::var("APPLICATION_ID")
         .expect("expected the application id")
         .parse()
@@ -81,25 +93,60 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let redis_url = std::env::var("REDIS_CONN").expect("Expected the redis connection url");
     let open_cloud_auth =
         std::env::var("OPEN_CLOUD_AUTH").expect("Expected the open cloud auth key");
+    let open_cloud_master_key = std::env::var("OPEN_CLOUD_MASTER_KEY").ok();
     let discord_public_key =
         std::env::var("DISCORD_PUBLIC_KEY").expect("Expected the discord public key");
     let roblox_proxy = std::env::var("ROBLOX_PROXY").ok();
     let error_logger = std::env::var("ERROR_LOGGER").expect("Expected the error logger");
+    let metrics_addr = std::env::var("METRICS_ADDR").ok();
+    let local_cache = std::env::var("LOCAL_CACHE_CAPACITY")
+        .ok()
+        .map(|capacity| LocalCacheConfig {
+            capacity: capacity
+                .parse()
+                .expect("Expected the local cache capacity to be a number"),
+            ..Default::default()
+        });
 
     let error_logger = twilight_util::link::webhook::parse(&error_logger)?;
 
     let redis = redis::Client::open(redis_url)?;
 
-    let cache = Cache::new(redis).await?;
-    let database = Arc::new(Database::new(&connection_string).await);
+    let cache = Cache::new(redis, local_cache).await?;
+    let database = Arc::new(Database::connect(&connection_string, &database_config()).await?);
+    let pending = database.pending_migrations().await?;
+    if !pending.is_empty() {
+        return Err(format!(
+            "{} migration(s) have not been applied, run `rowifi migrate`",
+            pending.len()
+        )
+        .into());
+    }
+    let api_keys = open_cloud_master_key
+        .map(|master_key| ApiKeyCipher::from_base64(&master_key))
+        .transpose()?;
+    if api_keys.is_none() {
+        tracing::warn!(
+            "OPEN_CLOUD_MASTER_KEY is not set, servers cannot register their own API keys"
+        );
+    }
     let twilight_http = Arc::new(TwilightClient::new(bot_token.clone()));
-    let roblox = RobloxClient::new(&open_cloud_auth, roblox_proxy);
+    let registry = Registry::new();
+    database.register_metrics(&registry)?;
+    cache.register_metrics(&registry)?;
+    let roblox = CachedRobloxClient::new(
+        RobloxClient::new(&open_cloud_auth, roblox_proxy)
+            .with_metrics(RobloxMetrics::new(&registry)?),
+        Some(cache.clone()),
+        RobloxCacheConfig::default(),
+    );
     let bot_context = BotContext::new(
         Id::<ApplicationMarker>::new(application_id),
         twilight_http,
         database,
         cache,
         roblox,
+        api_keys,
         (error_logger.0, error_logger.1.unwrap().to_string()),
     );
 
@@ -147,14 +194,22 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         .route("/backup/restore", post(backup_restore))
         .route("/backup/view", post(backup_view))
         .route("/backup/delete", post(backup_delete))
+        .route("/gameban/new", post(new_gameban))
+        .route("/gameban/delete", post(delete_gameban))
+        .route("/gameban/view", post(view_gamebans))
+        .route("/gameban/universe", post(link_universe))
         .route("/update-all", post(update_all))
         .route("/update-role", post(update_role))
         .route("/analytics/view", post(analytics_view))
         .route("/analytics/register", post(analytics_register))
         .route("/analytics/unregister", post(analytics_unregister))
         .route("/serverinfo", post(serverinfo))
+        .route("/apikey", post(api_key))
+        .route("/log-channel", post(log_channel))
         .route("/debug/update", post(debug_update))
         .route("/audit-logs", post(audit_logs))
+        .route("/staff-cache/guild", post(staff_cache_guild))
+        .route("/staff-cache/member", post(staff_cache_member))
         .route("/standby", post(standby_route));
 
     #[cfg(feature = "tower")]
@@ -190,12 +245,91 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         .layer(Extension(bot_context))
         .layer(TraceLayer::new_for_http());
     let app_with_middleware = middleware.layer(app);
+
+    // Served on its own listener since every request to the main one is a Discord interaction.
+    if let Some(metrics_addr) = metrics_addr {
+        let metrics_router = Router::new()
+            .route("/metrics", get(metrics))
+            .layer(Extension(registry));
+        let metrics_listener = TcpListener::bind(metrics_addr).await?;
+        tokio::spawn(async move {
+            if let Err(err) = axum::serve(metrics_listener, metrics_router).await {
+                tracing::error!("the metrics server stopped: {}", err);
+            }
+        });
+    }
+
     let listener = TcpListener::bind("0.0.0.0:8000").await?;
     axum::serve(listener, app_with_middleware.into_make_service()).await?;
 
     Ok(())
 }
 
+/// Applies the pending migrations. With `baseline`, a database created before migrations were
+/// introduced first has the initial migration recorded as applied instead of running it.
+async fn migrate(baseline: bool) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let connection_string =
+        std::env::var("DATABASE_CONN").expect("expected a database connection string.");
+    let database = Database::connect(&connection_string, &database_config()).await?;
+
+    if baseline && database.baseline().await? {
+        tracing::info!("recorded the existing tables as the initial migration");
+    }
+
+    let applied = database.migrate().await?;
+    if applied.is_empty() {
+        tracing::info!("the database is up to date");
+    }
+    for migration in applied {
+        tracing::info!(
+            "applied migration {} ({})",
+            migration.version,
+            migration.name
+        );
+    }
+
+    Ok(())
+}
+
+/// Reads the pool settings from the environment, falling back to the defaults of
+/// [`DatabaseConfig`]. Timeouts are in seconds. TLS is used when `DATABASE_TLS` is `true` or when
+/// any of the certificate paths are set.
+fn database_config() -> DatabaseConfig {
+    let timeout = |name: &str, default: Option<Duration>| {
+        std::env::var(name).ok().map_or(default, |secs| {
+            Some(Duration::from_secs(secs.parse().unwrap_or_else(|_| {
+                panic!("Expected {name} to be a number of seconds")
+            })))
+        })
+    };
+    let default = DatabaseConfig::default();
+    let ca_cert = std::env::var("DATABASE_CA_CERT").ok();
+    let client_cert = std::env::var("DATABASE_CLIENT_CERT").ok();
+    let client_key = std::env::var("DATABASE_CLIENT_KEY").ok();
+    let tls = std::env::var("DATABASE_TLS").is_ok_and(|tls| tls == "true")
+        || ca_cert.is_some()
+        || client_cert.is_some();
+
+    DatabaseConfig {
+        max_size: std::env::var("DATABASE_POOL_SIZE").map_or(default.max_size, |size| {
+            size.parse()
+                .expect("Expected the database pool size to be a number")
+        }),
+        create_timeout: timeout("DATABASE_CREATE_TIMEOUT", default.create_timeout),
+        wait_timeout: timeout("DATABASE_WAIT_TIMEOUT", default.wait_timeout),
+        recycle_timeout: timeout("DATABASE_RECYCLE_TIMEOUT", default.recycle_timeout),
+        tls: tls.then(|| TlsConfig {
+            ca_cert: ca_cert.map(Into::into),
+            client_cert: client_cert.map(|cert| ClientCert {
+                cert: cert.into(),
+                key: client_key
+                    .expect("Expected DATABASE_CLIENT_KEY to be set with DATABASE_CLIENT_CERT")
+                    .into(),
+            }),
+        }),
+    }
+}
+
 async fn pong() -> Json<InteractionResponse> {
     Json(InteractionResponse {
         kind: InteractionResponseType::Pong,
@@ -203,6 +337,15 @@ async fn pong() -> Json<InteractionResponse> {
     })
 }
 
+/// Exports the metrics in the Prometheus text format.
+async fn metrics(registry: Extension<Registry>) -> Response {
+    let mut buffer = String::new();
+    match TextEncoder::new().encode_utf8(&registry.gather(), &mut buffer) {
+        Ok(()) => ([(CONTENT_TYPE, prometheus::TEXT_FORMAT)], buffer).into_response(),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
+
 async fn standby_route(
     bot_standby: Extension<Arc<Standby>>,
     interaction: Json<Interaction>,
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Mutex, PoisonError},
+    time::Instant,
+};
+
+use super::{Batch, CacheBackend, Write};
+use crate::error::CacheError;
+
+/// A backend that keeps everything in a [`HashMap`] in the process. Meant for tests, where it
+/// behaves like Redis without needing a server.
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    expires_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+enum Value {
+    Bytes(Vec<u8>),
+    Counter(i64),
+    Set(BTreeSet<u64>),
+}
+
+impl MemoryBackend {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the live keys in the backend, for asserting on what was written or removed.
+    #[must_use]
+    pub fn keys(&self) -> Vec<String> {
+        let now = Instant::now();
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut keys = entries
+            .iter()
+            .filter(|(_, e)| !e.is_expired(now))
+            .map(|(k, _)| k.clone())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    fn read<T>(&self, key: &str, f: impl FnOnce(&Value) -> T) -> Option<T> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries
+            .get(key)
+            .filter(|e| !e.is_expired(Instant::now()))
+            .map(|e| f(&e.value))
+    }
+}
+
+impl Entry {
+    fn new(value: Value) -> Self {
+        Self {
+            value,
+            expires_at: None,
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|e| e <= now)
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(self
+            .read(key, |v| match v {
+                Value::Bytes(bytes) => Some(bytes.clone()),
+                Value::Counter(counter) => Some(counter.to_string().into_bytes()),
+                Value::Set(_) => None,
+            })
+            .flatten())
+    }
+
+    async fn get_many(&self, keys: &[String]) -> Result<Vec<Option<Vec<u8>>>, CacheError> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            values.push(self.get(key).await?);
+        }
+        Ok(values)
+    }
+
+    async fn get_counter(&self, key: &str) -> Result<Option<i64>, CacheError> {
+        Ok(self
+            .read(key, |v| match v {
+                Value::Counter(counter) => Some(*counter),
+                Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.parse().ok(),
+                Value::Set(_) => None,
+            })
+            .flatten())
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<u64>, CacheError> {
+        Ok(self
+            .read(key, |v| match v {
+                Value::Set(set) => set.iter().copied().collect(),
+                _ => Vec::new(),
+            })
+            .unwrap_or_default())
+    }
+
+    async fn scan_set(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<u64>), CacheError> {
+        let members = self.set_members(key).await?;
+        let start = usize::try_from(cursor)
+            .unwrap_or(usize::MAX)
+            .min(members.len());
+        let end = start.saturating_add(count.max(1)).min(members.len());
+        let next = if end == members.len() {
+            0
+        } else {
+            u64::try_from(end).unwrap_or_default()
+        };
+        Ok((next, members[start..end].to_vec()))
+    }
+
+    async fn execute(&self, batch: Batch) -> Result<(), CacheError> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.retain(|_, e| !e.is_expired(now));
+
+        for write in batch.into_writes() {
+            match write {
+                Write::Set { key, value, ttl } => {
+                    entries.insert(
+                        key,
+                        Entry {
+                            value: Value::Bytes(value),
+                            expires_at: ttl.map(|ttl| now + ttl),
+                        },
+                    );
+                }
+                Write::SetCounter { key, value } => {
+                    entries.insert(key, Entry::new(Value::Counter(value)));
+                }
+                Write::Incr { key, delta } => {
+                    let entry = entries
+                        .entry(key)
+                        .or_insert_with(|| Entry::new(Value::Counter(0)));
+                    if let Value::Counter(counter) = &mut entry.value {
+                        *counter += delta;
+                    }
+                }
+                Write::Del { key } => {
+                    entries.remove(&key);
+                }
+                Write::SetAdd { key, member } => {
+                    let entry = entries
+                        .entry(key)
+                        .or_insert_with(|| Entry::new(Value::Set(BTreeSet::new())));
+                    if let Value::Set(set) = &mut entry.value {
+                        set.insert(member);
+                    }
+                }
+                Write::SetRemove { key, member } => {
+                    let is_empty = match entries.get_mut(&key).map(|e| &mut e.value) {
+                        Some(Value::Set(set)) => {
+                            set.remove(&member);
+                            set.is_empty()
+                        }
+                        _ => false,
+                    };
+                    // Like Redis, empty sets don't exist.
+                    if is_empty {
+                        entries.remove(&key);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
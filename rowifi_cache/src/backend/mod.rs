@@ -0,0 +1,153 @@
+mod memory;
+mod redis;
+
+pub use self::{memory::MemoryBackend, redis::RedisBackend};
+
+use async_trait::async_trait;
+use std::time::Duration;
+
+use crate::error::CacheError;
+
+/// The storage the [`Cache`](crate::Cache) reads from and writes to. Values are opaque bytes,
+/// encoding them is left to the cache.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError>;
+
+    /// Returns the values in the same order as `keys`, with [`None`] for missing keys.
+    async fn get_many(&self, keys: &[String]) -> Result<Vec<Option<Vec<u8>>>, CacheError>;
+
+    /// Reads a counter written with [`Batch::set_counter`] or [`Batch::incr`].
+    async fn get_counter(&self, key: &str) -> Result<Option<i64>, CacheError>;
+
+    async fn set_members(&self, key: &str) -> Result<Vec<u64>, CacheError>;
+
+    /// Returns some of the members of a set starting from `cursor`, along with the cursor to
+    /// continue from. The scan is over once the returned cursor is `0`.
+    async fn scan_set(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<u64>), CacheError>;
+
+    /// Applies all the writes of the batch in a single round trip.
+    async fn execute(&self, batch: Batch) -> Result<(), CacheError>;
+}
+
+/// A write queued in a [`Batch`].
+#[derive(Clone, Debug)]
+pub enum Write {
+    Set {
+        key: String,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    },
+    SetCounter {
+        key: String,
+        value: i64,
+    },
+    Incr {
+        key: String,
+        delta: i64,
+    },
+    Del {
+        key: String,
+    },
+    SetAdd {
+        key: String,
+        member: u64,
+    },
+    SetRemove {
+        key: String,
+        member: u64,
+    },
+}
+
+impl Write {
+    /// The key the write changes.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Set { key, .. }
+            | Self::SetCounter { key, .. }
+            | Self::Incr { key, .. }
+            | Self::Del { key }
+            | Self::SetAdd { key, .. }
+            | Self::SetRemove { key, .. } => key,
+        }
+    }
+}
+
+/// A group of writes sent to the backend together, like a Redis pipeline.
+#[derive(Clone, Debug, Default)]
+pub struct Batch {
+    writes: Vec<Write>,
+}
+
+impl Batch {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, key: String, value: Vec<u8>) -> &mut Self {
+        self.writes.push(Write::Set {
+            key,
+            value,
+            ttl: None,
+        });
+        self
+    }
+
+    /// Sets a value that expires after `ttl`.
+    pub fn set_ex(&mut self, key: String, value: Vec<u8>, ttl: Duration) -> &mut Self {
+        self.writes.push(Write::Set {
+            key,
+            value,
+            ttl: Some(ttl),
+        });
+        self
+    }
+
+    pub fn set_counter(&mut self, key: String, value: i64) -> &mut Self {
+        self.writes.push(Write::SetCounter { key, value });
+        self
+    }
+
+    /// Adds `delta` to a counter. Missing counters start at `0`.
+    pub fn incr(&mut self, key: String, delta: i64) -> &mut Self {
+        self.writes.push(Write::Incr { key, delta });
+        self
+    }
+
+    pub fn del(&mut self, key: String) -> &mut Self {
+        self.writes.push(Write::Del { key });
+        self
+    }
+
+    pub fn sadd(&mut self, key: String, member: u64) -> &mut Self {
+        self.writes.push(Write::SetAdd { key, member });
+        self
+    }
+
+    pub fn srem(&mut self, key: String, member: u64) -> &mut Self {
+        self.writes.push(Write::SetRemove { key, member });
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    #[must_use]
+    pub fn writes(&self) -> &[Write] {
+        &self.writes
+    }
+
+    #[must_use]
+    pub fn into_writes(self) -> Vec<Write> {
+        self.writes
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, AsyncCommands, Client as RedisClient};
+
+use super::{Batch, CacheBackend, Write};
+use crate::error::CacheError;
+
+/// The production backend, storing everything in Redis.
+#[derive(Clone)]
+pub struct RedisBackend {
+    conn: ConnectionManager,
+}
+
+impl RedisBackend {
+    /// Create the backend given a Redis client.
+    ///
+    /// # Errors
+    ///
+    /// See [`CacheError`] for details.
+    pub async fn new(client: RedisClient) -> Result<Self, CacheError> {
+        let conn = client.get_connection_manager().await?;
+        Ok(Self { conn })
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        let mut conn = self.conn.clone();
+        Ok(conn.get(key).await?)
+    }
+
+    async fn get_many(&self, keys: &[String]) -> Result<Vec<Option<Vec<u8>>>, CacheError> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.conn.clone();
+        Ok(redis::cmd("MGET").arg(keys).query_async(&mut conn).await?)
+    }
+
+    async fn get_counter(&self, key: &str) -> Result<Option<i64>, CacheError> {
+        let mut conn = self.conn.clone();
+        Ok(conn.get(key).await?)
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<u64>, CacheError> {
+        let mut conn = self.conn.clone();
+        Ok(conn.smembers(key).await?)
+    }
+
+    async fn scan_set(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<u64>), CacheError> {
+        let mut conn = self.conn.clone();
+        Ok(redis::cmd("SSCAN")
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut conn)
+            .await?)
+    }
+
+    async fn execute(&self, batch: Batch) -> Result<(), CacheError> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipeline = redis::pipe();
+        for write in batch.into_writes() {
+            match write {
+                Write::Set {
+                    key,
+                    value,
+                    ttl: None,
+                } => pipeline.set(key, value),
+                // Redis rejects an expiry of zero seconds.
+                Write::Set {
+                    key,
+                    value,
+                    ttl: Some(ttl),
+                } => pipeline.set_ex(key, value, ttl.as_secs().max(1)),
+                Write::SetCounter { key, value } => pipeline.set(key, value),
+                Write::Incr { key, delta } => pipeline.incr(key, delta),
+                Write::Del { key } => pipeline.del(key),
+                Write::SetAdd { key, member } => pipeline.sadd(key, member),
+                Write::SetRemove { key, member } => pipeline.srem(key, member),
+            };
+        }
+
+        let mut conn = self.conn.clone();
+        pipeline.query_async(&mut conn).await?;
+
+        Ok(())
+    }
+}
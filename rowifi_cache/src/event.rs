@@ -1,15 +1,16 @@
 use async_trait::async_trait;
-use redis::{self, AsyncCommands};
 use rowifi_models::{
     discord::{
         application::interaction::InteractionType,
         cache::{CachedChannel, CachedGuild, CachedMember, CachedRole, CachedUser},
+        channel::Channel,
         gateway::{
             event::Event,
             payload::incoming::{
                 ChannelCreate, ChannelDelete, ChannelUpdate, GuildCreate, GuildDelete, GuildUpdate,
                 InteractionCreate, MemberAdd, MemberChunk, MemberRemove, MemberUpdate,
-                MessageCreate, RoleCreate, RoleDelete, RoleUpdate,
+                MessageCreate, RoleCreate, RoleDelete, RoleUpdate, ThreadCreate, ThreadDelete,
+                ThreadListSync, ThreadUpdate,
             },
         },
     },
@@ -17,6 +18,7 @@ use rowifi_models::{
 };
 
 use crate::{
+    backend::Batch,
     error::CacheError,
     process::{
         cache_guild, cache_guild_channel, cache_member, cache_partial_member, cache_role,
@@ -36,7 +38,8 @@ impl UpdateCache for Event {
         use Event::{
             ChannelCreate, ChannelDelete, ChannelUpdate, GuildCreate, GuildDelete, GuildUpdate,
             InteractionCreate, MemberAdd, MemberChunk, MemberRemove, MemberUpdate, MessageCreate,
-            RoleCreate, RoleDelete, RoleUpdate,
+            RoleCreate, RoleDelete, RoleUpdate, ThreadCreate, ThreadDelete, ThreadListSync,
+            ThreadUpdate,
         };
 
         match self {
@@ -55,6 +58,10 @@ impl UpdateCache for Event {
             RoleCreate(v) => cache.update(v).await,
             RoleUpdate(v) => cache.update(v).await,
             RoleDelete(v) => cache.update(v).await,
+            ThreadCreate(v) => cache.update(&**v).await,
+            ThreadDelete(v) => cache.update(v).await,
+            ThreadListSync(v) => cache.update(v).await,
+            ThreadUpdate(v) => cache.update(&**v).await,
             _ => Ok(()),
         }
     }
@@ -63,26 +70,7 @@ impl UpdateCache for Event {
 #[async_trait]
 impl UpdateCache for ChannelCreate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        if let Some(guild_id) = self.guild_id {
-            let guild_id = GuildId(guild_id);
-            if let Some(mut guild) = c.guild(guild_id).await? {
-                let mut pipeline = redis::pipe();
-
-                match cache_guild_channel(&mut pipeline, &self.0) {
-                    Ok(()) => {
-                        guild.channels.insert(ChannelId(self.id));
-                        pipeline.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
-                    }
-                    Err(err) => {
-                        tracing::error!(err = ?err);
-                    }
-                }
-
-                let mut conn = c.get();
-                pipeline.query_async(&mut conn).await?;
-            }
-        }
-        Ok(())
+        update_guild_channel(c, self).await
     }
 }
 
@@ -90,18 +78,17 @@ impl UpdateCache for ChannelCreate {
 impl UpdateCache for ChannelDelete {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
         if let Some(guild_id) = self.guild_id {
-            let mut pipeline = redis::pipe();
+            let mut batch = Batch::new();
 
             let guild_id = GuildId(guild_id);
             if let Some(mut guild) = c.guild(guild_id).await? {
                 guild.channels.remove(&ChannelId(self.id));
-                pipeline.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+                batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
             }
 
-            pipeline.del(CachedChannel::key(ChannelId(self.id)));
+            batch.del(CachedChannel::key(ChannelId(self.id)));
 
-            let mut conn = c.get();
-            pipeline.query_async(&mut conn).await?;
+            c.write(batch).await?;
         }
 
         Ok(())
@@ -111,31 +98,22 @@ impl UpdateCache for ChannelDelete {
 #[async_trait]
 impl UpdateCache for ChannelUpdate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        if self.guild_id.is_some() {
-            let mut pipeline = redis::pipe();
-
-            cache_guild_channel(&mut pipeline, self)?;
-
-            let mut conn = c.get();
-            pipeline.query_async(&mut conn).await?;
-        }
-
-        Ok(())
+        update_guild_channel(c, self).await
     }
 }
 
 #[async_trait]
 impl UpdateCache for GuildCreate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        let mut pipeline = redis::pipe();
-        if let GuildCreate::Available(guild) = self {
-            cache_guild(&mut pipeline, guild)?;
-        } else {
+        let mut batch = Batch::new();
+        let GuildCreate::Available(guild) = self else {
             return Ok(());
-        }
+        };
+        cache_guild(&mut batch, guild)?;
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        c.write(batch).await?;
+
+        c.reconcile_guild(guild).await?;
 
         Ok(())
     }
@@ -144,21 +122,29 @@ impl UpdateCache for GuildCreate {
 #[async_trait]
 impl UpdateCache for GuildDelete {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
+        // The guild is only unavailable during an outage and will come back with a
+        // `GuildCreate`.
+        if self.unavailable == Some(true) {
+            return Ok(());
+        }
+
         let guild_id = GuildId::new(self.id.get());
         if let Some(guild) = c.guild(guild_id).await? {
-            let mut pipeline = redis::pipe();
-            pipeline.del(CachedGuild::key(guild_id));
+            let mut batch = Batch::new();
+            batch.del(CachedGuild::key(guild_id));
+            batch.del(CachedGuild::member_count_key(guild_id));
 
             for channel in guild.channels {
-                pipeline.del(CachedChannel::key(channel));
+                batch.del(CachedChannel::key(channel));
             }
             for role in guild.roles {
-                pipeline.del(CachedRole::key(role));
+                batch.del(CachedRole::key(role));
             }
 
-            let mut conn = c.get();
-            pipeline.query_async(&mut conn).await?;
+            c.write(batch).await?;
         }
+        c.remove_guild_members(guild_id).await?;
+
         Ok(())
     }
 }
@@ -172,9 +158,9 @@ impl UpdateCache for GuildUpdate {
             guild.icon = self.icon;
             guild.owner_id = UserId(self.owner_id);
 
-            let mut conn = c.get();
-            conn.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?)
-                .await?;
+            let mut batch = Batch::new();
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+            c.write(batch).await?;
         }
         Ok(())
     }
@@ -183,16 +169,16 @@ impl UpdateCache for GuildUpdate {
 #[async_trait]
 impl UpdateCache for MemberAdd {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        let mut pipeline = redis::pipe();
+        let mut batch = Batch::new();
 
         let guild_id = GuildId(self.guild_id);
-        cache_member(&mut pipeline, guild_id, &self.member)?;
-        cache_user(&mut pipeline, &self.member.user)?;
+        cache_member(&mut batch, guild_id, &self.member)?;
+        cache_user(&mut batch, &self.member.user)?;
 
-        pipeline.sadd(format!("discord:m:{guild_id}"), self.user.id.get());
+        batch.sadd(CachedGuild::members_key(guild_id), self.user.id.get());
+        batch.incr(CachedGuild::member_count_key(guild_id), 1);
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        c.write(batch).await?;
 
         Ok(())
     }
@@ -205,21 +191,20 @@ impl UpdateCache for MemberChunk {
             return Ok(());
         }
 
-        let mut pipeline = redis::pipe();
+        let mut batch = Batch::new();
         let guild_id = GuildId(self.guild_id);
 
         for member in &self.members {
-            if let Err(err) = cache_member(&mut pipeline, guild_id, member) {
+            if let Err(err) = cache_member(&mut batch, guild_id, member) {
                 tracing::error!(err = ?err);
             }
-            if let Err(err) = cache_user(&mut pipeline, &member.user) {
+            if let Err(err) = cache_user(&mut batch, &member.user) {
                 tracing::error!(err = ?err);
             }
-            pipeline.sadd(format!("discord:m:{guild_id}"), member.user.id.get());
+            batch.sadd(CachedGuild::members_key(guild_id), member.user.id.get());
         }
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        c.write(batch).await?;
 
         Ok(())
     }
@@ -228,16 +213,16 @@ impl UpdateCache for MemberChunk {
 #[async_trait]
 impl UpdateCache for MemberRemove {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        let mut pipeline = redis::pipe();
+        let mut batch = Batch::new();
 
         let guild_id = GuildId(self.guild_id);
         let user_id = UserId(self.user.id);
 
-        pipeline.del(CachedMember::key(guild_id, user_id));
-        pipeline.srem(format!("discord:m:{guild_id}"), self.user.id.get());
+        batch.del(CachedMember::key(guild_id, user_id));
+        batch.srem(CachedGuild::members_key(guild_id), self.user.id.get());
+        batch.incr(CachedGuild::member_count_key(guild_id), -1);
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        c.write(batch).await?;
 
         Ok(())
     }
@@ -249,24 +234,23 @@ impl UpdateCache for MemberUpdate {
         let guild_id = GuildId(self.guild_id);
         let user_id = UserId(self.user.id);
 
-        let mut conn = c.get();
+        let mut batch = Batch::new();
         if let Some(mut member) = c.guild_member(guild_id, user_id).await? {
             member.nickname.clone_from(&self.nick);
             member.roles = self.roles.iter().map(|r| RoleId(*r)).collect();
 
-            conn.set(
+            batch.set(
                 CachedMember::key(guild_id, user_id),
                 rmp_serde::to_vec(&member)?,
-            )
-            .await?;
+            );
         }
         if let Some(mut user) = c.user(user_id).await? {
             user.username.clone_from(&self.user.name);
             user.avatar = self.avatar.map(|a| a.to_string());
 
-            conn.set(CachedUser::key(user_id), rmp_serde::to_vec(&user)?)
-                .await?;
+            batch.set(CachedUser::key(user_id), rmp_serde::to_vec(&user)?);
         }
+        c.write(batch).await?;
 
         Ok(())
     }
@@ -276,15 +260,14 @@ impl UpdateCache for MemberUpdate {
 impl UpdateCache for MessageCreate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
         if let (Some(guild_id), Some(member)) = (self.guild_id, &self.member) {
-            let mut pipeline = redis::pipe();
+            let mut batch = Batch::new();
 
             let guild_id = GuildId(guild_id);
-            cache_partial_member(&mut pipeline, guild_id, member, &self.author)?;
+            cache_partial_member(&mut batch, guild_id, member, &self.author)?;
 
-            pipeline.sadd(format!("discord:m:{guild_id}"), self.author.id.get());
+            batch.sadd(CachedGuild::members_key(guild_id), self.author.id.get());
 
-            let mut conn = c.get();
-            pipeline.query_async(&mut conn).await?;
+            c.write(batch).await?;
         }
 
         Ok(())
@@ -296,17 +279,16 @@ impl UpdateCache for InteractionCreate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
         if let Some(guild_id) = self.guild_id {
             if self.0.kind == InteractionType::ApplicationCommand {
-                let mut pipeline = redis::pipe();
+                let mut batch = Batch::new();
 
                 let guild_id = GuildId(guild_id);
                 let member = self.0.member.as_ref().unwrap();
                 let user = member.user.as_ref().unwrap();
-                cache_partial_member(&mut pipeline, guild_id, member, user)?;
+                cache_partial_member(&mut batch, guild_id, member, user)?;
 
-                pipeline.sadd(format!("discord:m:{guild_id}"), user.id.get());
+                batch.sadd(CachedGuild::members_key(guild_id), user.id.get());
 
-                let mut conn = c.get();
-                pipeline.query_async(&mut conn).await?;
+                c.write(batch).await?;
             }
         }
 
@@ -317,17 +299,16 @@ impl UpdateCache for InteractionCreate {
 #[async_trait]
 impl UpdateCache for RoleCreate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        let mut pipeline = redis::pipe();
+        let mut batch = Batch::new();
 
-        cache_role(&mut pipeline, &self.role)?;
+        cache_role(&mut batch, &self.role)?;
         let guild_id = GuildId(self.guild_id);
         if let Some(mut guild) = c.guild(guild_id).await? {
             guild.roles.insert(RoleId(self.role.id));
-            pipeline.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
         }
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        c.write(batch).await?;
 
         Ok(())
     }
@@ -336,18 +317,17 @@ impl UpdateCache for RoleCreate {
 #[async_trait]
 impl UpdateCache for RoleDelete {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        let mut pipeline = redis::pipe();
+        let mut batch = Batch::new();
 
         let guild_id = GuildId(self.guild_id);
         if let Some(mut guild) = c.guild(guild_id).await? {
             guild.roles.remove(&RoleId(self.role_id));
-            pipeline.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
         }
 
-        pipeline.del(CachedRole::key(RoleId(self.role_id)));
+        batch.del(CachedRole::key(RoleId(self.role_id)));
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        c.write(batch).await?;
 
         Ok(())
     }
@@ -356,19 +336,118 @@ impl UpdateCache for RoleDelete {
 #[async_trait]
 impl UpdateCache for RoleUpdate {
     async fn update(&self, c: &Cache) -> Result<(), CacheError> {
-        let mut pipeline = redis::pipe();
+        let mut batch = Batch::new();
 
-        cache_role(&mut pipeline, &self.role)?;
+        cache_role(&mut batch, &self.role)?;
 
         let guild_id = GuildId(self.guild_id);
         if let Some(mut guild) = c.guild(guild_id).await? {
             guild.roles.insert(RoleId(self.role.id));
-            pipeline.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+        }
+
+        c.write(batch).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UpdateCache for ThreadCreate {
+    async fn update(&self, c: &Cache) -> Result<(), CacheError> {
+        update_guild_channel(c, self).await
+    }
+}
+
+#[async_trait]
+impl UpdateCache for ThreadDelete {
+    async fn update(&self, c: &Cache) -> Result<(), CacheError> {
+        let mut batch = Batch::new();
+
+        let guild_id = GuildId(self.guild_id);
+        if let Some(mut guild) = c.guild(guild_id).await? {
+            guild.channels.remove(&ChannelId(self.id));
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
         }
 
-        let mut conn = c.get();
-        pipeline.query_async(&mut conn).await?;
+        batch.del(CachedChannel::key(ChannelId(self.id)));
+
+        c.write(batch).await?;
 
         Ok(())
     }
 }
+
+#[async_trait]
+impl UpdateCache for ThreadListSync {
+    async fn update(&self, c: &Cache) -> Result<(), CacheError> {
+        let mut batch = Batch::new();
+
+        let guild_id = GuildId(self.guild_id);
+        let mut guild = c.guild(guild_id).await?;
+        for thread in &self.threads {
+            match cache_guild_channel(&mut batch, guild_id, thread) {
+                Ok(true) => {
+                    if let Some(guild) = &mut guild {
+                        guild.channels.insert(ChannelId(thread.id));
+                    }
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::error!(err = ?err);
+                }
+            }
+        }
+        if let Some(guild) = guild {
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+        }
+
+        c.write(batch).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UpdateCache for ThreadUpdate {
+    async fn update(&self, c: &Cache) -> Result<(), CacheError> {
+        update_guild_channel(c, self).await
+    }
+}
+
+/// Caches a created or updated channel or thread and keeps the guild's channel list in sync.
+/// Channels that are no longer cached, such as archived threads, are removed.
+async fn update_guild_channel(c: &Cache, channel: &Channel) -> Result<(), CacheError> {
+    let Some(guild_id) = channel.guild_id else {
+        return Ok(());
+    };
+    let guild_id = GuildId(guild_id);
+    let channel_id = ChannelId(channel.id);
+
+    let mut batch = Batch::new();
+    let cached = match cache_guild_channel(&mut batch, guild_id, channel) {
+        Ok(cached) => cached,
+        Err(err) => {
+            tracing::error!(err = ?err);
+            false
+        }
+    };
+    if !cached {
+        batch.del(CachedChannel::key(channel_id));
+    }
+
+    if let Some(mut guild) = c.guild(guild_id).await? {
+        let changed = if cached {
+            guild.channels.insert(channel_id)
+        } else {
+            guild.channels.remove(&channel_id)
+        };
+        if changed {
+            batch.set(CachedGuild::key(guild_id), rmp_serde::to_vec(&guild)?);
+        }
+    }
+
+    c.write(batch).await?;
+
+    Ok(())
+}
@@ -5,40 +5,82 @@
 )]
 
 mod event;
+mod local;
 mod process;
 
+pub mod backend;
 pub mod error;
+pub mod permissions;
+pub mod roblox;
 
+use backend::{Batch, CacheBackend, MemoryBackend, RedisBackend};
+use local::{LocalCache, LocalValue};
 use process::{cache_guild, cache_member, cache_user};
-use redis::{aio::ConnectionManager, AsyncCommands, Client as RedisClient};
+use prometheus::Registry;
+use redis::Client as RedisClient;
 use rowifi_models::{
     discord::{
-        cache::{CachedGuild, CachedMember, CachedRole, CachedUser},
-        guild::{Guild, Member},
+        cache::{CachedChannel, CachedGuild, CachedMember, CachedRole, CachedUser},
+        guild::{Guild, Member, Permissions},
     },
-    id::{GuildId, RoleId, UserId},
+    id::{ChannelId, GuildId, RoleId, UserId},
 };
+use serde::de::DeserializeOwned;
 use std::{collections::HashSet, sync::Arc};
 
+/// How many members are written or removed per round trip when a whole guild is processed.
+const MEMBER_BATCH_SIZE: usize = 1000;
+
 use error::CacheError;
 use event::UpdateCache;
+use permissions::PermissionCalculator;
+
+pub use local::{LocalCacheConfig, LocalCacheStats};
 
 pub struct CacheInner {
-    pub(crate) conn: ConnectionManager,
+    pub(crate) backend: Arc<dyn CacheBackend>,
+    /// Copies of hot keys kept in the process, read before going to the backend. Members are
+    /// never kept here, see [`Cache::new`].
+    pub(crate) local: Option<LocalCache>,
 }
 
 #[derive(Clone)]
 pub struct Cache(Arc<CacheInner>);
 
 impl Cache {
-    /// Create a Cache client given a Redis client.
+    /// Create a Cache client given a Redis client. Passing a [`LocalCacheConfig`] keeps the
+    /// hot keys in the process as well.
+    ///
+    /// Writes made by this client drop the keys from its in-process cache, but writes made by
+    /// other processes sharing the backend don't. Servers, channels, roles, users and member
+    /// counts can therefore be up to [`LocalCacheConfig::ttl`] out of date unless this client
+    /// processes the events that change them. Members are always read from the backend since
+    /// they change far more often than the other keys.
     ///
     /// # Errors
     ///
     /// See [`CacheError`] for details.
-    pub async fn new(client: RedisClient) -> Result<Self, CacheError> {
-        let conn = client.get_connection_manager().await?;
-        Ok(Self(Arc::new(CacheInner { conn })))
+    pub async fn new(
+        client: RedisClient,
+        local: Option<LocalCacheConfig>,
+    ) -> Result<Self, CacheError> {
+        let backend = RedisBackend::new(client).await?;
+        Ok(Self::with_backend(Arc::new(backend), local))
+    }
+
+    /// Create a Cache client that stores its data in the given backend.
+    #[must_use]
+    pub fn with_backend(backend: Arc<dyn CacheBackend>, local: Option<LocalCacheConfig>) -> Self {
+        Self(Arc::new(CacheInner {
+            backend,
+            local: local.map(LocalCache::new),
+        }))
+    }
+
+    /// Create a Cache client backed by a [`MemoryBackend`]. Useful for tests.
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self::with_backend(Arc::new(MemoryBackend::new()), None)
     }
 
     /// Update data in the cache.
@@ -50,14 +92,146 @@ impl Cache {
         value.update(self).await
     }
 
-    /// Returns a connection from the pool.
+    /// Returns the backend the cache reads from and writes to. Writes should go through
+    /// [`Cache::write`] so that the in-process cache does not serve stale data.
+    #[must_use]
+    pub fn backend(&self) -> &dyn CacheBackend {
+        &*self.0.backend
+    }
+
+    /// Applies the writes of the batch to the backend and drops the keys they touch from the
+    /// in-process cache.
     ///
     /// # Errors
     ///
     /// See [`CacheError`] for details.
+    pub async fn write(&self, batch: Batch) -> Result<(), CacheError> {
+        let keys = self.0.local.as_ref().map(|_| {
+            batch
+                .writes()
+                .iter()
+                .map(|w| w.key().to_owned())
+                .collect::<Vec<_>>()
+        });
+
+        let res = self.backend().execute(batch).await;
+
+        // Invalidate even if the batch failed since part of it might have been applied.
+        if let (Some(local), Some(keys)) = (&self.0.local, keys) {
+            for key in keys {
+                local.invalidate(&key);
+            }
+        }
+
+        res
+    }
+
+    /// Returns the hit and miss counters of the in-process cache, if it is enabled.
     #[must_use]
-    pub fn get(&self) -> ConnectionManager {
-        self.0.conn.clone()
+    pub fn local_stats(&self) -> Option<LocalCacheStats> {
+        self.0.local.as_ref().map(LocalCache::stats)
+    }
+
+    /// Register the hit and miss counters of the in-process cache in `registry`. Nothing is
+    /// registered if it is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Errors if metrics with the same names are already registered.
+    pub fn register_metrics(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        match &self.0.local {
+            Some(local) => local.register_metrics(registry),
+            None => Ok(()),
+        }
+    }
+
+    /// Empties the in-process cache so that every key is read from the backend again.
+    pub fn clear_local(&self) {
+        if let Some(local) = &self.0.local {
+            local.clear();
+        }
+    }
+
+    async fn get_bytes(&self, key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        let Some(local) = &self.0.local else {
+            return self.backend().get(key).await;
+        };
+        if let Some(LocalValue::Bytes(bytes)) = local.get(key) {
+            return Ok(Some(bytes));
+        }
+
+        let res = self.backend().get(key).await?;
+        if let Some(bytes) = &res {
+            local.insert(key.to_owned(), LocalValue::Bytes(bytes.clone()));
+        }
+        Ok(res)
+    }
+
+    async fn get_counter(&self, key: &str) -> Result<Option<i64>, CacheError> {
+        let Some(local) = &self.0.local else {
+            return self.backend().get_counter(key).await;
+        };
+        if let Some(LocalValue::Counter(counter)) = local.get(key) {
+            return Ok(Some(counter));
+        }
+
+        let res = self.backend().get_counter(key).await?;
+        if let Some(counter) = res {
+            local.insert(key.to_owned(), LocalValue::Counter(counter));
+        }
+        Ok(res)
+    }
+
+    async fn read<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, CacheError> {
+        decode(self.get_bytes(key).await?)
+    }
+
+    /// Reads the key from the backend, bypassing the in-process cache.
+    async fn read_shared<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, CacheError> {
+        decode(self.backend().get(key).await?)
+    }
+
+    async fn read_many<T: DeserializeOwned>(&self, keys: &[String]) -> Result<Vec<T>, CacheError> {
+        self.read_many_in(self.0.local.as_ref(), keys).await
+    }
+
+    /// Reads the given keys in order, skipping the ones that are missing or fail to decode. Only
+    /// the keys missing from `local` are requested from the backend.
+    async fn read_many_in<T: DeserializeOwned>(
+        &self,
+        local: Option<&LocalCache>,
+        keys: &[String],
+    ) -> Result<Vec<T>, CacheError> {
+        let mut values = keys
+            .iter()
+            .map(|key| match local?.get(key)? {
+                LocalValue::Bytes(bytes) => Some(bytes),
+                LocalValue::Counter(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let missing = values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_none())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let missing_keys = missing.iter().map(|i| keys[*i].clone()).collect::<Vec<_>>();
+            let res = self.backend().get_many(&missing_keys).await?;
+            for ((i, key), bytes) in missing.into_iter().zip(missing_keys).zip(res) {
+                if let (Some(local), Some(bytes)) = (local, &bytes) {
+                    local.insert(key, LocalValue::Bytes(bytes.clone()));
+                }
+                values[i] = bytes;
+            }
+        }
+
+        Ok(values
+            .into_iter()
+            .flatten()
+            .filter_map(|r| rmp_serde::from_slice(&r).ok())
+            .collect())
     }
 
     /// Returns a user from the cache.
@@ -66,14 +240,7 @@ impl Cache {
     ///
     /// See [`CacheError`] for details.
     pub async fn user(&self, user_id: UserId) -> Result<Option<CachedUser>, CacheError> {
-        let mut conn = self.get();
-        let res: Option<Vec<u8>> = conn.get(CachedUser::key(user_id)).await?;
-
-        if let Some(res) = res {
-            Ok(rmp_serde::from_slice(&res)?)
-        } else {
-            Ok(None)
-        }
+        self.read(&CachedUser::key(user_id)).await
     }
 
     /// Returns the server from the cache.
@@ -82,14 +249,24 @@ impl Cache {
     ///
     /// See [`CacheError`] for details.
     pub async fn guild(&self, id: GuildId) -> Result<Option<CachedGuild>, CacheError> {
-        let mut conn = self.get();
-        let res: Option<Vec<u8>> = conn.get(CachedGuild::key(id)).await?;
-
-        if let Some(res) = res {
-            Ok(rmp_serde::from_slice(&res)?)
-        } else {
-            Ok(None)
+        let Some(mut guild) = self.read::<CachedGuild>(&CachedGuild::key(id)).await? else {
+            return Ok(None);
+        };
+        let member_count = self.get_counter(&CachedGuild::member_count_key(id)).await?;
+        if let Some(member_count) = member_count {
+            guild.member_count = u64::try_from(member_count).unwrap_or_default();
         }
+
+        Ok(Some(guild))
+    }
+
+    /// Returns a channel or thread from the cache.
+    ///
+    /// # Errors
+    ///
+    /// See [`CacheError`] for details.
+    pub async fn channel(&self, id: ChannelId) -> Result<Option<CachedChannel>, CacheError> {
+        self.read(&CachedChannel::key(id)).await
     }
 
     /// Returns a member for a particular guild from the cache.
@@ -102,14 +279,8 @@ impl Cache {
         guild_id: GuildId,
         user_id: UserId,
     ) -> Result<Option<CachedMember>, CacheError> {
-        let mut conn = self.get();
-        let res: Option<Vec<u8>> = conn.get(CachedMember::key(guild_id, user_id)).await?;
-
-        if let Some(res) = res {
-            Ok(rmp_serde::from_slice(&res)?)
-        } else {
-            Ok(None)
-        }
+        self.read_shared(&CachedMember::key(guild_id, user_id))
+            .await
     }
 
     /// Returns a list of members of a particular guild from the cache.
@@ -122,25 +293,12 @@ impl Cache {
         guild_id: GuildId,
         user_ids: impl Iterator<Item = UserId>,
     ) -> Result<Vec<CachedMember>, CacheError> {
-        let mut conn = self.get();
         let keys = user_ids
             .into_iter()
             .map(|u| CachedMember::key(guild_id, u))
             .collect::<Vec<_>>();
 
-        if keys.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let res: Vec<Vec<u8>> = conn.get(keys).await?;
-        let mut members = Vec::new();
-        for r in res {
-            if let Ok(member) = rmp_serde::from_slice::<CachedMember>(&r) {
-                members.push(member);
-            }
-        }
-
-        Ok(members)
+        self.read_many_in(None, &keys).await
     }
 
     /// Returns all the cached members for a particular guild.
@@ -149,8 +307,10 @@ impl Cache {
     ///
     /// See [`CacheError`] for details.
     pub async fn guild_members_set(&self, id: GuildId) -> Result<HashSet<UserId>, CacheError> {
-        let mut conn = self.get();
-        let res: Vec<u64> = conn.smembers(format!("discord:m:{id}")).await?;
+        let res = self
+            .backend()
+            .set_members(&CachedGuild::members_key(id))
+            .await?;
 
         Ok(res.into_iter().map(UserId::new).collect())
     }
@@ -164,24 +324,74 @@ impl Cache {
         &self,
         role_ids: impl Iterator<Item = RoleId>,
     ) -> Result<Vec<CachedRole>, CacheError> {
-        let mut conn = self.get();
         let keys = role_ids
             .into_iter()
             .map(CachedRole::key)
             .collect::<Vec<_>>();
-        if keys.is_empty() {
-            return Ok(Vec::new());
-        }
-        let res: Vec<Vec<u8>> = conn.get(keys).await?;
 
-        let mut roles = Vec::new();
-        for r in res {
-            if let Ok(role) = rmp_serde::from_slice::<CachedRole>(&r) {
-                roles.push(role);
-            }
+        self.read_many(&keys).await
+    }
+
+    /// Returns the permission calculator of a member, or [`None`] if the server or the member
+    /// is not cached.
+    ///
+    /// # Errors
+    ///
+    /// See [`CacheError`] for details.
+    pub async fn member_permissions(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<PermissionCalculator>, CacheError> {
+        let (Some(guild), Some(member)) = (
+            self.guild(guild_id).await?,
+            self.guild_member(guild_id, user_id).await?,
+        ) else {
+            return Ok(None);
+        };
+
+        let role_ids = member
+            .roles
+            .iter()
+            .copied()
+            .chain(std::iter::once(RoleId::new(guild_id.get())));
+        let roles = self.guild_roles(role_ids).await?;
+
+        Ok(Some(PermissionCalculator::new(&guild, &member, &roles)))
+    }
+
+    /// Returns a channel along with the permissions of a member in it. Threads use the
+    /// permission overwrites of their parent channel.
+    ///
+    /// # Errors
+    ///
+    /// See [`CacheError`] for details.
+    pub async fn channel_permissions(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<Option<(CachedChannel, Permissions)>, CacheError> {
+        let Some(channel) = self.channel(channel_id).await? else {
+            return Ok(None);
+        };
+        if channel.guild_id() != guild_id {
+            return Ok(None);
         }
+        let Some(calculator) = self.member_permissions(guild_id, user_id).await? else {
+            return Ok(None);
+        };
+
+        let permissions = if let CachedChannel::Thread(thread) = &channel {
+            match self.channel(thread.parent_id).await? {
+                Some(parent) => calculator.in_channel(parent.permission_overwrites()),
+                None => return Ok(None),
+            }
+        } else {
+            calculator.in_channel(channel.permission_overwrites())
+        };
 
-        Ok(roles)
+        Ok(Some((channel, permissions)))
     }
 
     /// Returns a list of users from the cache.
@@ -193,25 +403,12 @@ impl Cache {
         &self,
         user_ids: impl Iterator<Item = UserId>,
     ) -> Result<Vec<CachedUser>, CacheError> {
-        let mut conn = self.get();
         let keys = user_ids
             .into_iter()
             .map(CachedUser::key)
             .collect::<Vec<_>>();
 
-        if keys.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let res: Vec<Vec<u8>> = conn.get(keys).await?;
-        let mut users = Vec::new();
-        for r in res {
-            if let Ok(user) = rmp_serde::from_slice::<CachedUser>(&r) {
-                users.push(user);
-            }
-        }
-
-        Ok(users)
+        self.read_many(&keys).await
     }
 
     /// Add a member to the cache. Replaces if the member already exists.
@@ -224,28 +421,118 @@ impl Cache {
         guild_id: GuildId,
         member: &Member,
     ) -> Result<(CachedMember, CachedUser), CacheError> {
-        let mut pipeline = redis::pipe();
-        let cached_member = cache_member(&mut pipeline, guild_id, member)?;
-        let cached_user = cache_user(&mut pipeline, &member.user)?;
+        let mut batch = Batch::new();
+        let cached_member = cache_member(&mut batch, guild_id, member)?;
+        let cached_user = cache_user(&mut batch, &member.user)?;
 
-        let mut conn = self.get();
-        pipeline.query_async(&mut conn).await?;
+        self.write(batch).await?;
 
         Ok((cached_member, cached_user))
     }
 
+    /// Fixes drift in the cached members of a guild using the snapshot sent in a `GuildCreate`.
+    /// The members in the snapshot are cached. When it lists every member, which Discord only
+    /// does for small guilds, members that are no longer in the guild are removed too. The
+    /// member count itself is reset whenever the guild is cached.
+    ///
+    /// # Errors
+    ///
+    /// See [`CacheError`] for details.
+    pub async fn reconcile_guild(&self, guild: &Guild) -> Result<(), CacheError> {
+        let guild_id = GuildId::new(guild.id.get());
+
+        for members in guild.members.chunks(MEMBER_BATCH_SIZE) {
+            let mut batch = Batch::new();
+            for member in members {
+                cache_member(&mut batch, guild_id, member)?;
+                cache_user(&mut batch, &member.user)?;
+                batch.sadd(CachedGuild::members_key(guild_id), member.user.id.get());
+            }
+            self.write(batch).await?;
+        }
+
+        let is_complete = guild
+            .member_count
+            .is_some_and(|count| usize::try_from(count).is_ok_and(|c| c == guild.members.len()));
+        if !is_complete {
+            return Ok(());
+        }
+
+        let current = guild
+            .members
+            .iter()
+            .map(|m| UserId(m.user.id))
+            .collect::<HashSet<_>>();
+        let stale = self
+            .guild_members_set(guild_id)
+            .await?
+            .into_iter()
+            .filter(|u| !current.contains(u))
+            .collect::<Vec<_>>();
+        for user_ids in stale.chunks(MEMBER_BATCH_SIZE) {
+            let mut batch = Batch::new();
+            for user_id in user_ids {
+                batch.srem(CachedGuild::members_key(guild_id), user_id.get());
+                batch.del(CachedMember::key(guild_id, *user_id));
+            }
+            self.write(batch).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes every cached member of a guild along with the set tracking them. Members are
+    /// removed in batches so that large guilds don't block the backend.
+    ///
+    /// # Errors
+    ///
+    /// See [`CacheError`] for details.
+    pub async fn remove_guild_members(&self, guild_id: GuildId) -> Result<(), CacheError> {
+        let members_key = CachedGuild::members_key(guild_id);
+
+        // The set is only read while scanning, so the cursor stays valid as the keys go away.
+        let mut cursor = 0;
+        loop {
+            let (next, user_ids) = self
+                .backend()
+                .scan_set(&members_key, cursor, MEMBER_BATCH_SIZE)
+                .await?;
+            let mut batch = Batch::new();
+            for user_id in user_ids {
+                batch.del(CachedMember::key(guild_id, UserId::new(user_id)));
+            }
+            self.write(batch).await?;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        let mut batch = Batch::new();
+        batch.del(members_key);
+        self.write(batch).await?;
+
+        Ok(())
+    }
+
     /// Add a guild to the cache. Replaces if the guild already exists.
     ///
     /// # Errors
     ///
     /// See [`CacheError`] for details.
     pub async fn cache_guild(&self, guild: Guild) -> Result<CachedGuild, CacheError> {
-        let mut pipeline = redis::pipe();
-        let cached = cache_guild(&mut pipeline, &guild)?;
+        let mut batch = Batch::new();
+        let cached = cache_guild(&mut batch, &guild)?;
 
-        let mut conn = self.get();
-        pipeline.query_async(&mut conn).await?;
+        self.write(batch).await?;
 
         Ok(cached)
     }
 }
+
+fn decode<T: DeserializeOwned>(bytes: Option<Vec<u8>>) -> Result<Option<T>, CacheError> {
+    match bytes {
+        Some(bytes) => Ok(Some(rmp_serde::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,221 @@
+use prometheus::{IntCounter, Registry};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Settings of the in-process cache kept in front of the backend.
+#[derive(Clone, Copy, Debug)]
+pub struct LocalCacheConfig {
+    /// The maximum number of entries. The oldest entries are evicted first.
+    pub capacity: usize,
+    /// How long an entry is served before it is read from the backend again. Writes from other
+    /// processes are not seen until then, so this is how out of date an entry can get.
+    pub ttl: Duration,
+}
+
+/// Counters of the in-process cache since the cache was created.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LocalCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum LocalValue {
+    Bytes(Vec<u8>),
+    Counter(i64),
+}
+
+pub(crate) struct LocalCache {
+    config: LocalCacheConfig,
+    state: Mutex<State>,
+    hits: IntCounter,
+    misses: IntCounter,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    /// Keys in insertion order, along with the id of the entry they were inserted with. Ids that
+    /// no longer match are left behind by invalidations and skipped on eviction.
+    order: VecDeque<(String, u64)>,
+    next_id: u64,
+}
+
+struct Entry {
+    id: u64,
+    value: LocalValue,
+    expires_at: Instant,
+}
+
+impl Default for LocalCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+impl LocalCache {
+    pub fn new(config: LocalCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::default()),
+            hits: IntCounter::new(
+                "cache_local_hits_total",
+                "Reads served by the in-process cache",
+            )
+            .unwrap(),
+            misses: IntCounter::new(
+                "cache_local_misses_total",
+                "Reads the in-process cache passed on to the backend",
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<LocalValue> {
+        let mut state = self.lock();
+        let value = match state.entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.value.clone()),
+            Some(_) => {
+                state.entries.remove(key);
+                None
+            }
+            None => None,
+        };
+
+        if value.is_some() {
+            self.hits.inc();
+        } else {
+            self.misses.inc();
+        }
+        value
+    }
+
+    pub fn insert(&self, key: String, value: LocalValue) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+
+        if !state.entries.contains_key(&key) {
+            while state.entries.len() >= self.config.capacity {
+                let Some((oldest, oldest_id)) = state.order.pop_front() else {
+                    break;
+                };
+                if state
+                    .entries
+                    .get(&oldest)
+                    .is_some_and(|e| e.id == oldest_id)
+                {
+                    state.entries.remove(&oldest);
+                }
+            }
+        }
+
+        state.order.push_back((key.clone(), id));
+        state.entries.insert(
+            key,
+            Entry {
+                id,
+                value,
+                expires_at: Instant::now() + self.config.ttl,
+            },
+        );
+
+        // Drop the keys left behind by replaced and invalidated entries.
+        if state.order.len() > self.config.capacity.saturating_mul(2) {
+            let State { entries, order, .. } = &mut *state;
+            order.retain(|(key, id)| entries.get(key).is_some_and(|e| e.id == *id));
+        }
+    }
+
+    pub fn invalidate(&self, key: &str) {
+        self.lock().entries.remove(key);
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    pub fn stats(&self) -> LocalCacheStats {
+        LocalCacheStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            entries: self.lock().entries.len(),
+        }
+    }
+
+    pub fn register_metrics(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        registry.register(Box::new(self.hits.clone()))?;
+        registry.register(Box::new(self.misses.clone()))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(capacity: usize, ttl: Duration) -> LocalCache {
+        LocalCache::new(LocalCacheConfig { capacity, ttl })
+    }
+
+    fn bytes(value: &str) -> LocalValue {
+        LocalValue::Bytes(value.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_hits_and_misses() {
+        let cache = cache(10, Duration::from_secs(60));
+        assert_eq!(cache.get("a"), None);
+        cache.insert("a".into(), bytes("1"));
+        assert_eq!(cache.get("a"), Some(bytes("1")));
+
+        cache.invalidate("a");
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(
+            cache.stats(),
+            LocalCacheStats {
+                hits: 1,
+                misses: 2,
+                entries: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_expired_entries_are_misses() {
+        let cache = cache(10, Duration::ZERO);
+        cache.insert("a".into(), LocalValue::Counter(1));
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_oldest_entries_are_evicted() {
+        let cache = cache(2, Duration::from_secs(60));
+        cache.insert("a".into(), bytes("1"));
+        cache.insert("b".into(), bytes("2"));
+        // Replacing an entry does not make room for another one.
+        cache.insert("a".into(), bytes("3"));
+        cache.insert("c".into(), bytes("4"));
+
+        assert_eq!(cache.get("a"), Some(bytes("3")));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(bytes("4")));
+    }
+}
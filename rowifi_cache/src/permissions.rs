@@ -0,0 +1,253 @@
+use rowifi_models::{
+    discord::{
+        cache::{CachedChannel, CachedGuild, CachedMember, CachedRole},
+        channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        guild::Permissions,
+    },
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Computes the permissions of a member from the cached roles of the server and the permission
+/// overwrites of a channel, following the order Discord applies them in.
+#[derive(Clone, Debug)]
+pub struct PermissionCalculator {
+    guild_id: GuildId,
+    member_id: UserId,
+    member_roles: Vec<RoleId>,
+    guild_permissions: Permissions,
+}
+
+impl PermissionCalculator {
+    /// `roles` should contain the `@everyone` role and the roles of the member. Roles that
+    /// are missing are treated as granting no permissions.
+    #[must_use]
+    pub fn new(guild: &CachedGuild, member: &CachedMember, roles: &[CachedRole]) -> Self {
+        let guild_permissions = if member.id == guild.owner_id {
+            Permissions::all()
+        } else {
+            let everyone_id = RoleId::new(guild.id.get());
+            let permissions = roles
+                .iter()
+                .filter(|r| r.id == everyone_id || member.roles.contains(&r.id))
+                .fold(Permissions::empty(), |acc, r| acc | r.permissions);
+            if permissions.contains(Permissions::ADMINISTRATOR) {
+                Permissions::all()
+            } else {
+                permissions
+            }
+        };
+
+        Self {
+            guild_id: guild.id,
+            member_id: member.id,
+            member_roles: member.roles.clone(),
+            guild_permissions,
+        }
+    }
+
+    /// The permissions of the member in the server, without any channel overwrites.
+    #[must_use]
+    pub fn guild(&self) -> Permissions {
+        self.guild_permissions
+    }
+
+    #[must_use]
+    pub fn is_admin(&self) -> bool {
+        self.guild_permissions.contains(Permissions::ADMINISTRATOR)
+    }
+
+    /// The permissions of the member in a channel with the given overwrites. Threads have no
+    /// overwrites of their own, so the ones of their parent channel should be passed.
+    #[must_use]
+    pub fn in_channel(&self, overwrites: &[PermissionOverwrite]) -> Permissions {
+        if self.is_admin() {
+            return Permissions::all();
+        }
+
+        let mut permissions = self.guild_permissions;
+
+        if let Some(everyone) = overwrites
+            .iter()
+            .find(|o| o.kind == PermissionOverwriteType::Role && o.id.get() == self.guild_id.get())
+        {
+            permissions.remove(everyone.deny);
+            permissions.insert(everyone.allow);
+        }
+
+        let (mut allow, mut deny) = (Permissions::empty(), Permissions::empty());
+        for overwrite in overwrites.iter().filter(|o| {
+            o.kind == PermissionOverwriteType::Role
+                && self.member_roles.iter().any(|r| r.get() == o.id.get())
+        }) {
+            allow |= overwrite.allow;
+            deny |= overwrite.deny;
+        }
+        permissions.remove(deny);
+        permissions.insert(allow);
+
+        if let Some(member) = overwrites.iter().find(|o| {
+            o.kind == PermissionOverwriteType::Member && o.id.get() == self.member_id.get()
+        }) {
+            permissions.remove(member.deny);
+            permissions.insert(member.allow);
+        }
+
+        // Every other permission in a channel depends on being able to see it.
+        if permissions.contains(Permissions::VIEW_CHANNEL) {
+            permissions
+        } else {
+            Permissions::empty()
+        }
+    }
+}
+
+/// Whether `permissions` in `channel` are enough to send messages to it.
+#[must_use]
+pub fn can_send_messages(channel: &CachedChannel, permissions: Permissions) -> bool {
+    let required = match channel {
+        CachedChannel::Thread(_) => {
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES_IN_THREADS
+        }
+        _ => Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+    };
+    channel.is_messageable() && permissions.contains(required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rowifi_models::{
+        discord::{
+            cache::CachedTextChannel,
+            channel::ChannelType,
+            id::{marker::GenericMarker, Id},
+        },
+        id::ChannelId,
+    };
+    use std::collections::HashSet;
+
+    const GUILD: u64 = 1;
+    const OWNER: u64 = 2;
+    const MEMBER: u64 = 3;
+    const MOD_ROLE: u64 = 4;
+
+    fn guild() -> CachedGuild {
+        CachedGuild {
+            id: GuildId::new(GUILD),
+            name: "Guild".into(),
+            icon: None,
+            member_count: 2,
+            owner_id: UserId::new(OWNER),
+            roles: HashSet::from([RoleId::new(GUILD), RoleId::new(MOD_ROLE)]),
+            channels: HashSet::new(),
+        }
+    }
+
+    fn role(id: u64, permissions: Permissions) -> CachedRole {
+        CachedRole {
+            id: RoleId::new(id),
+            name: id.to_string(),
+            permissions,
+            managed: false,
+            position: 0,
+            color: 0,
+        }
+    }
+
+    fn member(id: u64, roles: &[u64]) -> CachedMember {
+        CachedMember {
+            roles: roles.iter().copied().map(RoleId::new).collect(),
+            nickname: None,
+            id: UserId::new(id),
+            avatar: None,
+        }
+    }
+
+    fn overwrite(
+        id: u64,
+        kind: PermissionOverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> PermissionOverwrite {
+        PermissionOverwrite {
+            allow,
+            deny,
+            id: Id::<GenericMarker>::new(id),
+            kind,
+        }
+    }
+
+    fn roles() -> Vec<CachedRole> {
+        vec![
+            role(
+                GUILD,
+                Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            ),
+            role(MOD_ROLE, Permissions::KICK_MEMBERS),
+        ]
+    }
+
+    #[test]
+    fn test_guild_permissions() {
+        let calculator =
+            PermissionCalculator::new(&guild(), &member(MEMBER, &[MOD_ROLE]), &roles());
+        assert_eq!(
+            calculator.guild(),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::KICK_MEMBERS
+        );
+        assert!(!calculator.is_admin());
+
+        let owner = PermissionCalculator::new(&guild(), &member(OWNER, &[]), &roles());
+        assert!(owner.is_admin());
+    }
+
+    #[test]
+    fn test_channel_overwrites() {
+        let overwrites = [
+            overwrite(
+                GUILD,
+                PermissionOverwriteType::Role,
+                Permissions::empty(),
+                Permissions::SEND_MESSAGES,
+            ),
+            overwrite(
+                MOD_ROLE,
+                PermissionOverwriteType::Role,
+                Permissions::SEND_MESSAGES,
+                Permissions::empty(),
+            ),
+        ];
+
+        let moderator = PermissionCalculator::new(&guild(), &member(MEMBER, &[MOD_ROLE]), &roles());
+        assert!(moderator
+            .in_channel(&overwrites)
+            .contains(Permissions::SEND_MESSAGES));
+
+        let everyone = PermissionCalculator::new(&guild(), &member(MEMBER, &[]), &roles());
+        let permissions = everyone.in_channel(&overwrites);
+        assert!(!permissions.contains(Permissions::SEND_MESSAGES));
+
+        let channel = CachedChannel::Text(CachedTextChannel {
+            id: ChannelId::new(5),
+            guild_id: GuildId::new(GUILD),
+            kind: ChannelType::GuildText,
+            name: "logs".into(),
+            position: 0,
+            parent_id: None,
+            permission_overwrites: overwrites.to_vec(),
+        });
+        assert!(!can_send_messages(&channel, permissions));
+    }
+
+    #[test]
+    fn test_member_overwrite_hides_channel() {
+        let overwrites = [overwrite(
+            MEMBER,
+            PermissionOverwriteType::Member,
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+        let calculator = PermissionCalculator::new(&guild(), &member(MEMBER, &[]), &roles());
+        assert_eq!(calculator.in_channel(&overwrites), Permissions::empty());
+    }
+}
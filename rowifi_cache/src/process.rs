@@ -1,8 +1,8 @@
-use redis::Pipeline;
 use rowifi_models::{
     discord::{
         cache::{
-            CachedChannel, CachedGuild, CachedMember, CachedRole, CachedTextChannel, CachedUser,
+            CachedCategoryChannel, CachedChannel, CachedForumChannel, CachedGuild, CachedMember,
+            CachedRole, CachedTextChannel, CachedThread, CachedUser, CachedVoiceChannel,
         },
         channel::{Channel, ChannelType},
         guild::{Guild, Member, PartialMember, Role},
@@ -11,86 +11,132 @@ use rowifi_models::{
     id::{ChannelId, GuildId, RoleId, UserId},
 };
 
-use crate::error::CacheError;
+use crate::{backend::Batch, error::CacheError};
 
-pub(crate) fn cache_guild(
-    pipeline: &mut Pipeline,
-    guild: &Guild,
-) -> Result<CachedGuild, CacheError> {
-    for channel in &guild.channels {
-        cache_guild_channel(pipeline, channel)?;
+pub(crate) fn cache_guild(batch: &mut Batch, guild: &Guild) -> Result<CachedGuild, CacheError> {
+    let guild_id = GuildId::new(guild.id.get());
+    for channel in guild.channels.iter().chain(&guild.threads) {
+        cache_guild_channel(batch, guild_id, channel)?;
     }
 
     for role in &guild.roles {
-        cache_role(pipeline, role)?;
+        cache_role(batch, role)?;
     }
 
     let cached = CachedGuild::from_guild(guild);
 
-    pipeline.set(CachedGuild::key(cached.id), rmp_serde::to_vec(&cached)?);
+    batch.set(CachedGuild::key(cached.id), rmp_serde::to_vec(&cached)?);
+    // Guilds fetched over http without counts would otherwise reset the counter.
+    if guild.member_count.is_some() || guild.approximate_member_count.is_some() {
+        batch.set_counter(
+            CachedGuild::member_count_key(cached.id),
+            i64::try_from(cached.member_count).unwrap_or(i64::MAX),
+        );
+    }
 
     Ok(cached)
 }
 
+/// Caches a channel or thread of a guild. Returns whether the channel was cached, since DM
+/// channels, directories and archived threads are not.
 pub(crate) fn cache_guild_channel(
-    pipeline: &mut Pipeline,
+    batch: &mut Batch,
+    guild_id: GuildId,
     channel: &Channel,
-) -> Result<(), CacheError> {
-    #[allow(clippy::single_match)]
-    match channel.kind {
-        ChannelType::GuildText => cache_text_channel(pipeline, channel)?,
-        _ => {}
-    }
-
-    Ok(())
-}
+) -> Result<bool, CacheError> {
+    let id = ChannelId::new(channel.id.get());
+    let name = channel.name.clone().unwrap_or_default();
+    let position = channel.position.unwrap_or_default();
+    let parent_id = channel.parent_id.map(ChannelId);
+    let permission_overwrites = channel.permission_overwrites.clone().unwrap_or_default();
+
+    let cached = match channel.kind {
+        ChannelType::GuildCategory => CachedChannel::Category(CachedCategoryChannel {
+            id,
+            guild_id,
+            name,
+            position,
+            permission_overwrites,
+        }),
+        ChannelType::GuildText | ChannelType::GuildAnnouncement => {
+            CachedChannel::Text(CachedTextChannel {
+                id,
+                guild_id,
+                kind: channel.kind,
+                name,
+                position,
+                parent_id,
+                permission_overwrites,
+            })
+        }
+        ChannelType::GuildForum | ChannelType::GuildMedia => {
+            CachedChannel::Forum(CachedForumChannel {
+                id,
+                guild_id,
+                kind: channel.kind,
+                name,
+                position,
+                parent_id,
+                permission_overwrites,
+            })
+        }
+        ChannelType::GuildVoice | ChannelType::GuildStageVoice => {
+            CachedChannel::Voice(CachedVoiceChannel {
+                id,
+                guild_id,
+                kind: channel.kind,
+                name,
+                position,
+                parent_id,
+                permission_overwrites,
+                bitrate: channel.bitrate,
+                user_limit: channel.user_limit,
+            })
+        }
+        ChannelType::PublicThread
+        | ChannelType::PrivateThread
+        | ChannelType::AnnouncementThread => {
+            let metadata = channel.thread_metadata.as_ref();
+            if metadata.is_some_and(|m| m.archived) {
+                return Ok(false);
+            }
+            let Some(parent_id) = parent_id else {
+                return Ok(false);
+            };
+            CachedChannel::Thread(CachedThread {
+                id,
+                guild_id,
+                kind: channel.kind,
+                name,
+                parent_id,
+                owner_id: channel.owner_id.map(UserId),
+                locked: metadata.is_some_and(|m| m.locked),
+            })
+        }
+        _ => return Ok(false),
+    };
 
-pub(crate) fn cache_text_channel(
-    pipeline: &mut Pipeline,
-    channel: &Channel,
-) -> Result<(), CacheError> {
-    if let Some(guild_id) = channel.guild_id {
-        let cached = CachedTextChannel {
-            id: ChannelId::new(channel.id.get()),
-            guild_id: GuildId::new(guild_id.get()),
-            name: channel.name.clone().unwrap_or_default(),
-            permission_overwrites: channel.permission_overwrites.as_ref().unwrap().clone(),
-        };
-
-        pipeline.set(CachedChannel::key(cached.id), rmp_serde::to_vec(&cached)?);
-    }
+    batch.set(CachedChannel::key(id), rmp_serde::to_vec(&cached)?);
 
-    Ok(())
+    Ok(true)
 }
 
-pub(crate) fn cache_role(pipeline: &mut Pipeline, role: &Role) -> Result<(), CacheError> {
-    let cached = CachedRole {
-        id: RoleId::new(role.id.get()),
-        name: role.name.clone(),
-        permissions: role.permissions,
-        managed: role.managed,
-        position: role.position,
-        color: role.color,
-    };
+pub(crate) fn cache_role(batch: &mut Batch, role: &Role) -> Result<(), CacheError> {
+    let cached = CachedRole::from_role(role);
 
-    pipeline.set(CachedRole::key(cached.id), rmp_serde::to_vec(&cached)?);
+    batch.set(CachedRole::key(cached.id), rmp_serde::to_vec(&cached)?);
 
     Ok(())
 }
 
 pub(crate) fn cache_member(
-    pipeline: &mut Pipeline,
+    batch: &mut Batch,
     guild_id: GuildId,
     member: &Member,
 ) -> Result<CachedMember, CacheError> {
-    let cached = CachedMember {
-        id: UserId(member.user.id),
-        roles: member.roles.iter().map(|r| RoleId(*r)).collect(),
-        nickname: member.nick.clone(),
-        avatar: member.avatar.map(|a| a.to_string()),
-    };
+    let cached = CachedMember::from_member(member);
 
-    pipeline.set(
+    batch.set(
         CachedMember::key(guild_id, cached.id),
         rmp_serde::to_vec(&cached)?,
     );
@@ -99,7 +145,7 @@ pub(crate) fn cache_member(
 }
 
 pub(crate) fn cache_partial_member(
-    pipeline: &mut Pipeline,
+    batch: &mut Batch,
     guild_id: GuildId,
     member: &PartialMember,
     user: &User,
@@ -111,7 +157,7 @@ pub(crate) fn cache_partial_member(
         avatar: member.avatar.map(|a| a.to_string()),
     };
 
-    pipeline.set(
+    batch.set(
         CachedMember::key(guild_id, cached.id),
         rmp_serde::to_vec(&cached)?,
     );
@@ -119,14 +165,14 @@ pub(crate) fn cache_partial_member(
     Ok(())
 }
 
-pub(crate) fn cache_user(pipeline: &mut Pipeline, user: &User) -> Result<CachedUser, CacheError> {
+pub(crate) fn cache_user(batch: &mut Batch, user: &User) -> Result<CachedUser, CacheError> {
     let cached = CachedUser {
         id: UserId(user.id),
         username: user.name.clone(),
         avatar: user.avatar.map(|a| a.to_string()),
     };
 
-    pipeline.set(CachedUser::key(cached.id), rmp_serde::to_vec(&cached)?);
+    batch.set(CachedUser::key(cached.id), rmp_serde::to_vec(&cached)?);
 
     Ok(cached)
 }
@@ -0,0 +1,312 @@
+use hyper::StatusCode;
+use rowifi_models::roblox::{
+    group::{GroupRole, GroupUserRole},
+    id::{GroupId, UserId},
+    user::PartialUser,
+};
+use rowifi_roblox::{
+    error::{ApiError, ApiErrorKind, ErrorKind, RobloxError},
+    RobloxClient,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{future::Future, ops::Deref, time::Duration};
+
+use crate::{backend::Batch, error::CacheError, Cache};
+
+/// The time-to-live of each of the cached Roblox endpoints.
+#[derive(Clone, Copy, Debug)]
+pub struct RobloxCacheConfig {
+    pub user_roles: Duration,
+    pub user: Duration,
+    pub group_ranks: Duration,
+    pub user_thumbnail: Duration,
+    /// How long a 404 from any of the cached endpoints is remembered.
+    pub not_found: Duration,
+}
+
+/// A [`RobloxClient`] that stores the responses of frequently requested endpoints in the cache.
+///
+/// Every other endpoint is available through [`Deref`] and goes straight to Roblox.
+#[derive(Clone)]
+pub struct CachedRobloxClient {
+    client: RobloxClient,
+    cache: Option<Cache>,
+    config: RobloxCacheConfig,
+}
+
+#[derive(Deserialize, Serialize)]
+enum CachedResponse<T> {
+    Found(T),
+    /// Roblox answered with a 404. `route` is the URL of that request, so that the error rebuilt
+    /// from the cache points to the same place as the original one.
+    NotFound {
+        route: Option<String>,
+    },
+}
+
+impl Default for RobloxCacheConfig {
+    fn default() -> Self {
+        Self {
+            user_roles: Duration::from_mins(5),
+            user: Duration::from_mins(30),
+            group_ranks: Duration::from_mins(30),
+            user_thumbnail: Duration::from_hours(1),
+            not_found: Duration::from_mins(10),
+        }
+    }
+}
+
+impl CachedRobloxClient {
+    /// Wrap a Roblox client. Passing `None` as the cache disables caching altogether.
+    #[must_use]
+    pub fn new(client: RobloxClient, cache: Option<Cache>, config: RobloxCacheConfig) -> Self {
+        Self {
+            client,
+            cache,
+            config,
+        }
+    }
+
+    /// Returns the underlying uncached client.
+    #[must_use]
+    pub fn client(&self) -> &RobloxClient {
+        &self.client
+    }
+
+    /// Get the ranks of the user of all the groups they are part of.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_user_roles(&self, user_id: UserId) -> Result<Vec<GroupUserRole>, RobloxError> {
+        self.cached(
+            user_roles_key(user_id),
+            self.config.user_roles,
+            self.client.get_user_roles(user_id),
+        )
+        .await
+    }
+
+    /// Gets the user from the Roblox Open Cloud API.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_user(&self, user_id: UserId) -> Result<PartialUser, RobloxError> {
+        self.cached(
+            user_key(user_id),
+            self.config.user,
+            self.client.get_user(user_id),
+        )
+        .await
+    }
+
+    /// Get a user's thumbnail.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_user_thumbnail(&self, user_id: UserId) -> Result<String, RobloxError> {
+        self.cached(
+            user_thumbnail_key(user_id),
+            self.config.user_thumbnail,
+            self.client.get_user_thumbnail(user_id),
+        )
+        .await
+    }
+
+    /// Get the ranks of a Roblox Group. Groups that do not exist are cached for
+    /// [`RobloxCacheConfig::not_found`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_group_ranks(
+        &self,
+        group_id: GroupId,
+    ) -> Result<Option<Vec<GroupRole>>, RobloxError> {
+        let Some(cache) = &self.cache else {
+            return self.client.get_group_ranks(group_id).await;
+        };
+
+        let key = group_ranks_key(group_id);
+        match read::<Vec<GroupRole>>(cache, &key).await {
+            Some(CachedResponse::Found(ranks)) => return Ok(Some(ranks)),
+            Some(CachedResponse::NotFound { .. }) => return Ok(None),
+            None => {}
+        }
+
+        let ranks = self.client.get_group_ranks(group_id).await?;
+        match &ranks {
+            Some(ranks) => {
+                write(
+                    cache,
+                    &key,
+                    &CachedResponse::Found(ranks),
+                    self.config.group_ranks,
+                )
+                .await;
+            }
+            None => {
+                write::<()>(
+                    cache,
+                    &key,
+                    &CachedResponse::NotFound { route: None },
+                    self.config.not_found,
+                )
+                .await;
+            }
+        }
+
+        Ok(ranks)
+    }
+
+    /// Removes the cached group ranks of a user so that the next read goes to Roblox.
+    pub async fn invalidate_user_roles(&self, user_id: UserId) {
+        self.invalidate(&[user_roles_key(user_id)]).await;
+    }
+
+    /// Removes the cached profile and thumbnail of a user.
+    pub async fn invalidate_user(&self, user_id: UserId) {
+        self.invalidate(&[user_key(user_id), user_thumbnail_key(user_id)])
+            .await;
+    }
+
+    /// Removes the cached ranks of a group.
+    pub async fn invalidate_group_ranks(&self, group_id: GroupId) {
+        self.invalidate(&[group_ranks_key(group_id)]).await;
+    }
+
+    async fn cached<T, F>(&self, key: String, ttl: Duration, fetch: F) -> Result<T, RobloxError>
+    where
+        T: DeserializeOwned + Serialize,
+        F: Future<Output = Result<T, RobloxError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return fetch.await;
+        };
+
+        match read::<T>(cache, &key).await {
+            Some(CachedResponse::Found(value)) => return Ok(value),
+            Some(CachedResponse::NotFound { route }) => {
+                return Err(RobloxError::from_parts(
+                    ErrorKind::Response {
+                        route: route.unwrap_or_default(),
+                        error: ApiError::new(StatusCode::NOT_FOUND),
+                    },
+                    None,
+                ))
+            }
+            None => {}
+        }
+
+        match fetch.await {
+            Ok(value) => {
+                write(cache, &key, &CachedResponse::Found(&value), ttl).await;
+                Ok(value)
+            }
+            Err(err) => {
+                if let ErrorKind::Response { route, error } = err.kind() {
+                    if error.kind == ApiErrorKind::NotFound {
+                        write::<()>(
+                            cache,
+                            &key,
+                            &CachedResponse::NotFound {
+                                route: Some(route.clone()),
+                            },
+                            self.config.not_found,
+                        )
+                        .await;
+                    }
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn invalidate(&self, keys: &[String]) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+
+        let mut batch = Batch::new();
+        for key in keys {
+            batch.del(key.clone());
+        }
+        let res = cache.write(batch).await;
+        if let Err(err) = res {
+            tracing::warn!(err = ?err, "failed to invalidate cached roblox responses");
+        }
+    }
+}
+
+impl Deref for CachedRobloxClient {
+    type Target = RobloxClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+/// Failures of the cache are logged and treated as a miss so that Roblox is always the fallback.
+async fn read<T: DeserializeOwned>(cache: &Cache, key: &str) -> Option<CachedResponse<T>> {
+    let res = async {
+        let bytes = cache.backend().get(key).await?;
+        match bytes {
+            Some(bytes) => Ok::<_, CacheError>(Some(rmp_serde::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+    .await;
+
+    res.unwrap_or_else(|err| {
+        tracing::warn!(key = key, err = ?err, "failed to read cached roblox response");
+        None
+    })
+}
+
+async fn write<T: Serialize>(cache: &Cache, key: &str, value: &CachedResponse<&T>, ttl: Duration) {
+    let res = async {
+        let bytes = rmp_serde::to_vec(value)?;
+        let mut batch = Batch::new();
+        batch.set_ex(key.to_owned(), bytes, ttl);
+        cache.write(batch).await
+    }
+    .await;
+
+    if let Err(err) = res {
+        tracing::warn!(key = key, err = ?err, "failed to cache roblox response");
+    }
+}
+
+fn user_key(user_id: UserId) -> String {
+    format!("roblox:users:{user_id}")
+}
+
+fn user_roles_key(user_id: UserId) -> String {
+    format!("roblox:users:{user_id}:roles")
+}
+
+fn user_thumbnail_key(user_id: UserId) -> String {
+    format!("roblox:users:{user_id}:thumbnail")
+}
+
+fn group_ranks_key(group_id: GroupId) -> String {
+    format!("roblox:groups:{group_id}:ranks")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedResponse;
+
+    #[test]
+    fn not_found_keeps_the_route() {
+        let route = "https://apis.roblox.com/cloud/v2/users/1";
+        let bytes = rmp_serde::to_vec(&CachedResponse::<&()>::NotFound {
+            route: Some(route.into()),
+        })
+        .unwrap();
+        let cached = rmp_serde::from_slice::<CachedResponse<()>>(&bytes).unwrap();
+        assert!(matches!(cached, CachedResponse::NotFound { route: Some(r) } if r == route));
+    }
+}
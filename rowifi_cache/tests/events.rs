@@ -0,0 +1,391 @@
+use rowifi_cache::{
+    backend::{CacheBackend, MemoryBackend},
+    Cache, LocalCacheConfig,
+};
+use rowifi_models::{
+    discord::{
+        cache::{CachedChannel, CachedGuild, CachedMember, CachedRole, CachedUser},
+        channel::Channel,
+        gateway::payload::incoming::{
+            ChannelCreate, GuildCreate, GuildDelete, MemberAdd, MemberRemove, RoleDelete,
+            RoleUpdate, ThreadCreate, ThreadUpdate,
+        },
+        guild::{Guild, Member},
+        id::Id,
+        user::User,
+    },
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const GUILD_ID: u64 = 1000;
+const OWNER_ID: u64 = 2000;
+const MEMBER_ID: u64 = 2001;
+const TEXT_CHANNEL: u64 = 3000;
+const THREAD: u64 = 3001;
+
+fn user_json(id: u64) -> Value {
+    json!({
+        "id": id.to_string(),
+        "username": format!("user{id}"),
+        "discriminator": "0",
+        "global_name": null,
+        "avatar": null,
+        "bot": false,
+    })
+}
+
+fn user(id: u64) -> User {
+    serde_json::from_value(user_json(id)).unwrap()
+}
+
+fn member(id: u64) -> Member {
+    serde_json::from_value(json!({
+        "avatar": null,
+        "communication_disabled_until": null,
+        "deaf": false,
+        "flags": 0,
+        "joined_at": "2020-01-01T00:00:00.000000+00:00",
+        "mute": false,
+        "nick": null,
+        "pending": false,
+        "premium_since": null,
+        "roles": [],
+        "user": user_json(id),
+    }))
+    .unwrap()
+}
+
+fn everyone_role_json(name: &str) -> Value {
+    json!({
+        "id": GUILD_ID.to_string(),
+        "name": name,
+        "color": 0,
+        "hoist": false,
+        "managed": false,
+        "mentionable": false,
+        "permissions": "3072",
+        "position": 0,
+        "flags": 0,
+    })
+}
+
+fn text_channel() -> Channel {
+    serde_json::from_value(json!({
+        "id": TEXT_CHANNEL.to_string(),
+        "guild_id": GUILD_ID.to_string(),
+        "type": 0,
+        "name": "general",
+        "position": 0,
+        "parent_id": null,
+        "permission_overwrites": [],
+    }))
+    .unwrap()
+}
+
+fn thread(archived: bool) -> Channel {
+    serde_json::from_value(json!({
+        "id": THREAD.to_string(),
+        "guild_id": GUILD_ID.to_string(),
+        "type": 11,
+        "name": "thread",
+        "parent_id": TEXT_CHANNEL.to_string(),
+        "owner_id": MEMBER_ID.to_string(),
+        "thread_metadata": {
+            "archived": archived,
+            "auto_archive_duration": 60,
+            "archive_timestamp": "2020-01-01T00:00:00.000000+00:00",
+            "locked": false,
+        },
+    }))
+    .unwrap()
+}
+
+/// A guild with the owner as its only member and the general channel.
+fn guild() -> Guild {
+    serde_json::from_value(json!({
+        "id": GUILD_ID.to_string(),
+        "name": "Test",
+        "icon": null,
+        "owner_id": OWNER_ID.to_string(),
+        "afk_channel_id": null,
+        "afk_timeout": 300,
+        "banner": null,
+        "default_message_notifications": 0,
+        "description": null,
+        "discovery_splash": null,
+        "emojis": [],
+        "explicit_content_filter": 0,
+        "features": [],
+        "max_video_channel_users": 25,
+        "member_count": 1,
+        "members": [member(OWNER_ID)],
+        "channels": [text_channel()],
+        "threads": [],
+        "mfa_level": 0,
+        "nsfw_level": 0,
+        "preferred_locale": "en-US",
+        "premium_progress_bar_enabled": false,
+        "premium_tier": 0,
+        "public_updates_channel_id": null,
+        "roles": [everyone_role_json("@everyone")],
+        "rules_channel_id": null,
+        "safety_alerts_channel_id": null,
+        "splash": null,
+        "system_channel_flags": 0,
+        "system_channel_id": null,
+        "vanity_url_code": null,
+        "verification_level": 0,
+        "widget_channel_id": null,
+    }))
+    .unwrap()
+}
+
+async fn cached_guild() -> (Cache, Arc<MemoryBackend>) {
+    cached_guild_with(None).await
+}
+
+async fn cached_guild_with(local: Option<LocalCacheConfig>) -> (Cache, Arc<MemoryBackend>) {
+    let backend = Arc::new(MemoryBackend::new());
+    let cache = Cache::with_backend(backend.clone(), local);
+    cache
+        .update(&GuildCreate::Available(guild()))
+        .await
+        .unwrap();
+    (cache, backend)
+}
+
+#[tokio::test]
+async fn guild_create_caches_guild_members_and_channels() {
+    let (cache, _) = cached_guild().await;
+    let guild_id = GuildId::new(GUILD_ID);
+
+    let guild = cache.guild(guild_id).await.unwrap().unwrap();
+    assert_eq!(guild.member_count, 1);
+    assert!(guild.channels.contains(&ChannelId::new(TEXT_CHANNEL)));
+    assert!(guild.roles.contains(&RoleId::new(GUILD_ID)));
+
+    let members = cache.guild_members_set(guild_id).await.unwrap();
+    assert_eq!(
+        members.into_iter().collect::<Vec<_>>(),
+        [UserId::new(OWNER_ID)]
+    );
+    assert!(cache
+        .guild_member(guild_id, UserId::new(OWNER_ID))
+        .await
+        .unwrap()
+        .is_some());
+    assert!(matches!(
+        cache.channel(ChannelId::new(TEXT_CHANNEL)).await.unwrap(),
+        Some(CachedChannel::Text(_))
+    ));
+}
+
+#[tokio::test]
+async fn member_events_keep_the_member_count_in_sync() {
+    let (cache, _) = cached_guild().await;
+    let guild_id = GuildId::new(GUILD_ID);
+    let user_id = UserId::new(MEMBER_ID);
+
+    cache
+        .update(&MemberAdd {
+            guild_id: Id::new(GUILD_ID),
+            member: member(MEMBER_ID),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        cache.guild(guild_id).await.unwrap().unwrap().member_count,
+        2
+    );
+    assert!(cache
+        .guild_member(guild_id, user_id)
+        .await
+        .unwrap()
+        .is_some());
+    assert!(cache
+        .guild_members_set(guild_id)
+        .await
+        .unwrap()
+        .contains(&user_id));
+
+    cache
+        .update(&MemberRemove {
+            guild_id: Id::new(GUILD_ID),
+            user: user(MEMBER_ID),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        cache.guild(guild_id).await.unwrap().unwrap().member_count,
+        1
+    );
+    assert!(cache
+        .guild_member(guild_id, user_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(!cache
+        .guild_members_set(guild_id)
+        .await
+        .unwrap()
+        .contains(&user_id));
+}
+
+#[tokio::test]
+async fn archived_threads_are_removed() {
+    let (cache, _) = cached_guild().await;
+    let guild_id = GuildId::new(GUILD_ID);
+    let thread_id = ChannelId::new(THREAD);
+
+    cache.update(&ThreadCreate(thread(false))).await.unwrap();
+    assert!(matches!(
+        cache.channel(thread_id).await.unwrap(),
+        Some(CachedChannel::Thread(t)) if t.parent_id == ChannelId::new(TEXT_CHANNEL)
+    ));
+    let guild = cache.guild(guild_id).await.unwrap().unwrap();
+    assert!(guild.channels.contains(&thread_id));
+
+    cache.update(&ThreadUpdate(thread(true))).await.unwrap();
+    assert!(cache.channel(thread_id).await.unwrap().is_none());
+    let guild = cache.guild(guild_id).await.unwrap().unwrap();
+    assert!(!guild.channels.contains(&thread_id));
+}
+
+#[tokio::test]
+async fn guild_delete_removes_guild_data() {
+    let (cache, backend) = cached_guild().await;
+    let guild_id = GuildId::new(GUILD_ID);
+    cache.update(&ChannelCreate(text_channel())).await.unwrap();
+
+    cache
+        .update(&GuildDelete {
+            id: Id::new(GUILD_ID),
+            unavailable: Some(true),
+        })
+        .await
+        .unwrap();
+    assert!(cache.guild(guild_id).await.unwrap().is_some());
+
+    cache
+        .update(&GuildDelete {
+            id: Id::new(GUILD_ID),
+            unavailable: None,
+        })
+        .await
+        .unwrap();
+    assert!(cache.guild(guild_id).await.unwrap().is_none());
+
+    let keys = backend.keys();
+    for key in [
+        CachedGuild::key(guild_id),
+        CachedGuild::member_count_key(guild_id),
+        CachedGuild::members_key(guild_id),
+        CachedMember::key(guild_id, UserId::new(OWNER_ID)),
+        CachedChannel::key(ChannelId::new(TEXT_CHANNEL)),
+        CachedRole::key(RoleId::new(GUILD_ID)),
+    ] {
+        assert!(!keys.contains(&key), "{key} was not removed");
+    }
+    // Users are shared between guilds and outlive them.
+    assert!(backend
+        .get(&CachedUser::key(UserId::new(OWNER_ID)))
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn local_cache_is_invalidated_by_events() {
+    let (cache, _) = cached_guild_with(Some(LocalCacheConfig::default())).await;
+    let guild_id = GuildId::new(GUILD_ID);
+
+    assert_eq!(
+        cache.guild(guild_id).await.unwrap().unwrap().member_count,
+        1
+    );
+    assert_eq!(
+        cache.guild(guild_id).await.unwrap().unwrap().member_count,
+        1
+    );
+    let stats = cache.local_stats().unwrap();
+    // The guild and its member count are misses the first time only.
+    assert_eq!((stats.hits, stats.misses), (2, 2));
+
+    cache
+        .update(&MemberAdd {
+            guild_id: Id::new(GUILD_ID),
+            member: member(MEMBER_ID),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        cache.guild(guild_id).await.unwrap().unwrap().member_count,
+        2
+    );
+    let stats = cache.local_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses), (3, 3));
+}
+
+#[tokio::test]
+async fn members_are_not_kept_locally() {
+    let (cache, backend) = cached_guild_with(Some(LocalCacheConfig::default())).await;
+    // Another process sharing the backend.
+    let other = Cache::with_backend(backend, None);
+    let guild_id = GuildId::new(GUILD_ID);
+
+    assert!(cache
+        .guild_member(guild_id, UserId::new(MEMBER_ID))
+        .await
+        .unwrap()
+        .is_none());
+    other
+        .cache_member(guild_id, &member(MEMBER_ID))
+        .await
+        .unwrap();
+    assert!(cache
+        .guild_member(guild_id, UserId::new(MEMBER_ID))
+        .await
+        .unwrap()
+        .is_some());
+    assert_eq!(cache.local_stats().unwrap().entries, 0);
+}
+
+#[tokio::test]
+async fn local_roles_are_invalidated_by_role_events() {
+    let (cache, _) = cached_guild_with(Some(LocalCacheConfig::default())).await;
+    let role_ids = || std::iter::once(RoleId::new(GUILD_ID));
+
+    assert_eq!(
+        cache.guild_roles(role_ids()).await.unwrap()[0].name,
+        "@everyone"
+    );
+    assert_eq!(
+        cache.guild_roles(role_ids()).await.unwrap()[0].name,
+        "@everyone"
+    );
+    let stats = cache.local_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+
+    cache
+        .update(&RoleUpdate {
+            guild_id: Id::new(GUILD_ID),
+            role: serde_json::from_value(everyone_role_json("everyone")).unwrap(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        cache.guild_roles(role_ids()).await.unwrap()[0].name,
+        "everyone"
+    );
+
+    cache
+        .update(&RoleDelete {
+            guild_id: Id::new(GUILD_ID),
+            role_id: Id::new(GUILD_ID),
+        })
+        .await
+        .unwrap();
+    assert!(cache.guild_roles(role_ids()).await.unwrap().is_empty());
+}
@@ -0,0 +1,144 @@
+pub mod set;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use rowifi_database::Database;
+use rowifi_models::id::GuildId;
+use std::{
+    error::Error as StdError,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
+
+use crate::{error::RoError, repos::GuildRepo};
+
+/// Encrypts the Open Cloud API keys of servers before they are stored.
+///
+/// Keys are sealed with AES-256-GCM under a master key that never touches the database. The
+/// server id is used as associated data, so a stored key cannot be moved to another server.
+pub struct ApiKeyCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+#[derive(Debug)]
+pub enum ApiKeyCipherError {
+    /// The master key is not 32 bytes of base64.
+    InvalidMasterKey,
+    /// The stored key was tampered with or sealed under another master key.
+    Decrypt,
+}
+
+impl ApiKeyCipher {
+    /// Create the cipher from a base64 encoded 32 byte master key.
+    pub fn from_base64(master_key: &str) -> Result<Self, ApiKeyCipherError> {
+        let bytes = STANDARD
+            .decode(master_key.trim())
+            .map_err(|_| ApiKeyCipherError::InvalidMasterKey)?;
+        let key = UnboundKey::new(&AES_256_GCM, &bytes)
+            .map_err(|_| ApiKeyCipherError::InvalidMasterKey)?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Seal the API key of a server. The random nonce is prepended to the output.
+    #[must_use]
+    pub fn encrypt(&self, guild_id: GuildId, api_key: &str) -> Vec<u8> {
+        let mut nonce = [0; NONCE_LEN];
+        self.rng.fill(&mut nonce).unwrap();
+
+        let mut sealed = api_key.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(guild_id.get().to_be_bytes()),
+                &mut sealed,
+            )
+            .unwrap();
+
+        let mut out = nonce.to_vec();
+        out.extend(sealed);
+        out
+    }
+
+    pub fn decrypt(&self, guild_id: GuildId, sealed: &[u8]) -> Result<String, ApiKeyCipherError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(ApiKeyCipherError::Decrypt);
+        }
+        let (nonce, sealed) = sealed.split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| ApiKeyCipherError::Decrypt)?;
+
+        let mut sealed = sealed.to_vec();
+        let api_key = self
+            .key
+            .open_in_place(nonce, Aad::from(guild_id.get().to_be_bytes()), &mut sealed)
+            .map_err(|_| ApiKeyCipherError::Decrypt)?;
+        String::from_utf8(api_key.to_vec()).map_err(|_| ApiKeyCipherError::Decrypt)
+    }
+}
+
+impl Debug for ApiKeyCipher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ApiKeyCipher").finish_non_exhaustive()
+    }
+}
+
+/// Get the decrypted Open Cloud API key of the server, if it registered one.
+pub async fn guild_api_key(
+    database: &Database,
+    cipher: &ApiKeyCipher,
+    guild_id: GuildId,
+) -> Result<Option<String>, RoError> {
+    let sealed = GuildRepo::new(database).open_cloud_key(guild_id).await?;
+    match sealed {
+        Some(sealed) => Ok(Some(cipher.decrypt(guild_id, &sealed)?)),
+        None => Ok(None),
+    }
+}
+
+impl Display for ApiKeyCipherError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidMasterKey => f.write_str("the master key must be 32 bytes of base64"),
+            Self::Decrypt => f.write_str("failed to decrypt the stored api key"),
+        }
+    }
+}
+
+impl StdError for ApiKeyCipherError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER_KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = ApiKeyCipher::from_base64(MASTER_KEY).unwrap();
+        let sealed = cipher.encrypt(GuildId::new(1), "secret-key");
+        assert!(!sealed.windows(10).any(|w| w == b"secret-key"));
+        assert_eq!(
+            cipher.decrypt(GuildId::new(1), &sealed).unwrap(),
+            "secret-key"
+        );
+    }
+
+    #[test]
+    fn test_bound_to_guild() {
+        let cipher = ApiKeyCipher::from_base64(MASTER_KEY).unwrap();
+        let sealed = cipher.encrypt(GuildId::new(1), "secret-key");
+        assert!(cipher.decrypt(GuildId::new(2), &sealed).is_err());
+    }
+
+    #[test]
+    fn test_invalid_master_key() {
+        assert!(ApiKeyCipher::from_base64("AAEC").is_err());
+        assert!(ApiKeyCipher::from_base64("not base64").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::Utc;
+use rowifi_database::Database;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, SettingModify},
+    id::{GuildId, UserId},
+    roblox::api_key::ApiKeyInfo,
+};
+use rowifi_roblox::{error::ApiErrorKind, RobloxClient};
+
+use super::ApiKeyCipher;
+use crate::{error::RoError, guild::write_config, repos::GuildRepo};
+
+/// The scopes that workflows and the datastore commands need, as `(scope, operation)`. Workflows
+/// read and update entries, the datastore commands list, create and delete them as well.
+pub const REQUIRED_SCOPES: &[(&str, &str)] = &[
+    ("universe-datastores.objects", "list"),
+    ("universe-datastores.objects", "read"),
+    ("universe-datastores.objects", "create"),
+    ("universe-datastores.objects", "update"),
+    ("universe-datastores.objects", "delete"),
+    ("universe-messaging-service", "publish"),
+];
+
+#[derive(Debug)]
+pub enum SetApiKeyError {
+    /// Roblox does not recognise the key, or it is disabled or expired.
+    InvalidKey,
+    /// The key lacks some of the [`REQUIRED_SCOPES`], formatted as `scope:operation`.
+    MissingScopes(Vec<String>),
+    Generic(RoError),
+}
+
+/// Registers the Open Cloud API key the server's workflows and datastore commands run with, or
+/// goes back to the bot's own key if `api_key` is [`None`]. The key is checked against
+/// [`REQUIRED_SCOPES`] and stored encrypted.
+///
+/// # Errors
+///
+/// See [`SetApiKeyError`] for details.
+pub async fn set_api_key(
+    roblox: &RobloxClient,
+    database: &Database,
+    cipher: &ApiKeyCipher,
+    guild_id: GuildId,
+    author_id: UserId,
+    api_key: Option<&str>,
+) -> Result<Option<ApiKeyInfo>, SetApiKeyError> {
+    let info = match api_key {
+        Some(api_key) => Some(validate_api_key(roblox, api_key).await?),
+        None => None,
+    };
+    let sealed = api_key.map(|k| cipher.encrypt(guild_id, k));
+
+    let log = AuditLog {
+        kind: AuditLogKind::SettingModify,
+        guild_id: Some(guild_id),
+        user_id: Some(author_id),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::SettingModify(SettingModify {
+            setting: "open_cloud_key".into(),
+            value: info
+                .as_ref()
+                .map_or_else(|| "None".into(), |i| i.name.clone()),
+        }),
+    };
+
+    write_config(database, guild_id, None, &log, |transaction| {
+        let sealed = sealed.clone();
+        Box::pin(async move {
+            GuildRepo::new(transaction)
+                .set_open_cloud_key(guild_id, sealed.as_deref())
+                .await
+        })
+    })
+    .await?;
+
+    Ok(info)
+}
+
+async fn validate_api_key(
+    roblox: &RobloxClient,
+    api_key: &str,
+) -> Result<ApiKeyInfo, SetApiKeyError> {
+    let info = match roblox.introspect_api_key(api_key).await {
+        Ok(info) => info,
+        Err(err) => {
+            if let Some(error) = err.api_error() {
+                if matches!(
+                    error.kind,
+                    ApiErrorKind::NotFound
+                        | ApiErrorKind::Unauthorized
+                        | ApiErrorKind::InvalidArgument { .. }
+                ) {
+                    return Err(SetApiKeyError::InvalidKey);
+                }
+            }
+            return Err(RoError::from(err).into());
+        }
+    };
+    if !info.enabled || info.expired {
+        return Err(SetApiKeyError::InvalidKey);
+    }
+
+    let missing = REQUIRED_SCOPES
+        .iter()
+        .filter(|(scope, operation)| !info.allows(scope, operation))
+        .map(|(scope, operation)| format!("{scope}:{operation}"))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(SetApiKeyError::MissingScopes(missing));
+    }
+
+    Ok(info)
+}
+
+impl From<RoError> for SetApiKeyError {
+    fn from(err: RoError) -> Self {
+        SetApiKeyError::Generic(err)
+    }
+}
@@ -1,6 +1,7 @@
-use crate::error::RoError;
+use super::{asset_info, asset_name, AssetInfo};
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindCreate},
     bind::{AssetType, Assetbind, BindType, Template},
@@ -8,18 +9,26 @@ use rowifi_models::{
     id::{GuildId, RoleId, UserId},
     roblox::id::AssetId,
 };
+use rowifi_roblox::RobloxClient;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(Debug, Serialize)]
 pub struct AddAssetbind {
     pub bind: Assetbind,
+    pub info: AssetInfo,
     pub ignored_roles: Vec<RoleId>,
     pub modified: bool,
 }
 
 #[derive(Debug)]
 pub enum AddAssetbindError {
+    /// There is no item of any type with this ID.
+    NotFound,
+    /// The ID belongs to an item of another type.
+    WrongType {
+        actual: AssetType,
+    },
     Generic(RoError),
 }
 
@@ -33,20 +42,25 @@ pub struct AssetbindArguments {
 }
 
 /// Adds a assetbind to the server. Modifies it if the assetbind already exists.
-/// Validates the discord roles if they exist and are not managed.
+/// Validates that the item exists as the chosen type, and the discord roles if they exist and
+/// are not managed.
 ///
 /// # Errors
 ///
 /// See [`AddAssetbindError`] for details.
+#[allow(clippy::too_many_arguments)]
 pub async fn add_assetbind(
+    roblox: &RobloxClient,
     database: &Database,
     guild_id: GuildId,
     author_id: UserId,
-    mut existing_assetbinds: Vec<Assetbind>,
+    existing_assetbinds: &[Assetbind],
+    config_version: i64,
     server_roles: &HashMap<RoleId, CachedRole>,
     args: AssetbindArguments,
 ) -> Result<AddAssetbind, AddAssetbindError> {
-    // TODO: Check for a way to validate an asset
+    let info = validate_asset(roblox, args.kind, args.asset_id).await?;
+
     let mut ignored_roles = Vec::new();
     let mut roles_to_add = Vec::new();
     // Check if the discord roles provided exist or if they are some integration's roles.
@@ -68,26 +82,9 @@ pub async fn add_assetbind(
         template: args.template,
     };
 
-    let mut modified = false;
-    if let Some(bind) = existing_assetbinds
-        .iter_mut()
-        .find(|r| r.asset_id == new_bind.asset_id)
-    {
-        bind.priority = new_bind.priority;
-        bind.template = new_bind.template.clone();
-        bind.discord_roles.clone_from(&new_bind.discord_roles);
-        modified = true;
-    } else {
-        existing_assetbinds.push(new_bind.clone());
-    }
-
-    database
-        .execute(
-            "UPDATE guilds SET assetbinds = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(existing_assetbinds)],
-        )
-        .await
-        .map_err(RoError::from)?;
+    let modified = existing_assetbinds
+        .iter()
+        .any(|r| r.asset_id == new_bind.asset_id);
 
     let log = AuditLog {
         kind: AuditLogKind::BindCreate,
@@ -100,28 +97,54 @@ pub async fn add_assetbind(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-    VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await
-        .map_err(RoError::from)?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let bind = new_bind.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .upsert_assetbind(guild_id, &bind)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     Ok(AddAssetbind {
         bind: new_bind,
+        info,
         ignored_roles,
         modified,
     })
 }
 
+/// Look up the item as the chosen type. If it does not exist, check the other types so that a
+/// badge ID bound as a gamepass can be told apart from a typo.
+///
+/// # Errors
+///
+/// See [`AddAssetbindError`] for details.
+pub async fn validate_asset(
+    roblox: &RobloxClient,
+    kind: AssetType,
+    asset_id: AssetId,
+) -> Result<AssetInfo, AddAssetbindError> {
+    if let Some(info) = asset_info(roblox, kind, asset_id).await? {
+        return Ok(info);
+    }
+
+    for other in [AssetType::Asset, AssetType::Badge, AssetType::Gamepass] {
+        if other != kind && asset_name(roblox, other, asset_id).await?.is_some() {
+            return Err(AddAssetbindError::WrongType { actual: other });
+        }
+    }
+    Err(AddAssetbindError::NotFound)
+}
+
 impl From<RoError> for AddAssetbindError {
     fn from(err: RoError) -> Self {
         Self::Generic(err)
@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindDelete},
     bind::{Assetbind, BindType},
@@ -9,7 +9,7 @@ use rowifi_models::{
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 
 #[derive(Debug, Serialize)]
 pub struct DeleteAssetbind {
@@ -25,6 +25,7 @@ pub struct DeleteAssetbind {
 pub async fn delete_assetbinds(
     database: &Database,
     assetbinds: &[Assetbind],
+    config_version: i64,
     guild_id: GuildId,
     author_id: UserId,
     args: Vec<AssetId>,
@@ -51,17 +52,7 @@ pub async fn delete_assetbinds(
         });
     }
 
-    let new_assetbinds = assetbinds
-        .iter()
-        .filter(|r| !binds_to_delete.contains(&r.asset_id))
-        .cloned()
-        .collect::<Vec<_>>();
-    database
-        .execute(
-            "UPDATE guilds SET assetbinds = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(new_assetbinds)],
-        )
-        .await?;
+    let asset_ids = binds_to_delete.iter().copied().collect::<Vec<_>>();
 
     #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
     let log = AuditLog {
@@ -75,19 +66,22 @@ pub async fn delete_assetbinds(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let asset_ids = asset_ids.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .delete_assetbinds(guild_id, &asset_ids)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     #[allow(clippy::cast_possible_truncation)]
     Ok(DeleteAssetbind {
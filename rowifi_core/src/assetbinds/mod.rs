@@ -1,2 +1,126 @@
 pub mod add;
 pub mod delete;
+
+use futures_util::TryStreamExt;
+use rowifi_database::Database;
+use rowifi_models::{
+    bind::{AssetType, Assetbind},
+    id::GuildId,
+    roblox::{asset::IconKind, id::AssetId, thumbnail::Thumbnail},
+};
+use rowifi_roblox::{fan_out, RobloxClient};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::{error::RoError, repos::BindRepo};
+
+/// The name and icon of the asset, badge or gamepass an assetbind is for.
+#[derive(Clone, Debug, Serialize)]
+pub struct AssetInfo {
+    pub name: String,
+    /// Missing while Roblox is still generating the icon or if it was moderated.
+    pub icon_url: Option<String>,
+}
+
+/// Get the assetbinds of the server in the order they were added.
+pub async fn guild_assetbinds(
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<Vec<Assetbind>, RoError> {
+    let assetbinds = BindRepo::new(database).assetbinds(guild_id).await?;
+    Ok(assetbinds)
+}
+
+/// Look up the item with the given ID as the given type. Returns [`None`] if there is no such
+/// item of that type.
+pub async fn asset_info(
+    roblox: &RobloxClient,
+    kind: AssetType,
+    asset_id: AssetId,
+) -> Result<Option<AssetInfo>, RoError> {
+    let Some(name) = asset_name(roblox, kind, asset_id).await? else {
+        return Ok(None);
+    };
+    let icon_url = asset_icons(roblox, kind, &[asset_id.0])
+        .await
+        .into_iter()
+        .next()
+        .and_then(|t| t.image_url);
+    Ok(Some(AssetInfo { name, icon_url }))
+}
+
+/// Look up the items of many assetbinds at once. Binds whose item no longer exists are left out.
+pub async fn asset_infos(
+    roblox: &RobloxClient,
+    assetbinds: &[Assetbind],
+) -> Result<HashMap<AssetId, AssetInfo>, RoError> {
+    let items = assetbinds
+        .iter()
+        .map(|b| (b.asset_type, b.asset_id))
+        .collect::<Vec<_>>();
+    let names = fan_out(items, |(kind, asset_id)| async move {
+        let name = asset_name(roblox, kind, asset_id).await?;
+        Ok::<_, RoError>(name.map(|name| (asset_id, name)))
+    })
+    .try_filter_map(|name| async move { Ok(name) })
+    .try_collect::<HashMap<_, _>>()
+    .await?;
+
+    let mut icons = HashMap::new();
+    for kind in [AssetType::Asset, AssetType::Badge, AssetType::Gamepass] {
+        let ids = assetbinds
+            .iter()
+            .filter(|b| b.asset_type == kind && names.contains_key(&b.asset_id))
+            .map(|b| b.asset_id.0)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            continue;
+        }
+        for thumbnail in asset_icons(roblox, kind, &ids).await {
+            if let Some(image_url) = thumbnail.image_url {
+                icons.insert(AssetId(thumbnail.target_id), image_url);
+            }
+        }
+    }
+
+    Ok(names
+        .into_iter()
+        .map(|(asset_id, name)| {
+            let icon_url = icons.remove(&asset_id);
+            (asset_id, AssetInfo { name, icon_url })
+        })
+        .collect())
+}
+
+async fn asset_name(
+    roblox: &RobloxClient,
+    kind: AssetType,
+    asset_id: AssetId,
+) -> Result<Option<String>, RoError> {
+    let name = match kind {
+        AssetType::Asset => roblox.get_asset_details(asset_id).await?.map(|a| a.name),
+        AssetType::Badge => roblox.get_badge(asset_id.0).await?.map(|b| b.name),
+        AssetType::Gamepass => roblox.get_game_pass(asset_id.0).await?.map(|g| g.name),
+    };
+    Ok(name)
+}
+
+/// Icons are only decoration, so the items are still described without them if Roblox fails to
+/// return them.
+async fn asset_icons(roblox: &RobloxClient, kind: AssetType, ids: &[u64]) -> Vec<Thumbnail> {
+    match roblox.get_icons(icon_kind(kind), ids).await {
+        Ok(icons) => icons,
+        Err(err) => {
+            tracing::warn!(err = ?err, ?kind, "failed to get the icons of assetbind items");
+            Vec::new()
+        }
+    }
+}
+
+fn icon_kind(kind: AssetType) -> IconKind {
+    match kind {
+        AssetType::Asset => IconKind::Asset,
+        AssetType::Badge => IconKind::Badge,
+        AssetType::Gamepass => IconKind::GamePass,
+    }
+}
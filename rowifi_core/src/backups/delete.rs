@@ -1,7 +1,7 @@
 use rowifi_database::Database;
 use rowifi_models::id::UserId;
 
-use crate::error::RoError;
+use crate::{error::RoError, repos::BackupRepo};
 
 pub struct BackupArguments {
     pub name: String,
@@ -12,11 +12,6 @@ pub async fn delete_backup(
     author: UserId,
     args: BackupArguments,
 ) -> Result<bool, RoError> {
-    let rows = database
-        .execute(
-            "DELETE FROM backups WHERE user_id = $1 AND name = $2",
-            &[&author, &args.name],
-        )
-        .await?;
-    Ok(rows > 0)
+    let deleted = BackupRepo::new(database).delete(author, &args.name).await?;
+    Ok(deleted)
 }
@@ -1,16 +1,16 @@
 use rowifi_cache::Cache;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     backup::{
         BackupAssetbind, BackupBypassRole, BackupCustombind, BackupGroupbind, BackupGuild,
         BackupRankbind,
     },
-    guild::PartialRoGuild,
+    guild::{GuildBinds, PartialRoGuild},
     id::UserId,
 };
 use std::collections::HashMap;
 
-use crate::error::RoError;
+use crate::{error::RoError, repos::BackupRepo};
 
 pub struct BackupArguments {
     pub name: String,
@@ -22,6 +22,7 @@ pub async fn create_backup(
     database: &Database,
     cache: &Cache,
     guild: PartialRoGuild,
+    binds: GuildBinds,
     args: BackupArguments,
 ) -> Result<(), RoError> {
     let server = cache.guild(guild.guild_id).await?.unwrap();
@@ -52,12 +53,12 @@ pub async fn create_backup(
             })
         })
         .collect();
-    let rankbinds = guild
+    let rankbinds = binds
         .rankbinds
         .iter()
         .map(|r| BackupRankbind {
             group_id: r.group_id,
-            roblox_rank_id: r.roblox_rank_id.clone(),
+            roblox_rank_id: r.roblox_rank_id,
             group_rank_id: r.group_rank_id,
             priority: r.priority,
             template: r.template.clone(),
@@ -68,7 +69,7 @@ pub async fn create_backup(
                 .collect(),
         })
         .collect();
-    let groupbinds = guild
+    let groupbinds = binds
         .groupbinds
         .iter()
         .map(|g| BackupGroupbind {
@@ -82,7 +83,7 @@ pub async fn create_backup(
                 .collect(),
         })
         .collect();
-    let custombinds = guild
+    let custombinds = binds
         .custombinds
         .iter()
         .map(|c| BackupCustombind {
@@ -97,7 +98,7 @@ pub async fn create_backup(
                 .collect(),
         })
         .collect();
-    let assetbinds = guild
+    let assetbinds = binds
         .assetbinds
         .iter()
         .map(|a| BackupAssetbind {
@@ -122,18 +123,15 @@ pub async fn create_backup(
         assetbinds,
         custombinds,
         xp_binds: guild.xp_binds.clone(),
-        deny_lists: guild.deny_lists.clone(),
+        deny_lists: binds.deny_lists,
         default_template: guild.default_template.unwrap_or_default(),
         update_on_join: guild.update_on_join.unwrap_or_default(),
         event_types: guild.event_types.clone(),
         auto_detection: guild.auto_detection.unwrap_or_default(),
         sync_xp_on_setrank: guild.sync_xp_on_setrank.unwrap_or_default(),
     };
-    database
-        .execute(
-            "INSERT INTO backups(user_id, name, data) VALUES($1, $2, $3) ON CONFLICT(user_id, name) DO UPDATE SET data = $3",
-            &[&args.author, &args.name, &Json(backup)],
-        )
+    BackupRepo::new(database)
+        .upsert(args.author, &args.name, &backup)
         .await?;
 
     Ok(())
@@ -1,15 +1,23 @@
+use chrono::Utc;
 use rowifi_cache::Cache;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::{Database, DatabaseError, Transaction};
 use rowifi_models::{
-    backup::BackupGuild,
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, SettingModify},
     bind::{Assetbind, Custombind, Groupbind, Rankbind},
-    guild::BypassRole,
+    guild::{BypassRole, GuildBinds, PartialRoGuild},
     id::{GuildId, RoleId, UserId},
 };
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use twilight_http::Client as TwilightClient;
 
-use crate::error::RoError;
+use crate::{
+    error::RoError,
+    guild::write_config,
+    repos::{BackupRepo, BindRepo, DenyListRepo, GuildRepo},
+};
 
 pub struct BackupArguments {
     pub name: String,
@@ -20,10 +28,6 @@ pub enum BackupError {
     Other(RoError),
 }
 
-pub struct BackupGuildRow {
-    pub data: Json<BackupGuild>,
-}
-
 #[allow(clippy::too_many_lines)]
 pub async fn restore_backup(
     database: &Database,
@@ -33,16 +37,11 @@ pub async fn restore_backup(
     args: BackupArguments,
     guild_id: GuildId,
 ) -> Result<(), BackupError> {
-    let backup_guild = database
-        .query_opt::<BackupGuildRow>(
-            "SELECT data FROM backups WHERE user_id = $1 and name = $2",
-            &[&author, &args.name],
-        )
+    let backup_guild = BackupRepo::new(database)
+        .get(author, &args.name)
         .await
         .map_err(|err| BackupError::Other(err.into()))?;
-    let backup_guild = if let Some(b) = backup_guild {
-        b.data.0
-    } else {
+    let Some(backup_guild) = backup_guild else {
         return Err(BackupError::NotFound);
     };
 
@@ -123,7 +122,7 @@ pub async fn restore_backup(
         .map(|b| Rankbind {
             group_id: b.group_id,
             group_rank_id: b.group_rank_id,
-            roblox_rank_id: b.roblox_rank_id.clone(),
+            roblox_rank_id: b.roblox_rank_id,
             priority: b.priority,
             template: b.template.clone(),
             discord_roles: b
@@ -188,37 +187,92 @@ pub async fn restore_backup(
         })
         .collect::<Vec<_>>();
 
-    database.execute(
-        "UPDATE guilds SET bypass_roles = $1, unverified_roles = $2, verified_roles = $3, rankbinds = $4, groupbinds = $5, assetbinds = $6, custombinds = $7, xp_binds = $8, deny_lists = $9, default_template = $10, update_on_join = $11, event_types = $12, auto_detection = $13, sync_xp_on_setrank = $14 WHERE guild_id = $15", 
-        &[
-            &Json(bypass_roles),
-            &unverified_roles,
-            &verified_roles,
-            &Json(rankbinds),
-            &Json(groupbinds),
-            &Json(custombinds),
-            &Json(assetbinds),
-            &Json(backup_guild.xp_binds),
-            &Json(backup_guild.deny_lists),
-            &backup_guild.default_template,
-            &backup_guild.update_on_join,
-            &Json(backup_guild.event_types),
-            &backup_guild.auto_detection,
-            &backup_guild.sync_xp_on_setrank,
-            &guild_id
-        ])
+    let log = AuditLog {
+        kind: AuditLogKind::SettingModify,
+        guild_id: Some(guild_id),
+        user_id: Some(author),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::SettingModify(SettingModify {
+            setting: "backup".into(),
+            value: args.name,
+        }),
+    };
+    let guild = PartialRoGuild {
+        bypass_roles,
+        unverified_roles,
+        verified_roles,
+        xp_binds: backup_guild.xp_binds,
+        default_template: Some(backup_guild.default_template),
+        update_on_join: Some(backup_guild.update_on_join),
+        event_types: backup_guild.event_types,
+        auto_detection: Some(backup_guild.auto_detection),
+        sync_xp_on_setrank: Some(backup_guild.sync_xp_on_setrank),
+        ..PartialRoGuild::new(guild_id)
+    };
+    let binds = GuildBinds {
+        rankbinds,
+        groupbinds,
+        assetbinds,
+        custombinds,
+        deny_lists: backup_guild.deny_lists,
+    };
+    let restored = Arc::new((guild, binds));
+    write_config(database, guild_id, None, &log, |transaction| {
+        let restored = Arc::clone(&restored);
+        Box::pin(async move {
+            let (guild, binds) = &*restored;
+            GuildRepo::new(transaction).restore_settings(guild).await?;
+            replace_binds(transaction, guild_id, binds).await
+        })
+    })
     .await
-    .map_err(|err| BackupError::Other(err.into()))?;
+    .map_err(BackupError::Other)?;
 
     Ok(())
 }
 
-impl TryFrom<rowifi_database::postgres::Row> for BackupGuildRow {
-    type Error = rowifi_database::postgres::Error;
-
-    fn try_from(row: rowifi_database::postgres::Row) -> Result<Self, Self::Error> {
-        let data = row.try_get("data")?;
+/// Replace the binds and deny lists of the server with the ones from the backup. Backups taken
+/// before binds had their own tables may contain duplicates, of which only the first is kept.
+async fn replace_binds(
+    transaction: &Transaction,
+    guild_id: GuildId,
+    binds: &GuildBinds,
+) -> Result<(), DatabaseError> {
+    let bind_repo = BindRepo::new(transaction);
+    let deny_list_repo = DenyListRepo::new(transaction);
+    bind_repo.clear(guild_id).await?;
+    deny_list_repo.clear(guild_id).await?;
 
-        Ok(Self { data })
+    let mut seen = HashSet::new();
+    for bind in &binds.rankbinds {
+        if seen.insert((bind.group_id, bind.group_rank_id)) {
+            bind_repo.upsert_rankbind(guild_id, bind).await?;
+        }
     }
+    let mut seen = HashSet::new();
+    for bind in &binds.groupbinds {
+        if seen.insert(bind.group_id) {
+            bind_repo.upsert_groupbind(guild_id, bind).await?;
+        }
+    }
+    let mut seen = HashSet::new();
+    for bind in &binds.assetbinds {
+        if seen.insert(bind.asset_id) {
+            bind_repo.upsert_assetbind(guild_id, bind).await?;
+        }
+    }
+    let mut seen = HashSet::new();
+    for bind in &binds.custombinds {
+        if seen.insert(bind.custom_bind_id) {
+            bind_repo.insert_custombind(guild_id, bind).await?;
+        }
+    }
+    let mut seen = HashSet::new();
+    for deny_list in &binds.deny_lists {
+        if seen.insert(deny_list.id) {
+            deny_list_repo.insert(guild_id, deny_list).await?;
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,234 @@
+use rowifi_models::{
+    discord::{
+        cache::{CachedGuild, CachedMember, CachedRole},
+        guild::{Guild, Member, Permissions},
+    },
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter, Result as FmtResult},
+    hash::Hash,
+};
+use twilight_http::{error::ErrorType, Client as DiscordClient};
+
+use crate::error::RoError;
+
+/// A difference between what the cache holds for a server or member and what Discord returns.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Drift {
+    /// Discord knows about the server or member but the cache does not.
+    NotCached,
+    Name {
+        cached: String,
+        live: String,
+    },
+    Owner {
+        cached: UserId,
+        live: UserId,
+    },
+    /// Roles, of a server or a member, that Discord has and the cache is missing.
+    MissingRoles(Vec<RoleId>),
+    /// Roles that the cache still has but Discord does not.
+    StaleRoles(Vec<RoleId>),
+    RolePermissions {
+        id: RoleId,
+        cached: Permissions,
+        live: Permissions,
+    },
+    MissingChannels(Vec<ChannelId>),
+    StaleChannels(Vec<ChannelId>),
+    Nickname {
+        cached: Option<String>,
+        live: Option<String>,
+    },
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotCached => f.write_str("Not cached"),
+            Self::Name { cached, live } => write!(f, "Name: `{cached}` (live: `{live}`)"),
+            Self::Owner { cached, live } => write!(f, "Owner: <@{cached}> (live: <@{live}>)"),
+            Self::MissingRoles(roles) => write!(f, "Missing roles: {}", mentions(roles, "<@&")),
+            Self::StaleRoles(roles) => write!(f, "Stale roles: {}", mentions(roles, "<@&")),
+            Self::RolePermissions { id, cached, live } => write!(
+                f,
+                "Permissions of <@&{id}>: `{}` (live: `{}`)",
+                cached.bits(),
+                live.bits()
+            ),
+            Self::MissingChannels(channels) => {
+                write!(f, "Missing channels: {}", mentions(channels, "<#"))
+            }
+            Self::StaleChannels(channels) => {
+                write!(f, "Stale channels: {}", mentions(channels, "<#"))
+            }
+            Self::Nickname { cached, live } => write!(
+                f,
+                "Nickname: `{}` (live: `{}`)",
+                cached.as_deref().unwrap_or("None"),
+                live.as_deref().unwrap_or("None")
+            ),
+        }
+    }
+}
+
+/// Compares the cached server and its roles against the server fetched from Discord. Roles
+/// whose entry is missing from the cache are reported as missing even if the server lists them.
+#[must_use]
+pub fn guild_drift(
+    cached: Option<&CachedGuild>,
+    cached_roles: &[CachedRole],
+    live: &Guild,
+) -> Vec<Drift> {
+    let Some(cached) = cached else {
+        return vec![Drift::NotCached];
+    };
+    let live_guild = CachedGuild::from_guild(live);
+    let live_roles = live
+        .roles
+        .iter()
+        .map(|r| (RoleId(r.id), CachedRole::from_role(r)))
+        .collect::<HashMap<_, _>>();
+    let cached_roles = cached_roles
+        .iter()
+        .filter(|r| cached.roles.contains(&r.id))
+        .map(|r| (r.id, r))
+        .collect::<HashMap<_, _>>();
+
+    let mut drift = Vec::new();
+    if cached.name != live_guild.name {
+        drift.push(Drift::Name {
+            cached: cached.name.clone(),
+            live: live_guild.name.clone(),
+        });
+    }
+    if cached.owner_id != live_guild.owner_id {
+        drift.push(Drift::Owner {
+            cached: cached.owner_id,
+            live: live_guild.owner_id,
+        });
+    }
+
+    let (missing, stale) = difference(&cached_roles.keys().copied().collect(), &live_guild.roles);
+    if !missing.is_empty() {
+        drift.push(Drift::MissingRoles(missing));
+    }
+    if !stale.is_empty() {
+        drift.push(Drift::StaleRoles(stale));
+    }
+
+    let mut role_ids = cached_roles.keys().copied().collect::<Vec<_>>();
+    role_ids.sort_unstable();
+    for id in role_ids {
+        let (cached, Some(live)) = (cached_roles[&id], live_roles.get(&id)) else {
+            continue;
+        };
+        if cached.permissions != live.permissions {
+            drift.push(Drift::RolePermissions {
+                id,
+                cached: cached.permissions,
+                live: live.permissions,
+            });
+        }
+    }
+
+    let (missing, stale) = difference(&cached.channels, &live_guild.channels);
+    if !missing.is_empty() {
+        drift.push(Drift::MissingChannels(missing));
+    }
+    if !stale.is_empty() {
+        drift.push(Drift::StaleChannels(stale));
+    }
+
+    drift
+}
+
+/// Compares the cached member against the member fetched from Discord.
+#[must_use]
+pub fn member_drift(cached: Option<&CachedMember>, live: &Member) -> Vec<Drift> {
+    let Some(cached) = cached else {
+        return vec![Drift::NotCached];
+    };
+    let live = CachedMember::from_member(live);
+
+    let mut drift = Vec::new();
+    if cached.nickname != live.nickname {
+        drift.push(Drift::Nickname {
+            cached: cached.nickname.clone(),
+            live: live.nickname,
+        });
+    }
+
+    let (missing, stale) = difference(
+        &cached.roles.iter().copied().collect(),
+        &live.roles.into_iter().collect(),
+    );
+    if !missing.is_empty() {
+        drift.push(Drift::MissingRoles(missing));
+    }
+    if !stale.is_empty() {
+        drift.push(Drift::StaleRoles(stale));
+    }
+
+    drift
+}
+
+/// Fetches the server from Discord along with its channels and active threads, which the
+/// server endpoint does not return. Returns [`None`] if the bot is not in the server.
+pub async fn fetch_guild(
+    http: &DiscordClient,
+    guild_id: GuildId,
+) -> Result<Option<Guild>, RoError> {
+    let mut guild = match http.guild(guild_id.0).with_counts(true).await {
+        Ok(res) => res.model().await?,
+        Err(err) if is_not_found(&err) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    guild.channels = http.guild_channels(guild_id.0).await?.models().await?;
+    guild.threads = http
+        .active_threads(guild_id.0)
+        .await?
+        .model()
+        .await?
+        .threads;
+
+    Ok(Some(guild))
+}
+
+/// Fetches a member of the server from Discord. Returns [`None`] if they are not in the server.
+pub async fn fetch_member(
+    http: &DiscordClient,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Option<Member>, RoError> {
+    match http.guild_member(guild_id.0, user_id.0).await {
+        Ok(res) => Ok(Some(res.model().await?)),
+        Err(err) if is_not_found(&err) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn is_not_found(err: &twilight_http::Error) -> bool {
+    matches!(err.kind(), ErrorType::Response { status, .. } if *status == 404)
+}
+
+/// Returns the sorted ids that only `live` has and the ones that only `cached` has.
+fn difference<T: Copy + Eq + Hash + Ord>(
+    cached: &HashSet<T>,
+    live: &HashSet<T>,
+) -> (Vec<T>, Vec<T>) {
+    let mut missing = live.difference(cached).copied().collect::<Vec<_>>();
+    let mut stale = cached.difference(live).copied().collect::<Vec<_>>();
+    missing.sort_unstable();
+    stale.sort_unstable();
+    (missing, stale)
+}
+
+fn mentions<T: Display>(ids: &[T], prefix: &str) -> String {
+    ids.iter()
+        .map(|id| format!("{prefix}{id}>"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,41 @@
+use rowifi_cache::{permissions::can_send_messages, Cache};
+use rowifi_models::id::{ChannelId, GuildId, UserId};
+use serde::Serialize;
+
+use crate::error::RoError;
+
+/// Why a channel cannot be used to send messages to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum InvalidChannel {
+    /// The channel does not exist in the server, or the member is not cached.
+    NotFound,
+    /// Categories, forums and locked threads don't hold messages directly.
+    NotMessageable,
+    /// The member can't view the channel or send messages in it.
+    MissingPermissions,
+}
+
+/// Checks that the channel belongs to the server, holds messages and that `user_id`, usually the
+/// bot, can send messages in it. Returns why the channel can't be used otherwise.
+pub async fn validate_message_channel(
+    cache: &Cache,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    user_id: UserId,
+) -> Result<Option<InvalidChannel>, RoError> {
+    let Some((channel, permissions)) = cache
+        .channel_permissions(guild_id, channel_id, user_id)
+        .await?
+    else {
+        return Ok(Some(InvalidChannel::NotFound));
+    };
+
+    if !channel.is_messageable() {
+        return Ok(Some(InvalidChannel::NotMessageable));
+    }
+    if !can_send_messages(&channel, permissions) {
+        return Ok(Some(InvalidChannel::MissingPermissions));
+    }
+
+    Ok(None)
+}
@@ -5,27 +5,39 @@
 )]
 
 use regex::Regex;
+use rowifi_cache::Cache;
 use rowifi_database::{Database, DatabaseError};
 use rowifi_models::{
     custom::{
         action, ActionInputSource, ActionMetadata, ActionType, Value, ValueType, Workflow,
         WorkflowNode,
     },
-    id::{GuildId, UserId},
+    id::{ChannelId, GuildId, UserId},
     roblox::id::{UniverseId, UserId as RobloxUserId},
-    user::RoUser,
 };
 use rowifi_roblox::{
-    error::{ErrorKind, RobloxError},
-    RobloxClient, UpdateDatastoreEntryArgs,
+    error::RobloxError, MemoryStoreSortedMapItemArgs, RobloxClient, UpdateDatastoreEntryArgs,
 };
 use serde::Serialize;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     sync::LazyLock,
+    time::Duration,
 };
 use twilight_http::Client as TwilightClient;
 
+use crate::{
+    api_keys::{guild_api_key, ApiKeyCipher},
+    channels::{validate_message_channel, InvalidChannel},
+    error::RoError,
+    repos::UserRepo,
+};
+
+/// How long memory store items written by workflows are kept when no `ttl` input is given.
+const DEFAULT_MEMORY_STORE_TTL: Duration = Duration::from_secs(60 * 60);
+/// How long an item read from a queue stays hidden before it can be read again.
+const QUEUE_INVISIBILITY_WINDOW: Duration = Duration::from_secs(30);
+
 static TEMPLATE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{(.*?)\}").unwrap());
 
 pub struct ExecutionContext {
@@ -37,6 +49,9 @@ pub struct WorkflowContext<'a> {
     pub roblox: &'a RobloxClient,
     pub database: &'a Database,
     pub guild_id: GuildId,
+    /// Decrypts the Open Cloud API key registered by the server, which is then used instead of
+    /// the one `roblox` was created with. [`None`] if servers cannot register keys.
+    pub api_keys: Option<&'a ApiKeyCipher>,
 }
 
 pub struct WorkflowNodeExecution {
@@ -72,6 +87,8 @@ pub enum WorkflowExecutionError {
         id: usize,
         err: WorkflowNodeExecutionError,
     },
+    /// The API key registered by the server could not be loaded.
+    ApiKey(RoError),
 }
 
 pub struct ValidationContext {
@@ -92,7 +109,8 @@ pub enum WorkflowNodeExecutionError {
     IncorrectInputFormat,
     IncorrectOutputFormat,
     Discord(twilight_http::Error),
-    Roblox(RobloxError),
+    /// Boxed as it is the largest variant by far, which every `Result` of a node would pay for.
+    Roblox(Box<RobloxError>),
     Database(DatabaseError),
 }
 
@@ -125,6 +143,12 @@ pub enum WorkflowValidationError {
         id: usize,
         err: WorkflowNodeValidationError,
     },
+    /// A `SendMessage` node targets a channel the bot can't send messages in.
+    InvalidChannel {
+        id: usize,
+        channel_id: ChannelId,
+        reason: InvalidChannel,
+    },
 }
 
 pub async fn execute_workflow(
@@ -141,6 +165,20 @@ pub async fn execute_workflow(
             kind: ActionType::Start,
         })?;
 
+    let api_key = match workflow_context.api_keys {
+        Some(cipher) => guild_api_key(workflow_context.database, cipher, workflow_context.guild_id)
+            .await
+            .map_err(WorkflowExecutionError::ApiKey)?,
+        None => None,
+    };
+    let guild_roblox = api_key
+        .as_deref()
+        .map(|api_key| workflow_context.roblox.with_api_key(api_key));
+    let workflow_context = &WorkflowContext {
+        roblox: guild_roblox.as_ref().unwrap_or(workflow_context.roblox),
+        ..*workflow_context
+    };
+
     let mut queue = VecDeque::new();
     queue.push_back(start);
     while let Some(node) = queue.pop_front() {
@@ -377,11 +415,7 @@ pub async fn execute_node(
                     &entry_id,
                     UpdateDatastoreEntryArgs {
                         value: entry.value,
-                        users: entry
-                            .users
-                            .into_iter()
-                            .map(|u| RobloxUserId(u.parse().unwrap()))
-                            .collect(),
+                        users: entry.users.into_iter().map(|u| u.user_id).collect(),
                         attributes: Some(entry.attributes),
                     },
                 )
@@ -423,9 +457,8 @@ pub async fn execute_node(
                 Value::String(_) => return Err(WorkflowNodeExecutionError::IncorrectInputFormat),
             };
 
-            let user = workflow_context
-                .database
-                .query_opt::<RoUser>("SELECT * FROM roblox_users WHERE user_id = $1", &[&user_id])
+            let user = UserRepo::new(workflow_context.database)
+                .get(user_id)
                 .await?;
             if let Some(user) = user {
                 execution_node.outputs.insert(
@@ -539,17 +572,10 @@ pub async fn execute_node(
             {
                 Ok(u) => u,
                 Err(err) => {
-                    if let ErrorKind::Response {
-                        route: _,
-                        status,
-                        bytes: _,
-                    } = err.kind()
-                    {
-                        if status.as_u16() == 404 {
-                            return Ok(WorkflowExecutionNodeResult {
-                                outcome: "failure".into(),
-                            });
-                        }
+                    if err.is_not_found() {
+                        return Ok(WorkflowExecutionNodeResult {
+                            outcome: "failure".into(),
+                        });
                     }
                     return Err(err.into());
                 }
@@ -562,6 +588,149 @@ pub async fn execute_node(
                 outcome: "success".into(),
             });
         }
+        ActionMetadata::GetSortedMapItem => {
+            let universe_id = universe_id_input(execution_node)?;
+            let map_id = string_input(execution_node, "map_id")?;
+            let item_id = string_input(execution_node, "item_id")?;
+
+            let Some(item) = workflow_context
+                .roblox
+                .get_memory_store_sorted_map_item(universe_id, &map_id, &item_id)
+                .await?
+            else {
+                return Ok(WorkflowExecutionNodeResult {
+                    outcome: "failure".into(),
+                });
+            };
+            insert_json_outputs(node, execution_node, &item.value)?;
+            return Ok(WorkflowExecutionNodeResult {
+                outcome: "success".into(),
+            });
+        }
+        ActionMetadata::UpdateSortedMapItem => {
+            let universe_id = universe_id_input(execution_node)?;
+            let map_id = string_input(execution_node, "map_id")?;
+            let item_id = string_input(execution_node, "item_id")?;
+            let ttl = ttl_input(execution_node)?;
+
+            let current = workflow_context
+                .roblox
+                .get_memory_store_sorted_map_item(universe_id, &map_id, &item_id)
+                .await?;
+            let (mut value, sort_key) = match current {
+                Some(item) => {
+                    let sort_key = item.sort_key();
+                    (item.value, sort_key)
+                }
+                None => (serde_json::json!({}), None),
+            };
+            for input in &node.inputs {
+                if matches!(
+                    input.name.as_str(),
+                    "universe_id" | "map_id" | "item_id" | "ttl"
+                ) {
+                    continue;
+                }
+                let new_value = execution_node.inputs.get(&input.name).unwrap();
+                set_json_path(&mut value, &input.name, new_value)?;
+            }
+
+            let item = workflow_context
+                .roblox
+                .update_memory_store_sorted_map_item(
+                    universe_id,
+                    &map_id,
+                    &item_id,
+                    &MemoryStoreSortedMapItemArgs {
+                        value,
+                        ttl,
+                        sort_key,
+                    },
+                    true,
+                )
+                .await?;
+            insert_json_outputs(node, execution_node, &item.value)?;
+        }
+        ActionMetadata::DeleteSortedMapItem => {
+            let universe_id = universe_id_input(execution_node)?;
+            let map_id = string_input(execution_node, "map_id")?;
+            let item_id = string_input(execution_node, "item_id")?;
+
+            workflow_context
+                .roblox
+                .delete_memory_store_sorted_map_item(universe_id, &map_id, &item_id)
+                .await?;
+        }
+        ActionMetadata::EnqueueQueueItem => {
+            let universe_id = universe_id_input(execution_node)?;
+            let queue_id = string_input(execution_node, "queue_id")?;
+            let ttl = ttl_input(execution_node)?;
+            let data = match execution_node
+                .inputs
+                .get("data")
+                .ok_or(WorkflowNodeExecutionError::InputNotFound)?
+            {
+                Value::Number(n) => serde_json::json!(*n),
+                Value::String(s) => serde_json::json!(*s),
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let priority = match execution_node.inputs.get("priority") {
+                Some(Value::Number(n)) => Some(*n as f64),
+                Some(Value::String(_)) => {
+                    return Err(WorkflowNodeExecutionError::InputTypeMismatch)
+                }
+                None => None,
+            };
+
+            workflow_context
+                .roblox
+                .enqueue_memory_store_queue_item(universe_id, &queue_id, data, priority, ttl)
+                .await?;
+        }
+        ActionMetadata::ReadQueueItem => {
+            let universe_id = universe_id_input(execution_node)?;
+            let queue_id = string_input(execution_node, "queue_id")?;
+
+            let read = workflow_context
+                .roblox
+                .read_memory_store_queue_items(
+                    universe_id,
+                    &queue_id,
+                    1,
+                    false,
+                    QUEUE_INVISIBILITY_WINDOW,
+                )
+                .await?;
+            let (Some(read_id), Some(item)) = (read.read_id, read.items.into_iter().next()) else {
+                return Ok(WorkflowExecutionNodeResult {
+                    outcome: "failure".into(),
+                });
+            };
+            let data = match item.data {
+                serde_json::Value::Number(n) => n
+                    .as_i64()
+                    .map_or_else(|| Value::String(n.to_string()), Value::Number),
+                serde_json::Value::String(s) => Value::String(s),
+                other => Value::String(other.to_string()),
+            };
+            execution_node.outputs.insert("data".into(), data);
+            execution_node
+                .outputs
+                .insert("read_id".into(), Value::String(read_id));
+            return Ok(WorkflowExecutionNodeResult {
+                outcome: "success".into(),
+            });
+        }
+        ActionMetadata::DiscardQueueItems => {
+            let universe_id = universe_id_input(execution_node)?;
+            let queue_id = string_input(execution_node, "queue_id")?;
+            let read_id = string_input(execution_node, "read_id")?;
+
+            workflow_context
+                .roblox
+                .discard_memory_store_queue_items(universe_id, &queue_id, &read_id)
+                .await?;
+        }
     }
 
     Ok(WorkflowExecutionNodeResult {
@@ -638,6 +807,31 @@ pub fn validate_workflow(
     Ok(())
 }
 
+/// Checks that the bot can send messages in the channels of every `SendMessage` node. This needs
+/// the cache, so it is kept apart from [`validate_workflow`].
+pub async fn validate_workflow_channels(
+    workflow: &Workflow,
+    cache: &Cache,
+    guild_id: GuildId,
+    bot_id: UserId,
+) -> Result<Option<WorkflowValidationError>, RoError> {
+    for node in &workflow.nodes {
+        if let ActionMetadata::SendMessage(action::SendMessage { channel, .. }) = &node.metadata {
+            if let Some(reason) =
+                validate_message_channel(cache, guild_id, *channel, bot_id).await?
+            {
+                return Ok(Some(WorkflowValidationError::InvalidChannel {
+                    id: node.id,
+                    channel_id: *channel,
+                    reason,
+                }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
 #[allow(clippy::too_many_lines)]
 pub fn validate_node(
     node: &WorkflowNode,
@@ -720,12 +914,7 @@ pub fn validate_node(
                 });
             }
 
-            if execution_node.inputs.len() <= 3 {
-                return Err(WorkflowNodeValidationError::IncorrectInputs {
-                    actual: execution_node.inputs.len() as u32,
-                    expected: 4,
-                });
-            }
+            require_value_inputs(execution_node, &["universe_id", "datastore_id", "entry_id"])?;
 
             for output in &node.outputs {
                 execution_node.outputs.insert(output.name.clone());
@@ -775,8 +964,157 @@ pub fn validate_node(
             }
             execution_node.outputs.insert(node.outputs[0].name.clone());
         }
+        ActionMetadata::GetSortedMapItem | ActionMetadata::UpdateSortedMapItem => {
+            require_inputs(execution_node, &["universe_id", "map_id", "item_id"])?;
+            if node.kind == ActionType::UpdateSortedMapItem {
+                require_value_inputs(execution_node, &["universe_id", "map_id", "item_id", "ttl"])?;
+            }
+
+            for output in &node.outputs {
+                execution_node.outputs.insert(output.name.clone());
+            }
+        }
+        ActionMetadata::DeleteSortedMapItem => {
+            require_inputs(execution_node, &["universe_id", "map_id", "item_id"])?;
+        }
+        ActionMetadata::EnqueueQueueItem => {
+            require_inputs(execution_node, &["universe_id", "queue_id", "data"])?;
+        }
+        ActionMetadata::ReadQueueItem => {
+            require_inputs(execution_node, &["universe_id", "queue_id"])?;
+            execution_node.outputs.insert("data".into());
+            execution_node.outputs.insert("read_id".into());
+        }
+        ActionMetadata::DiscardQueueItems => {
+            require_inputs(execution_node, &["universe_id", "queue_id", "read_id"])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn require_inputs(
+    execution_node: &WorkflowNodeValidation,
+    names: &[&str],
+) -> Result<(), WorkflowNodeValidationError> {
+    match names
+        .iter()
+        .find(|name| !execution_node.inputs.contains(**name))
+    {
+        Some(name) => Err(WorkflowNodeValidationError::InputNotFound {
+            name: (*name).to_string(),
+        }),
+        None => Ok(()),
     }
+}
+
+/// Checks that a node which writes its inputs into a stored value has at least one input besides
+/// the `locators` that pick the value, which may be optional.
+fn require_value_inputs(
+    execution_node: &WorkflowNodeValidation,
+    locators: &[&str],
+) -> Result<(), WorkflowNodeValidationError> {
+    let actual = execution_node.inputs.len();
+    let present = execution_node
+        .inputs
+        .iter()
+        .filter(|name| locators.contains(&name.as_str()))
+        .count();
+    if actual > present {
+        return Ok(());
+    }
+    Err(WorkflowNodeValidationError::IncorrectInputs {
+        actual: actual as u32,
+        expected: present as u32 + 1,
+    })
+}
 
+fn universe_id_input(
+    execution_node: &WorkflowNodeExecution,
+) -> Result<UniverseId, WorkflowNodeExecutionError> {
+    match execution_node
+        .inputs
+        .get("universe_id")
+        .ok_or(WorkflowNodeExecutionError::InputNotFound)?
+    {
+        Value::Number(n) => Ok(UniverseId((*n) as u64)),
+        Value::String(_) => Err(WorkflowNodeExecutionError::InputTypeMismatch),
+    }
+}
+
+fn string_input(
+    execution_node: &WorkflowNodeExecution,
+    name: &str,
+) -> Result<String, WorkflowNodeExecutionError> {
+    execution_node
+        .inputs
+        .get(name)
+        .map(ToString::to_string)
+        .ok_or(WorkflowNodeExecutionError::InputNotFound)
+}
+
+/// The optional `ttl` input, in seconds.
+fn ttl_input(
+    execution_node: &WorkflowNodeExecution,
+) -> Result<Duration, WorkflowNodeExecutionError> {
+    match execution_node.inputs.get("ttl") {
+        Some(Value::Number(n)) if *n > 0 => Ok(Duration::from_secs(*n as u64)),
+        Some(_) => Err(WorkflowNodeExecutionError::IncorrectInputFormat),
+        None => Ok(DEFAULT_MEMORY_STORE_TTL),
+    }
+}
+
+/// Sets each output of the node to the value at its dotted path in `value`.
+fn insert_json_outputs(
+    node: &WorkflowNode,
+    execution_node: &mut WorkflowNodeExecution,
+    value: &serde_json::Value,
+) -> Result<(), WorkflowNodeExecutionError> {
+    for output in &node.outputs {
+        let mut current = value;
+        for path in output.name.split('.') {
+            current = current
+                .get(path)
+                .ok_or(WorkflowNodeExecutionError::IncorrectOutputFormat)?;
+        }
+        let output_value = match output.value {
+            ValueType::Number => Value::Number(
+                current
+                    .as_i64()
+                    .ok_or(WorkflowNodeExecutionError::IncorrectOutputFormat)?,
+            ),
+            ValueType::String => Value::String(
+                current
+                    .as_str()
+                    .ok_or(WorkflowNodeExecutionError::IncorrectOutputFormat)?
+                    .to_string(),
+            ),
+        };
+        execution_node
+            .outputs
+            .insert(output.name.clone(), output_value);
+    }
+    Ok(())
+}
+
+/// Sets the value at a dotted path, creating the objects along the way.
+fn set_json_path(
+    value: &mut serde_json::Value,
+    path: &str,
+    new_value: &Value,
+) -> Result<(), WorkflowNodeExecutionError> {
+    let mut current = value;
+    for segment in path.split('.') {
+        current = current
+            .as_object_mut()
+            .ok_or(WorkflowNodeExecutionError::IncorrectInputFormat)?
+            .entry(segment)
+            .or_insert_with(|| serde_json::json!({}));
+    }
+    *current = match new_value {
+        Value::Number(n) => serde_json::json!(*n),
+        Value::String(s) => serde_json::json!(*s),
+    };
     Ok(())
 }
 
@@ -788,7 +1126,7 @@ impl From<twilight_http::Error> for WorkflowNodeExecutionError {
 
 impl From<RobloxError> for WorkflowNodeExecutionError {
     fn from(err: RobloxError) -> Self {
-        Self::Roblox(err)
+        Self::Roblox(Box::new(err))
     }
 }
 
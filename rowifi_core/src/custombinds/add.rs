@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindCreate},
     bind::{BindType, Custombind, Template},
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, ops::Add};
 
 use super::parser::parser;
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 
 #[derive(Debug, Serialize)]
 pub struct AddCustombind {
@@ -42,6 +42,7 @@ pub async fn add_custombind(
     guild_id: GuildId,
     author_id: UserId,
     existing_custombinds: &[Custombind],
+    config_version: i64,
     server_roles: &HashMap<RoleId, CachedRole>,
     args: CustombindArguments,
 ) -> Result<AddCustombind, AddCustombindError> {
@@ -63,7 +64,7 @@ pub async fn add_custombind(
         return Err(AddCustombindError::Code(err.to_string()));
     }
 
-    let bind = Json(Custombind {
+    let bind = Custombind {
         custom_bind_id: existing_custombinds
             .iter()
             .map(|c| c.custom_bind_id)
@@ -74,15 +75,7 @@ pub async fn add_custombind(
         discord_roles: roles_to_add,
         priority: args.priority.unwrap_or_default(),
         template: args.template,
-    });
-
-    database
-        .execute(
-            "UPDATE guilds SET custombinds = custombinds || $2::jsonb WHERE guild_id = $1",
-            &[&guild_id, &bind],
-        )
-        .await
-        .map_err(|err| AddCustombindError::Other(err.into()))?;
+    };
 
     let log = AuditLog {
         kind: AuditLogKind::BindCreate,
@@ -95,23 +88,26 @@ pub async fn add_custombind(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-    VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await
-        .map_err(|err| AddCustombindError::Other(err.into()))?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let bind = bind.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .insert_custombind(guild_id, &bind)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await
+    .map_err(AddCustombindError::Other)?;
 
     Ok(AddCustombind {
-        bind: bind.0,
+        bind,
         ignored_roles,
     })
 }
@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindDelete},
     bind::{BindType, Custombind},
@@ -8,7 +8,7 @@ use rowifi_models::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 
 #[derive(Debug, Serialize)]
 pub struct DeleteCustombind {
@@ -29,6 +29,7 @@ pub struct CustombindArguments {
 pub async fn delete_custombinds(
     database: &Database,
     custombinds: &[Custombind],
+    config_version: i64,
     guild_id: GuildId,
     author_id: UserId,
     args: Vec<CustombindArguments>,
@@ -55,17 +56,7 @@ pub async fn delete_custombinds(
         });
     }
 
-    let new_custombinds = custombinds
-        .iter()
-        .filter(|c| !binds_to_delete.contains(&c.custom_bind_id))
-        .cloned()
-        .collect::<Vec<_>>();
-    database
-        .execute(
-            "UPDATE guilds SET custombinds = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(new_custombinds)],
-        )
-        .await?;
+    let custom_bind_ids = binds_to_delete.iter().copied().collect::<Vec<_>>();
 
     #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
     let log = AuditLog {
@@ -79,19 +70,22 @@ pub async fn delete_custombinds(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let custom_bind_ids = custom_bind_ids.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .delete_custombinds(guild_id, &custom_bind_ids)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     #[allow(clippy::cast_possible_truncation)]
     Ok(DeleteCustombind {
@@ -2,3 +2,17 @@ pub mod add;
 pub mod delete;
 pub mod evaluate;
 pub mod parser;
+
+use rowifi_database::Database;
+use rowifi_models::{bind::Custombind, id::GuildId};
+
+use crate::{error::RoError, repos::BindRepo};
+
+/// Get the custombinds of the server in the order they were added.
+pub async fn guild_custombinds(
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<Vec<Custombind>, RoError> {
+    let custombinds = BindRepo::new(database).custombinds(guild_id).await?;
+    Ok(custombinds)
+}
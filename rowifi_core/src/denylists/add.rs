@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, DenylistCreate},
     deny_list::{DenyList, DenyListActionType, DenyListData, DenyListType},
@@ -8,7 +8,9 @@ use rowifi_models::{
 };
 use serde::Deserialize;
 
-use crate::{custombinds::parser::parser, error::RoError};
+use crate::{
+    custombinds::parser::parser, error::RoError, guild::write_config, repos::DenyListRepo,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct DenylistArguments {
@@ -38,7 +40,8 @@ pub async fn add_denylist(
     database: &Database,
     guild_id: GuildId,
     author_id: UserId,
-    mut existing_denylists: Vec<DenyList>,
+    existing_denylists: &[DenyList],
+    config_version: i64,
     args: DenylistArguments,
 ) -> Result<DenyList, AddDenylistError> {
     let data = match args.kind {
@@ -76,23 +79,11 @@ pub async fn add_denylist(
         data,
     };
 
-    if let Some(denylist) = existing_denylists
-        .iter_mut()
+    // A denylist for the same user, group or code is modified instead of added again.
+    let existing_id = existing_denylists
+        .iter()
         .find(|d| d.data == new_denylist.data)
-    {
-        denylist.reason.clone_from(&new_denylist.reason);
-        denylist.action_type = new_denylist.action_type;
-    } else {
-        existing_denylists.push(new_denylist.clone());
-    }
-
-    database
-        .execute(
-            "UPDATE guilds SET deny_lists = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(existing_denylists)],
-        )
-        .await
-        .map_err(|err| AddDenylistError::Generic(err.into()))?;
+        .map(|d| d.id);
 
     let log = AuditLog {
         kind: AuditLogKind::DenylistCreate,
@@ -102,20 +93,26 @@ pub async fn add_denylist(
         metadata: AuditLogData::DenylistCreate(DenylistCreate { kind: args.kind }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await
-        .map_err(|err| AddDenylistError::Generic(err.into()))?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let denylist = new_denylist.clone();
+            Box::pin(async move {
+                let repo = DenyListRepo::new(transaction);
+                if let Some(id) = existing_id {
+                    repo.update(guild_id, &DenyList { id, ..denylist }).await?;
+                } else {
+                    repo.insert(guild_id, &denylist).await?;
+                }
+                Ok(())
+            })
+        },
+    )
+    .await
+    .map_err(AddDenylistError::Generic)?;
 
     Ok(new_denylist)
 }
@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, DenylistDelete},
     deny_list::DenyList,
@@ -8,7 +8,7 @@ use rowifi_models::{
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::DenyListRepo};
 
 #[derive(Debug, Serialize)]
 pub struct DeleteDenylist {
@@ -24,6 +24,7 @@ pub struct DeleteDenylist {
 pub async fn delete_denylists(
     database: &Database,
     denylists: &[DenyList],
+    config_version: i64,
     guild_id: GuildId,
     author_id: UserId,
     args: Vec<u32>,
@@ -50,17 +51,7 @@ pub async fn delete_denylists(
         });
     }
 
-    let new_denylists = denylists
-        .iter()
-        .filter(|d| !denylists_to_delete.contains(&d.id))
-        .cloned()
-        .collect::<Vec<_>>();
-    database
-        .execute(
-            "UPDATE guilds SET deny_lists = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(new_denylists)],
-        )
-        .await?;
+    let deny_list_ids = denylists_to_delete.iter().copied().collect::<Vec<_>>();
 
     #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
     let log = AuditLog {
@@ -73,19 +64,22 @@ pub async fn delete_denylists(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-            VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let deny_list_ids = deny_list_ids.clone();
+            Box::pin(async move {
+                DenyListRepo::new(transaction)
+                    .delete(guild_id, &deny_list_ids)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     #[allow(clippy::cast_possible_truncation)]
     Ok(DeleteDenylist {
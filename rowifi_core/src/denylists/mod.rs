@@ -1,2 +1,16 @@
 pub mod add;
 pub mod delete;
+
+use rowifi_database::Database;
+use rowifi_models::{deny_list::DenyList, id::GuildId};
+
+use crate::{error::RoError, repos::DenyListRepo};
+
+/// Get the deny lists of the server in the order they were added.
+pub async fn guild_deny_lists(
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<Vec<DenyList>, RoError> {
+    let deny_lists = DenyListRepo::new(database).list(guild_id).await?;
+    Ok(deny_lists)
+}
@@ -8,6 +8,8 @@ use std::{
 use twilight_http::{response::DeserializeBodyError, Error as DiscordHttpError};
 use twilight_validate::message::MessageValidationError;
 
+use crate::api_keys::ApiKeyCipherError;
+
 #[derive(Debug)]
 pub struct RoError {
     source: Option<Box<dyn StdError + Send + Sync>>,
@@ -17,6 +19,8 @@ pub struct RoError {
 #[derive(Debug)]
 pub enum ErrorKind {
     Cache,
+    /// The configuration of the server was changed by someone else since it was read.
+    Conflict,
     Database,
     Discord,
     Function,
@@ -24,6 +28,16 @@ pub enum ErrorKind {
 }
 
 impl RoError {
+    /// The error returned when a change is based on a configuration of the server that is no
+    /// longer the latest one.
+    #[must_use]
+    pub const fn conflict() -> Self {
+        Self {
+            source: None,
+            kind: ErrorKind::Conflict,
+        }
+    }
+
     #[must_use]
     pub const fn kind(&self) -> &ErrorKind {
         &self.kind
@@ -49,6 +63,7 @@ impl Display for RoError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self.kind {
             ErrorKind::Cache => f.write_str("cache error: ")?,
+            ErrorKind::Conflict => f.write_str("configuration changed, please retry")?,
             ErrorKind::Database => f.write_str("database error: ")?,
             ErrorKind::Discord => f.write_str("discord error: ")?,
             ErrorKind::Function => f.write_str("function error: ")?,
@@ -122,3 +137,12 @@ impl From<MessageValidationError> for RoError {
         }
     }
 }
+
+impl From<ApiKeyCipherError> for RoError {
+    fn from(err: ApiKeyCipherError) -> Self {
+        Self {
+            source: Some(Box::new(err)),
+            kind: ErrorKind::Function,
+        }
+    }
+}
@@ -1,8 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{
-    postgres::{types::Json, Row},
-    Database,
-};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, EventLog as AuditEventLog},
     events::{EventLog, EventType},
@@ -10,7 +7,10 @@ use rowifi_models::{
     roblox::id::UserId as RobloxUserId,
 };
 
-use crate::error::RoError;
+use crate::{
+    error::RoError,
+    repos::{AuditLogRepo, EventRepo},
+};
 
 pub struct EventLogArguments {
     pub host_id: RobloxUserId,
@@ -24,10 +24,6 @@ pub enum EventLogError {
     Other(RoError),
 }
 
-pub struct EventLogRow {
-    pub guild_event_id: i64,
-}
-
 /// Logs an event for the server. Also checks if the attendees are valid.
 ///
 /// # Errors
@@ -54,53 +50,29 @@ pub async fn log_event(
         notes: args.notes,
     };
 
-    let row = database.query_opt::<EventLogRow>(
-        r"INSERT INTO events(guild_id, event_type, guild_event_id, host_id, timestamp, attendees, notes)
-        VALUES($1, $2, (SELECT COALESCE(max(guild_event_id) + 1, 1) FROM events WHERE guild_id = $1), $3, $4, $5, $6)
-        RETURNING guild_event_id",
-        &[&new_event.guild_id, &new_event.event_type, &new_event.host_id, &new_event.timestamp, &new_event.attendees, &new_event.notes]
-    ).await.map_err(RoError::from)?;
-
-    new_event.guild_event_id = row.unwrap().guild_event_id;
-
-    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-    let log = AuditLog {
-        kind: AuditLogKind::BindDelete,
-        guild_id: Some(guild_id),
-        user_id: Some(author_id),
-        timestamp: Utc::now(),
-        metadata: AuditLogData::EventLog(AuditEventLog {
-            guild_event_id: new_event.guild_event_id,
-        }),
-    };
+    new_event.guild_event_id = database
+        .transaction(|transaction| {
+            let event = new_event.clone();
+            Box::pin(async move {
+                let guild_event_id = EventRepo::new(transaction).insert(&event).await?;
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
+                let log = AuditLog {
+                    kind: AuditLogKind::EventLog,
+                    guild_id: Some(event.guild_id),
+                    user_id: Some(author_id),
+                    timestamp: Utc::now(),
+                    metadata: AuditLogData::EventLog(AuditEventLog { guild_event_id }),
+                };
+                AuditLogRepo::new(transaction).insert(&log).await?;
+                Ok(guild_event_id)
+            })
+        })
         .await
         .map_err(RoError::from)?;
 
     Ok(new_event)
 }
 
-impl TryFrom<Row> for EventLogRow {
-    type Error = rowifi_database::postgres::Error;
-
-    fn try_from(row: Row) -> Result<Self, Self::Error> {
-        let guild_event_id = row.try_get("guild_event_id")?;
-        Ok(Self { guild_event_id })
-    }
-}
-
 impl From<RoError> for EventLogError {
     fn from(err: RoError) -> Self {
         EventLogError::Other(err)
@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, EventTypeCreate},
     events::EventType,
@@ -7,7 +7,7 @@ use rowifi_models::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::GuildRepo};
 
 #[derive(Debug, Serialize)]
 pub struct AddEventType {
@@ -36,6 +36,7 @@ pub async fn add_event_type(
     guild_id: GuildId,
     author_id: UserId,
     mut existing_event_types: Vec<EventType>,
+    config_version: i64,
     args: EventTypeArguments,
 ) -> Result<AddEventType, AddEventTypeError> {
     if existing_event_types.iter().any(|e| e.id == args.id) {
@@ -49,14 +50,6 @@ pub async fn add_event_type(
     };
     existing_event_types.push(new_event_type.clone());
 
-    database
-        .execute(
-            "UPDATE guilds SET event_types = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(existing_event_types)],
-        )
-        .await
-        .map_err(RoError::from)?;
-
     let log = AuditLog {
         kind: AuditLogKind::EventTypeCreate,
         guild_id: Some(guild_id),
@@ -65,20 +58,21 @@ pub async fn add_event_type(
         metadata: AuditLogData::EventTypeCreate(EventTypeCreate { id: args.id }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await
-        .map_err(RoError::from)?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let existing_event_types = existing_event_types.clone();
+            Box::pin(async move {
+                GuildRepo::new(transaction)
+                    .set_event_types(guild_id, &existing_event_types)
+                    .await
+            })
+        },
+    )
+    .await?;
 
     Ok(AddEventType {
         event: new_event_type,
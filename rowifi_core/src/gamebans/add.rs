@@ -0,0 +1,59 @@
+use chrono::Utc;
+use rowifi_database::Database;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, GameBan},
+    id::{GuildId, UserId},
+    roblox::{
+        id::{UniverseId, UserId as RobloxUserId},
+        user_restriction::UserRestriction,
+    },
+};
+use rowifi_roblox::RobloxClient;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::{error::RoError, repos::AuditLogRepo};
+
+#[derive(Debug, Deserialize)]
+pub struct GamebanArguments {
+    pub user_id: RobloxUserId,
+    pub reason: String,
+    /// The length of the ban. Permanent if [`None`].
+    pub duration: Option<Duration>,
+}
+
+/// Bans a Roblox user from the universe linked to the server. `author_id` is [`None`] when the
+/// bot applies the ban on its own, such as for a deny list.
+///
+/// # Errors
+///
+/// See [`RoError`] for details.
+pub async fn add_gameban(
+    roblox: &RobloxClient,
+    database: &Database,
+    guild_id: GuildId,
+    author_id: Option<UserId>,
+    universe_id: UniverseId,
+    args: GamebanArguments,
+) -> Result<UserRestriction, RoError> {
+    let restriction = roblox
+        .ban_user(universe_id, None, args.user_id, &args.reason, args.duration)
+        .await?;
+
+    let log = AuditLog {
+        kind: AuditLogKind::GameBan,
+        guild_id: Some(guild_id),
+        user_id: author_id,
+        timestamp: Utc::now(),
+        metadata: AuditLogData::GameBan(GameBan {
+            target_roblox_user: args.user_id,
+            universe_id,
+            reason: args.reason,
+            duration: args.duration.map(|d| d.as_secs()),
+        }),
+    };
+
+    AuditLogRepo::new(database).insert(&log).await?;
+
+    Ok(restriction)
+}
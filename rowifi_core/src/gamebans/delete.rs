@@ -0,0 +1,44 @@
+use chrono::Utc;
+use rowifi_database::Database;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, GameUnban},
+    id::{GuildId, UserId},
+    roblox::{
+        id::{UniverseId, UserId as RobloxUserId},
+        user_restriction::UserRestriction,
+    },
+};
+use rowifi_roblox::RobloxClient;
+
+use crate::{error::RoError, repos::AuditLogRepo};
+
+/// Lifts the ban of a Roblox user in the universe linked to the server.
+///
+/// # Errors
+///
+/// See [`RoError`] for details.
+pub async fn delete_gameban(
+    roblox: &RobloxClient,
+    database: &Database,
+    guild_id: GuildId,
+    author_id: UserId,
+    universe_id: UniverseId,
+    user_id: RobloxUserId,
+) -> Result<UserRestriction, RoError> {
+    let restriction = roblox.unban_user(universe_id, None, user_id).await?;
+
+    let log = AuditLog {
+        kind: AuditLogKind::GameUnban,
+        guild_id: Some(guild_id),
+        user_id: Some(author_id),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::GameUnban(GameUnban {
+            target_roblox_user: user_id,
+            universe_id,
+        }),
+    };
+
+    AuditLogRepo::new(database).insert(&log).await?;
+
+    Ok(restriction)
+}
@@ -0,0 +1,76 @@
+use chrono::Utc;
+use rowifi_database::Database;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, SettingModify},
+    id::{GuildId, UserId},
+    roblox::{id::UniverseId, universe::Universe},
+};
+use rowifi_roblox::{error::ApiErrorKind, RobloxClient};
+
+use crate::{error::RoError, guild::write_config, repos::GuildRepo};
+
+#[derive(Debug)]
+pub enum LinkUniverseError {
+    InvalidUniverse,
+    Generic(RoError),
+}
+
+/// Links a universe to the server, or unlinks the current one if `universe_id` is [`None`].
+/// Validates that the universe exists and can be read with the bot's Open Cloud key.
+///
+/// # Errors
+///
+/// See [`LinkUniverseError`] for details.
+pub async fn link_universe(
+    roblox: &RobloxClient,
+    database: &Database,
+    guild_id: GuildId,
+    author_id: UserId,
+    universe_id: Option<UniverseId>,
+) -> Result<Option<Universe>, LinkUniverseError> {
+    let universe = match universe_id {
+        Some(universe_id) => match roblox.get_universe(universe_id).await {
+            Ok(universe) => Some(universe),
+            Err(err) => {
+                if let Some(error) = err.api_error() {
+                    if matches!(
+                        error.kind,
+                        ApiErrorKind::NotFound | ApiErrorKind::PermissionDenied { .. }
+                    ) {
+                        return Err(LinkUniverseError::InvalidUniverse);
+                    }
+                }
+                return Err(RoError::from(err).into());
+            }
+        },
+        None => None,
+    };
+
+    let log = AuditLog {
+        kind: AuditLogKind::SettingModify,
+        guild_id: Some(guild_id),
+        user_id: Some(author_id),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::SettingModify(SettingModify {
+            setting: "linked_universe".into(),
+            value: universe_id.map_or_else(|| "None".into(), |u| u.to_string()),
+        }),
+    };
+
+    write_config(database, guild_id, None, &log, |transaction| {
+        Box::pin(async move {
+            GuildRepo::new(transaction)
+                .set_linked_universe(guild_id, universe_id)
+                .await
+        })
+    })
+    .await?;
+
+    Ok(universe)
+}
+
+impl From<RoError> for LinkUniverseError {
+    fn from(err: RoError) -> Self {
+        LinkUniverseError::Generic(err)
+    }
+}
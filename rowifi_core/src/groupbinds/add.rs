@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindCreate},
     bind::{BindType, Groupbind, Template},
@@ -11,7 +11,7 @@ use rowifi_roblox::RobloxClient;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 
 #[derive(Debug, Serialize)]
 pub struct AddGroupbind {
@@ -40,12 +40,14 @@ pub struct GroupbindArguments {
 /// # Errors
 ///
 /// See [`AddGroupbindError`] for details.
+#[allow(clippy::too_many_arguments)]
 pub async fn add_groupbind(
     roblox: &RobloxClient,
     database: &Database,
     guild_id: GuildId,
     author_id: UserId,
-    mut existing_groupbinds: Vec<Groupbind>,
+    existing_groupbinds: &[Groupbind],
+    config_version: i64,
     server_roles: &HashMap<RoleId, CachedRole>,
     args: GroupbindArguments,
 ) -> Result<AddGroupbind, AddGroupbindError> {
@@ -78,26 +80,9 @@ pub async fn add_groupbind(
         template: args.template,
     };
 
-    let mut modified = false;
-    if let Some(bind) = existing_groupbinds
-        .iter_mut()
-        .find(|r| r.group_id == new_bind.group_id)
-    {
-        bind.priority = new_bind.priority;
-        bind.template = new_bind.template.clone();
-        bind.discord_roles.clone_from(&new_bind.discord_roles);
-        modified = true;
-    } else {
-        existing_groupbinds.push(new_bind.clone());
-    }
-
-    database
-        .execute(
-            "UPDATE guilds SET groupbinds = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(existing_groupbinds)],
-        )
-        .await
-        .map_err(RoError::from)?;
+    let modified = existing_groupbinds
+        .iter()
+        .any(|r| r.group_id == new_bind.group_id);
 
     let log = AuditLog {
         kind: AuditLogKind::BindCreate,
@@ -110,20 +95,22 @@ pub async fn add_groupbind(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await
-        .map_err(RoError::from)?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let bind = new_bind.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .upsert_groupbind(guild_id, &bind)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     Ok(AddGroupbind {
         bind: new_bind,
@@ -1,2 +1,16 @@
 pub mod add;
 pub mod delete;
+
+use rowifi_database::Database;
+use rowifi_models::{bind::Groupbind, id::GuildId};
+
+use crate::{error::RoError, repos::BindRepo};
+
+/// Get the groupbinds of the server in the order they were added.
+pub async fn guild_groupbinds(
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<Vec<Groupbind>, RoError> {
+    let groupbinds = BindRepo::new(database).groupbinds(guild_id).await?;
+    Ok(groupbinds)
+}
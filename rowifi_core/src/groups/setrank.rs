@@ -0,0 +1,104 @@
+use chrono::Utc;
+use rowifi_database::Database;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, SetRank},
+    id::{GuildId, UserId},
+    roblox::{
+        group::GroupRole,
+        id::{GroupId, RoleId, UserId as RobloxUserId},
+    },
+};
+use rowifi_roblox::RobloxClient;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::RoError, repos::AuditLogRepo};
+
+#[derive(Debug, Serialize)]
+pub struct SetRankResult {
+    pub previous_role: RoleId,
+    pub rank: GroupRole,
+}
+
+#[derive(Debug)]
+pub enum SetRankError {
+    InvalidGroup,
+    /// The rank does not exist, or is the guest (0) or owner (255) rank which cannot be assigned.
+    InvalidRank,
+    NotInGroup,
+    Generic(RoError),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetRankArguments {
+    pub group_id: GroupId,
+    pub user_id: RobloxUserId,
+    pub rank_id: u32,
+}
+
+/// Changes the rank of a Roblox user in a group through Open Cloud.
+///
+/// # Errors
+///
+/// See [`SetRankError`] for details.
+pub async fn set_rank(
+    roblox: &RobloxClient,
+    database: &Database,
+    guild_id: GuildId,
+    author_id: UserId,
+    args: SetRankArguments,
+) -> Result<SetRankResult, SetRankError> {
+    let Some(ranks) = roblox
+        .get_group_ranks(args.group_id)
+        .await
+        .map_err(RoError::from)?
+    else {
+        return Err(SetRankError::InvalidGroup);
+    };
+
+    if args.rank_id == 0 || args.rank_id == 255 {
+        return Err(SetRankError::InvalidRank);
+    }
+    let Some(rank) = ranks.into_iter().find(|r| r.rank == args.rank_id) else {
+        return Err(SetRankError::InvalidRank);
+    };
+    let Some(membership) = roblox
+        .get_group_membership(args.group_id, args.user_id)
+        .await
+        .map_err(RoError::from)?
+    else {
+        return Err(SetRankError::NotInGroup);
+    };
+
+    roblox
+        .update_group_membership(args.group_id, membership.id(), rank.id)
+        .await
+        .map_err(RoError::from)?;
+
+    let log = AuditLog {
+        kind: AuditLogKind::SetRank,
+        guild_id: Some(guild_id),
+        user_id: Some(author_id),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::SetRank(SetRank {
+            target_roblox_user: args.user_id,
+            group_id: args.group_id,
+            group_rank_id: args.rank_id,
+        }),
+    };
+
+    AuditLogRepo::new(database)
+        .insert(&log)
+        .await
+        .map_err(RoError::from)?;
+
+    Ok(SetRankResult {
+        previous_role: membership.role_id(),
+        rank,
+    })
+}
+
+impl From<RoError> for SetRankError {
+    fn from(err: RoError) -> Self {
+        SetRankError::Generic(err)
+    }
+}
@@ -0,0 +1,73 @@
+use futures_util::try_join;
+use rowifi_database::{Database, Transaction, TransactionFuture};
+use rowifi_models::{audit_log::AuditLog, guild::GuildBinds, id::GuildId};
+
+use crate::{
+    assetbinds::guild_assetbinds,
+    custombinds::guild_custombinds,
+    denylists::guild_deny_lists,
+    error::RoError,
+    groupbinds::guild_groupbinds,
+    rankbinds::guild_rankbinds,
+    repos::{AuditLogRepo, ConfigBump, GuildRepo},
+};
+
+/// Load all binds and deny lists of the server. Load them after the
+/// [`PartialRoGuild`](rowifi_models::guild::PartialRoGuild) they are used with, so that a change made in between is caught as a conflict through its
+/// `config_version` instead of going unnoticed.
+pub async fn load_binds(database: &Database, guild_id: GuildId) -> Result<GuildBinds, RoError> {
+    let (rankbinds, groupbinds, assetbinds, custombinds, deny_lists) = try_join!(
+        guild_rankbinds(database, guild_id),
+        guild_groupbinds(database, guild_id),
+        guild_assetbinds(database, guild_id),
+        guild_custombinds(database, guild_id),
+        guild_deny_lists(database, guild_id),
+    )?;
+    Ok(GuildBinds {
+        rankbinds,
+        groupbinds,
+        assetbinds,
+        custombinds,
+        deny_lists,
+    })
+}
+
+/// Write a change to the configuration of the server. `write` runs in a transaction that also
+/// bumps the configuration version and adds `log` to the audit log. The server's row is created if
+/// it has none. If `config_version` is given and the configuration changed since it was read,
+/// nothing is written and [`RoError::conflict`] is returned.
+pub async fn write_config<T, F>(
+    database: &Database,
+    guild_id: GuildId,
+    config_version: Option<i64>,
+    log: &AuditLog,
+    mut write: F,
+) -> Result<T, RoError>
+where
+    T: Send + 'static,
+    F: for<'t> FnMut(&'t Transaction) -> TransactionFuture<'t, T>,
+{
+    let written = database
+        .transaction(|transaction| {
+            let log = log.clone();
+            let write = write(transaction);
+            Box::pin(async move {
+                let repo = GuildRepo::new(transaction);
+                let mut bump = repo.bump_config_version(guild_id, config_version).await?;
+                if bump == ConfigBump::Missing {
+                    // Settings can be written before anything read the configuration and
+                    // created the row.
+                    repo.create(guild_id).await?;
+                    bump = repo.bump_config_version(guild_id, config_version).await?;
+                }
+                if bump != ConfigBump::Bumped {
+                    return Ok(None);
+                }
+                let value = write.await?;
+                AuditLogRepo::new(transaction).insert(&log).await?;
+                Ok(Some(value))
+            })
+        })
+        .await?;
+    written.ok_or_else(RoError::conflict)
+}
@@ -7,13 +7,21 @@
     clippy::missing_panics_doc
 )]
 
+pub mod api_keys;
 pub mod assetbinds;
 pub mod backups;
+pub mod cache_repair;
+pub mod channels;
 pub mod custom;
 pub mod custombinds;
 pub mod denylists;
 pub mod error;
 pub mod events;
+pub mod gamebans;
 pub mod groupbinds;
+pub mod groups;
+pub mod guild;
+pub mod log_channel;
 pub mod rankbinds;
+pub mod repos;
 pub mod user;
@@ -0,0 +1,70 @@
+use chrono::Utc;
+use rowifi_cache::Cache;
+use rowifi_database::Database;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, SettingModify},
+    id::{ChannelId, GuildId, UserId},
+};
+
+use crate::{
+    channels::{validate_message_channel, InvalidChannel},
+    error::RoError,
+    guild::write_config,
+    repos::GuildRepo,
+};
+
+#[derive(Debug)]
+pub enum SetLogChannelError {
+    InvalidChannel(InvalidChannel),
+    Generic(RoError),
+}
+
+/// Sets the channel the server's logs are sent to, or stops sending them if `channel_id` is
+/// [`None`]. Validates that the bot can send messages in the channel.
+///
+/// # Errors
+///
+/// See [`SetLogChannelError`] for details.
+pub async fn set_log_channel(
+    cache: &Cache,
+    database: &Database,
+    guild_id: GuildId,
+    author_id: UserId,
+    bot_id: UserId,
+    channel_id: Option<ChannelId>,
+) -> Result<(), SetLogChannelError> {
+    if let Some(channel_id) = channel_id {
+        if let Some(invalid) = validate_message_channel(cache, guild_id, channel_id, bot_id).await?
+        {
+            return Err(SetLogChannelError::InvalidChannel(invalid));
+        }
+    }
+
+    let log = AuditLog {
+        kind: AuditLogKind::SettingModify,
+        guild_id: Some(guild_id),
+        user_id: Some(author_id),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::SettingModify(SettingModify {
+            setting: "log_channel".into(),
+            value: channel_id.map_or_else(|| "None".into(), |c| c.to_string()),
+        }),
+    };
+
+    write_config(database, guild_id, None, &log, |transaction| {
+        Box::pin(async move {
+            GuildRepo::new(transaction)
+                .set_log_channel(guild_id, channel_id)
+                .await
+        })
+    })
+    .await?;
+
+    Ok(())
+}
+
+impl From<RoError> for SetLogChannelError {
+    fn from(err: RoError) -> Self {
+        SetLogChannelError::Generic(err)
+    }
+}
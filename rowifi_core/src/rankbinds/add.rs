@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindCreate},
     bind::{BindType, Rankbind, Template},
@@ -11,7 +11,7 @@ use rowifi_roblox::RobloxClient;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 
 #[derive(Debug, Serialize)]
 pub struct AddRankbind {
@@ -42,12 +42,14 @@ pub struct RankbindArguments {
 /// # Errors
 ///
 /// See [`AddRankbindError`] for details.
+#[allow(clippy::too_many_arguments)]
 pub async fn add_rankbind(
     roblox: &RobloxClient,
     database: &Database,
     guild_id: GuildId,
     author_id: UserId,
-    mut existing_rankbinds: Vec<Rankbind>,
+    existing_rankbinds: &[Rankbind],
+    config_version: i64,
     server_roles: &HashMap<RoleId, CachedRole>,
     args: RankbindArguments,
 ) -> Result<AddRankbind, AddRankbindError> {
@@ -80,31 +82,14 @@ pub async fn add_rankbind(
         group_id: args.group_id,
         discord_roles: roles_to_add,
         group_rank_id: args.rank_id,
-        roblox_rank_id: rank.id.clone(),
+        roblox_rank_id: rank.id,
         priority: args.priority.unwrap_or_default(),
         template: args.template,
     };
 
-    let mut modified = false;
-    if let Some(bind) = existing_rankbinds
-        .iter_mut()
-        .find(|r| r.group_id == new_bind.group_id && r.group_rank_id == new_bind.group_rank_id)
-    {
-        bind.priority = new_bind.priority;
-        bind.template = new_bind.template.clone();
-        bind.discord_roles.clone_from(&new_bind.discord_roles);
-        modified = true;
-    } else {
-        existing_rankbinds.push(new_bind.clone());
-    }
-
-    database
-        .execute(
-            "UPDATE guilds SET rankbinds = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(existing_rankbinds)],
-        )
-        .await
-        .map_err(RoError::from)?;
+    let modified = existing_rankbinds
+        .iter()
+        .any(|r| r.group_id == new_bind.group_id && r.group_rank_id == new_bind.group_rank_id);
 
     let log = AuditLog {
         kind: AuditLogKind::BindCreate,
@@ -117,20 +102,22 @@ pub async fn add_rankbind(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await
-        .map_err(RoError::from)?;
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let bind = new_bind.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .upsert_rankbind(guild_id, &bind)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     Ok(AddRankbind {
         bind: new_bind,
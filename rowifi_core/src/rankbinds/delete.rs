@@ -1,5 +1,5 @@
 use chrono::Utc;
-use rowifi_database::{postgres::types::Json, Database};
+use rowifi_database::Database;
 use rowifi_models::{
     audit_log::{AuditLog, AuditLogData, AuditLogKind, BindDelete},
     bind::{BindType, Rankbind},
@@ -9,7 +9,7 @@ use rowifi_models::{
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
-use crate::error::RoError;
+use crate::{error::RoError, guild::write_config, repos::BindRepo};
 
 #[derive(Debug, Serialize)]
 pub struct DeleteRankbind {
@@ -31,6 +31,7 @@ pub struct RankbindArguments {
 pub async fn delete_rankbinds(
     database: &Database,
     rankbinds: &[Rankbind],
+    config_version: i64,
     guild_id: GuildId,
     author_id: UserId,
     args: Vec<RankbindArguments>,
@@ -57,18 +58,6 @@ pub async fn delete_rankbinds(
         });
     }
 
-    let new_rankbinds = rankbinds
-        .iter()
-        .filter(|r| !binds_to_delete.contains(&(r.group_id, r.group_rank_id)))
-        .cloned()
-        .collect::<Vec<_>>();
-    database
-        .execute(
-            "UPDATE guilds SET rankbinds = $2 WHERE guild_id = $1",
-            &[&guild_id, &Json(new_rankbinds)],
-        )
-        .await?;
-
     #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
     let log = AuditLog {
         kind: AuditLogKind::BindDelete,
@@ -81,19 +70,23 @@ pub async fn delete_rankbinds(
         }),
     };
 
-    database
-        .execute(
-            r"INSERT INTO audit_logs(kind, guild_id, user_id, timestamp, metadata) 
-        VALUES($1, $2, $3, $4, $5)",
-            &[
-                &log.kind,
-                &log.guild_id,
-                &log.user_id,
-                &log.timestamp,
-                &Json(log.metadata),
-            ],
-        )
-        .await?;
+    let ranks = binds_to_delete.iter().copied().collect::<Vec<_>>();
+    write_config(
+        database,
+        guild_id,
+        Some(config_version),
+        &log,
+        |transaction| {
+            let ranks = ranks.clone();
+            Box::pin(async move {
+                BindRepo::new(transaction)
+                    .delete_rankbinds(guild_id, &ranks)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+    .await?;
 
     #[allow(clippy::cast_possible_truncation)]
     Ok(DeleteRankbind {
@@ -1,2 +1,16 @@
 pub mod add;
 pub mod delete;
+
+use rowifi_database::Database;
+use rowifi_models::{bind::Rankbind, id::GuildId};
+
+use crate::{error::RoError, repos::BindRepo};
+
+/// Get the rankbinds of the server in the order they were added.
+pub async fn guild_rankbinds(
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<Vec<Rankbind>, RoError> {
+    let rankbinds = BindRepo::new(database).rankbinds(guild_id).await?;
+    Ok(rankbinds)
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use rowifi_database::{Database, DatabaseError, Executor};
+use rowifi_models::{analytics::AnalyticsGroup, roblox::id::GroupId};
+
+/// Reads the member counts collected for the groups servers registered for analytics.
+pub struct AnalyticsRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+impl<'a, E: Executor> AnalyticsRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    /// Get the counts collected for the group after `since`, oldest first.
+    pub async fn group_history(
+        &self,
+        group_id: GroupId,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnalyticsGroup>, DatabaseError> {
+        self.executor
+            .query(
+                "SELECT group_id, roles, member_count, timestamp FROM group_analytics WHERE group_id = $1 AND timestamp > $2 ORDER BY timestamp",
+                &[&group_id, &since],
+            )
+            .await
+    }
+}
@@ -0,0 +1,61 @@
+use rowifi_database::{postgres::types::Json, Database, DatabaseError, Executor};
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogKind},
+    id::{GuildId, UserId},
+};
+
+/// Reads and writes the audit logs of servers.
+pub struct AuditLogRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+/// Narrows down the audit logs returned by [`AuditLogRepo::list`]. Filters that are [`None`]
+/// match every log.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AuditLogFilter {
+    pub user_id: Option<UserId>,
+    pub kind: Option<AuditLogKind>,
+}
+
+const AUDIT_LOG_COLUMNS: &str = "kind, guild_id, user_id, timestamp, metadata";
+
+impl<'a, E: Executor> AuditLogRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    pub async fn insert(&self, log: &AuditLog) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                &format!("INSERT INTO audit_logs({AUDIT_LOG_COLUMNS}) VALUES($1, $2, $3, $4, $5)"),
+                &[
+                    &log.kind,
+                    &log.guild_id,
+                    &log.user_id,
+                    &log.timestamp,
+                    &Json(&log.metadata),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Get a page of the server's audit logs matching `filter`, oldest first.
+    pub async fn list(
+        &self,
+        guild_id: GuildId,
+        filter: AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditLog>, DatabaseError> {
+        self.executor
+            .query(
+                &format!(
+                    "SELECT {AUDIT_LOG_COLUMNS} FROM audit_logs WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR user_id = $2) AND ($3::INT IS NULL OR kind = $3) ORDER BY timestamp LIMIT $4 OFFSET $5"
+                ),
+                &[&guild_id, &filter.user_id, &filter.kind, &limit, &offset],
+            )
+            .await
+    }
+}
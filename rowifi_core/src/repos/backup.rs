@@ -0,0 +1,98 @@
+use rowifi_database::{
+    postgres::{types::Json, Row},
+    Database, DatabaseError, Executor,
+};
+use rowifi_models::{backup::BackupGuild, id::UserId};
+
+/// Reads and writes the server configurations users backed up.
+pub struct BackupRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+struct BackupNameRow {
+    name: String,
+}
+
+struct BackupDataRow {
+    data: Json<BackupGuild>,
+}
+
+impl<'a, E: Executor> BackupRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    /// Get the names of the user's backups.
+    pub async fn names(&self, user_id: UserId) -> Result<Vec<String>, DatabaseError> {
+        let rows = self
+            .executor
+            .query::<BackupNameRow>(
+                "SELECT name FROM backups WHERE user_id = $1 ORDER BY name",
+                &[&user_id],
+            )
+            .await?;
+        Ok(rows.into_iter().map(|r| r.name).collect())
+    }
+
+    pub async fn get(
+        &self,
+        user_id: UserId,
+        name: &str,
+    ) -> Result<Option<BackupGuild>, DatabaseError> {
+        let row = self
+            .executor
+            .query_opt::<BackupDataRow>(
+                "SELECT data FROM backups WHERE user_id = $1 AND name = $2",
+                &[&user_id, &name],
+            )
+            .await?;
+        Ok(row.map(|r| r.data.0))
+    }
+
+    /// Save the backup under `name`, overwriting the user's backup with the same name.
+    pub async fn upsert(
+        &self,
+        user_id: UserId,
+        name: &str,
+        backup: &BackupGuild,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "INSERT INTO backups(user_id, name, data) VALUES($1, $2, $3) ON CONFLICT(user_id, name) DO UPDATE SET data = $3",
+                &[&user_id, &name, &Json(backup)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns whether the backup existed.
+    pub async fn delete(&self, user_id: UserId, name: &str) -> Result<bool, DatabaseError> {
+        let deleted = self
+            .executor
+            .execute(
+                "DELETE FROM backups WHERE user_id = $1 AND name = $2",
+                &[&user_id, &name],
+            )
+            .await?;
+        Ok(deleted > 0)
+    }
+}
+
+impl TryFrom<Row> for BackupNameRow {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let name = row.try_get("name")?;
+        Ok(Self { name })
+    }
+}
+
+impl TryFrom<Row> for BackupDataRow {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let data = row.try_get("data")?;
+        Ok(Self { data })
+    }
+}
@@ -0,0 +1,233 @@
+use rowifi_database::{Database, DatabaseError, Executor};
+use rowifi_models::{
+    bind::{Assetbind, Custombind, Groupbind, Rankbind},
+    id::GuildId,
+    roblox::id::{AssetId, GroupId},
+};
+
+/// Reads and writes the rankbinds, groupbinds, assetbinds and custombinds of servers. Binds are
+/// read in the order they were added.
+pub struct BindRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+impl<'a, E: Executor> BindRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    pub async fn rankbinds(&self, guild_id: GuildId) -> Result<Vec<Rankbind>, DatabaseError> {
+        self.executor
+            .query(
+                "SELECT group_id, group_rank_id, roblox_rank_id, discord_roles, priority, template FROM rankbinds WHERE guild_id = $1 ORDER BY rankbind_id",
+                &[&guild_id],
+            )
+            .await
+    }
+
+    pub async fn groupbinds(&self, guild_id: GuildId) -> Result<Vec<Groupbind>, DatabaseError> {
+        self.executor
+            .query(
+                "SELECT group_id, discord_roles, priority, template FROM groupbinds WHERE guild_id = $1 ORDER BY groupbind_id",
+                &[&guild_id],
+            )
+            .await
+    }
+
+    pub async fn assetbinds(&self, guild_id: GuildId) -> Result<Vec<Assetbind>, DatabaseError> {
+        self.executor
+            .query(
+                "SELECT asset_id, asset_type, discord_roles, priority, template FROM assetbinds WHERE guild_id = $1 ORDER BY assetbind_id",
+                &[&guild_id],
+            )
+            .await
+    }
+
+    pub async fn custombinds(&self, guild_id: GuildId) -> Result<Vec<Custombind>, DatabaseError> {
+        self.executor
+            .query(
+                "SELECT custom_bind_id, code, discord_roles, priority, template FROM custombinds WHERE guild_id = $1 ORDER BY custom_bind_id",
+                &[&guild_id],
+            )
+            .await
+    }
+
+    /// Add the rankbind, replacing the server's rankbind for the same rank if it has one.
+    pub async fn upsert_rankbind(
+        &self,
+        guild_id: GuildId,
+        bind: &Rankbind,
+    ) -> Result<(), DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let group_rank_id = bind.group_rank_id as i32;
+        self.executor
+            .execute(
+                r"INSERT INTO rankbinds(guild_id, group_id, group_rank_id, roblox_rank_id, discord_roles, priority, template)
+                VALUES($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT(guild_id, group_id, group_rank_id) DO UPDATE SET discord_roles = $5, priority = $6, template = $7",
+                &[
+                    &guild_id,
+                    &bind.group_id,
+                    &group_rank_id,
+                    &bind.roblox_rank_id,
+                    &bind.discord_roles,
+                    &bind.priority,
+                    &bind.template,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Add the groupbind, replacing the server's groupbind for the same group if it has one.
+    pub async fn upsert_groupbind(
+        &self,
+        guild_id: GuildId,
+        bind: &Groupbind,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                r"INSERT INTO groupbinds(guild_id, group_id, discord_roles, priority, template)
+                VALUES($1, $2, $3, $4, $5)
+                ON CONFLICT(guild_id, group_id) DO UPDATE SET discord_roles = $3, priority = $4, template = $5",
+                &[
+                    &guild_id,
+                    &bind.group_id,
+                    &bind.discord_roles,
+                    &bind.priority,
+                    &bind.template,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Add the assetbind, replacing the server's assetbind for the same item if it has one.
+    pub async fn upsert_assetbind(
+        &self,
+        guild_id: GuildId,
+        bind: &Assetbind,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                r"INSERT INTO assetbinds(guild_id, asset_id, asset_type, discord_roles, priority, template)
+                VALUES($1, $2, $3, $4, $5, $6)
+                ON CONFLICT(guild_id, asset_id) DO UPDATE SET discord_roles = $4, priority = $5, template = $6",
+                &[
+                    &guild_id,
+                    &bind.asset_id,
+                    &bind.asset_type,
+                    &bind.discord_roles,
+                    &bind.priority,
+                    &bind.template,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn insert_custombind(
+        &self,
+        guild_id: GuildId,
+        bind: &Custombind,
+    ) -> Result<(), DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let custom_bind_id = bind.custom_bind_id as i32;
+        self.executor
+            .execute(
+                r"INSERT INTO custombinds(guild_id, custom_bind_id, code, discord_roles, priority, template)
+                VALUES($1, $2, $3, $4, $5, $6)",
+                &[
+                    &guild_id,
+                    &custom_bind_id,
+                    &bind.code,
+                    &bind.discord_roles,
+                    &bind.priority,
+                    &bind.template,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Delete the rankbinds for the given `(group, rank)` pairs. Returns how many were deleted.
+    pub async fn delete_rankbinds(
+        &self,
+        guild_id: GuildId,
+        ranks: &[(GroupId, u32)],
+    ) -> Result<u64, DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let (group_ids, rank_ids): (Vec<_>, Vec<_>) = ranks
+            .iter()
+            .map(|(group_id, rank_id)| (*group_id, *rank_id as i32))
+            .unzip();
+        self.executor
+            .execute(
+                r"DELETE FROM rankbinds WHERE guild_id = $1
+                AND (group_id, group_rank_id) IN (SELECT * FROM UNNEST($2::BIGINT[], $3::INT[]))",
+                &[&guild_id, &group_ids, &rank_ids],
+            )
+            .await
+    }
+
+    /// Returns how many groupbinds were deleted.
+    pub async fn delete_groupbinds(
+        &self,
+        guild_id: GuildId,
+        group_ids: &[GroupId],
+    ) -> Result<u64, DatabaseError> {
+        self.executor
+            .execute(
+                "DELETE FROM groupbinds WHERE guild_id = $1 AND group_id = ANY($2)",
+                &[&guild_id, &group_ids],
+            )
+            .await
+    }
+
+    /// Returns how many assetbinds were deleted.
+    pub async fn delete_assetbinds(
+        &self,
+        guild_id: GuildId,
+        asset_ids: &[AssetId],
+    ) -> Result<u64, DatabaseError> {
+        self.executor
+            .execute(
+                "DELETE FROM assetbinds WHERE guild_id = $1 AND asset_id = ANY($2)",
+                &[&guild_id, &asset_ids],
+            )
+            .await
+    }
+
+    /// Returns how many custombinds were deleted.
+    pub async fn delete_custombinds(
+        &self,
+        guild_id: GuildId,
+        custom_bind_ids: &[u32],
+    ) -> Result<u64, DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let custom_bind_ids = custom_bind_ids
+            .iter()
+            .map(|id| *id as i32)
+            .collect::<Vec<_>>();
+        self.executor
+            .execute(
+                "DELETE FROM custombinds WHERE guild_id = $1 AND custom_bind_id = ANY($2)",
+                &[&guild_id, &custom_bind_ids],
+            )
+            .await
+    }
+
+    /// Delete every bind of the server.
+    pub async fn clear(&self, guild_id: GuildId) -> Result<(), DatabaseError> {
+        for table in ["rankbinds", "groupbinds", "assetbinds", "custombinds"] {
+            self.executor
+                .execute(
+                    &format!("DELETE FROM {table} WHERE guild_id = $1"),
+                    &[&guild_id],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
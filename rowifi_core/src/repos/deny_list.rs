@@ -0,0 +1,100 @@
+use rowifi_database::{Database, DatabaseError, Executor};
+use rowifi_models::{
+    deny_list::{DenyList, DenyListData},
+    id::GuildId,
+};
+
+/// Reads and writes the deny lists of servers. Deny lists are read in the order they were added.
+pub struct DenyListRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+impl<'a, E: Executor> DenyListRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    pub async fn list(&self, guild_id: GuildId) -> Result<Vec<DenyList>, DatabaseError> {
+        self.executor
+            .query(
+                "SELECT deny_list_id, kind, action_type, reason, user_id, group_id, code FROM deny_lists WHERE guild_id = $1 ORDER BY deny_list_id",
+                &[&guild_id],
+            )
+            .await
+    }
+
+    pub async fn insert(
+        &self,
+        guild_id: GuildId,
+        deny_list: &DenyList,
+    ) -> Result<(), DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let deny_list_id = deny_list.id as i32;
+        let (user_id, group_id, code) = match &deny_list.data {
+            DenyListData::User(user_id) => (Some(*user_id), None, None),
+            DenyListData::Group(group_id) => (None, Some(*group_id), None),
+            DenyListData::Custom(code) => (None, None, Some(code.as_str())),
+        };
+        self.executor
+            .execute(
+                r"INSERT INTO deny_lists(guild_id, deny_list_id, kind, action_type, reason, user_id, group_id, code)
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[
+                    &guild_id,
+                    &deny_list_id,
+                    &deny_list.kind(),
+                    &deny_list.action_type,
+                    &deny_list.reason,
+                    &user_id,
+                    &group_id,
+                    &code,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Change the reason and action of the deny list with the same ID. What it matches against
+    /// is left as it is.
+    pub async fn update(
+        &self,
+        guild_id: GuildId,
+        deny_list: &DenyList,
+    ) -> Result<(), DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let deny_list_id = deny_list.id as i32;
+        self.executor
+            .execute(
+                "UPDATE deny_lists SET reason = $3, action_type = $4 WHERE guild_id = $1 AND deny_list_id = $2",
+                &[
+                    &guild_id,
+                    &deny_list_id,
+                    &deny_list.reason,
+                    &deny_list.action_type,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns how many deny lists were deleted.
+    pub async fn delete(&self, guild_id: GuildId, ids: &[u32]) -> Result<u64, DatabaseError> {
+        #[allow(clippy::cast_possible_wrap)]
+        let ids = ids.iter().map(|id| *id as i32).collect::<Vec<_>>();
+        self.executor
+            .execute(
+                "DELETE FROM deny_lists WHERE guild_id = $1 AND deny_list_id = ANY($2)",
+                &[&guild_id, &ids],
+            )
+            .await
+    }
+
+    /// Delete every deny list of the server.
+    pub async fn clear(&self, guild_id: GuildId) -> Result<(), DatabaseError> {
+        self.executor
+            .execute("DELETE FROM deny_lists WHERE guild_id = $1", &[&guild_id])
+            .await?;
+        Ok(())
+    }
+}
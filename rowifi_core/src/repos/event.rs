@@ -0,0 +1,99 @@
+use rowifi_database::{postgres::Row, Database, DatabaseError, Executor};
+use rowifi_models::{events::EventLog, id::GuildId, roblox::id::UserId as RobloxUserId};
+
+/// Reads and writes the events logged in servers.
+pub struct EventRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+struct GuildEventIdRow {
+    guild_event_id: i64,
+}
+
+const EVENT_COLUMNS: &str =
+    "guild_id, guild_event_id, event_type, host_id, timestamp, attendees, notes";
+
+impl<'a, E: Executor> EventRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    /// Insert the event with the next id of its server, ignoring `event.guild_event_id`. Returns
+    /// the id it was given.
+    pub async fn insert(&self, event: &EventLog) -> Result<i64, DatabaseError> {
+        let row = self
+            .executor
+            .query_opt::<GuildEventIdRow>(
+                r"INSERT INTO events(guild_id, event_type, guild_event_id, host_id, timestamp, attendees, notes)
+                VALUES($1, $2, (SELECT COALESCE(max(guild_event_id) + 1, 1) FROM events WHERE guild_id = $1), $3, $4, $5, $6)
+                RETURNING guild_event_id",
+                &[
+                    &event.guild_id,
+                    &event.event_type,
+                    &event.host_id,
+                    &event.timestamp,
+                    &event.attendees,
+                    &event.notes,
+                ],
+            )
+            .await?;
+        Ok(row
+            .expect("an insert without a conflict target always returns its row")
+            .guild_event_id)
+    }
+
+    pub async fn get(
+        &self,
+        guild_id: GuildId,
+        guild_event_id: i64,
+    ) -> Result<Option<EventLog>, DatabaseError> {
+        self.executor
+            .query_opt(
+                &format!(
+                    "SELECT {EVENT_COLUMNS} FROM events WHERE guild_id = $1 AND guild_event_id = $2"
+                ),
+                &[&guild_id, &guild_event_id],
+            )
+            .await
+    }
+
+    /// Get the events of the server that `roblox_id` attended.
+    pub async fn attended_by(
+        &self,
+        guild_id: GuildId,
+        roblox_id: RobloxUserId,
+    ) -> Result<Vec<EventLog>, DatabaseError> {
+        self.executor
+            .query(
+                &format!(
+                    "SELECT {EVENT_COLUMNS} FROM events WHERE guild_id = $1 AND $2 = ANY(attendees)"
+                ),
+                &[&guild_id, &roblox_id],
+            )
+            .await
+    }
+
+    /// Get the events of the server that `roblox_id` hosted.
+    pub async fn hosted_by(
+        &self,
+        guild_id: GuildId,
+        roblox_id: RobloxUserId,
+    ) -> Result<Vec<EventLog>, DatabaseError> {
+        self.executor
+            .query(
+                &format!("SELECT {EVENT_COLUMNS} FROM events WHERE guild_id = $1 AND host_id = $2"),
+                &[&guild_id, &roblox_id],
+            )
+            .await
+    }
+}
+
+impl TryFrom<Row> for GuildEventIdRow {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let guild_event_id = row.try_get("guild_event_id")?;
+        Ok(Self { guild_event_id })
+    }
+}
@@ -0,0 +1,293 @@
+use rowifi_database::{
+    postgres::{types::Json, Row},
+    Database, DatabaseError, Executor,
+};
+use rowifi_models::{
+    events::EventType,
+    guild::PartialRoGuild,
+    id::{ChannelId, GuildId},
+    roblox::id::{GroupId, UniverseId},
+};
+
+/// A column of the `guilds` table that is read into a [`PartialRoGuild`]. `guild_id` is always
+/// read. Fields of the columns that were not selected are left at their defaults.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GuildColumn {
+    Kind,
+    BypassRoles,
+    UnverifiedRoles,
+    VerifiedRoles,
+    DefaultTemplate,
+    UpdateOnJoin,
+    EventTypes,
+    AutoDetection,
+    XpBinds,
+    SyncXpOnSetrank,
+    RegisteredGroups,
+    StickyRoles,
+    LogChannel,
+    LinkedUniverse,
+    ConfigVersion,
+}
+
+/// The outcome of [`GuildRepo::bump_config_version`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigBump {
+    Bumped,
+    /// The configuration changed since the given version was read.
+    Stale,
+    /// The server has no row.
+    Missing,
+}
+
+/// Reads and writes the configuration of servers.
+pub struct GuildRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+struct OpenCloudKeyRow {
+    open_cloud_key: Option<Vec<u8>>,
+}
+
+impl GuildColumn {
+    /// Every column that is read into a [`PartialRoGuild`].
+    pub const ALL: &'static [Self] = &[
+        Self::Kind,
+        Self::BypassRoles,
+        Self::UnverifiedRoles,
+        Self::VerifiedRoles,
+        Self::DefaultTemplate,
+        Self::UpdateOnJoin,
+        Self::EventTypes,
+        Self::AutoDetection,
+        Self::XpBinds,
+        Self::SyncXpOnSetrank,
+        Self::RegisteredGroups,
+        Self::StickyRoles,
+        Self::LogChannel,
+        Self::LinkedUniverse,
+        Self::ConfigVersion,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Kind => "kind",
+            Self::BypassRoles => "bypass_roles",
+            Self::UnverifiedRoles => "unverified_roles",
+            Self::VerifiedRoles => "verified_roles",
+            Self::DefaultTemplate => "default_template",
+            Self::UpdateOnJoin => "update_on_join",
+            Self::EventTypes => "event_types",
+            Self::AutoDetection => "auto_detection",
+            Self::XpBinds => "xp_binds",
+            Self::SyncXpOnSetrank => "sync_xp_on_setrank",
+            Self::RegisteredGroups => "registered_groups",
+            Self::StickyRoles => "sticky_roles",
+            Self::LogChannel => "log_channel",
+            Self::LinkedUniverse => "linked_universe",
+            Self::ConfigVersion => "config_version",
+        }
+    }
+}
+
+impl<'a, E: Executor> GuildRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    /// Get the given columns of the server. Returns [`None`] if the server has no row.
+    pub async fn get(
+        &self,
+        guild_id: GuildId,
+        columns: &[GuildColumn],
+    ) -> Result<Option<PartialRoGuild>, DatabaseError> {
+        self.executor
+            .query_opt(&select(columns), &[&guild_id])
+            .await
+    }
+
+    /// Get the given columns of the server, creating its row with the default configuration if
+    /// it does not have one.
+    pub async fn get_or_create(
+        &self,
+        guild_id: GuildId,
+        columns: &[GuildColumn],
+    ) -> Result<PartialRoGuild, DatabaseError> {
+        if let Some(guild) = self.get(guild_id, columns).await? {
+            return Ok(guild);
+        }
+        self.create(guild_id).await?;
+        Ok(PartialRoGuild::new(guild_id))
+    }
+
+    /// Bump the configuration version of the server, only if it is still `config_version` when
+    /// one is given. A change based on that version may only be written if it was bumped.
+    pub async fn bump_config_version(
+        &self,
+        guild_id: GuildId,
+        config_version: Option<i64>,
+    ) -> Result<ConfigBump, DatabaseError> {
+        let updated = self
+            .executor
+            .execute(
+                "UPDATE guilds SET config_version = config_version + 1 WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR config_version = $2)",
+                &[&guild_id, &config_version],
+            )
+            .await?;
+        if updated > 0 {
+            Ok(ConfigBump::Bumped)
+        } else if self.get(guild_id, &[]).await?.is_some() {
+            Ok(ConfigBump::Stale)
+        } else {
+            Ok(ConfigBump::Missing)
+        }
+    }
+
+    /// Create the row of the server with the default configuration if it does not have one.
+    pub async fn create(&self, guild_id: GuildId) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "INSERT INTO guilds(guild_id) VALUES($1) ON CONFLICT DO NOTHING",
+                &[&guild_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the settings a backup restores: the bypass, unverified and verified roles, the
+    /// XP binds, the default template, the event types and the update flags. Settings missing
+    /// from `guild` are reset to their defaults.
+    pub async fn restore_settings(&self, guild: &PartialRoGuild) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "UPDATE guilds SET bypass_roles = $2, unverified_roles = $3, verified_roles = $4, xp_binds = $5, default_template = $6, update_on_join = $7, event_types = $8, auto_detection = $9, sync_xp_on_setrank = $10 WHERE guild_id = $1",
+                &[
+                    &guild.guild_id,
+                    &Json(&guild.bypass_roles),
+                    &guild.unverified_roles,
+                    &guild.verified_roles,
+                    &Json(&guild.xp_binds),
+                    &guild.default_template.clone().unwrap_or_default(),
+                    &guild.update_on_join.unwrap_or_default(),
+                    &Json(&guild.event_types),
+                    &guild.auto_detection.unwrap_or_default(),
+                    &guild.sync_xp_on_setrank.unwrap_or_default(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the groups registered for analytics if the server's configuration is still at
+    /// `config_version`. Returns whether they were replaced.
+    pub async fn set_registered_groups(
+        &self,
+        guild_id: GuildId,
+        registered_groups: &[GroupId],
+        config_version: i64,
+    ) -> Result<bool, DatabaseError> {
+        let updated = self
+            .executor
+            .execute(
+                "UPDATE guilds SET registered_groups = $2, config_version = config_version + 1 WHERE guild_id = $1 AND config_version = $3",
+                &[&guild_id, &registered_groups, &config_version],
+            )
+            .await?;
+        Ok(updated > 0)
+    }
+
+    pub async fn set_log_channel(
+        &self,
+        guild_id: GuildId,
+        log_channel: Option<ChannelId>,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "UPDATE guilds SET log_channel = $2 WHERE guild_id = $1",
+                &[&guild_id, &log_channel],
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_linked_universe(
+        &self,
+        guild_id: GuildId,
+        linked_universe: Option<UniverseId>,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "UPDATE guilds SET linked_universe = $2 WHERE guild_id = $1",
+                &[&guild_id, &linked_universe],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Get the sealed Open Cloud API key of the server, if it registered one.
+    pub async fn open_cloud_key(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Option<Vec<u8>>, DatabaseError> {
+        let row = self
+            .executor
+            .query_opt::<OpenCloudKeyRow>(
+                "SELECT open_cloud_key FROM guilds WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+        Ok(row.and_then(|r| r.open_cloud_key))
+    }
+
+    pub async fn set_open_cloud_key(
+        &self,
+        guild_id: GuildId,
+        open_cloud_key: Option<&[u8]>,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "UPDATE guilds SET open_cloud_key = $2 WHERE guild_id = $1",
+                &[&guild_id, &open_cloud_key],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn select(columns: &[GuildColumn]) -> String {
+    let mut statement = String::from("SELECT guild_id");
+    for column in columns {
+        statement.push_str(", ");
+        statement.push_str(column.name());
+    }
+    statement.push_str(" FROM guilds WHERE guild_id = $1");
+    statement
+}
+
+impl TryFrom<Row> for OpenCloudKeyRow {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let open_cloud_key = row.try_get("open_cloud_key")?;
+        Ok(Self { open_cloud_key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select, GuildColumn};
+
+    #[test]
+    fn guild_id_is_always_selected() {
+        assert_eq!(
+            select(&[]),
+            "SELECT guild_id FROM guilds WHERE guild_id = $1"
+        );
+        assert_eq!(
+            select(&[GuildColumn::LogChannel, GuildColumn::ConfigVersion]),
+            "SELECT guild_id, log_channel, config_version FROM guilds WHERE guild_id = $1"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use rowifi_database::{postgres::Row, Database, DatabaseError, Executor};
+use rowifi_models::id::{GuildId, RoleId};
+
+/// Reads and writes the queue of servers waiting for all their members (or those with a role)
+/// to be updated.
+pub struct MassUpdateRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+/// How far along the mass update of a server is.
+#[derive(Clone, Copy, Debug)]
+pub struct MassUpdateProgress {
+    /// The members left to update.
+    pub updates: i32,
+    pub errored: i32,
+}
+
+struct CountRow {
+    count: i64,
+}
+
+impl<'a, E: Executor> MassUpdateRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    /// Returns [`None`] if the server is not queued.
+    pub async fn get(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Option<MassUpdateProgress>, DatabaseError> {
+        self.executor
+            .query_opt(
+                "SELECT updates, errored FROM mass_update_guilds WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await
+    }
+
+    /// The 1-based position of the first queued member of the server among the queued members
+    /// of every server. Returns [`None`] if none of its members are queued yet.
+    pub async fn queue_position(&self, guild_id: GuildId) -> Result<Option<i64>, DatabaseError> {
+        let row = self
+            .executor
+            .query_opt::<CountRow>(
+                r"WITH mass_update_counts AS (SELECT guild_id, ROW_NUMBER() OVER (ORDER BY timestamp ASC) AS row_num FROM mass_update_users)
+                SELECT row_num AS count FROM mass_update_counts WHERE guild_id = $1 LIMIT 1",
+                &[&guild_id],
+            )
+            .await?;
+        Ok(row.map(|r| r.count))
+    }
+
+    /// How many members of every server are waiting to be updated.
+    pub async fn queued_users(&self) -> Result<i64, DatabaseError> {
+        let row = self
+            .executor
+            .query_opt::<CountRow>("SELECT COUNT(*) AS count FROM mass_update_users", &[])
+            .await?;
+        Ok(row.map_or(0, |r| r.count))
+    }
+
+    /// Queue the server. Only members with `role_id` are updated if it is given.
+    pub async fn enqueue(
+        &self,
+        guild_id: GuildId,
+        role_id: Option<RoleId>,
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "INSERT INTO mass_update_guilds(guild_id, role_id) VALUES($1, $2)",
+                &[&guild_id, &role_id],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl TryFrom<Row> for MassUpdateProgress {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let updates = row.try_get("updates")?;
+        let errored = row.try_get("errored")?;
+
+        Ok(Self { updates, errored })
+    }
+}
+
+impl TryFrom<Row> for CountRow {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let count = row.try_get("count")?;
+
+        Ok(Self { count })
+    }
+}
@@ -0,0 +1,25 @@
+//! Typed access to the tables of the database. Each repository runs on a
+//! [`Database`](rowifi_database::Database) or on a [`Transaction`](rowifi_database::Transaction)
+//! when its writes have to be atomic with others.
+
+mod analytics;
+mod audit_log;
+mod backup;
+mod bind;
+mod deny_list;
+mod event;
+mod guild;
+mod mass_update;
+mod user;
+
+pub use self::{
+    analytics::AnalyticsRepo,
+    audit_log::{AuditLogFilter, AuditLogRepo},
+    backup::BackupRepo,
+    bind::BindRepo,
+    deny_list::DenyListRepo,
+    event::EventRepo,
+    guild::{ConfigBump, GuildColumn, GuildRepo},
+    mass_update::{MassUpdateProgress, MassUpdateRepo},
+    user::UserRepo,
+};
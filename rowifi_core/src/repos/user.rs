@@ -0,0 +1,90 @@
+use rowifi_database::{Database, DatabaseError, Executor};
+use rowifi_models::{
+    id::{GuildId, UserId},
+    roblox::id::UserId as RobloxUserId,
+    user::RoUser,
+};
+use std::collections::HashMap;
+
+/// Reads and writes the Roblox accounts linked to Discord users.
+pub struct UserRepo<'a, E = Database> {
+    executor: &'a E,
+}
+
+const USER_COLUMNS: &str = "user_id, default_account_id, linked_accounts, other_accounts, flags";
+
+impl<'a, E: Executor> UserRepo<'a, E> {
+    #[must_use]
+    pub fn new(executor: &'a E) -> Self {
+        Self { executor }
+    }
+
+    /// Get the user's linked accounts. Returns [`None`] if the user is not verified.
+    pub async fn get(&self, user_id: UserId) -> Result<Option<RoUser>, DatabaseError> {
+        self.executor
+            .query_opt(
+                &format!("SELECT {USER_COLUMNS} FROM roblox_users WHERE user_id = $1"),
+                &[&user_id],
+            )
+            .await
+    }
+
+    /// Use `roblox_id` as the user's account in the server.
+    pub async fn link_account(
+        &self,
+        user_id: UserId,
+        guild_id: GuildId,
+        roblox_id: RobloxUserId,
+    ) -> Result<(), DatabaseError> {
+        let mut linked_account = HashMap::new();
+        linked_account.insert(guild_id.to_string(), Some(roblox_id.0.to_string()));
+        self.executor
+            .execute(
+                "UPDATE roblox_users SET linked_accounts = linked_accounts || $2 WHERE user_id = $1",
+                &[&user_id, &linked_account],
+            )
+            .await?;
+        self.executor
+            .execute(
+                "INSERT INTO linked_users(roblox_id, user_id, guild_id) VALUES($1, $2, $3) ON CONFLICT(guild_id, user_id) DO UPDATE SET roblox_id = $1",
+                &[&roblox_id, &user_id, &guild_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_default_account(
+        &self,
+        user_id: UserId,
+        default_account_id: RobloxUserId,
+        other_accounts: &[RobloxUserId],
+    ) -> Result<(), DatabaseError> {
+        self.executor
+            .execute(
+                "UPDATE roblox_users SET default_account_id = $2, other_accounts = $3 WHERE user_id = $1",
+                &[&user_id, &default_account_id, &other_accounts],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the accounts the user uses in specific servers and their other accounts.
+    pub async fn set_accounts(
+        &self,
+        user_id: UserId,
+        linked_accounts: &HashMap<GuildId, RobloxUserId>,
+        other_accounts: &[RobloxUserId],
+    ) -> Result<(), DatabaseError> {
+        let linked_accounts = linked_accounts
+            .iter()
+            .map(|(k, v)| (k.to_string(), Some(v.0.to_string())))
+            .collect::<HashMap<_, _>>();
+        self.executor
+            .execute(
+                "UPDATE roblox_users SET linked_accounts = $2, other_accounts = $3 WHERE user_id = $1",
+                &[&user_id, &linked_accounts, &other_accounts],
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -1,18 +1,15 @@
 use itertools::Itertools;
+use rowifi_cache::roblox::CachedRobloxClient;
 use rowifi_models::{
     bind::{AssetType, Bind},
     deny_list::{DenyList, DenyListData},
     discord::cache::{CachedGuild, CachedMember, CachedUser},
-    guild::{BypassRoleKind, PartialRoGuild},
+    guild::{BypassRoleKind, GuildBinds, PartialRoGuild},
     id::{RoleId, UserId},
     roblox::{id::UserId as RobloxUserId, inventory::InventoryItem},
     user::RoUser,
 };
-use rowifi_roblox::{
-    error::{ErrorKind, RobloxError},
-    filter::AssetFilterBuilder,
-    RobloxClient,
-};
+use rowifi_roblox::{error::RobloxError, filter::AssetFilterBuilder};
 use std::collections::{HashMap, HashSet};
 use twilight_http::Client as DiscordClient;
 
@@ -27,12 +24,13 @@ use crate::{
 
 pub struct UpdateUser<'u> {
     pub http: &'u DiscordClient,
-    pub roblox: &'u RobloxClient,
+    pub roblox: &'u CachedRobloxClient,
     pub discord_member: &'u CachedMember,
     pub discord_user: &'u CachedUser,
     pub user: &'u RoUser,
     pub server: &'u CachedGuild,
     pub guild: &'u PartialRoGuild,
+    pub binds: &'u GuildBinds,
     pub all_roles: &'u [RoleId],
 }
 
@@ -76,22 +74,15 @@ impl UpdateUser<'_> {
         let roblox_user = match self.roblox.get_user(*user_id).await {
             Ok(u) => u,
             Err(err) => {
-                if let ErrorKind::Response {
-                    route: _,
-                    status,
-                    bytes: _,
-                } = err.kind()
-                {
-                    if status.as_u16() == 404 {
-                        return Err(UpdateUserError::BannedAccount(*user_id));
-                    }
+                if err.is_not_found() {
+                    return Err(UpdateUserError::BannedAccount(*user_id));
                 }
                 return Err(err.into());
             }
         };
 
         let mut asset_filter = AssetFilterBuilder::new();
-        for assetbind in &self.guild.assetbinds {
+        for assetbind in &self.binds.assetbinds {
             match assetbind.asset_type {
                 AssetType::Asset => asset_filter = asset_filter.asset(assetbind.asset_id),
                 AssetType::Badge => asset_filter = asset_filter.badge(assetbind.asset_id),
@@ -111,7 +102,7 @@ impl UpdateUser<'_> {
             .collect::<HashSet<_>>();
 
         let mut active_deny_lists = Vec::new();
-        for denylist in &self.guild.deny_lists {
+        for denylist in &self.binds.deny_lists {
             let success = match &denylist.data {
                 DenyListData::User(u) => *u == roblox_user.id,
                 DenyListData::Group(g) => user_ranks.contains_key(g),
@@ -168,7 +159,7 @@ impl UpdateUser<'_> {
 
         let mut nickname_bind: Option<Bind> = None;
         tracing::trace!("{:?}", user_ranks);
-        for rankbind in &self.guild.rankbinds {
+        for rankbind in &self.binds.rankbinds {
             // Check if the user's rank in the group is the same as the rankbind
             // or check if the bind is for the Guest role and the user is not in
             // the group
@@ -188,7 +179,7 @@ impl UpdateUser<'_> {
             }
         }
 
-        for groupbind in &self.guild.groupbinds {
+        for groupbind in &self.binds.groupbinds {
             if user_ranks.contains_key(&groupbind.group_id) {
                 if let Some(ref highest) = nickname_bind {
                     if highest.priority() < groupbind.priority {
@@ -202,7 +193,7 @@ impl UpdateUser<'_> {
         }
 
         // TODO: Have parsed custombinds stored somewhere
-        for custombind in &self.guild.custombinds {
+        for custombind in &self.binds.custombinds {
             let exp = custombinds::parser::parser(&custombind.code).map_err(|err| {
                 UpdateUserError::CustombindParsing {
                     id: custombind.custom_bind_id,
@@ -237,7 +228,7 @@ impl UpdateUser<'_> {
             }
         }
 
-        for assetbind in &self.guild.assetbinds {
+        for assetbind in &self.binds.assetbinds {
             if inventory_items.contains(&assetbind.asset_id.0.to_string()) {
                 if let Some(ref highest) = nickname_bind {
                     if highest.priority() < assetbind.priority {
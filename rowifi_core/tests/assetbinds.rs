@@ -0,0 +1,97 @@
+use rowifi_core::assetbinds::{
+    add::{validate_asset, AddAssetbindError},
+    asset_infos,
+};
+use rowifi_models::{
+    bind::{AssetType, Assetbind, Template},
+    roblox::id::AssetId,
+};
+use rowifi_roblox::{BaseUrls, RobloxClient};
+use rowifi_roblox_mock::{Fixtures, InventoryFixture, MockRoblox};
+
+fn fixtures() -> Fixtures {
+    Fixtures::new()
+        .catalog_item(InventoryFixture::Asset(10), "Dominus")
+        .catalog_item(InventoryFixture::Badge(20), "Welcome")
+        .catalog_item(InventoryFixture::Gamepass(30), "VIP")
+}
+
+fn assetbind(asset_type: AssetType, asset_id: u64) -> Assetbind {
+    Assetbind {
+        asset_id: AssetId(asset_id),
+        asset_type,
+        discord_roles: Vec::new(),
+        priority: 0,
+        template: Template("{roblox-username}".into()),
+    }
+}
+
+#[tokio::test]
+async fn validate_asset_returns_name_and_icon() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let info = validate_asset(&roblox, AssetType::Badge, AssetId(20))
+        .await
+        .unwrap();
+    assert_eq!(info.name, "Welcome");
+    assert_eq!(
+        info.icon_url.as_deref(),
+        Some("https://tr.rbxcdn.com/20.png")
+    );
+}
+
+#[tokio::test]
+async fn validate_asset_rejects_wrong_type() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let res = validate_asset(&roblox, AssetType::Gamepass, AssetId(20)).await;
+    assert!(matches!(
+        res,
+        Err(AddAssetbindError::WrongType {
+            actual: AssetType::Badge
+        })
+    ));
+
+    let res = validate_asset(&roblox, AssetType::Asset, AssetId(99)).await;
+    assert!(matches!(res, Err(AddAssetbindError::NotFound)));
+}
+
+#[tokio::test]
+async fn asset_infos_skips_missing_items() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let binds = vec![
+        assetbind(AssetType::Asset, 10),
+        assetbind(AssetType::Gamepass, 30),
+        assetbind(AssetType::Badge, 99),
+    ];
+    let infos = asset_infos(&roblox, &binds).await.unwrap();
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[&AssetId(10)].name, "Dominus");
+    assert_eq!(
+        infos[&AssetId(30)].icon_url.as_deref(),
+        Some("https://tr.rbxcdn.com/30.png")
+    );
+}
+
+#[tokio::test]
+async fn asset_infos_survive_failing_icons() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let base_urls = BaseUrls {
+        thumbnails: format!("http://{}/unavailable", mock.address()),
+        ..mock.base_urls()
+    };
+    let roblox = RobloxClient::new("mock-open-cloud-key", None).with_base_urls(base_urls);
+
+    let binds = vec![
+        assetbind(AssetType::Asset, 10),
+        assetbind(AssetType::Badge, 20),
+    ];
+    let infos = asset_infos(&roblox, &binds).await.unwrap();
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[&AssetId(20)].name, "Welcome");
+    assert!(infos.values().all(|info| info.icon_url.is_none()));
+}
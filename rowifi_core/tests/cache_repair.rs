@@ -0,0 +1,193 @@
+use rowifi_core::cache_repair::{guild_drift, member_drift, Drift};
+use rowifi_models::{
+    discord::{
+        cache::{CachedGuild, CachedMember, CachedRole},
+        guild::{Guild, Member, Permissions},
+    },
+    id::{ChannelId, RoleId, UserId},
+};
+use serde_json::{json, Value};
+
+const GUILD_ID: u64 = 1000;
+const OWNER_ID: u64 = 2000;
+const MEMBER_ID: u64 = 2001;
+const MOD_ROLE: u64 = 1001;
+const GENERAL: u64 = 3000;
+
+fn role(id: u64, permissions: u64) -> Value {
+    json!({
+        "id": id.to_string(),
+        "name": id.to_string(),
+        "color": 0,
+        "hoist": false,
+        "managed": false,
+        "mentionable": false,
+        "permissions": permissions.to_string(),
+        "position": 0,
+        "flags": 0,
+    })
+}
+
+/// The server as Discord returns it: `@everyone` can view channels and send messages, and the
+/// moderator role can kick members.
+fn live_guild() -> Guild {
+    serde_json::from_value(json!({
+        "id": GUILD_ID.to_string(),
+        "name": "Test",
+        "icon": null,
+        "owner_id": OWNER_ID.to_string(),
+        "afk_channel_id": null,
+        "afk_timeout": 300,
+        "banner": null,
+        "default_message_notifications": 0,
+        "description": null,
+        "discovery_splash": null,
+        "emojis": [],
+        "explicit_content_filter": 0,
+        "features": [],
+        "max_video_channel_users": 25,
+        "approximate_member_count": 2,
+        "members": [],
+        "channels": [{
+            "id": GENERAL.to_string(),
+            "guild_id": GUILD_ID.to_string(),
+            "type": 0,
+            "name": "general",
+            "position": 0,
+            "permission_overwrites": [],
+        }],
+        "threads": [],
+        "mfa_level": 0,
+        "nsfw_level": 0,
+        "preferred_locale": "en-US",
+        "premium_progress_bar_enabled": false,
+        "premium_tier": 0,
+        "public_updates_channel_id": null,
+        "roles": [role(GUILD_ID, 3072), role(MOD_ROLE, 2)],
+        "rules_channel_id": null,
+        "safety_alerts_channel_id": null,
+        "splash": null,
+        "system_channel_flags": 0,
+        "system_channel_id": null,
+        "vanity_url_code": null,
+        "verification_level": 0,
+        "widget_channel_id": null,
+    }))
+    .unwrap()
+}
+
+fn live_member() -> Member {
+    serde_json::from_value(json!({
+        "avatar": null,
+        "communication_disabled_until": null,
+        "deaf": false,
+        "flags": 0,
+        "joined_at": "2020-01-01T00:00:00.000000+00:00",
+        "mute": false,
+        "nick": "Builder",
+        "pending": false,
+        "premium_since": null,
+        "roles": [MOD_ROLE.to_string()],
+        "user": {
+            "id": MEMBER_ID.to_string(),
+            "username": "builderman",
+            "discriminator": "0",
+            "global_name": null,
+            "avatar": null,
+            "bot": false,
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn guild_drift_is_empty_when_in_sync() {
+    let live = live_guild();
+    let cached = CachedGuild::from_guild(&live);
+    let roles = live
+        .roles
+        .iter()
+        .map(CachedRole::from_role)
+        .collect::<Vec<_>>();
+
+    assert_eq!(guild_drift(Some(&cached), &roles, &live), Vec::new());
+    assert_eq!(guild_drift(None, &[], &live), vec![Drift::NotCached]);
+}
+
+#[test]
+fn guild_drift_reports_roles_and_channels() {
+    let live = live_guild();
+    let mut cached = CachedGuild::from_guild(&live);
+    cached.name = "Old".into();
+    cached.channels.remove(&ChannelId::new(GENERAL));
+    cached.channels.insert(ChannelId::new(GENERAL + 1));
+    cached.roles.insert(RoleId::new(MOD_ROLE + 1));
+
+    let mut roles = live
+        .roles
+        .iter()
+        .map(CachedRole::from_role)
+        .collect::<Vec<_>>();
+    roles[1].permissions = Permissions::empty();
+    let mut deleted = roles[1].clone();
+    deleted.id = RoleId::new(MOD_ROLE + 1);
+    roles.push(deleted);
+
+    assert_eq!(
+        guild_drift(Some(&cached), &roles, &live),
+        vec![
+            Drift::Name {
+                cached: "Old".into(),
+                live: "Test".into()
+            },
+            Drift::StaleRoles(vec![RoleId::new(MOD_ROLE + 1)]),
+            Drift::RolePermissions {
+                id: RoleId::new(MOD_ROLE),
+                cached: Permissions::empty(),
+                live: Permissions::KICK_MEMBERS,
+            },
+            Drift::MissingChannels(vec![ChannelId::new(GENERAL)]),
+            Drift::StaleChannels(vec![ChannelId::new(GENERAL + 1)]),
+        ]
+    );
+}
+
+#[test]
+fn guild_drift_reports_uncached_roles_as_missing() {
+    let live = live_guild();
+    let cached = CachedGuild::from_guild(&live);
+    let roles = vec![CachedRole::from_role(&live.roles[0])];
+
+    assert_eq!(
+        guild_drift(Some(&cached), &roles, &live),
+        vec![Drift::MissingRoles(vec![RoleId::new(MOD_ROLE)])]
+    );
+}
+
+#[test]
+fn member_drift_reports_nickname_and_roles() {
+    let live = live_member();
+    let cached = CachedMember {
+        id: UserId::new(MEMBER_ID),
+        roles: vec![RoleId::new(MOD_ROLE + 1)],
+        nickname: None,
+        avatar: None,
+    };
+
+    assert_eq!(
+        member_drift(Some(&cached), &live),
+        vec![
+            Drift::Nickname {
+                cached: None,
+                live: Some("Builder".into())
+            },
+            Drift::MissingRoles(vec![RoleId::new(MOD_ROLE)]),
+            Drift::StaleRoles(vec![RoleId::new(MOD_ROLE + 1)]),
+        ]
+    );
+    assert_eq!(
+        member_drift(Some(&CachedMember::from_member(&live)), &live),
+        Vec::new()
+    );
+    assert_eq!(member_drift(None, &live), vec![Drift::NotCached]);
+}
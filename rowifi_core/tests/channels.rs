@@ -0,0 +1,165 @@
+use rowifi_cache::Cache;
+use rowifi_core::channels::{validate_message_channel, InvalidChannel};
+use rowifi_models::{
+    discord::guild::{Guild, Member},
+    id::{ChannelId, GuildId, UserId},
+};
+use serde_json::{json, Value};
+
+const GUILD_ID: u64 = 1000;
+const OWNER_ID: u64 = 2000;
+const BOT_ID: u64 = 2001;
+const GENERAL: u64 = 3000;
+const ANNOUNCEMENTS: u64 = 3001;
+const CATEGORY: u64 = 3002;
+
+fn member(id: u64) -> Member {
+    serde_json::from_value(json!({
+        "avatar": null,
+        "communication_disabled_until": null,
+        "deaf": false,
+        "flags": 0,
+        "joined_at": "2020-01-01T00:00:00.000000+00:00",
+        "mute": false,
+        "nick": null,
+        "pending": false,
+        "premium_since": null,
+        "roles": [],
+        "user": {
+            "id": id.to_string(),
+            "username": format!("user{id}"),
+            "discriminator": "0",
+            "global_name": null,
+            "avatar": null,
+            "bot": false,
+        },
+    }))
+    .unwrap()
+}
+
+fn channel(id: u64, kind: u8, permission_overwrites: Value) -> Value {
+    json!({
+        "id": id.to_string(),
+        "guild_id": GUILD_ID.to_string(),
+        "type": kind,
+        "name": id.to_string(),
+        "position": 0,
+        "parent_id": null,
+        "permission_overwrites": permission_overwrites,
+    })
+}
+
+/// `@everyone` can send messages everywhere except in the announcements channel.
+fn guild() -> Guild {
+    let deny_everyone = json!([{
+        "id": GUILD_ID.to_string(),
+        "type": 0,
+        "allow": "0",
+        "deny": "2048",
+    }]);
+    serde_json::from_value(json!({
+        "id": GUILD_ID.to_string(),
+        "name": "Test",
+        "icon": null,
+        "owner_id": OWNER_ID.to_string(),
+        "afk_channel_id": null,
+        "afk_timeout": 300,
+        "banner": null,
+        "default_message_notifications": 0,
+        "description": null,
+        "discovery_splash": null,
+        "emojis": [],
+        "explicit_content_filter": 0,
+        "features": [],
+        "max_video_channel_users": 25,
+        "member_count": 2,
+        "members": [],
+        "channels": [
+            channel(GENERAL, 0, json!([])),
+            channel(ANNOUNCEMENTS, 0, deny_everyone),
+            channel(CATEGORY, 4, json!([])),
+        ],
+        "threads": [],
+        "mfa_level": 0,
+        "nsfw_level": 0,
+        "preferred_locale": "en-US",
+        "premium_progress_bar_enabled": false,
+        "premium_tier": 0,
+        "public_updates_channel_id": null,
+        "roles": [{
+            "id": GUILD_ID.to_string(),
+            "name": "@everyone",
+            "color": 0,
+            "hoist": false,
+            "managed": false,
+            "mentionable": false,
+            "permissions": "3072",
+            "position": 0,
+            "flags": 0,
+        }],
+        "rules_channel_id": null,
+        "safety_alerts_channel_id": null,
+        "splash": null,
+        "system_channel_flags": 0,
+        "system_channel_id": null,
+        "vanity_url_code": null,
+        "verification_level": 0,
+        "widget_channel_id": null,
+    }))
+    .unwrap()
+}
+
+async fn cache() -> Cache {
+    let cache = Cache::in_memory();
+    cache.cache_guild(guild()).await.unwrap();
+    cache
+        .cache_member(GuildId::new(GUILD_ID), &member(BOT_ID))
+        .await
+        .unwrap();
+    cache
+}
+
+async fn validate(cache: &Cache, channel_id: u64) -> Option<InvalidChannel> {
+    validate_message_channel(
+        cache,
+        GuildId::new(GUILD_ID),
+        ChannelId::new(channel_id),
+        UserId::new(BOT_ID),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn validate_message_channel_accepts_writable_channels() {
+    let cache = cache().await;
+    assert_eq!(validate(&cache, GENERAL).await, None);
+}
+
+#[tokio::test]
+async fn validate_message_channel_rejects_invalid_channels() {
+    let cache = cache().await;
+    assert_eq!(
+        validate(&cache, ANNOUNCEMENTS).await,
+        Some(InvalidChannel::MissingPermissions)
+    );
+    assert_eq!(
+        validate(&cache, CATEGORY).await,
+        Some(InvalidChannel::NotMessageable)
+    );
+    assert_eq!(validate(&cache, 9999).await, Some(InvalidChannel::NotFound));
+}
+
+#[tokio::test]
+async fn validate_message_channel_requires_a_cached_member() {
+    let cache = cache().await;
+    let res = validate_message_channel(
+        &cache,
+        GuildId::new(GUILD_ID),
+        ChannelId::new(GENERAL),
+        UserId::new(OWNER_ID),
+    )
+    .await
+    .unwrap();
+    assert_eq!(res, Some(InvalidChannel::NotFound));
+}
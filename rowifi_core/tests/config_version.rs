@@ -0,0 +1,134 @@
+use chrono::Utc;
+use rowifi_core::{
+    error::ErrorKind,
+    events::types::add::{add_event_type, AddEventTypeError, EventTypeArguments},
+    guild::write_config,
+    repos::{GuildColumn, GuildRepo},
+};
+use rowifi_database::{test_util, Database};
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, SettingModify},
+    guild::PartialRoGuild,
+    id::{ChannelId, GuildId, UserId},
+};
+
+const GUILD_ID: u64 = 4700;
+const AUTHOR_ID: u64 = 4701;
+const NEW_GUILD_ID: u64 = 4702;
+const LOG_CHANNEL_ID: u64 = 4710;
+
+async fn guild(database: &Database) -> PartialRoGuild {
+    database
+        .query_opt::<PartialRoGuild>(
+            "SELECT guild_id, event_types, config_version FROM guilds WHERE guild_id = $1",
+            &[&GuildId::new(GUILD_ID)],
+        )
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn stale_configuration_is_not_written() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(GUILD_ID);
+    database
+        .execute("DELETE FROM guilds WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+    database
+        .execute("INSERT INTO guilds(guild_id) VALUES($1)", &[&guild_id])
+        .await
+        .unwrap();
+
+    // Two admins open the server configuration at the same time.
+    let first = guild(&database).await;
+    let second = guild(&database).await;
+
+    add_event_type(
+        &database,
+        guild_id,
+        UserId::new(AUTHOR_ID),
+        first.event_types,
+        first.config_version,
+        EventTypeArguments {
+            id: 1,
+            name: "Training".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = add_event_type(
+        &database,
+        guild_id,
+        UserId::new(AUTHOR_ID),
+        second.event_types,
+        second.config_version,
+        EventTypeArguments {
+            id: 2,
+            name: "Raid".into(),
+        },
+    )
+    .await;
+    assert!(matches!(
+        res,
+        Err(AddEventTypeError::Generic(err)) if matches!(err.kind(), ErrorKind::Conflict)
+    ));
+
+    let guild = guild(&database).await;
+    assert_eq!(guild.config_version, first.config_version + 1);
+    assert_eq!(
+        guild
+            .event_types
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Training"]
+    );
+}
+
+#[tokio::test]
+async fn configuration_is_written_for_servers_without_a_row() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(NEW_GUILD_ID);
+    database
+        .execute("DELETE FROM guilds WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+
+    let log = AuditLog {
+        kind: AuditLogKind::SettingModify,
+        guild_id: Some(guild_id),
+        user_id: Some(UserId::new(AUTHOR_ID)),
+        timestamp: Utc::now(),
+        metadata: AuditLogData::SettingModify(SettingModify {
+            setting: "log_channel".into(),
+            value: LOG_CHANNEL_ID.to_string(),
+        }),
+    };
+    write_config(&database, guild_id, None, &log, |transaction| {
+        Box::pin(async move {
+            GuildRepo::new(transaction)
+                .set_log_channel(guild_id, Some(ChannelId::new(LOG_CHANNEL_ID)))
+                .await
+        })
+    })
+    .await
+    .unwrap();
+
+    let guild = GuildRepo::new(&database)
+        .get(
+            guild_id,
+            &[GuildColumn::LogChannel, GuildColumn::ConfigVersion],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(guild.log_channel, Some(ChannelId::new(LOG_CHANNEL_ID)));
+    assert_eq!(guild.config_version, 1);
+}
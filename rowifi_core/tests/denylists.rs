@@ -0,0 +1,121 @@
+use rowifi_core::denylists::{
+    add::{add_denylist, DenylistArguments},
+    delete::delete_denylists,
+    guild_deny_lists,
+};
+use rowifi_database::{test_util, Database};
+use rowifi_models::{
+    deny_list::{DenyListActionType, DenyListData, DenyListType},
+    guild::PartialRoGuild,
+    id::{GuildId, UserId},
+    roblox::id::{GroupId, UserId as RobloxUserId},
+};
+
+const GUILD_ID: u64 = 4800;
+const AUTHOR_ID: u64 = 4801;
+
+async fn config_version(database: &Database) -> i64 {
+    database
+        .query_opt::<PartialRoGuild>(
+            "SELECT guild_id, config_version FROM guilds WHERE guild_id = $1",
+            &[&GuildId::new(GUILD_ID)],
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .config_version
+}
+
+async fn add(database: &Database, args: DenylistArguments) {
+    let guild_id = GuildId::new(GUILD_ID);
+    let existing = guild_deny_lists(database, guild_id).await.unwrap();
+    add_denylist(
+        database,
+        guild_id,
+        UserId::new(AUTHOR_ID),
+        &existing,
+        config_version(database).await,
+        args,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn denylists_are_stored_as_rows() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(GUILD_ID);
+    database
+        .execute("DELETE FROM guilds WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+    database
+        .execute("INSERT INTO guilds(guild_id) VALUES($1)", &[&guild_id])
+        .await
+        .unwrap();
+
+    add(
+        &database,
+        DenylistArguments {
+            kind: DenyListType::User,
+            action: DenyListActionType::None,
+            reason: "alt account".into(),
+            user_id: Some(RobloxUserId(1)),
+            group_id: None,
+            code: None,
+        },
+    )
+    .await;
+    add(
+        &database,
+        DenylistArguments {
+            kind: DenyListType::Group,
+            action: DenyListActionType::Kick,
+            reason: "raiders".into(),
+            user_id: None,
+            group_id: Some(GroupId(2)),
+            code: None,
+        },
+    )
+    .await;
+    // Denylisting the same user again modifies the existing denylist.
+    add(
+        &database,
+        DenylistArguments {
+            kind: DenyListType::User,
+            action: DenyListActionType::Ban,
+            reason: "exploiting".into(),
+            user_id: Some(RobloxUserId(1)),
+            group_id: None,
+            code: None,
+        },
+    )
+    .await;
+
+    let deny_lists = guild_deny_lists(&database, guild_id).await.unwrap();
+    assert_eq!(deny_lists.len(), 2);
+    assert_eq!(deny_lists[0].id, 1);
+    assert_eq!(deny_lists[0].data, DenyListData::User(RobloxUserId(1)));
+    assert_eq!(deny_lists[0].reason, "exploiting");
+    assert_eq!(deny_lists[0].action_type, DenyListActionType::Ban);
+    assert_eq!(deny_lists[1].id, 2);
+    assert_eq!(deny_lists[1].data, DenyListData::Group(GroupId(2)));
+
+    let res = delete_denylists(
+        &database,
+        &deny_lists,
+        config_version(&database).await,
+        guild_id,
+        UserId::new(AUTHOR_ID),
+        vec![1, 3],
+    )
+    .await
+    .unwrap();
+    assert_eq!(res.deleted, 1);
+    assert_eq!(res.invalid, [3]);
+
+    let deny_lists = guild_deny_lists(&database, guild_id).await.unwrap();
+    assert_eq!(deny_lists.iter().map(|d| d.id).collect::<Vec<_>>(), [2]);
+}
@@ -0,0 +1,448 @@
+use chrono::Utc;
+use rowifi_core::repos::{
+    AuditLogFilter, AuditLogRepo, BackupRepo, BindRepo, ConfigBump, DenyListRepo, EventRepo,
+    GuildColumn, GuildRepo, MassUpdateRepo, UserRepo,
+};
+use rowifi_database::test_util;
+use rowifi_models::{
+    audit_log::{AuditLog, AuditLogData, AuditLogKind, BindCreate},
+    backup::BackupGuild,
+    bind::{BindType, Custombind, Rankbind, Template},
+    deny_list::{DenyList, DenyListActionType, DenyListData},
+    events::EventLog,
+    id::{GuildId, RoleId, UserId},
+    roblox::id::{GroupId, RoleId as RobloxRoleId, UserId as RobloxUserId},
+};
+
+const GUILD_ID: u64 = 5000;
+const AUDIT_GUILD_ID: u64 = 5001;
+const EVENT_GUILD_ID: u64 = 5002;
+const USER_GUILD_ID: u64 = 5003;
+const BIND_GUILD_ID: u64 = 5004;
+const DENY_LIST_GUILD_ID: u64 = 5005;
+const MASS_UPDATE_GUILD_ID: u64 = 5006;
+const AUTHOR_ID: u64 = 5010;
+const OTHER_AUTHOR_ID: u64 = 5011;
+const BACKUP_USER_ID: u64 = 5012;
+const LINKED_USER_ID: u64 = 5013;
+const HOST_ID: u64 = 5020;
+const ATTENDEE_ID: u64 = 5021;
+const GROUP_ID: u64 = 5030;
+
+fn bind_create(guild_id: GuildId, user_id: UserId) -> AuditLog {
+    AuditLog {
+        kind: AuditLogKind::BindCreate,
+        metadata: AuditLogData::BindCreate(BindCreate {
+            count: 1,
+            kind: BindType::Rank,
+        }),
+        guild_id: Some(guild_id),
+        user_id: Some(user_id),
+        timestamp: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn guild_is_created_and_read_with_every_column() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(GUILD_ID);
+    database
+        .execute("DELETE FROM guilds WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+
+    let repo = GuildRepo::new(&database);
+    assert!(repo
+        .get(guild_id, GuildColumn::ALL)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        repo.bump_config_version(guild_id, None).await.unwrap(),
+        ConfigBump::Missing
+    );
+    repo.get_or_create(guild_id, GuildColumn::ALL)
+        .await
+        .unwrap();
+
+    let guild = repo.get(guild_id, GuildColumn::ALL).await.unwrap().unwrap();
+    assert_eq!(guild.guild_id, guild_id);
+
+    assert_eq!(
+        repo.bump_config_version(guild_id, Some(guild.config_version))
+            .await
+            .unwrap(),
+        ConfigBump::Bumped
+    );
+    assert_eq!(
+        repo.bump_config_version(guild_id, Some(guild.config_version))
+            .await
+            .unwrap(),
+        ConfigBump::Stale
+    );
+    assert_eq!(
+        repo.bump_config_version(guild_id, None).await.unwrap(),
+        ConfigBump::Bumped
+    );
+    let bumped = repo
+        .get(guild_id, &[GuildColumn::ConfigVersion])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bumped.config_version, guild.config_version + 2);
+}
+
+#[tokio::test]
+async fn audit_logs_are_filtered_by_user_and_kind() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(AUDIT_GUILD_ID);
+    database
+        .execute("DELETE FROM audit_logs WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+
+    let repo = AuditLogRepo::new(&database);
+    repo.insert(&bind_create(guild_id, UserId::new(AUTHOR_ID)))
+        .await
+        .unwrap();
+    repo.insert(&bind_create(guild_id, UserId::new(OTHER_AUTHOR_ID)))
+        .await
+        .unwrap();
+
+    let all = repo
+        .list(guild_id, AuditLogFilter::default(), 100, 0)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+
+    let by_user = repo
+        .list(
+            guild_id,
+            AuditLogFilter {
+                user_id: Some(UserId::new(AUTHOR_ID)),
+                kind: None,
+            },
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(by_user.len(), 1);
+    assert_eq!(by_user[0].user_id, Some(UserId::new(AUTHOR_ID)));
+
+    let by_kind = repo
+        .list(
+            guild_id,
+            AuditLogFilter {
+                user_id: None,
+                kind: Some(AuditLogKind::BindCreate),
+            },
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(by_kind.len(), 2);
+
+    let other_kind = repo
+        .list(
+            guild_id,
+            AuditLogFilter {
+                user_id: Some(UserId::new(AUTHOR_ID)),
+                kind: Some(AuditLogKind::BindDelete),
+            },
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+    assert!(other_kind.is_empty());
+
+    let second_page = repo
+        .list(guild_id, AuditLogFilter::default(), 100, 1)
+        .await
+        .unwrap();
+    assert_eq!(second_page.len(), 1);
+}
+
+#[tokio::test]
+async fn events_are_numbered_per_server() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(EVENT_GUILD_ID);
+    database
+        .execute("DELETE FROM events WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+
+    let event = EventLog {
+        guild_id,
+        event_type: 1,
+        guild_event_id: 0,
+        host_id: RobloxUserId(HOST_ID),
+        timestamp: Utc::now(),
+        attendees: vec![RobloxUserId(ATTENDEE_ID)],
+        notes: Some("Training".into()),
+    };
+    let repo = EventRepo::new(&database);
+    assert_eq!(repo.insert(&event).await.unwrap(), 1);
+    assert_eq!(repo.insert(&event).await.unwrap(), 2);
+
+    let second = repo.get(guild_id, 2).await.unwrap().unwrap();
+    assert_eq!(second.guild_event_id, 2);
+    assert_eq!(second.notes.as_deref(), Some("Training"));
+    assert!(repo.get(guild_id, 3).await.unwrap().is_none());
+
+    let attended = repo
+        .attended_by(guild_id, RobloxUserId(ATTENDEE_ID))
+        .await
+        .unwrap();
+    assert_eq!(attended.len(), 2);
+    let hosted = repo
+        .hosted_by(guild_id, RobloxUserId(HOST_ID))
+        .await
+        .unwrap();
+    assert_eq!(hosted.len(), 2);
+    assert!(repo
+        .hosted_by(guild_id, RobloxUserId(ATTENDEE_ID))
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn backups_are_saved_under_their_name() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let user_id = UserId::new(BACKUP_USER_ID);
+    database
+        .execute("DELETE FROM backups WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+
+    let backup = BackupGuild {
+        bypass_roles: Vec::new(),
+        unverified_roles: Vec::new(),
+        verified_roles: vec!["Verified".into()],
+        rankbinds: Vec::new(),
+        groupbinds: Vec::new(),
+        assetbinds: Vec::new(),
+        custombinds: Vec::new(),
+        xp_binds: Vec::new(),
+        deny_lists: Vec::new(),
+        default_template: Template::default(),
+        update_on_join: true,
+        event_types: Vec::new(),
+        auto_detection: false,
+        sync_xp_on_setrank: false,
+    };
+    let repo = BackupRepo::new(&database);
+    repo.upsert(user_id, "main", &backup).await.unwrap();
+    repo.upsert(user_id, "main", &backup).await.unwrap();
+    repo.upsert(user_id, "alt", &backup).await.unwrap();
+
+    assert_eq!(repo.names(user_id).await.unwrap(), ["alt", "main"]);
+    let saved = repo.get(user_id, "main").await.unwrap().unwrap();
+    assert_eq!(saved.verified_roles, ["Verified"]);
+    assert!(saved.update_on_join);
+
+    assert!(repo.delete(user_id, "main").await.unwrap());
+    assert!(!repo.delete(user_id, "main").await.unwrap());
+    assert!(repo.get(user_id, "main").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn linked_account_is_used_in_the_server() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let user_id = UserId::new(LINKED_USER_ID);
+    let guild_id = GuildId::new(USER_GUILD_ID);
+    database
+        .execute("DELETE FROM roblox_users WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+    database
+        .execute("DELETE FROM linked_users WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+
+    let repo = UserRepo::new(&database);
+    assert!(repo.get(user_id).await.unwrap().is_none());
+    database
+        .execute(
+            "INSERT INTO roblox_users(user_id, default_account_id, other_accounts) VALUES($1, $2, $3)",
+            &[&user_id, &RobloxUserId(HOST_ID), &vec![RobloxUserId(ATTENDEE_ID)]],
+        )
+        .await
+        .unwrap();
+
+    repo.link_account(user_id, guild_id, RobloxUserId(ATTENDEE_ID))
+        .await
+        .unwrap();
+    let user = repo.get(user_id).await.unwrap().unwrap();
+    assert_eq!(
+        user.linked_accounts.get(&guild_id),
+        Some(&RobloxUserId(ATTENDEE_ID))
+    );
+
+    repo.set_default_account(user_id, RobloxUserId(ATTENDEE_ID), &[RobloxUserId(HOST_ID)])
+        .await
+        .unwrap();
+    let user = repo.get(user_id).await.unwrap().unwrap();
+    assert_eq!(user.default_account_id, RobloxUserId(ATTENDEE_ID));
+    assert_eq!(user.other_accounts, [RobloxUserId(HOST_ID)]);
+}
+
+#[tokio::test]
+async fn rankbinds_are_replaced_per_rank() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(BIND_GUILD_ID);
+    database
+        .execute("DELETE FROM guilds WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+    GuildRepo::new(&database)
+        .get_or_create(guild_id, &[])
+        .await
+        .unwrap();
+
+    let rankbind = |group_rank_id, priority| Rankbind {
+        group_id: GroupId(GROUP_ID),
+        discord_roles: vec![RoleId::new(1)],
+        group_rank_id,
+        roblox_rank_id: RobloxRoleId(u64::from(group_rank_id)),
+        priority,
+        template: Template::default(),
+    };
+    let repo = BindRepo::new(&database);
+    repo.upsert_rankbind(guild_id, &rankbind(1, 0))
+        .await
+        .unwrap();
+    repo.upsert_rankbind(guild_id, &rankbind(2, 0))
+        .await
+        .unwrap();
+    repo.upsert_rankbind(guild_id, &rankbind(1, 5))
+        .await
+        .unwrap();
+
+    let rankbinds = repo.rankbinds(guild_id).await.unwrap();
+    let ranks = rankbinds
+        .iter()
+        .map(|r| (r.group_rank_id, r.priority))
+        .collect::<Vec<_>>();
+    assert_eq!(ranks, [(1, 5), (2, 0)]);
+
+    let deleted = repo
+        .delete_rankbinds(guild_id, &[(GroupId(GROUP_ID), 2), (GroupId(GROUP_ID), 3)])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+
+    let custombind = Custombind {
+        custom_bind_id: 1,
+        discord_roles: Vec::new(),
+        code: "IsInGroup(1)".into(),
+        priority: 0,
+        template: Template::default(),
+    };
+    repo.insert_custombind(guild_id, &custombind).await.unwrap();
+    assert!(repo.insert_custombind(guild_id, &custombind).await.is_err());
+
+    repo.clear(guild_id).await.unwrap();
+    assert!(repo.rankbinds(guild_id).await.unwrap().is_empty());
+    assert!(repo.custombinds(guild_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn deny_lists_keep_what_they_match_on_update() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(DENY_LIST_GUILD_ID);
+    database
+        .execute("DELETE FROM guilds WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+    GuildRepo::new(&database)
+        .get_or_create(guild_id, &[])
+        .await
+        .unwrap();
+
+    let deny_list = DenyList {
+        id: 1,
+        reason: "alt".into(),
+        action_type: DenyListActionType::None,
+        data: DenyListData::User(RobloxUserId(HOST_ID)),
+    };
+    let repo = DenyListRepo::new(&database);
+    repo.insert(guild_id, &deny_list).await.unwrap();
+    repo.insert(
+        guild_id,
+        &DenyList {
+            id: 2,
+            data: DenyListData::Group(GroupId(GROUP_ID)),
+            ..deny_list.clone()
+        },
+    )
+    .await
+    .unwrap();
+    repo.update(
+        guild_id,
+        &DenyList {
+            reason: "exploiting".into(),
+            action_type: DenyListActionType::Ban,
+            data: DenyListData::Custom("IsInGroup(1)".into()),
+            ..deny_list
+        },
+    )
+    .await
+    .unwrap();
+
+    let deny_lists = repo.list(guild_id).await.unwrap();
+    assert_eq!(deny_lists.len(), 2);
+    assert_eq!(deny_lists[0].reason, "exploiting");
+    assert_eq!(deny_lists[0].action_type, DenyListActionType::Ban);
+    assert_eq!(
+        deny_lists[0].data,
+        DenyListData::User(RobloxUserId(HOST_ID))
+    );
+
+    assert_eq!(repo.delete(guild_id, &[2, 3]).await.unwrap(), 1);
+    repo.clear(guild_id).await.unwrap();
+    assert!(repo.list(guild_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn mass_updates_are_queued_once() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(MASS_UPDATE_GUILD_ID);
+    database
+        .execute(
+            "DELETE FROM mass_update_guilds WHERE guild_id = $1",
+            &[&guild_id],
+        )
+        .await
+        .unwrap();
+
+    let repo = MassUpdateRepo::new(&database);
+    assert!(repo.get(guild_id).await.unwrap().is_none());
+    assert!(repo.queue_position(guild_id).await.unwrap().is_none());
+    assert!(repo.queued_users().await.unwrap() >= 0);
+
+    repo.enqueue(guild_id, Some(RoleId::new(1))).await.unwrap();
+    assert!(repo.enqueue(guild_id, None).await.is_err());
+    let progress = repo.get(guild_id).await.unwrap().unwrap();
+    assert_eq!(progress.updates, 0);
+    assert_eq!(progress.errored, 0);
+}
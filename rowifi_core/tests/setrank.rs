@@ -0,0 +1,169 @@
+use rowifi_core::{
+    groups::setrank::{set_rank, SetRankArguments, SetRankError},
+    repos::{AuditLogFilter, AuditLogRepo},
+};
+use rowifi_database::{
+    deadpool::{Manager, Pool},
+    postgres::{Config, NoTls},
+    test_util, Database,
+};
+use rowifi_models::{
+    audit_log::{AuditLogData, AuditLogKind},
+    id::{GuildId, UserId},
+    roblox::{
+        group::GroupRole,
+        id::{GroupId, RoleId, UserId as RobloxUserId},
+        resource::RolePath,
+    },
+};
+use rowifi_roblox_mock::{Fixtures, MockRoblox};
+
+const GUILD_ID: u64 = 5100;
+const AUTHOR_ID: u64 = 5101;
+const GROUP_ID: u64 = 5110;
+const MEMBER_ID: u64 = 5120;
+const OUTSIDER_ID: u64 = 5121;
+
+const MEMBER_ROLE: u64 = 1;
+const OFFICER_ROLE: u64 = 2;
+
+/// The failing lookups return before the audit log is written, so the pool never connects.
+fn lazy_database() -> Database {
+    let config = "host=localhost user=rowifi".parse::<Config>().unwrap();
+    let pool = Pool::builder(Manager::new(config, NoTls)).build().unwrap();
+    Database::from_pool(pool)
+}
+
+fn rank(id: u64, rank: u32, name: &str) -> GroupRole {
+    GroupRole {
+        path: RolePath::new(GroupId(GROUP_ID), RoleId(id)),
+        id: RoleId(id),
+        rank,
+        display_name: name.into(),
+        member_count: None,
+    }
+}
+
+fn fixtures() -> Fixtures {
+    Fixtures::new()
+        .group_ranks(
+            GROUP_ID,
+            vec![
+                rank(0, 0, "Guest"),
+                rank(MEMBER_ROLE, 1, "Member"),
+                rank(OFFICER_ROLE, 100, "Officer"),
+                rank(255, 255, "Owner"),
+            ],
+        )
+        .group_member(GROUP_ID, MEMBER_ID, MEMBER_ROLE)
+}
+
+fn args(user_id: u64, rank_id: u32) -> SetRankArguments {
+    SetRankArguments {
+        group_id: GroupId(GROUP_ID),
+        user_id: RobloxUserId(user_id),
+        rank_id,
+    }
+}
+
+#[tokio::test]
+async fn rank_is_changed_and_logged() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let guild_id = GuildId::new(GUILD_ID);
+    database
+        .execute("DELETE FROM audit_logs WHERE guild_id = $1", &[&guild_id])
+        .await
+        .unwrap();
+    let mock = MockRoblox::start(fixtures()).await;
+
+    let res = set_rank(
+        &mock.client(),
+        &database,
+        guild_id,
+        UserId::new(AUTHOR_ID),
+        args(MEMBER_ID, 100),
+    )
+    .await
+    .unwrap();
+    assert_eq!(res.previous_role, RoleId(MEMBER_ROLE));
+    assert_eq!(res.rank.id, RoleId(OFFICER_ROLE));
+    assert_eq!(
+        mock.group_member_role(GROUP_ID, MEMBER_ID),
+        Some(RoleId(OFFICER_ROLE))
+    );
+
+    let logs = AuditLogRepo::new(&database)
+        .list(guild_id, AuditLogFilter::default(), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].kind, AuditLogKind::SetRank);
+    assert_eq!(logs[0].user_id, Some(UserId::new(AUTHOR_ID)));
+    let AuditLogData::SetRank(ref data) = logs[0].metadata else {
+        panic!("unexpected metadata {:?}", logs[0].metadata);
+    };
+    assert_eq!(data.target_roblox_user, RobloxUserId(MEMBER_ID));
+    assert_eq!(data.group_rank_id, 100);
+}
+
+#[tokio::test]
+async fn guest_owner_and_unknown_ranks_are_rejected() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+    let database = lazy_database();
+
+    for rank_id in [0, 255, 50] {
+        let res = set_rank(
+            &roblox,
+            &database,
+            GuildId::new(GUILD_ID),
+            UserId::new(AUTHOR_ID),
+            args(MEMBER_ID, rank_id),
+        )
+        .await;
+        assert!(matches!(res, Err(SetRankError::InvalidRank)), "{rank_id}");
+    }
+    assert_eq!(
+        mock.group_member_role(GROUP_ID, MEMBER_ID),
+        Some(RoleId(MEMBER_ROLE))
+    );
+}
+
+#[tokio::test]
+async fn users_outside_the_group_are_rejected() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let database = lazy_database();
+
+    let res = set_rank(
+        &mock.client(),
+        &database,
+        GuildId::new(GUILD_ID),
+        UserId::new(AUTHOR_ID),
+        args(OUTSIDER_ID, 100),
+    )
+    .await;
+    assert!(matches!(res, Err(SetRankError::NotInGroup)));
+    assert_eq!(mock.group_member_role(GROUP_ID, OUTSIDER_ID), None);
+}
+
+#[tokio::test]
+async fn unknown_groups_are_rejected() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let database = lazy_database();
+
+    let res = set_rank(
+        &mock.client(),
+        &database,
+        GuildId::new(GUILD_ID),
+        UserId::new(AUTHOR_ID),
+        SetRankArguments {
+            group_id: GroupId(GROUP_ID + 1),
+            user_id: RobloxUserId(MEMBER_ID),
+            rank_id: 100,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(SetRankError::InvalidGroup)));
+}
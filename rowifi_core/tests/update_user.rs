@@ -0,0 +1,254 @@
+use rowifi_cache::roblox::{CachedRobloxClient, RobloxCacheConfig};
+use rowifi_core::user::update::{UpdateUser, UpdateUserError};
+use rowifi_models::{
+    bind::{AssetType, Assetbind, Rankbind, Template},
+    deny_list::{DenyList, DenyListActionType, DenyListData},
+    discord::cache::{CachedGuild, CachedMember, CachedUser},
+    guild::{GuildBinds, PartialRoGuild},
+    id::{GuildId, RoleId, UserId},
+    roblox::{
+        group::{GroupUserRole, PartialGroup, PartialRank},
+        id::{AssetId, GroupId, RoleId as RobloxRoleId, UserId as RobloxUserId},
+    },
+    user::{RoUser, UserFlags},
+};
+use rowifi_roblox_mock::{DiscordRequest, Fixtures, InventoryFixture, MockRoblox};
+use std::collections::HashMap;
+use twilight_http::Client as DiscordClient;
+
+const GUILD_ID: u64 = 1000;
+const DISCORD_ID: u64 = 2000;
+const ROBLOX_ID: u64 = 3000;
+const GROUP_ID: u64 = 4000;
+
+const RANK_ROLE: u64 = 1;
+const BADGE_ROLE: u64 = 2;
+const VERIFIED_ROLE: u64 = 3;
+const UNVERIFIED_ROLE: u64 = 4;
+
+fn fixtures() -> Fixtures {
+    Fixtures::new()
+        .user(ROBLOX_ID, "builderman")
+        .user_roles(
+            ROBLOX_ID,
+            vec![GroupUserRole {
+                group: PartialGroup {
+                    id: GroupId(GROUP_ID),
+                    name: "RoWifi".into(),
+                    member_count: 10,
+                },
+                role: PartialRank {
+                    id: RobloxRoleId(1),
+                    name: Some("Member".into()),
+                    rank: 5,
+                    member_count: None,
+                },
+            }],
+        )
+        .inventory(
+            ROBLOX_ID,
+            vec![InventoryFixture::Badge(77), InventoryFixture::Asset(88)],
+        )
+}
+
+fn discord(mock: &MockRoblox) -> DiscordClient {
+    DiscordClient::builder()
+        .token("Bot test".into())
+        .proxy(mock.address().to_string(), true)
+        .ratelimiter(None)
+        .build()
+}
+
+fn roblox(mock: &MockRoblox) -> CachedRobloxClient {
+    CachedRobloxClient::new(mock.client(), None, RobloxCacheConfig::default())
+}
+
+fn server() -> CachedGuild {
+    CachedGuild {
+        id: GuildId::new(GUILD_ID),
+        name: "Test".into(),
+        icon: None,
+        member_count: 1,
+        owner_id: UserId::new(1),
+        roles: [RANK_ROLE, BADGE_ROLE, VERIFIED_ROLE, UNVERIFIED_ROLE]
+            .into_iter()
+            .map(RoleId::new)
+            .collect(),
+        channels: Default::default(),
+    }
+}
+
+fn guild() -> PartialRoGuild {
+    let mut guild = PartialRoGuild::new(GuildId::new(GUILD_ID));
+    guild.verified_roles = vec![RoleId::new(VERIFIED_ROLE)];
+    guild.unverified_roles = vec![RoleId::new(UNVERIFIED_ROLE)];
+    guild
+}
+
+fn binds() -> GuildBinds {
+    GuildBinds {
+        rankbinds: vec![Rankbind {
+            group_id: GroupId(GROUP_ID),
+            discord_roles: vec![RoleId::new(RANK_ROLE)],
+            group_rank_id: 5,
+            roblox_rank_id: RobloxRoleId(1),
+            priority: 1,
+            template: Template("[M] {roblox-username}".into()),
+        }],
+        assetbinds: vec![Assetbind {
+            asset_id: AssetId(77),
+            asset_type: AssetType::Badge,
+            discord_roles: vec![RoleId::new(BADGE_ROLE)],
+            priority: 0,
+            template: Template("{roblox-id}".into()),
+        }],
+        ..GuildBinds::default()
+    }
+}
+
+fn member() -> (CachedMember, CachedUser) {
+    (
+        CachedMember {
+            roles: vec![RoleId::new(UNVERIFIED_ROLE)],
+            nickname: None,
+            id: UserId::new(DISCORD_ID),
+            avatar: None,
+        },
+        CachedUser {
+            id: UserId::new(DISCORD_ID),
+            username: "discorduser".into(),
+            avatar: None,
+        },
+    )
+}
+
+fn user() -> RoUser {
+    RoUser {
+        user_id: UserId::new(DISCORD_ID),
+        default_account_id: RobloxUserId(ROBLOX_ID),
+        linked_accounts: HashMap::new(),
+        other_accounts: Vec::new(),
+        flags: UserFlags::NONE,
+    }
+}
+
+fn all_roles(guild: &PartialRoGuild, binds: &GuildBinds) -> Vec<RoleId> {
+    let mut all_roles = binds
+        .rankbinds
+        .iter()
+        .flat_map(|b| b.discord_roles.clone())
+        .collect::<Vec<_>>();
+    all_roles.extend(
+        binds
+            .assetbinds
+            .iter()
+            .flat_map(|b| b.discord_roles.clone()),
+    );
+    all_roles.extend(&guild.verified_roles);
+    all_roles.extend(&guild.unverified_roles);
+    all_roles
+}
+
+#[tokio::test]
+async fn update_applies_rank_and_asset_binds() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let (http, roblox) = (discord(&mock), roblox(&mock));
+    let (server, guild, binds, user) = (server(), guild(), binds(), user());
+    let (discord_member, discord_user) = member();
+    let all_roles = all_roles(&guild, &binds);
+
+    let Ok((mut added, removed, nickname)) = (UpdateUser {
+        http: &http,
+        roblox: &roblox,
+        discord_member: &discord_member,
+        discord_user: &discord_user,
+        user: &user,
+        server: &server,
+        guild: &guild,
+        binds: &binds,
+        all_roles: &all_roles,
+    })
+    .execute()
+    .await
+    else {
+        panic!("update failed");
+    };
+
+    added.sort_unstable();
+    assert_eq!(
+        added,
+        [RANK_ROLE, BADGE_ROLE, VERIFIED_ROLE].map(RoleId::new)
+    );
+    assert_eq!(removed, vec![RoleId::new(UNVERIFIED_ROLE)]);
+    assert_eq!(nickname, "[M] builderman");
+
+    let requests = mock.discord_requests();
+    let [DiscordRequest::UpdateMember {
+        guild_id,
+        user_id,
+        body,
+    }] = requests.as_slice()
+    else {
+        panic!("expected a single member update, got {requests:?}");
+    };
+    assert_eq!((*guild_id, *user_id), (GUILD_ID, DISCORD_ID));
+    assert_eq!(body["nick"], "[M] builderman");
+}
+
+#[tokio::test]
+async fn update_stops_on_deny_list() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let (http, roblox) = (discord(&mock), roblox(&mock));
+    let (server, guild, user) = (server(), guild(), user());
+    let (discord_member, discord_user) = member();
+    let mut binds = binds();
+    binds.deny_lists = vec![DenyList {
+        id: 1,
+        reason: "alt account".into(),
+        action_type: DenyListActionType::None,
+        data: DenyListData::Group(GroupId(GROUP_ID)),
+    }];
+    let all_roles = all_roles(&guild, &binds);
+
+    let res = UpdateUser {
+        http: &http,
+        roblox: &roblox,
+        discord_member: &discord_member,
+        discord_user: &discord_user,
+        user: &user,
+        server: &server,
+        guild: &guild,
+        binds: &binds,
+        all_roles: &all_roles,
+    }
+    .execute()
+    .await;
+
+    assert!(matches!(res, Err(UpdateUserError::DenyList((_, d))) if d.id == 1));
+    assert!(mock.discord_requests().is_empty());
+}
+
+#[tokio::test]
+async fn update_reports_missing_roblox_account() {
+    let mock = MockRoblox::start(Fixtures::new()).await;
+    let (http, roblox) = (discord(&mock), roblox(&mock));
+    let (server, guild, binds, user) = (server(), guild(), binds(), user());
+    let (discord_member, discord_user) = member();
+    let all_roles = all_roles(&guild, &binds);
+
+    let res = UpdateUser {
+        http: &http,
+        roblox: &roblox,
+        discord_member: &discord_member,
+        discord_user: &discord_user,
+        user: &user,
+        server: &server,
+        guild: &guild,
+        binds: &binds,
+        all_roles: &all_roles,
+    }
+    .execute()
+    .await;
+
+    assert!(matches!(res, Err(UpdateUserError::BannedAccount(id)) if id.0 == ROBLOX_ID));
+}
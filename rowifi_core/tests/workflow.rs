@@ -0,0 +1,763 @@
+use rowifi_core::{
+    api_keys::ApiKeyCipher,
+    custom::{
+        execute_workflow, validate_workflow, ExecutionContext, ValidationContext, WorkflowContext,
+        WorkflowNodeExecution, WorkflowNodeValidation, WorkflowNodeValidationError,
+        WorkflowValidationError,
+    },
+    repos::{GuildColumn, GuildRepo},
+};
+use rowifi_database::{
+    deadpool::{Manager, Pool},
+    postgres::{Config, NoTls},
+    test_util, Database,
+};
+use rowifi_models::{
+    custom::{
+        action::SendMessage, ActionInput, ActionInputSource, ActionInputSourceType, ActionMetadata,
+        ActionOutput, ActionType, Value, ValueType, Workflow, WorkflowNode,
+    },
+    id::{ChannelId, CommandId, GuildId},
+    roblox::id::UniverseId,
+};
+use rowifi_roblox_mock::{DiscordRequest, Fixtures, MockRoblox, PublishedMessage};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use twilight_http::Client as DiscordClient;
+
+const UNIVERSE_ID: u64 = 500;
+const API_KEY_GUILD_ID: u64 = 5200;
+const NO_API_KEY_GUILD_ID: u64 = 5201;
+const MASTER_KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+/// Workflows that don't read from the database run against this pool, which never connects.
+fn database() -> Database {
+    let config = "host=localhost user=rowifi".parse::<Config>().unwrap();
+    let pool = Pool::builder(Manager::new(config, NoTls)).build().unwrap();
+    Database::from_pool(pool)
+}
+
+fn discord(mock: &MockRoblox) -> DiscordClient {
+    DiscordClient::builder()
+        .token("Bot test".into())
+        .proxy(mock.address().to_string(), true)
+        .ratelimiter(None)
+        .build()
+}
+
+fn input(name: &str, source: ActionInputSource) -> ActionInput {
+    let kind = match source {
+        ActionInputSource::Static(_) => ActionInputSourceType::Static,
+        ActionInputSource::Action { .. } => ActionInputSourceType::Action,
+        ActionInputSource::External(_) => ActionInputSourceType::External,
+    };
+    ActionInput {
+        name: name.into(),
+        description: String::new(),
+        source,
+        kind,
+    }
+}
+
+fn from_node(action_id: usize, output_name: &str) -> ActionInputSource {
+    ActionInputSource::Action {
+        action_id,
+        output_name: output_name.into(),
+    }
+}
+
+fn output(name: &str, value: ValueType) -> ActionOutput {
+    ActionOutput {
+        name: name.into(),
+        value,
+    }
+}
+
+fn node(
+    id: usize,
+    kind: ActionType,
+    metadata: ActionMetadata,
+    inputs: Vec<ActionInput>,
+    outputs: Vec<ActionOutput>,
+    next: &[(&str, usize)],
+) -> WorkflowNode {
+    WorkflowNode {
+        id,
+        name: format!("{kind:?}"),
+        next: next.iter().map(|(k, v)| ((*k).to_string(), *v)).collect(),
+        inputs,
+        outputs,
+        kind,
+        metadata,
+    }
+}
+
+async fn run(
+    mock: &MockRoblox,
+    nodes: Vec<WorkflowNode>,
+    args: HashMap<String, Value>,
+) -> ExecutionContext {
+    run_in_guild(mock, &database(), None, GuildId::new(1), nodes, args).await
+}
+
+async fn run_in_guild(
+    mock: &MockRoblox,
+    database: &Database,
+    api_keys: Option<&ApiKeyCipher>,
+    guild_id: GuildId,
+    nodes: Vec<WorkflowNode>,
+    args: HashMap<String, Value>,
+) -> ExecutionContext {
+    let (bot, roblox) = (discord(mock), mock.client());
+    let workflow = Workflow {
+        name: "test".into(),
+        description: String::new(),
+        nodes,
+        command: CommandId::new(1),
+    };
+    let mut execution_context = ExecutionContext {
+        nodes: workflow
+            .nodes
+            .iter()
+            .map(|n| WorkflowNodeExecution {
+                id: n.id,
+                inputs: HashMap::new(),
+                outputs: HashMap::new(),
+            })
+            .collect(),
+    };
+    let workflow_context = WorkflowContext {
+        bot: &bot,
+        roblox: &roblox,
+        database,
+        guild_id,
+        api_keys,
+    };
+
+    execute_workflow(&workflow, &workflow_context, &mut execution_context, &args)
+        .await
+        .unwrap();
+    execution_context
+}
+
+#[tokio::test]
+async fn workflow_publishes_message_with_username() {
+    let mock = MockRoblox::start(Fixtures::new().user(42, "builderman")).await;
+    let nodes = vec![
+        node(
+            0,
+            ActionType::Start,
+            ActionMetadata::Start,
+            vec![input(
+                "user_id",
+                ActionInputSource::External(ValueType::Number),
+            )],
+            vec![output("user_id", ValueType::Number)],
+            &[("next", 1)],
+        ),
+        node(
+            1,
+            ActionType::GetUsernameFromId,
+            ActionMetadata::GetUsernameFromId,
+            vec![input("user_id", from_node(0, "user_id"))],
+            vec![output("username", ValueType::String)],
+            &[("success", 2)],
+        ),
+        node(
+            2,
+            ActionType::JoinString,
+            ActionMetadata::JoinString,
+            vec![
+                input(
+                    "prefix",
+                    ActionInputSource::Static(Value::String("Welcome ".into())),
+                ),
+                input("username", from_node(1, "username")),
+            ],
+            vec![output("message", ValueType::String)],
+            &[("next", 3)],
+        ),
+        node(
+            3,
+            ActionType::PublishUniverseMessage,
+            ActionMetadata::PublishUniverseMessage,
+            vec![
+                input(
+                    "universe_id",
+                    ActionInputSource::Static(Value::Number(UNIVERSE_ID as i64)),
+                ),
+                input(
+                    "topic",
+                    ActionInputSource::Static(Value::String("joins".into())),
+                ),
+                input("message", from_node(2, "message")),
+            ],
+            Vec::new(),
+            &[],
+        ),
+    ];
+    let args = HashMap::from([("user_id".to_string(), Value::Number(42))]);
+
+    run(&mock, nodes, args).await;
+
+    assert_eq!(
+        mock.published_messages(),
+        vec![PublishedMessage {
+            universe_id: UniverseId(UNIVERSE_ID),
+            topic: "joins".into(),
+            message: "Welcome builderman".into(),
+        }]
+    );
+}
+
+fn publish_nodes() -> Vec<WorkflowNode> {
+    vec![
+        node(
+            0,
+            ActionType::Start,
+            ActionMetadata::Start,
+            Vec::new(),
+            Vec::new(),
+            &[("next", 1)],
+        ),
+        node(
+            1,
+            ActionType::PublishUniverseMessage,
+            ActionMetadata::PublishUniverseMessage,
+            vec![
+                input(
+                    "universe_id",
+                    ActionInputSource::Static(Value::Number(UNIVERSE_ID as i64)),
+                ),
+                input(
+                    "topic",
+                    ActionInputSource::Static(Value::String("joins".into())),
+                ),
+                input(
+                    "message",
+                    ActionInputSource::Static(Value::String("hello".into())),
+                ),
+            ],
+            Vec::new(),
+            &[],
+        ),
+    ]
+}
+
+/// Creates the server and stores `api_key` as its registered key.
+async fn register_api_key(
+    database: &Database,
+    cipher: &ApiKeyCipher,
+    guild_id: GuildId,
+    api_key: Option<&str>,
+) {
+    let repo = GuildRepo::new(database);
+    repo.get_or_create(guild_id, &[GuildColumn::ConfigVersion])
+        .await
+        .unwrap();
+    let sealed = api_key.map(|k| cipher.encrypt(guild_id, k));
+    repo.set_open_cloud_key(guild_id, sealed.as_deref())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn workflow_uses_guild_api_key() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let cipher = ApiKeyCipher::from_base64(MASTER_KEY).unwrap();
+    let guild_id = GuildId::new(API_KEY_GUILD_ID);
+    register_api_key(&database, &cipher, guild_id, Some("guild-key")).await;
+    let mock = MockRoblox::start(Fixtures::new()).await;
+
+    run_in_guild(
+        &mock,
+        &database,
+        Some(&cipher),
+        guild_id,
+        publish_nodes(),
+        HashMap::new(),
+    )
+    .await;
+
+    assert_eq!(mock.published_messages().len(), 1);
+    assert_eq!(mock.api_keys_used(), vec!["guild-key".to_string()]);
+}
+
+#[tokio::test]
+async fn workflow_uses_bot_api_key_without_a_registered_key() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let cipher = ApiKeyCipher::from_base64(MASTER_KEY).unwrap();
+    let guild_id = GuildId::new(NO_API_KEY_GUILD_ID);
+    register_api_key(&database, &cipher, guild_id, None).await;
+    let mock = MockRoblox::start(Fixtures::new()).await;
+
+    run_in_guild(
+        &mock,
+        &database,
+        Some(&cipher),
+        guild_id,
+        publish_nodes(),
+        HashMap::new(),
+    )
+    .await;
+
+    assert_eq!(
+        mock.api_keys_used(),
+        vec!["mock-open-cloud-key".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn workflow_takes_failure_branch_for_unknown_user() {
+    let mock = MockRoblox::start(Fixtures::new()).await;
+    let nodes = vec![
+        node(
+            0,
+            ActionType::Start,
+            ActionMetadata::Start,
+            vec![input(
+                "user_id",
+                ActionInputSource::External(ValueType::Number),
+            )],
+            vec![output("user_id", ValueType::Number)],
+            &[("next", 1)],
+        ),
+        node(
+            1,
+            ActionType::GetUsernameFromId,
+            ActionMetadata::GetUsernameFromId,
+            vec![input("user_id", from_node(0, "user_id"))],
+            vec![output("username", ValueType::String)],
+            &[("failure", 2)],
+        ),
+        node(
+            2,
+            ActionType::SendMessage,
+            ActionMetadata::SendMessage(SendMessage {
+                message: "Could not find {user_id}".into(),
+                channel: ChannelId::new(900),
+            }),
+            vec![input("user_id", from_node(0, "user_id"))],
+            Vec::new(),
+            &[],
+        ),
+    ];
+    let args = HashMap::from([("user_id".to_string(), Value::Number(42))]);
+
+    run(&mock, nodes, args).await;
+
+    let requests = mock.discord_requests();
+    let [DiscordRequest::CreateMessage { channel_id, body }] = requests.as_slice() else {
+        panic!("expected a single message, got {requests:?}");
+    };
+    assert_eq!(*channel_id, 900);
+    assert_eq!(body["content"], "Could not find 42");
+}
+
+#[tokio::test]
+async fn workflow_updates_datastore_entry() {
+    let mock = MockRoblox::start(Fixtures::new().datastore_entry(
+        UNIVERSE_ID,
+        "players",
+        "42",
+        json!({ "coins": 100, "name": "builderman" }),
+    ))
+    .await;
+    let universe_id = || ActionInputSource::Static(Value::Number(UNIVERSE_ID as i64));
+    let datastore_id = || ActionInputSource::Static(Value::String("players".into()));
+    let nodes = vec![
+        node(
+            0,
+            ActionType::Start,
+            ActionMetadata::Start,
+            vec![input(
+                "entry_id",
+                ActionInputSource::External(ValueType::String),
+            )],
+            vec![output("entry_id", ValueType::String)],
+            &[("next", 1)],
+        ),
+        node(
+            1,
+            ActionType::GetDatastoreEntry,
+            ActionMetadata::GetDatastoreEntry,
+            vec![
+                input("universe_id", universe_id()),
+                input("datastore_id", datastore_id()),
+                input("entry_id", from_node(0, "entry_id")),
+            ],
+            vec![output("coins", ValueType::Number)],
+            &[("next", 2)],
+        ),
+        node(
+            2,
+            ActionType::Add,
+            ActionMetadata::Add,
+            vec![
+                input("current", from_node(1, "coins")),
+                input("reward", ActionInputSource::Static(Value::Number(50))),
+            ],
+            vec![output("total", ValueType::Number)],
+            &[("next", 3)],
+        ),
+        node(
+            3,
+            ActionType::UpdateDatastoreEntry,
+            ActionMetadata::UpdateDatastoreEntry,
+            vec![
+                input("universe_id", universe_id()),
+                input("datastore_id", datastore_id()),
+                input("entry_id", from_node(0, "entry_id")),
+                input("coins", from_node(2, "total")),
+            ],
+            vec![output("coins", ValueType::Number)],
+            &[],
+        ),
+    ];
+    let args = HashMap::from([("entry_id".to_string(), Value::String("42".into()))]);
+
+    let execution = run(&mock, nodes, args).await;
+
+    let entry = mock.datastore_entry(UNIVERSE_ID, "players", "42").unwrap();
+    assert_eq!(entry.value, json!({ "coins": 150, "name": "builderman" }));
+    let update = execution.nodes.iter().find(|n| n.id == 3).unwrap();
+    assert!(matches!(
+        update.outputs.get("coins"),
+        Some(Value::Number(150))
+    ));
+}
+
+fn universe_id() -> ActionInputSource {
+    ActionInputSource::Static(Value::Number(UNIVERSE_ID as i64))
+}
+
+fn string(value: &str) -> ActionInputSource {
+    ActionInputSource::Static(Value::String(value.into()))
+}
+
+fn item_id_start() -> WorkflowNode {
+    node(
+        0,
+        ActionType::Start,
+        ActionMetadata::Start,
+        vec![input(
+            "item_id",
+            ActionInputSource::External(ValueType::String),
+        )],
+        vec![output("item_id", ValueType::String)],
+        &[("next", 1)],
+    )
+}
+
+#[tokio::test]
+async fn workflow_updates_sorted_map_item() {
+    let mock = MockRoblox::start(Fixtures::new().sorted_map_item(
+        UNIVERSE_ID,
+        "scores",
+        "42",
+        json!({ "wins": 1, "name": "builderman" }),
+    ))
+    .await;
+    let nodes = vec![
+        item_id_start(),
+        node(
+            1,
+            ActionType::GetSortedMapItem,
+            ActionMetadata::GetSortedMapItem,
+            vec![
+                input("universe_id", universe_id()),
+                input("map_id", string("scores")),
+                input("item_id", from_node(0, "item_id")),
+            ],
+            vec![output("wins", ValueType::Number)],
+            &[("success", 2)],
+        ),
+        node(
+            2,
+            ActionType::Add,
+            ActionMetadata::Add,
+            vec![
+                input("current", from_node(1, "wins")),
+                input("reward", ActionInputSource::Static(Value::Number(1))),
+            ],
+            vec![output("total", ValueType::Number)],
+            &[("next", 3)],
+        ),
+        node(
+            3,
+            ActionType::UpdateSortedMapItem,
+            ActionMetadata::UpdateSortedMapItem,
+            vec![
+                input("universe_id", universe_id()),
+                input("map_id", string("scores")),
+                input("item_id", from_node(0, "item_id")),
+                input("ttl", ActionInputSource::Static(Value::Number(60))),
+                input("wins", from_node(2, "total")),
+            ],
+            vec![output("wins", ValueType::Number)],
+            &[],
+        ),
+    ];
+    let args = HashMap::from([("item_id".to_string(), Value::String("42".into()))]);
+
+    let execution = run(&mock, nodes, args).await;
+
+    let item = mock.sorted_map_item(UNIVERSE_ID, "scores", "42").unwrap();
+    assert_eq!(item.value, json!({ "wins": 2, "name": "builderman" }));
+    let update = execution.nodes.iter().find(|n| n.id == 3).unwrap();
+    assert!(matches!(update.outputs.get("wins"), Some(Value::Number(2))));
+}
+
+#[tokio::test]
+async fn workflow_deletes_sorted_map_item() {
+    let mock = MockRoblox::start(Fixtures::new().sorted_map_item(
+        UNIVERSE_ID,
+        "scores",
+        "42",
+        json!({ "wins": 1 }),
+    ))
+    .await;
+    let locators = || {
+        vec![
+            input("universe_id", universe_id()),
+            input("map_id", string("scores")),
+            input("item_id", from_node(0, "item_id")),
+        ]
+    };
+    let nodes = vec![
+        item_id_start(),
+        node(
+            1,
+            ActionType::DeleteSortedMapItem,
+            ActionMetadata::DeleteSortedMapItem,
+            locators(),
+            Vec::new(),
+            &[("next", 2)],
+        ),
+        node(
+            2,
+            ActionType::GetSortedMapItem,
+            ActionMetadata::GetSortedMapItem,
+            locators(),
+            vec![output("wins", ValueType::Number)],
+            &[("failure", 3)],
+        ),
+        node(
+            3,
+            ActionType::SendMessage,
+            ActionMetadata::SendMessage(SendMessage {
+                message: "{item_id} was deleted".into(),
+                channel: ChannelId::new(900),
+            }),
+            vec![input("item_id", from_node(0, "item_id"))],
+            Vec::new(),
+            &[],
+        ),
+    ];
+    let args = HashMap::from([("item_id".to_string(), Value::String("42".into()))]);
+
+    run(&mock, nodes, args).await;
+
+    assert!(mock.sorted_map_item(UNIVERSE_ID, "scores", "42").is_none());
+    let requests = mock.discord_requests();
+    let [DiscordRequest::CreateMessage { body, .. }] = requests.as_slice() else {
+        panic!("expected a single message, got {requests:?}");
+    };
+    assert_eq!(body["content"], "42 was deleted");
+}
+
+#[tokio::test]
+async fn workflow_enqueues_reads_and_discards_queue_items() {
+    let mock =
+        MockRoblox::start(Fixtures::new().queue_item(UNIVERSE_ID, "jobs", json!("first"))).await;
+    let nodes = vec![
+        node(
+            0,
+            ActionType::Start,
+            ActionMetadata::Start,
+            Vec::new(),
+            Vec::new(),
+            &[("next", 1)],
+        ),
+        node(
+            1,
+            ActionType::EnqueueQueueItem,
+            ActionMetadata::EnqueueQueueItem,
+            vec![
+                input("universe_id", universe_id()),
+                input("queue_id", string("jobs")),
+                input("data", string("urgent")),
+                input("priority", ActionInputSource::Static(Value::Number(5))),
+            ],
+            Vec::new(),
+            &[("next", 2)],
+        ),
+        node(
+            2,
+            ActionType::ReadQueueItem,
+            ActionMetadata::ReadQueueItem,
+            vec![
+                input("universe_id", universe_id()),
+                input("queue_id", string("jobs")),
+            ],
+            vec![
+                output("data", ValueType::String),
+                output("read_id", ValueType::String),
+            ],
+            &[("success", 3)],
+        ),
+        node(
+            3,
+            ActionType::DiscardQueueItems,
+            ActionMetadata::DiscardQueueItems,
+            vec![
+                input("universe_id", universe_id()),
+                input("queue_id", string("jobs")),
+                input("read_id", from_node(2, "read_id")),
+            ],
+            Vec::new(),
+            &[],
+        ),
+    ];
+
+    let execution = run(&mock, nodes, HashMap::new()).await;
+
+    let read = execution.nodes.iter().find(|n| n.id == 2).unwrap();
+    assert!(matches!(read.outputs.get("data"), Some(Value::String(s)) if s == "urgent"));
+    let data = mock
+        .queue_items(UNIVERSE_ID, "jobs")
+        .into_iter()
+        .map(|i| i.data)
+        .collect::<Vec<_>>();
+    assert_eq!(data, [json!("first")]);
+    assert_eq!(mock.pending_queue_reads(), 0);
+}
+
+#[tokio::test]
+async fn workflow_takes_failure_branch_for_empty_queue() {
+    let mock = MockRoblox::start(Fixtures::new()).await;
+    let nodes = vec![
+        node(
+            0,
+            ActionType::Start,
+            ActionMetadata::Start,
+            Vec::new(),
+            Vec::new(),
+            &[("next", 1)],
+        ),
+        node(
+            1,
+            ActionType::ReadQueueItem,
+            ActionMetadata::ReadQueueItem,
+            vec![
+                input("universe_id", universe_id()),
+                input("queue_id", string("jobs")),
+            ],
+            Vec::new(),
+            &[("failure", 2)],
+        ),
+        node(
+            2,
+            ActionType::SendMessage,
+            ActionMetadata::SendMessage(SendMessage {
+                message: "The queue is empty".into(),
+                channel: ChannelId::new(900),
+            }),
+            Vec::new(),
+            Vec::new(),
+            &[],
+        ),
+    ];
+
+    run(&mock, nodes, HashMap::new()).await;
+
+    assert_eq!(mock.discord_requests().len(), 1);
+    assert_eq!(mock.pending_queue_reads(), 0);
+}
+
+#[test]
+fn update_sorted_map_item_requires_a_value_input() {
+    let workflow = |inputs: Vec<ActionInput>| Workflow {
+        name: "test".into(),
+        description: String::new(),
+        nodes: vec![
+            node(
+                0,
+                ActionType::Start,
+                ActionMetadata::Start,
+                Vec::new(),
+                Vec::new(),
+                &[("next", 1)],
+            ),
+            node(
+                1,
+                ActionType::UpdateSortedMapItem,
+                ActionMetadata::UpdateSortedMapItem,
+                inputs,
+                Vec::new(),
+                &[],
+            ),
+        ],
+        command: CommandId::new(1),
+    };
+    let validate = |workflow: &Workflow| {
+        let mut context = ValidationContext {
+            nodes: workflow
+                .nodes
+                .iter()
+                .map(|n| WorkflowNodeValidation {
+                    id: n.id,
+                    inputs: HashSet::new(),
+                    outputs: HashSet::new(),
+                })
+                .collect(),
+        };
+        validate_workflow(workflow, &mut context)
+    };
+    let locators = || {
+        vec![
+            input("universe_id", universe_id()),
+            input("map_id", string("scores")),
+            input("item_id", string("42")),
+        ]
+    };
+
+    let res = validate(&workflow(locators()));
+    assert!(
+        matches!(
+            res,
+            Err(WorkflowValidationError::Node {
+                id: 1,
+                err: WorkflowNodeValidationError::IncorrectInputs {
+                    actual: 3,
+                    expected: 4
+                }
+            })
+        ),
+        "{res:?}"
+    );
+
+    let mut inputs = locators();
+    inputs.push(input("ttl", ActionInputSource::Static(Value::Number(60))));
+    let res = validate(&workflow(inputs.clone()));
+    assert!(
+        matches!(
+            res,
+            Err(WorkflowValidationError::Node {
+                id: 1,
+                err: WorkflowNodeValidationError::IncorrectInputs {
+                    actual: 4,
+                    expected: 5
+                }
+            })
+        ),
+        "{res:?}"
+    );
+
+    inputs.push(input("wins", ActionInputSource::Static(Value::Number(1))));
+    assert!(validate(&workflow(inputs)).is_ok());
+}
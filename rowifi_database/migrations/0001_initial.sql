@@ -0,0 +1,90 @@
+CREATE EXTENSION IF NOT EXISTS hstore;
+
+CREATE TABLE guilds (
+    guild_id BIGINT PRIMARY KEY,
+    kind INT NOT NULL DEFAULT 0,
+    bypass_roles JSONB NOT NULL DEFAULT '[]',
+    unverified_roles BIGINT[] NOT NULL DEFAULT '{}',
+    verified_roles BIGINT[] NOT NULL DEFAULT '{}',
+    rankbinds JSONB NOT NULL DEFAULT '[]',
+    groupbinds JSONB NOT NULL DEFAULT '[]',
+    assetbinds JSONB NOT NULL DEFAULT '[]',
+    custombinds JSONB NOT NULL DEFAULT '[]',
+    xp_binds JSONB NOT NULL DEFAULT '[]',
+    deny_lists JSONB NOT NULL DEFAULT '[]',
+    default_template TEXT NOT NULL DEFAULT '{roblox-username}',
+    update_on_join BOOLEAN NOT NULL DEFAULT FALSE,
+    event_types JSONB NOT NULL DEFAULT '[]',
+    auto_detection BOOLEAN NOT NULL DEFAULT FALSE,
+    sync_xp_on_setrank BOOLEAN NOT NULL DEFAULT FALSE,
+    registered_groups BIGINT[] NOT NULL DEFAULT '{}',
+    sticky_roles BIGINT[] NOT NULL DEFAULT '{}',
+    log_channel BIGINT
+);
+
+CREATE TABLE roblox_users (
+    user_id BIGINT PRIMARY KEY,
+    default_account_id BIGINT NOT NULL,
+    linked_accounts HSTORE NOT NULL DEFAULT '',
+    other_accounts BIGINT[] NOT NULL DEFAULT '{}',
+    flags BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE linked_users (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    roblox_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE audit_logs (
+    audit_log_id BIGSERIAL PRIMARY KEY,
+    kind INT NOT NULL,
+    guild_id BIGINT,
+    user_id BIGINT,
+    timestamp TIMESTAMPTZ NOT NULL,
+    metadata JSONB NOT NULL
+);
+CREATE INDEX audit_logs_guild_id_timestamp_idx ON audit_logs (guild_id, timestamp);
+
+CREATE TABLE events (
+    guild_id BIGINT NOT NULL,
+    guild_event_id BIGINT NOT NULL,
+    event_type INT NOT NULL,
+    host_id BIGINT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL,
+    attendees BIGINT[] NOT NULL DEFAULT '{}',
+    notes TEXT,
+    PRIMARY KEY (guild_id, guild_event_id)
+);
+CREATE INDEX events_guild_id_host_id_idx ON events (guild_id, host_id);
+
+CREATE TABLE backups (
+    user_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    data JSONB NOT NULL,
+    PRIMARY KEY (user_id, name)
+);
+
+CREATE TABLE mass_update_guilds (
+    guild_id BIGINT PRIMARY KEY,
+    role_id BIGINT,
+    updates INT NOT NULL DEFAULT 0,
+    errored INT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE mass_update_users (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, user_id)
+);
+CREATE INDEX mass_update_users_timestamp_idx ON mass_update_users (timestamp);
+
+CREATE TABLE group_analytics (
+    group_id BIGINT NOT NULL,
+    roles JSONB NOT NULL DEFAULT '[]',
+    member_count BIGINT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (group_id, timestamp)
+);
@@ -0,0 +1,3 @@
+-- Bumped by every write to the configuration of a guild. Writes that are based on a previous
+-- read of the configuration only apply if the version is still the one that was read.
+ALTER TABLE guilds ADD COLUMN config_version BIGINT NOT NULL DEFAULT 0;
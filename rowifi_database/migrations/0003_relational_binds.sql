@@ -0,0 +1,147 @@
+-- Binds and deny lists get a row each instead of living in JSONB arrays on the guild. Rows are
+-- loaded in the order of their IDs, which follows the order of the arrays they are copied from.
+-- XP binds stay on the guild since they are managed outside of this repository. The columns the
+-- rows are copied from are left in place, including the duplicates reported below, and are only
+-- dropped by a migration of a later release once the copies have been checked against them.
+
+CREATE TABLE rankbinds (
+    rankbind_id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    group_id BIGINT NOT NULL,
+    group_rank_id INT NOT NULL,
+    roblox_rank_id BIGINT NOT NULL,
+    discord_roles BIGINT[] NOT NULL DEFAULT '{}',
+    priority INT NOT NULL DEFAULT 0,
+    template TEXT NOT NULL,
+    UNIQUE (guild_id, group_id, group_rank_id)
+);
+CREATE INDEX rankbinds_group_id_idx ON rankbinds (group_id);
+
+CREATE TABLE groupbinds (
+    groupbind_id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    group_id BIGINT NOT NULL,
+    discord_roles BIGINT[] NOT NULL DEFAULT '{}',
+    priority INT NOT NULL DEFAULT 0,
+    template TEXT NOT NULL,
+    UNIQUE (guild_id, group_id)
+);
+CREATE INDEX groupbinds_group_id_idx ON groupbinds (group_id);
+
+CREATE TABLE assetbinds (
+    assetbind_id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    asset_id BIGINT NOT NULL,
+    asset_type INT NOT NULL CHECK (asset_type BETWEEN 0 AND 2),
+    discord_roles BIGINT[] NOT NULL DEFAULT '{}',
+    priority INT NOT NULL DEFAULT 0,
+    template TEXT NOT NULL,
+    UNIQUE (guild_id, asset_id)
+);
+
+CREATE TABLE custombinds (
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    custom_bind_id INT NOT NULL,
+    code TEXT NOT NULL,
+    discord_roles BIGINT[] NOT NULL DEFAULT '{}',
+    priority INT NOT NULL DEFAULT 0,
+    template TEXT NOT NULL,
+    PRIMARY KEY (guild_id, custom_bind_id)
+);
+
+CREATE TABLE deny_lists (
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    deny_list_id INT NOT NULL,
+    kind INT NOT NULL,
+    action_type INT NOT NULL DEFAULT 0 CHECK (action_type BETWEEN 0 AND 2),
+    reason TEXT NOT NULL,
+    user_id BIGINT,
+    group_id BIGINT,
+    code TEXT,
+    PRIMARY KEY (guild_id, deny_list_id),
+    CHECK (
+        (kind = 0 AND user_id IS NOT NULL AND group_id IS NULL AND code IS NULL)
+        OR (kind = 1 AND group_id IS NOT NULL AND user_id IS NULL AND code IS NULL)
+        OR (kind = 2 AND code IS NOT NULL AND user_id IS NULL AND group_id IS NULL)
+    )
+);
+CREATE INDEX deny_lists_group_id_idx ON deny_lists (group_id) WHERE group_id IS NOT NULL;
+
+-- Duplicates that older versions let into the arrays are dropped. The first one is kept since it
+-- is the one that was being matched against.
+INSERT INTO rankbinds (guild_id, group_id, group_rank_id, roblox_rank_id, discord_roles, priority, template)
+SELECT
+    g.guild_id,
+    (b.value->>'group_id')::BIGINT,
+    (b.value->>'group_rank_id')::INT,
+    (b.value->>'roblox_rank_id')::BIGINT,
+    ARRAY(SELECT jsonb_array_elements_text(COALESCE(b.value->'discord_roles', '[]'))::BIGINT),
+    COALESCE((b.value->>'priority')::INT, 0),
+    COALESCE(b.value->>'template', '{roblox-username}')
+FROM guilds g, jsonb_array_elements(g.rankbinds) WITH ORDINALITY AS b(value, position)
+ORDER BY g.guild_id, b.position
+ON CONFLICT DO NOTHING;
+
+INSERT INTO groupbinds (guild_id, group_id, discord_roles, priority, template)
+SELECT
+    g.guild_id,
+    (b.value->>'group_id')::BIGINT,
+    ARRAY(SELECT jsonb_array_elements_text(COALESCE(b.value->'discord_roles', '[]'))::BIGINT),
+    COALESCE((b.value->>'priority')::INT, 0),
+    COALESCE(b.value->>'template', '{roblox-username}')
+FROM guilds g, jsonb_array_elements(g.groupbinds) WITH ORDINALITY AS b(value, position)
+ORDER BY g.guild_id, b.position
+ON CONFLICT DO NOTHING;
+
+INSERT INTO assetbinds (guild_id, asset_id, asset_type, discord_roles, priority, template)
+SELECT
+    g.guild_id,
+    (b.value->>'asset_id')::BIGINT,
+    COALESCE((b.value->>'asset_type')::INT, 0),
+    ARRAY(SELECT jsonb_array_elements_text(COALESCE(b.value->'discord_roles', '[]'))::BIGINT),
+    COALESCE((b.value->>'priority')::INT, 0),
+    COALESCE(b.value->>'template', '{roblox-username}')
+FROM guilds g, jsonb_array_elements(g.assetbinds) WITH ORDINALITY AS b(value, position)
+ORDER BY g.guild_id, b.position
+ON CONFLICT DO NOTHING;
+
+INSERT INTO custombinds (guild_id, custom_bind_id, code, discord_roles, priority, template)
+SELECT
+    g.guild_id,
+    (b.value->>'custom_bind_id')::INT,
+    b.value->>'code',
+    ARRAY(SELECT jsonb_array_elements_text(COALESCE(b.value->'discord_roles', '[]'))::BIGINT),
+    COALESCE((b.value->>'priority')::INT, 0),
+    COALESCE(b.value->>'template', '{roblox-username}')
+FROM guilds g, jsonb_array_elements(g.custombinds) WITH ORDINALITY AS b(value, position)
+ORDER BY g.guild_id, b.position
+ON CONFLICT DO NOTHING;
+
+INSERT INTO deny_lists (guild_id, deny_list_id, kind, action_type, reason, user_id, group_id, code)
+SELECT
+    g.guild_id,
+    (d.value->>'id')::INT,
+    (d.value->>'kind')::INT,
+    COALESCE((d.value->>'action_type')::INT, 0),
+    COALESCE(d.value->>'reason', ''),
+    CASE WHEN (d.value->>'kind')::INT = 0 THEN (d.value->>'user_id')::BIGINT END,
+    CASE WHEN (d.value->>'kind')::INT = 1 THEN (d.value->>'group_id')::BIGINT END,
+    CASE WHEN (d.value->>'kind')::INT = 2 THEN d.value->>'code' END
+FROM guilds g, jsonb_array_elements(g.deny_lists) WITH ORDINALITY AS d(value, position)
+ORDER BY g.guild_id, d.position
+ON CONFLICT DO NOTHING;
+
+-- Reports the entries that were skipped as duplicates. The migration runner logs the rows.
+SELECT kind, skipped
+FROM (
+    SELECT 'rankbinds' AS kind, (SELECT COALESCE(sum(jsonb_array_length(rankbinds)), 0) FROM guilds) - (SELECT count(*) FROM rankbinds) AS skipped
+    UNION ALL
+    SELECT 'groupbinds', (SELECT COALESCE(sum(jsonb_array_length(groupbinds)), 0) FROM guilds) - (SELECT count(*) FROM groupbinds)
+    UNION ALL
+    SELECT 'assetbinds', (SELECT COALESCE(sum(jsonb_array_length(assetbinds)), 0) FROM guilds) - (SELECT count(*) FROM assetbinds)
+    UNION ALL
+    SELECT 'custombinds', (SELECT COALESCE(sum(jsonb_array_length(custombinds)), 0) FROM guilds) - (SELECT count(*) FROM custombinds)
+    UNION ALL
+    SELECT 'deny_lists', (SELECT COALESCE(sum(jsonb_array_length(deny_lists)), 0) FROM guilds) - (SELECT count(*) FROM deny_lists)
+) AS copied
+WHERE skipped > 0;
@@ -0,0 +1,5 @@
+-- The universe that ban-type deny lists and `/gameban` apply to, and the sealed Open Cloud API key
+-- the server registered. Databases that were baselined may already have them.
+ALTER TABLE guilds
+    ADD COLUMN IF NOT EXISTS linked_universe BIGINT,
+    ADD COLUMN IF NOT EXISTS open_cloud_key BYTEA;
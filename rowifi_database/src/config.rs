@@ -0,0 +1,115 @@
+use rustls::{
+    crypto::ring,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    ClientConfig, RootCertStore,
+};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio_postgres_rustls::MakeRustlsConnect;
+
+use crate::DatabaseError;
+
+/// Settings of the connection pool.
+#[derive(Clone, Debug)]
+pub struct DatabaseConfig {
+    /// The maximum number of connections kept open.
+    pub max_size: usize,
+    /// How long to wait for a new connection to be established.
+    pub create_timeout: Option<Duration>,
+    /// How long to wait for a connection to become available when all of them are in use.
+    pub wait_timeout: Option<Duration>,
+    /// How long to wait for an idle connection to be checked before it is handed out again.
+    pub recycle_timeout: Option<Duration>,
+    /// Connect over TLS. Whether plaintext connections are still allowed is decided by the
+    /// `sslmode` of the connection string, which should be `require` for servers that need TLS.
+    pub tls: Option<TlsConfig>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TlsConfig {
+    /// PEM file with the certificates to trust instead of the bundled web PKI roots.
+    pub ca_cert: Option<PathBuf>,
+    /// Certificate presented to servers that authenticate clients with certificates.
+    pub client_cert: Option<ClientCert>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientCert {
+    /// PEM file with the certificate chain, leaf first.
+    pub cert: PathBuf,
+    /// PEM file with the private key of the certificate.
+    pub key: PathBuf,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 16,
+            create_timeout: Some(Duration::from_secs(30)),
+            wait_timeout: Some(Duration::from_secs(30)),
+            recycle_timeout: Some(Duration::from_secs(30)),
+            tls: None,
+        }
+    }
+}
+
+impl TlsConfig {
+    pub(crate) fn connector(&self) -> Result<MakeRustlsConnect, DatabaseError> {
+        let mut roots = RootCertStore::empty();
+        if let Some(ca_cert) = &self.ca_cert {
+            for cert in CertificateDer::pem_file_iter(ca_cert).map_err(boxed)? {
+                roots.add(cert.map_err(boxed)?).map_err(boxed)?;
+            }
+        } else {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+
+        let builder = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(boxed)?
+            .with_root_certificates(roots);
+        let config = match &self.client_cert {
+            Some(client_cert) => {
+                let chain = CertificateDer::pem_file_iter(&client_cert.cert)
+                    .map_err(boxed)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(boxed)?;
+                let key = PrivateKeyDer::from_pem_file(&client_cert.key).map_err(boxed)?;
+                builder.with_client_auth_cert(chain, key).map_err(boxed)?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        Ok(MakeRustlsConnect::new(config))
+    }
+}
+
+fn boxed<E: std::error::Error + Send + Sync + 'static>(err: E) -> DatabaseError {
+    DatabaseError(Box::new(err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientCert, TlsConfig};
+
+    #[test]
+    fn web_pki_roots_are_used_without_a_ca() {
+        assert!(TlsConfig::default().connector().is_ok());
+    }
+
+    #[test]
+    fn missing_certificates_are_an_error() {
+        let tls = TlsConfig {
+            ca_cert: Some("does-not-exist.pem".into()),
+            client_cert: None,
+        };
+        assert!(tls.connector().is_err());
+
+        let tls = TlsConfig {
+            ca_cert: None,
+            client_cert: Some(ClientCert {
+                cert: "does-not-exist.pem".into(),
+                key: "does-not-exist.key".into(),
+            }),
+        };
+        assert!(tls.connector().is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     error::Error as StdError,
     fmt::{Display, Formatter},
 };
-use tokio_postgres::Error as PostgresError;
+use tokio_postgres::{error::SqlState, Error as PostgresError};
 
 #[derive(Debug)]
 pub struct DatabaseError(pub(crate) Box<dyn StdError + Send + Sync>);
@@ -13,6 +13,18 @@ impl DatabaseError {
     pub fn into_source(self) -> Box<dyn StdError + Send + Sync> {
         self.0
     }
+
+    /// Whether the error was caused by a transaction that conflicted with a concurrent one and
+    /// can be retried.
+    #[must_use]
+    pub fn is_serialization_failure(&self) -> bool {
+        let code = self
+            .0
+            .downcast_ref::<PostgresError>()
+            .and_then(PostgresError::code);
+        code == Some(&SqlState::T_R_SERIALIZATION_FAILURE)
+            || code == Some(&SqlState::T_R_DEADLOCK_DETECTED)
+    }
 }
 
 impl From<PostgresError> for DatabaseError {
@@ -0,0 +1,137 @@
+use std::{error::Error, future::Future, sync::Arc};
+use tokio_postgres::{types::ToSql, Row};
+
+use crate::{Database, DatabaseError, Transaction};
+
+/// Something queries can be run on, either a [`Database`] or a [`Transaction`]. Lets the same
+/// code run on its own or as part of a larger transaction.
+pub trait Executor: Sync {
+    /// See [`Database::query`].
+    fn query<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        T::Error: Error + Send + Sync + 'static;
+
+    /// See [`Database::query_opt`].
+    fn query_opt<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        DatabaseError: From<<T as TryFrom<Row>>::Error>;
+
+    /// See [`Database::execute`].
+    fn execute(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<u64, DatabaseError>> + Send;
+}
+
+impl Executor for Database {
+    fn query<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        T::Error: Error + Send + Sync + 'static,
+    {
+        Database::query(self, statement, params)
+    }
+
+    fn query_opt<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        DatabaseError: From<<T as TryFrom<Row>>::Error>,
+    {
+        Database::query_opt(self, statement, params)
+    }
+
+    fn execute(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<u64, DatabaseError>> + Send {
+        Database::execute(self, statement, params)
+    }
+}
+
+impl Executor for Transaction {
+    fn query<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        T::Error: Error + Send + Sync + 'static,
+    {
+        Transaction::query(self, statement, params)
+    }
+
+    fn query_opt<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        DatabaseError: From<<T as TryFrom<Row>>::Error>,
+    {
+        Transaction::query_opt(self, statement, params)
+    }
+
+    fn execute(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<u64, DatabaseError>> + Send {
+        Transaction::execute(self, statement, params)
+    }
+}
+
+impl<E: Executor + Send> Executor for Arc<E> {
+    fn query<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        T::Error: Error + Send + Sync + 'static,
+    {
+        E::query(self, statement, params)
+    }
+
+    fn query_opt<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, DatabaseError>> + Send
+    where
+        T: TryFrom<Row> + Send,
+        DatabaseError: From<<T as TryFrom<Row>>::Error>,
+    {
+        E::query_opt(self, statement, params)
+    }
+
+    fn execute(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<u64, DatabaseError>> + Send {
+        E::execute(self, statement, params)
+    }
+}
@@ -3,45 +3,108 @@
 
 use deadpool_postgres::{Manager, Object, Pool, Runtime};
 use std::error::Error;
-use std::{str::FromStr, time::Duration};
+use std::str::FromStr;
 use tokio_postgres::types::ToSql;
 use tokio_postgres::{Config as TokioPostgresConfig, NoTls, Row};
 
-pub use crate::error::DatabaseError;
+pub use crate::{
+    config::{ClientCert, DatabaseConfig, TlsConfig},
+    error::DatabaseError,
+    executor::Executor,
+    migrations::{Migration, MigrationError, MIGRATIONS},
+    transaction::{Transaction, TransactionFuture},
+};
 pub use deadpool_postgres as deadpool;
 pub use tokio_postgres as postgres;
 
+mod config;
 mod error;
+mod executor;
+mod metrics;
+mod migrations;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+mod transaction;
 
 #[derive(Clone)]
 pub struct Database {
     pool: Pool,
 }
 
+/// Connection counts of the pool, see [`Database::stats`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PoolStats {
+    pub max_size: usize,
+    /// Connections currently handed out.
+    pub in_use: usize,
+    /// Open connections that are ready to be handed out.
+    pub idle: usize,
+    /// Tasks waiting for a connection.
+    pub waiting: usize,
+}
+
 impl Database {
-    /// Create a connection pool to the database with the given connection string
+    /// Create a connection pool to the database with the given connection string and the default
+    /// [`DatabaseConfig`].
     ///
     /// # Panics
-    /// May panic if the pool is built incorrectly.
+    /// Panics if the connection string is invalid or the database cannot be reached.
     pub async fn new(connection_string: &str) -> Self {
-        let postgres_config = TokioPostgresConfig::from_str(connection_string).unwrap();
-        let manager = Manager::new(postgres_config, NoTls);
+        Self::connect(connection_string, &DatabaseConfig::default())
+            .await
+            .unwrap()
+    }
+
+    /// Create a connection pool to the database with the given connection string and settings,
+    /// and check that a connection can be made.
+    ///
+    /// # Errors
+    ///
+    /// Returns Err if the connection string is invalid, the TLS certificates could not be loaded
+    /// or the database could not be reached.
+    pub async fn connect(
+        connection_string: &str,
+        config: &DatabaseConfig,
+    ) -> Result<Self, DatabaseError> {
+        let postgres_config = TokioPostgresConfig::from_str(connection_string)?;
+        let manager = match &config.tls {
+            Some(tls) => Manager::new(postgres_config, tls.connector()?),
+            None => Manager::new(postgres_config, NoTls),
+        };
         let pool = Pool::builder(manager)
-            .max_size(16)
+            .max_size(config.max_size)
             .runtime(Runtime::Tokio1)
-            .recycle_timeout(Some(Duration::from_secs(30)))
-            .create_timeout(Some(Duration::from_secs(30)))
-            .wait_timeout(Some(Duration::from_secs(30)))
+            .recycle_timeout(config.recycle_timeout)
+            .create_timeout(config.create_timeout)
+            .wait_timeout(config.wait_timeout)
             .build()
-            .unwrap();
+            .map_err(|err| DatabaseError(Box::new(err)))?;
 
-        tracing::info!("attempting database connection");
-        let _ = pool.get().await.unwrap();
+        tracing::info!(tls = config.tls.is_some(), "attempting database connection");
+        let _ = pool.get().await?;
 
         tracing::info!("database connection successful");
+        Ok(Self { pool })
+    }
+
+    /// Wrap an existing pool. Unlike [`Database::new`], this does not check the connection.
+    #[must_use]
+    pub fn from_pool(pool: Pool) -> Self {
         Self { pool }
     }
 
+    /// A snapshot of the connections in the pool.
+    #[must_use]
+    pub fn stats(&self) -> PoolStats {
+        let status = self.pool.status();
+        PoolStats {
+            max_size: status.max_size,
+            in_use: status.size - status.available,
+            idle: status.available,
+            waiting: status.waiting,
+        }
+    }
+
     /// Get a connection from the pool
     /// # Errors
     ///
@@ -0,0 +1,74 @@
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    IntGauge, Opts, Registry,
+};
+
+use crate::Database;
+
+/// Exports [`Database::stats`] as gauges that are read from the pool on every scrape.
+struct PoolCollector {
+    database: Database,
+    max_size: IntGauge,
+    in_use: IntGauge,
+    idle: IntGauge,
+    waiting: IntGauge,
+}
+
+impl Database {
+    /// Register the connection counts of the pool in `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if metrics with the same names are already registered.
+    pub fn register_metrics(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        let collector = PoolCollector {
+            database: self.clone(),
+            max_size: IntGauge::with_opts(Opts::new(
+                "database_pool_max_size",
+                "Maximum number of connections in the database pool",
+            ))?,
+            in_use: IntGauge::with_opts(Opts::new(
+                "database_pool_connections_in_use",
+                "Database connections currently handed out",
+            ))?,
+            idle: IntGauge::with_opts(Opts::new(
+                "database_pool_connections_idle",
+                "Open database connections ready to be handed out",
+            ))?,
+            waiting: IntGauge::with_opts(Opts::new(
+                "database_pool_waiters",
+                "Tasks waiting for a database connection",
+            ))?,
+        };
+        registry.register(Box::new(collector))
+    }
+}
+
+impl PoolCollector {
+    fn gauges(&self) -> [&IntGauge; 4] {
+        [&self.max_size, &self.in_use, &self.idle, &self.waiting]
+    }
+}
+
+impl Collector for PoolCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.gauges()
+            .into_iter()
+            .flat_map(Collector::desc)
+            .collect()
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn collect(&self) -> Vec<MetricFamily> {
+        let stats = self.database.stats();
+        self.max_size.set(stats.max_size as i64);
+        self.in_use.set(stats.in_use as i64);
+        self.idle.set(stats.idle as i64);
+        self.waiting.set(stats.waiting as i64);
+        self.gauges()
+            .into_iter()
+            .flat_map(Collector::collect)
+            .collect()
+    }
+}
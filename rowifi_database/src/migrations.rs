@@ -0,0 +1,284 @@
+use sha2::{Digest, Sha256};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use tokio_postgres::SimpleQueryMessage;
+
+use crate::{Database, DatabaseError};
+
+/// A schema change that is applied to the database exactly once.
+#[derive(Clone, Copy, Debug)]
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// The migrations embedded in the binary, in the order they are applied.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../migrations/0001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "guild_config_version",
+        sql: include_str!("../migrations/0002_guild_config_version.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "relational_binds",
+        sql: include_str!("../migrations/0003_relational_binds.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "guild_roblox_settings",
+        sql: include_str!("../migrations/0004_guild_roblox_settings.sql"),
+    },
+];
+
+/// Arbitrary key of the advisory lock held while migrating so that two instances starting at the
+/// same time do not apply the same migration twice.
+const MIGRATION_LOCK: i64 = 0x726f_7769_6669;
+
+const CREATE_MIGRATIONS_TABLE: &str = r"CREATE TABLE IF NOT EXISTS schema_migrations (
+    version BIGINT PRIMARY KEY,
+    name TEXT NOT NULL,
+    checksum TEXT NOT NULL,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+)";
+
+#[derive(Debug)]
+pub enum MigrationError {
+    /// An applied migration no longer matches the file it was applied from.
+    ChecksumMismatch { version: i64, name: String },
+    /// The database has a migration applied that this binary does not know about.
+    Unknown { version: i64, name: String },
+}
+
+impl Migration {
+    /// The hex encoded SHA-256 of the migration's SQL.
+    #[must_use]
+    pub fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+impl Database {
+    /// Apply every embedded migration that has not been applied yet, each in its own transaction.
+    /// Returns the migrations that were applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a migration fails or if the migrations already applied do not match
+    /// the embedded ones. See [`MigrationError`] for details.
+    pub async fn migrate(&self) -> Result<Vec<Migration>, DatabaseError> {
+        let mut conn = self.get().await?;
+        // The table is only created under the lock since concurrent creations of it conflict.
+        conn.execute("SELECT pg_advisory_lock($1)", &[&MIGRATION_LOCK])
+            .await?;
+
+        let res = async {
+            conn.batch_execute(CREATE_MIGRATIONS_TABLE).await?;
+            let applied = conn
+                .query(
+                    "SELECT version, name, checksum FROM schema_migrations ORDER BY version",
+                    &[],
+                )
+                .await?;
+            let mut versions = Vec::with_capacity(applied.len());
+            for row in &applied {
+                let version: i64 = row.try_get("version")?;
+                let name: String = row.try_get("name")?;
+                let checksum: String = row.try_get("checksum")?;
+                versions.push(version);
+                match MIGRATIONS.iter().find(|m| m.version == version) {
+                    Some(migration) if migration.checksum() == checksum => {}
+                    Some(_) => {
+                        return Err(DatabaseError(Box::new(MigrationError::ChecksumMismatch {
+                            version,
+                            name,
+                        })))
+                    }
+                    None => {
+                        return Err(DatabaseError(Box::new(MigrationError::Unknown {
+                            version,
+                            name,
+                        })))
+                    }
+                }
+            }
+
+            let mut pending = Vec::new();
+            for migration in MIGRATIONS.iter().filter(|m| !versions.contains(&m.version)) {
+                tracing::info!(
+                    version = migration.version,
+                    name = migration.name,
+                    "applying migration"
+                );
+                let transaction = conn.transaction().await?;
+                // Rows returned by a migration report what it did, such as the data it skipped.
+                for message in transaction.simple_query(migration.sql).await? {
+                    if let SimpleQueryMessage::Row(row) = message {
+                        let report = (0..row.len())
+                            .map(|i| {
+                                format!(
+                                    "{}={}",
+                                    row.columns()[i].name(),
+                                    row.get(i).unwrap_or("NULL")
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        tracing::warn!(
+                            version = migration.version,
+                            name = migration.name,
+                            report,
+                            "migration report"
+                        );
+                    }
+                }
+                transaction
+                    .execute(
+                        "INSERT INTO schema_migrations(version, name, checksum) VALUES($1, $2, $3)",
+                        &[&migration.version, &migration.name, &migration.checksum()],
+                    )
+                    .await?;
+                transaction.commit().await?;
+                pending.push(*migration);
+            }
+            Ok::<_, DatabaseError>(pending)
+        }
+        .await;
+
+        conn.execute("SELECT pg_advisory_unlock($1)", &[&MIGRATION_LOCK])
+            .await?;
+        res
+    }
+
+    /// Records the initial migration as applied without running it, for databases whose tables
+    /// were created before migrations were introduced. Nothing is recorded unless the `guilds`
+    /// table exists and no migration has been recorded yet. Returns whether it was recorded.
+    ///
+    /// The existing tables are assumed to match the initial migration, the later migrations are
+    /// left to [`Database::migrate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns Err if there is a pool error or the version table could not be written.
+    pub async fn baseline(&self) -> Result<bool, DatabaseError> {
+        let conn = self.get().await?;
+        conn.execute("SELECT pg_advisory_lock($1)", &[&MIGRATION_LOCK])
+            .await?;
+
+        let res = async {
+            conn.batch_execute(CREATE_MIGRATIONS_TABLE).await?;
+            let row = conn
+                .query_one(
+                    "SELECT EXISTS (SELECT 1 FROM schema_migrations) AS recorded, to_regclass(format('%I.guilds', current_schema())) IS NOT NULL AS legacy",
+                    &[],
+                )
+                .await?;
+            let recorded: bool = row.try_get("recorded")?;
+            let legacy: bool = row.try_get("legacy")?;
+            if recorded || !legacy {
+                return Ok(false);
+            }
+
+            let initial = &MIGRATIONS[0];
+            tracing::info!(
+                version = initial.version,
+                name = initial.name,
+                "recording migration as applied"
+            );
+            conn.execute(
+                "INSERT INTO schema_migrations(version, name, checksum) VALUES($1, $2, $3)",
+                &[&initial.version, &initial.name, &initial.checksum()],
+            )
+            .await?;
+            Ok::<_, DatabaseError>(true)
+        }
+        .await;
+
+        conn.execute("SELECT pg_advisory_unlock($1)", &[&MIGRATION_LOCK])
+            .await?;
+        res
+    }
+
+    /// The embedded migrations that have not been applied to the database yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns Err if there is a pool error or the version table could not be read.
+    pub async fn pending_migrations(&self) -> Result<Vec<Migration>, DatabaseError> {
+        let conn = self.get().await?;
+        let exists: bool = conn
+            .query_one(
+                "SELECT to_regclass('schema_migrations') IS NOT NULL AS exists",
+                &[],
+            )
+            .await?
+            .try_get("exists")?;
+        if !exists {
+            return Ok(MIGRATIONS.to_vec());
+        }
+        let latest: Option<i64> = conn
+            .query_one("SELECT max(version) AS latest FROM schema_migrations", &[])
+            .await?
+            .try_get("latest")?;
+        Ok(MIGRATIONS
+            .iter()
+            .filter(|m| Some(m.version) > latest)
+            .copied()
+            .collect())
+    }
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ChecksumMismatch { version, name } => write!(
+                f,
+                "migration {version} ({name}) was changed after it was applied"
+            ),
+            Self::Unknown { version, name } => write!(
+                f,
+                "migration {version} ({name}) is applied but is not known to this build"
+            ),
+        }
+    }
+}
+
+impl StdError for MigrationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Migration, MIGRATIONS};
+
+    #[test]
+    fn versions_are_sequential() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(
+                migration.version,
+                i64::try_from(i + 1).unwrap(),
+                "{}",
+                migration.name
+            );
+        }
+    }
+
+    #[test]
+    fn checksum_is_the_sha256_of_the_sql() {
+        let migration = Migration {
+            version: 1,
+            name: "test",
+            sql: "a",
+        };
+        assert_eq!(
+            migration.checksum(),
+            "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Helpers for tests that run against a Postgres server, enabled by the `test-util` feature.
+
+use crate::Database;
+
+/// The connection string in `DATABASE_TEST_CONN`. Tests that need a Postgres server are skipped
+/// when it is not set, except on CI where a skipped test would hide a failure.
+///
+/// # Panics
+///
+/// Panics if it is not set while `CI` is.
+#[must_use]
+pub fn connection_string() -> Option<String> {
+    let Ok(conn) = std::env::var("DATABASE_TEST_CONN") else {
+        assert!(
+            std::env::var_os("CI").is_none(),
+            "DATABASE_TEST_CONN must be set on CI"
+        );
+        return None;
+    };
+    Some(conn)
+}
+
+/// Connects to the database in [`connection_string`] and applies the migrations.
+///
+/// # Panics
+///
+/// Panics if the database cannot be reached or migrated.
+pub async fn database() -> Option<Database> {
+    let database = Database::new(&connection_string()?).await;
+    database.migrate().await.unwrap();
+    Some(database)
+}
@@ -0,0 +1,191 @@
+use deadpool_postgres::Object;
+use std::{error::Error, future::Future, pin::Pin};
+use tokio_postgres::{types::ToSql, IsolationLevel, Row};
+
+use crate::{Database, DatabaseError};
+
+/// How many times [`Database::transaction`] runs a transaction that keeps failing to serialize.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// The future returned by the closure passed to [`Database::transaction`].
+pub type TransactionFuture<'t, T> =
+    Pin<Box<dyn Future<Output = Result<T, DatabaseError>> + Send + 't>>;
+
+/// A transaction on a connection taken from the pool. It must be finished with
+/// [`Transaction::commit`] or [`Transaction::rollback`]. A transaction that is dropped
+/// without being finished is rolled back by closing its connection instead of returning it to
+/// the pool.
+pub struct Transaction {
+    conn: Option<Object>,
+}
+
+impl Database {
+    /// Begin a transaction with the default isolation level of the database.
+    /// # Errors
+    ///
+    /// Return Err if there is a pool error or the transaction could not be started.
+    pub async fn begin(&self) -> Result<Transaction, DatabaseError> {
+        let conn = self.get().await?;
+        conn.batch_execute("BEGIN").await?;
+        Ok(Transaction { conn: Some(conn) })
+    }
+
+    /// Begin a transaction with the given isolation level.
+    /// # Errors
+    ///
+    /// Return Err if there is a pool error or the transaction could not be started.
+    pub async fn begin_with(
+        &self,
+        isolation_level: IsolationLevel,
+    ) -> Result<Transaction, DatabaseError> {
+        let statement = match isolation_level {
+            IsolationLevel::ReadUncommitted => "BEGIN ISOLATION LEVEL READ UNCOMMITTED",
+            IsolationLevel::RepeatableRead => "BEGIN ISOLATION LEVEL REPEATABLE READ",
+            IsolationLevel::Serializable => "BEGIN ISOLATION LEVEL SERIALIZABLE",
+            _ => "BEGIN ISOLATION LEVEL READ COMMITTED",
+        };
+        let conn = self.get().await?;
+        conn.batch_execute(statement).await?;
+        Ok(Transaction { conn: Some(conn) })
+    }
+
+    /// Run the closure in a serializable transaction and commit it. The transaction is rolled
+    /// back if the closure returns an error. Transactions that fail because of a concurrent one
+    /// are retried, so the closure may be called more than once.
+    /// # Errors
+    ///
+    /// Return Err if the closure returns an error, the transaction could not be committed or it
+    /// could still not be serialized after the last attempt.
+    pub async fn transaction<T, F>(&self, mut f: F) -> Result<T, DatabaseError>
+    where
+        F: for<'t> FnMut(&'t Transaction) -> TransactionFuture<'t, T>,
+    {
+        let mut attempt = 1;
+        loop {
+            let transaction = self.begin_with(IsolationLevel::Serializable).await?;
+            let res = match f(&transaction).await {
+                Ok(value) => transaction.commit().await.map(|()| value),
+                Err(err) => {
+                    // A failed rollback closes the connection, which rolls back the transaction
+                    // as well, so the error of the closure is the one worth returning.
+                    let _ = transaction.rollback().await;
+                    Err(err)
+                }
+            };
+            match res {
+                Err(err) if err.is_serialization_failure() && attempt < MAX_ATTEMPTS => {
+                    tracing::debug!(
+                        attempt,
+                        "retrying transaction after a serialization failure"
+                    );
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+impl Transaction {
+    fn conn(&self) -> &Object {
+        self.conn
+            .as_ref()
+            .expect("the connection is only taken when the transaction is finished")
+    }
+
+    /// Get a list of items from a query inside the transaction.
+    /// # Errors
+    ///
+    /// Return Err if the query failed to acquire data from the database or the returned data
+    /// could not be deserialized.
+    pub async fn query<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<T>, DatabaseError>
+    where
+        T: TryFrom<Row>,
+        T::Error: Error + Send + Sync + 'static,
+    {
+        let conn = self.conn();
+        let statement = conn.prepare_cached(statement).await?;
+        let rows = conn.query(&statement, params).await?;
+        let items = rows
+            .into_iter()
+            .map(|r| T::try_from(r))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| DatabaseError(Box::new(err)))?;
+        Ok(items)
+    }
+
+    /// Get an item from a query inside the transaction. Returns [None] if the item does not exist.
+    /// # Errors
+    ///
+    /// Return Err if the query failed to acquire data from the database or the returned data
+    /// could not be deserialized.
+    pub async fn query_opt<T>(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<T>, DatabaseError>
+    where
+        T: TryFrom<Row>,
+        DatabaseError: From<<T as TryFrom<Row>>::Error>,
+    {
+        let conn = self.conn();
+        let statement = conn.prepare_cached(statement).await?;
+        let row = conn.query_opt(&statement, params).await?;
+        match row {
+            Some(r) => Ok(Some(T::try_from(r)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Execute the given query inside the transaction. Returns the number of rows modified.
+    /// # Errors
+    ///
+    /// Return Err if the query failed.
+    pub async fn execute(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.conn();
+        let statement = conn.prepare_cached(statement).await?;
+        let row = conn.execute(&statement, params).await?;
+        Ok(row)
+    }
+
+    /// Commit the transaction.
+    /// # Errors
+    ///
+    /// Return Err if the transaction could not be committed, in which case it is rolled back.
+    pub async fn commit(mut self) -> Result<(), DatabaseError> {
+        self.finish("COMMIT").await
+    }
+
+    /// Roll back the transaction.
+    /// # Errors
+    ///
+    /// Return Err if the rollback failed.
+    pub async fn rollback(mut self) -> Result<(), DatabaseError> {
+        self.finish("ROLLBACK").await
+    }
+
+    async fn finish(&mut self, statement: &str) -> Result<(), DatabaseError> {
+        // If the statement fails, the connection is left in the transaction and dropped with it.
+        self.conn().batch_execute(statement).await?;
+        // The connection is back to idle so it can be returned to the pool.
+        drop(self.conn.take());
+        Ok(())
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            // Close the connection so it cannot be reused while still in the transaction.
+            drop(Object::take(conn));
+        }
+    }
+}
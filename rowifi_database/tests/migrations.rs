@@ -0,0 +1,144 @@
+use rowifi_database::{test_util, Database, MIGRATIONS};
+
+#[tokio::test]
+async fn migrate_brings_the_database_up_to_date() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+
+    assert!(database.pending_migrations().await.unwrap().is_empty());
+    // Migrations that were applied are not applied again.
+    assert!(database.migrate().await.unwrap().is_empty());
+
+    let conn = database.get().await.unwrap();
+    let row = conn
+        .query_one("SELECT max(version) AS version FROM schema_migrations", &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        row.get::<_, i64>("version"),
+        MIGRATIONS.last().unwrap().version
+    );
+    for table in [
+        "guilds",
+        "roblox_users",
+        "linked_users",
+        "audit_logs",
+        "events",
+        "backups",
+        "mass_update_guilds",
+        "mass_update_users",
+        "group_analytics",
+        "rankbinds",
+        "groupbinds",
+        "assetbinds",
+        "custombinds",
+        "deny_lists",
+    ] {
+        let row = conn
+            .query_one("SELECT to_regclass($1) IS NOT NULL AS exists", &[&table])
+            .await
+            .unwrap();
+        assert!(row.get::<_, bool>("exists"), "{table} was not created");
+    }
+}
+
+#[tokio::test]
+async fn baseline_records_the_initial_migration_of_existing_databases() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    // Tables created by the baseline test live in their own schema.
+    database
+        .get()
+        .await
+        .unwrap()
+        .batch_execute(
+            "DROP SCHEMA IF EXISTS migrations_baseline CASCADE; CREATE SCHEMA migrations_baseline",
+        )
+        .await
+        .unwrap();
+    let conn = test_util::connection_string().unwrap();
+    let legacy = Database::new(&format!(
+        "{conn} options='-c search_path=migrations_baseline,public'"
+    ))
+    .await;
+
+    // There is nothing to baseline in an empty database.
+    assert!(!legacy.baseline().await.unwrap());
+
+    legacy
+        .get()
+        .await
+        .unwrap()
+        .batch_execute(include_str!("legacy_schema.sql"))
+        .await
+        .unwrap();
+    assert!(legacy.baseline().await.unwrap());
+    assert!(!legacy.baseline().await.unwrap());
+    // Binds that older versions duplicated in the arrays are only copied once.
+    legacy
+        .get()
+        .await
+        .unwrap()
+        .batch_execute(
+            r#"INSERT INTO guilds(guild_id, rankbinds) VALUES(1, '[
+                {"group_id": 1, "group_rank_id": 1, "roblox_rank_id": 11, "template": "first"},
+                {"group_id": 1, "group_rank_id": 1, "roblox_rank_id": 11, "template": "second"}
+            ]')"#,
+        )
+        .await
+        .unwrap();
+
+    let applied = legacy.migrate().await.unwrap();
+    assert_eq!(
+        applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+        MIGRATIONS[1..]
+            .iter()
+            .map(|m| m.version)
+            .collect::<Vec<_>>()
+    );
+    assert!(legacy.pending_migrations().await.unwrap().is_empty());
+    let templates = legacy
+        .get()
+        .await
+        .unwrap()
+        .query("SELECT template FROM rankbinds WHERE guild_id = 1", &[])
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get::<_, String>("template"))
+        .collect::<Vec<_>>();
+    assert_eq!(templates, ["first"]);
+    // Columns added after the initial schema are created on baselined databases too.
+    legacy
+        .get()
+        .await
+        .unwrap()
+        .execute(
+            "UPDATE guilds SET linked_universe = 1, open_cloud_key = '\\x00' WHERE guild_id = 1",
+            &[],
+        )
+        .await
+        .unwrap();
+    // The skipped duplicate is still in the column it was copied from.
+    let kept = legacy
+        .get()
+        .await
+        .unwrap()
+        .query_one(
+            "SELECT jsonb_array_length(rankbinds) AS kept FROM guilds WHERE guild_id = 1",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(kept.get::<_, i32>("kept"), 2);
+
+    database
+        .get()
+        .await
+        .unwrap()
+        .batch_execute("DROP SCHEMA migrations_baseline CASCADE")
+        .await
+        .unwrap();
+}
@@ -0,0 +1,32 @@
+use rowifi_database::{
+    deadpool::{Manager, Pool, Runtime},
+    postgres::NoTls,
+    test_util, Database,
+};
+
+#[tokio::test]
+async fn stats_count_connections_in_use() {
+    let Some(conn) = test_util::connection_string() else {
+        return;
+    };
+    let manager = Manager::new(conn.parse().unwrap(), NoTls);
+    let pool = Pool::builder(manager)
+        .max_size(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let database = Database::from_pool(pool);
+
+    let first = database.get().await.unwrap();
+    let second = database.get().await.unwrap();
+    let stats = database.stats();
+    assert_eq!(stats.max_size, 2);
+    assert_eq!(stats.in_use, 2);
+    assert_eq!(stats.idle, 0);
+
+    drop(second);
+    let stats = database.stats();
+    assert_eq!(stats.in_use, 1);
+    assert_eq!(stats.idle, 1);
+    drop(first);
+}
@@ -0,0 +1,174 @@
+use rowifi_database::{
+    postgres::{IsolationLevel, Row},
+    test_util, Database,
+};
+
+struct Backup {
+    name: String,
+}
+
+impl TryFrom<Row> for Backup {
+    type Error = rowifi_database::postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let name = row.try_get("name")?;
+        Ok(Self { name })
+    }
+}
+
+async fn backups(database: &Database, user_id: i64) -> Vec<String> {
+    database
+        .query::<Backup>(
+            "SELECT name FROM backups WHERE user_id = $1 ORDER BY name",
+            &[&user_id],
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect()
+}
+
+#[tokio::test]
+async fn commit_and_rollback() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let user_id = 1;
+    database
+        .execute("DELETE FROM backups WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+
+    let transaction = database.begin().await.unwrap();
+    transaction
+        .execute(
+            "INSERT INTO backups(user_id, name, data) VALUES($1, 'committed', '{}')",
+            &[&user_id],
+        )
+        .await
+        .unwrap();
+    // Not visible outside of the transaction until it is committed.
+    assert!(backups(&database, user_id).await.is_empty());
+    transaction.commit().await.unwrap();
+
+    let transaction = database.begin().await.unwrap();
+    transaction
+        .execute(
+            "INSERT INTO backups(user_id, name, data) VALUES($1, 'rolled back', '{}')",
+            &[&user_id],
+        )
+        .await
+        .unwrap();
+    transaction.rollback().await.unwrap();
+
+    let transaction = database.begin().await.unwrap();
+    transaction
+        .execute(
+            "INSERT INTO backups(user_id, name, data) VALUES($1, 'dropped', '{}')",
+            &[&user_id],
+        )
+        .await
+        .unwrap();
+    drop(transaction);
+
+    assert_eq!(backups(&database, user_id).await, ["committed"]);
+}
+
+#[tokio::test]
+async fn transaction_rolls_back_when_the_closure_fails() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let user_id = 2;
+    database
+        .execute("DELETE FROM backups WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+    database
+        .execute(
+            "INSERT INTO backups(user_id, name, data) VALUES($1, 'existing', '{}')",
+            &[&user_id],
+        )
+        .await
+        .unwrap();
+
+    let res = database
+        .transaction(|transaction| {
+            Box::pin(async move {
+                transaction
+                    .execute(
+                        "INSERT INTO backups(user_id, name, data) VALUES($1, 'new', '{}')",
+                        &[&user_id],
+                    )
+                    .await?;
+                // Violates the primary key.
+                transaction
+                    .execute(
+                        "INSERT INTO backups(user_id, name, data) VALUES($1, 'existing', '{}')",
+                        &[&user_id],
+                    )
+                    .await?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(res.is_err());
+    assert_eq!(backups(&database, user_id).await, ["existing"]);
+}
+
+#[tokio::test]
+async fn transaction_retries_serialization_failures() {
+    let Some(database) = test_util::database().await else {
+        return;
+    };
+    let user_id = 3;
+    database
+        .execute("DELETE FROM backups WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+
+    let mut attempts = 0;
+    let count = database
+        .transaction(|transaction| {
+            attempts += 1;
+            let first = attempts == 1;
+            let database = database.clone();
+            Box::pin(async move {
+                let rows = transaction
+                    .query::<Backup>("SELECT name FROM backups WHERE user_id = $1", &[&user_id])
+                    .await?;
+                if first {
+                    // Another transaction that reads and writes the same rows and commits
+                    // first makes this one impossible to serialize.
+                    let other = database.begin_with(IsolationLevel::Serializable).await?;
+                    other
+                        .query::<Backup>("SELECT name FROM backups WHERE user_id = $1", &[&user_id])
+                        .await?;
+                    other
+                        .execute(
+                            "INSERT INTO backups(user_id, name, data) VALUES($1, 'concurrent', '{}')",
+                            &[&user_id],
+                        )
+                        .await?;
+                    other.commit().await?;
+                }
+                transaction
+                    .execute(
+                        "INSERT INTO backups(user_id, name, data) VALUES($1, $2, '{}')",
+                        &[&user_id, &format!("backup-{}", rows.len())],
+                    )
+                    .await?;
+                Ok(rows.len())
+            })
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(attempts, 2);
+    assert_eq!(count, 1);
+    assert_eq!(
+        backups(&database, user_id).await,
+        ["backup-1", "concurrent"]
+    );
+}
@@ -6,7 +6,7 @@ use rowifi_models::{
         application::interaction::application_command::{CommandDataOption, CommandOptionValue},
         id::{marker::RoleMarker, Id},
     },
-    id::{RoleId, UserId},
+    id::{ChannelId, GuildId, RoleId, UserId},
     roblox::id::GroupId,
 };
 use std::{
@@ -181,6 +181,29 @@ impl Argument for RoleId {
     }
 }
 
+impl Argument for ChannelId {
+    fn from_interaction(option: &CommandDataOption) -> Result<Self, ArgumentError> {
+        match option.value {
+            CommandOptionValue::Channel(channel) => Ok(ChannelId(channel)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Server IDs don't fit in an integer option, so they are passed as strings.
+impl Argument for GuildId {
+    fn from_interaction(option: &CommandDataOption) -> Result<Self, ArgumentError> {
+        match &option.value {
+            CommandOptionValue::String(value) => value
+                .trim()
+                .parse()
+                .map(GuildId)
+                .map_err(|_| ArgumentError::BadArgument),
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl Argument for GroupId {
     fn from_interaction(option: &CommandDataOption) -> Result<Self, ArgumentError> {
         match option.value {
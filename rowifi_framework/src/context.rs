@@ -1,5 +1,9 @@
-use rowifi_cache::Cache;
-use rowifi_core::error::RoError;
+use rowifi_cache::{roblox::CachedRobloxClient, Cache};
+use rowifi_core::{
+    api_keys::ApiKeyCipher,
+    error::RoError,
+    repos::{GuildColumn, GuildRepo},
+};
 use rowifi_database::Database;
 use rowifi_models::{
     discord::{
@@ -21,7 +25,6 @@ use rowifi_models::{
     guild::PartialRoGuild,
     id::{ChannelId, GuildId, UserId},
 };
-use rowifi_roblox::RobloxClient;
 use std::{
     future::IntoFuture,
     ops::Deref,
@@ -44,7 +47,11 @@ pub struct BotContextInner {
     pub database: Arc<Database>,
     /// The cache holding all discord data
     pub cache: Cache,
-    pub roblox: RobloxClient,
+    /// The module used to make requests to Roblox. Hot lookups are served from the cache
+    pub roblox: CachedRobloxClient,
+    /// Encrypts and decrypts the Open Cloud API keys registered by servers. Servers cannot
+    /// register keys when no master key is configured
+    pub api_keys: Option<ApiKeyCipher>,
     pub error_logger: (Id<WebhookMarker>, String),
 }
 
@@ -75,7 +82,8 @@ impl BotContext {
         http: Arc<TwilightClient>,
         database: Arc<Database>,
         cache: Cache,
-        roblox: RobloxClient,
+        roblox: CachedRobloxClient,
+        api_keys: Option<ApiKeyCipher>,
         error_logger: (Id<WebhookMarker>, String),
     ) -> Self {
         Self(Arc::new(BotContextInner {
@@ -84,6 +92,7 @@ impl BotContext {
             database,
             cache,
             roblox,
+            api_keys,
             error_logger,
         }))
     }
@@ -128,29 +137,21 @@ impl BotContext {
         }
     }
 
-    /// Gets the guild from the database. If it does not exist, it creates a row
-    /// in the database and returns it.
+    /// Gets the given columns of the guild from the database. If it does not exist, it creates a
+    /// row in the database and returns it.
     ///
     /// # Errors
     ///
     /// See [`DatabaseError`](rowifi_database::DatabaseError) for details
-    pub async fn get_guild(
+    pub async fn get_guild_config(
         &self,
-        statement: &str,
+        columns: &[GuildColumn],
         guild_id: GuildId,
     ) -> Result<PartialRoGuild, RoError> {
-        let res = self
-            .database
-            .query_opt::<PartialRoGuild>(statement, &[&guild_id])
+        let guild = GuildRepo::new(&self.database)
+            .get_or_create(guild_id, columns)
             .await?;
-        if let Some(guild) = res {
-            Ok(guild)
-        } else {
-            self.database
-                .execute("INSERT INTO guilds(guild_id) VALUES($1)", &[&guild_id])
-                .await?;
-            Ok(PartialRoGuild::new(guild_id))
-        }
+        Ok(guild)
     }
 
     /// Get the server from the cache. If it is not present in the cache, get it from the Discord API.
@@ -162,7 +163,13 @@ impl BotContext {
         if let Ok(Some(guild)) = self.cache.guild(guild_id).await {
             Ok(guild)
         } else {
-            let guild = self.http.guild(guild_id.0).await?.model().await?;
+            let guild = self
+                .http
+                .guild(guild_id.0)
+                .with_counts(true)
+                .await?
+                .model()
+                .await?;
             let cached = self.cache.cache_guild(guild).await?;
             Ok(cached)
         }
@@ -10,7 +10,10 @@ pub mod context;
 pub mod prelude;
 pub mod utils;
 
-use std::sync::{atomic::AtomicBool, Arc};
+use std::{
+    error::Error,
+    sync::{atomic::AtomicBool, Arc},
+};
 
 use axum::{
     body::Body,
@@ -20,7 +23,7 @@ use axum::{
     Json,
 };
 use context::BotContext;
-use rowifi_core::error::RoError;
+use rowifi_core::error::{ErrorKind, RoError};
 use rowifi_models::{
     discord::{
         application::interaction::{
@@ -34,6 +37,8 @@ use rowifi_models::{
     id::{ChannelId, GuildId, UserId},
 };
 
+use rowifi_roblox::error::{ApiErrorKind, ApiKeySource, RobloxError};
+
 use crate::{arguments::Arguments, context::CommandContext};
 
 pub struct Command<A> {
@@ -193,11 +198,86 @@ fn recurse_skip_subcommands(data: &[CommandDataOption]) -> &[CommandDataOption]
 }
 
 pub async fn handle_error(bot: BotContext, ctx: CommandContext, err: RoError) {
-    let _ = ctx.respond(&bot)
-        .content("Something went wrong. Please try again. If the issue persists, please contact the RoWifi support server.")
-        .unwrap()
-        .await;
-    send_error_log(bot, ctx, err).await;
+    if matches!(err.kind(), ErrorKind::Conflict) {
+        let _ = ctx
+            .respond(&bot)
+            .content("The server configuration changed, please retry.")
+            .unwrap()
+            .await;
+        return;
+    }
+
+    let api_error = err
+        .source()
+        .and_then(|e| e.downcast_ref::<RobloxError>())
+        .and_then(RobloxError::api_error);
+    let Some(api_error) = api_error else {
+        let _ = ctx.respond(&bot)
+            .content("Something went wrong. Please try again. If the issue persists, please contact the RoWifi support server.")
+            .unwrap()
+            .await;
+        send_error_log(bot, ctx, err).await;
+        return;
+    };
+
+    let (content, log) = match &api_error.kind {
+        ApiErrorKind::NotFound => (
+            "Roblox could not find what you were looking for. Please check the IDs you provided."
+                .to_string(),
+            false,
+        ),
+        ApiErrorKind::RateLimited { retry_after } => {
+            let content = match retry_after {
+                Some(retry_after) => format!(
+                    "Roblox is rate limiting RoWifi. Please try again in {} seconds.",
+                    retry_after.as_secs().max(1)
+                ),
+                None => "Roblox is rate limiting RoWifi. Please try again in a while.".to_string(),
+            };
+            (content, false)
+        }
+        ApiErrorKind::Unauthorized => (
+            "Roblox rejected the credentials used by RoWifi. If the issue persists, please contact the RoWifi support server."
+                .to_string(),
+            true,
+        ),
+        ApiErrorKind::PermissionDenied { scope } => {
+            // A server's own key is for its admins to fix, so only failures of RoWifi's key are
+            // logged.
+            let (key, log) = match api_error.api_key {
+                ApiKeySource::Bot => ("RoWifi's Open Cloud API key", true),
+                ApiKeySource::Server => (
+                    "The Open Cloud API key registered with `/server api-key`",
+                    false,
+                ),
+            };
+            let content = match scope {
+                Some(scope) => format!("{key} is missing the `{scope}` permission."),
+                None => format!("{key} does not have access to this resource."),
+            };
+            (content, log)
+        }
+        ApiErrorKind::InvalidArgument { field } => {
+            let content = match field {
+                Some(field) => format!("Roblox rejected the value of `{field}`."),
+                None => "Roblox rejected one of the values provided.".to_string(),
+            };
+            (content, true)
+        }
+        ApiErrorKind::Upstream => (
+            "Roblox is having issues right now. Please try again later.".to_string(),
+            false,
+        ),
+        ApiErrorKind::Other => (
+            "Something went wrong. Please try again. If the issue persists, please contact the RoWifi support server."
+                .to_string(),
+            true,
+        ),
+    };
+    let _ = ctx.respond(&bot).content(&content).unwrap().await;
+    if log {
+        send_error_log(bot, ctx, err).await;
+    }
 }
 
 pub async fn send_error_log(bot: BotContext, ctx: CommandContext, err: RoError) {
@@ -5,7 +5,7 @@ pub use crate::Command;
 
 pub use axum::{response::IntoResponse, Extension, Json};
 pub use chrono::{DateTime, Utc};
-pub use rowifi_core::error::RoError;
+pub use rowifi_core::{error::RoError, repos::GuildColumn};
 pub use rowifi_derive::Arguments;
 pub use twilight_util::builder::{
     embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder, ImageSource},
@@ -13,7 +13,7 @@ use crate::{
     bind::BindType,
     deny_list::DenyListType,
     id::{GuildId, UserId},
-    roblox::id::{GroupId, UserId as RobloxUserId},
+    roblox::id::{GroupId, UniverseId, UserId as RobloxUserId},
 };
 
 #[derive(Clone, Debug, Serialize)]
@@ -46,6 +46,8 @@ pub enum AuditLogKind {
     XPLock = 16,
     XPUnlock = 17,
     CustomCommandCreate = 18,
+    GameBan = 19,
+    GameUnban = 20,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -69,6 +71,8 @@ pub enum AuditLogData {
     XPLock(XPLock),
     XPUnlock(XPUnlock),
     CustomCommandCreate(CustomCommandCreate),
+    GameBan(GameBan),
+    GameUnban(GameUnban),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -178,6 +182,22 @@ pub struct CustomCommandCreate {
     pub name: String,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GameBan {
+    pub target_roblox_user: RobloxUserId,
+    pub universe_id: UniverseId,
+    pub reason: String,
+    /// The length of the ban in seconds. Permanent if missing.
+    #[serde(default)]
+    pub duration: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GameUnban {
+    pub target_roblox_user: RobloxUserId,
+    pub universe_id: UniverseId,
+}
+
 impl TryFrom<tokio_postgres::Row> for AuditLog {
     type Error = AuditLogDeserializeError;
 
@@ -237,6 +257,12 @@ impl TryFrom<tokio_postgres::Row> for AuditLog {
             AuditLogKind::CustomCommandCreate => AuditLogData::CustomCommandCreate(
                 CustomCommandCreate::deserialize(metadata.0.as_ref())?,
             ),
+            AuditLogKind::GameBan => {
+                AuditLogData::GameBan(GameBan::deserialize(metadata.0.as_ref())?)
+            }
+            AuditLogKind::GameUnban => {
+                AuditLogData::GameUnban(GameUnban::deserialize(metadata.0.as_ref())?)
+            }
         };
 
         Ok(Self {
@@ -272,6 +298,8 @@ impl TryFrom<u32> for AuditLogKind {
             16 => Ok(Self::XPLock),
             17 => Ok(Self::XPUnlock),
             18 => Ok(Self::CustomCommandCreate),
+            19 => Ok(Self::GameBan),
+            20 => Ok(Self::GameUnban),
             _ => Err(()),
         }
     }
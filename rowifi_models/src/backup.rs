@@ -5,7 +5,7 @@ use crate::{
     deny_list::DenyList,
     events::EventType,
     guild::BypassRoleKind,
-    roblox::id::{AssetId, GroupId},
+    roblox::id::{AssetId, GroupId, RoleId},
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -35,7 +35,7 @@ pub struct BackupRankbind {
     /// The ID (0-255) of the rank
     pub group_rank_id: u32,
     /// The global rank ID
-    pub roblox_rank_id: String,
+    pub roblox_rank_id: RoleId,
     /// The priority of the bind. Used for determining the nickname
     pub priority: i32,
     /// The format of the nickname
@@ -1,6 +1,11 @@
+use bytes::BytesMut;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use tokio_postgres::{
+    types::{to_sql_checked, FromSql, IsNull, ToSql, Type},
+    Row,
+};
 
 use crate::{id::RoleId, roblox::id::AssetId};
 
@@ -36,6 +41,60 @@ impl Assetbind {
     }
 }
 
+impl TryFrom<Row> for Assetbind {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let asset_id = row.try_get("asset_id")?;
+        let asset_type = row.try_get("asset_type")?;
+        let discord_roles = row.try_get("discord_roles")?;
+        let priority = row.try_get("priority")?;
+        let template = row.try_get("template")?;
+
+        Ok(Self {
+            asset_id,
+            asset_type,
+            discord_roles,
+            priority,
+            template,
+        })
+    }
+}
+
+impl ToSql for AssetType {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        i32::to_sql(&(*self as i32), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i32 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for AssetType {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        match i32::from_sql(ty, raw)? {
+            0 => Ok(AssetType::Asset),
+            1 => Ok(AssetType::Badge),
+            2 => Ok(AssetType::Gamepass),
+            _ => unreachable!(),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i32 as FromSql>::accepts(ty)
+    }
+}
+
 impl Display for AssetType {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match *self {
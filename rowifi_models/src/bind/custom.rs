@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
 
 use super::Template;
 use crate::id::RoleId;
@@ -23,3 +24,24 @@ impl Custombind {
         &self.discord_roles
     }
 }
+
+impl TryFrom<Row> for Custombind {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let custom_bind_id: i32 = row.try_get("custom_bind_id")?;
+        let discord_roles = row.try_get("discord_roles")?;
+        let code = row.try_get("code")?;
+        let priority = row.try_get("priority")?;
+        let template = row.try_get("template")?;
+
+        #[allow(clippy::cast_sign_loss)]
+        Ok(Self {
+            custom_bind_id: custom_bind_id as u32,
+            discord_roles,
+            code,
+            priority,
+            template,
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
 
 use crate::{id::RoleId, roblox::id::GroupId};
 
@@ -22,3 +23,21 @@ impl Groupbind {
         &self.discord_roles
     }
 }
+
+impl TryFrom<Row> for Groupbind {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let group_id = row.try_get("group_id")?;
+        let discord_roles = row.try_get("discord_roles")?;
+        let priority = row.try_get("priority")?;
+        let template = row.try_get("template")?;
+
+        Ok(Self {
+            group_id,
+            discord_roles,
+            priority,
+            template,
+        })
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
 
-use crate::{id::RoleId, roblox::id::GroupId};
+use crate::{
+    id::RoleId,
+    roblox::id::{GroupId, RoleId as RobloxRoleId},
+};
 
 use super::Template;
 
@@ -13,7 +17,7 @@ pub struct Rankbind {
     /// The ID (0-255) of the rank
     pub group_rank_id: u32,
     /// The global rank ID
-    pub roblox_rank_id: String,
+    pub roblox_rank_id: RobloxRoleId,
     /// The priority of the bind. Used for determining the nickname
     pub priority: i32,
     /// The format of the nickname
@@ -26,3 +30,26 @@ impl Rankbind {
         &self.discord_roles
     }
 }
+
+impl TryFrom<Row> for Rankbind {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let group_id = row.try_get("group_id")?;
+        let discord_roles = row.try_get("discord_roles")?;
+        let group_rank_id: i32 = row.try_get("group_rank_id")?;
+        let roblox_rank_id = row.try_get("roblox_rank_id")?;
+        let priority = row.try_get("priority")?;
+        let template = row.try_get("template")?;
+
+        #[allow(clippy::cast_sign_loss)]
+        Ok(Self {
+            group_id,
+            discord_roles,
+            group_rank_id: group_rank_id as u32,
+            roblox_rank_id,
+            priority,
+            template,
+        })
+    }
+}
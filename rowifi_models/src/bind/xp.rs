@@ -1,12 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::roblox::id::GroupId;
+use crate::roblox::id::{GroupId, RoleId};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct XPBind {
     pub group_id: GroupId,
     pub group_rank_id: u32,
-    pub roblox_rank_id: String,
+    pub roblox_rank_id: RoleId,
     /// XP required to reach this rank
     pub xp: i64,
 }
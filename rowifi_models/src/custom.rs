@@ -43,6 +43,12 @@ pub enum ActionType {
     PublishUniverseMessage = 7,
     GetUsernameFromId = 8,
     GetIdFromUsername = 9,
+    GetSortedMapItem = 10,
+    UpdateSortedMapItem = 11,
+    DeleteSortedMapItem = 12,
+    EnqueueQueueItem = 13,
+    ReadQueueItem = 14,
+    DiscardQueueItems = 15,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -58,6 +64,12 @@ pub enum ActionMetadata {
     PublishUniverseMessage,
     GetUsernameFromId,
     GetIdFromUsername,
+    GetSortedMapItem,
+    UpdateSortedMapItem,
+    DeleteSortedMapItem,
+    EnqueueQueueItem,
+    ReadQueueItem,
+    DiscardQueueItems,
 }
 
 pub mod action {
@@ -252,6 +264,12 @@ impl<'de> Deserialize<'de> for WorkflowNode {
                     ActionType::PublishUniverseMessage => ActionMetadata::PublishUniverseMessage,
                     ActionType::GetUsernameFromId => ActionMetadata::GetUsernameFromId,
                     ActionType::GetIdFromUsername => ActionMetadata::GetIdFromUsername,
+                    ActionType::GetSortedMapItem => ActionMetadata::GetSortedMapItem,
+                    ActionType::UpdateSortedMapItem => ActionMetadata::UpdateSortedMapItem,
+                    ActionType::DeleteSortedMapItem => ActionMetadata::DeleteSortedMapItem,
+                    ActionType::EnqueueQueueItem => ActionMetadata::EnqueueQueueItem,
+                    ActionType::ReadQueueItem => ActionMetadata::ReadQueueItem,
+                    ActionType::DiscardQueueItems => ActionMetadata::DiscardQueueItems,
                 };
 
                 Ok(WorkflowNode {
@@ -1,6 +1,11 @@
+use bytes::BytesMut;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use tokio_postgres::{
+    types::{to_sql_checked, FromSql, IsNull, ToSql, Type},
+    Row,
+};
 
 use crate::roblox::id::{GroupId, UserId};
 
@@ -97,6 +102,99 @@ impl Serialize for DenyList {
     }
 }
 
+impl TryFrom<Row> for DenyList {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let id: i32 = row.try_get("deny_list_id")?;
+        let reason = row.try_get("reason")?;
+        let kind: DenyListType = row.try_get("kind")?;
+        let action_type = row.try_get("action_type")?;
+        // The table only allows the column matching the kind to be set.
+        let data = match kind {
+            DenyListType::User => DenyListData::User(row.try_get("user_id")?),
+            DenyListType::Group => DenyListData::Group(row.try_get("group_id")?),
+            DenyListType::Custom => DenyListData::Custom(row.try_get("code")?),
+        };
+
+        #[allow(clippy::cast_sign_loss)]
+        Ok(Self {
+            id: id as u32,
+            reason,
+            action_type,
+            data,
+        })
+    }
+}
+
+impl ToSql for DenyListType {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        i32::to_sql(&(*self as i32), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i32 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for DenyListType {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        match i32::from_sql(ty, raw)? {
+            0 => Ok(DenyListType::User),
+            1 => Ok(DenyListType::Group),
+            2 => Ok(DenyListType::Custom),
+            _ => unreachable!(),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i32 as FromSql>::accepts(ty)
+    }
+}
+
+impl ToSql for DenyListActionType {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        i32::to_sql(&(*self as i32), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i32 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for DenyListActionType {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        match i32::from_sql(ty, raw)? {
+            0 => Ok(DenyListActionType::None),
+            1 => Ok(DenyListActionType::Kick),
+            2 => Ok(DenyListActionType::Ban),
+            _ => unreachable!(),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i32 as FromSql>::accepts(ty)
+    }
+}
+
 impl Display for DenyListType {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
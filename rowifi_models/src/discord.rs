@@ -4,27 +4,86 @@ pub mod cache {
     use serde::{Deserialize, Serialize};
     use std::collections::HashSet;
     use twilight_model::{
-        channel::permission_overwrite::PermissionOverwrite,
-        guild::{Guild, Permissions},
+        channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+        guild::{Guild, Member, Permissions, Role},
         util::ImageHash,
     };
 
     use crate::id::{ChannelId, GuildId, RoleId, UserId};
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
-    #[serde(untagged)]
     pub enum CachedChannel {
+        Category(CachedCategoryChannel),
+        /// Text and announcement channels.
         Text(CachedTextChannel),
+        /// Forum and media channels.
+        Forum(CachedForumChannel),
+        /// Voice and stage channels, which also have a text chat.
+        Voice(CachedVoiceChannel),
+        /// Active public, private and announcement threads.
+        Thread(CachedThread),
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct CachedCategoryChannel {
+        pub id: ChannelId,
+        pub guild_id: GuildId,
+        pub name: String,
+        pub position: i32,
+        pub permission_overwrites: Vec<PermissionOverwrite>,
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct CachedTextChannel {
         pub id: ChannelId,
         pub guild_id: GuildId,
+        pub kind: ChannelType,
         pub name: String,
+        pub position: i32,
+        /// The category the channel is in.
+        pub parent_id: Option<ChannelId>,
         pub permission_overwrites: Vec<PermissionOverwrite>,
     }
 
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct CachedForumChannel {
+        pub id: ChannelId,
+        pub guild_id: GuildId,
+        pub kind: ChannelType,
+        pub name: String,
+        pub position: i32,
+        /// The category the channel is in.
+        pub parent_id: Option<ChannelId>,
+        pub permission_overwrites: Vec<PermissionOverwrite>,
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct CachedVoiceChannel {
+        pub id: ChannelId,
+        pub guild_id: GuildId,
+        pub kind: ChannelType,
+        pub name: String,
+        pub position: i32,
+        /// The category the channel is in.
+        pub parent_id: Option<ChannelId>,
+        pub permission_overwrites: Vec<PermissionOverwrite>,
+        pub bitrate: Option<u32>,
+        pub user_limit: Option<u32>,
+    }
+
+    /// Threads don't have permission overwrites of their own and use the ones of their parent.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct CachedThread {
+        pub id: ChannelId,
+        pub guild_id: GuildId,
+        pub kind: ChannelType,
+        pub name: String,
+        /// The text, announcement or forum channel the thread was created in.
+        pub parent_id: ChannelId,
+        pub owner_id: Option<UserId>,
+        pub locked: bool,
+    }
+
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct CachedGuild {
         pub id: GuildId,
@@ -71,10 +130,81 @@ pub mod cache {
             format!("discord:channels:{id}")
         }
 
+        #[must_use]
+        pub fn id(&self) -> ChannelId {
+            match self {
+                CachedChannel::Category(c) => c.id,
+                CachedChannel::Text(c) => c.id,
+                CachedChannel::Forum(c) => c.id,
+                CachedChannel::Voice(c) => c.id,
+                CachedChannel::Thread(c) => c.id,
+            }
+        }
+
+        #[must_use]
+        pub fn guild_id(&self) -> GuildId {
+            match self {
+                CachedChannel::Category(c) => c.guild_id,
+                CachedChannel::Text(c) => c.guild_id,
+                CachedChannel::Forum(c) => c.guild_id,
+                CachedChannel::Voice(c) => c.guild_id,
+                CachedChannel::Thread(c) => c.guild_id,
+            }
+        }
+
+        #[must_use]
+        pub fn kind(&self) -> ChannelType {
+            match self {
+                CachedChannel::Category(_) => ChannelType::GuildCategory,
+                CachedChannel::Text(c) => c.kind,
+                CachedChannel::Forum(c) => c.kind,
+                CachedChannel::Voice(c) => c.kind,
+                CachedChannel::Thread(c) => c.kind,
+            }
+        }
+
         #[must_use]
         pub fn name(&self) -> &str {
             match self {
+                CachedChannel::Category(c) => &c.name,
                 CachedChannel::Text(c) => &c.name,
+                CachedChannel::Forum(c) => &c.name,
+                CachedChannel::Voice(c) => &c.name,
+                CachedChannel::Thread(c) => &c.name,
+            }
+        }
+
+        /// The category of a channel or the channel a thread belongs to.
+        #[must_use]
+        pub fn parent_id(&self) -> Option<ChannelId> {
+            match self {
+                CachedChannel::Category(_) => None,
+                CachedChannel::Text(c) => c.parent_id,
+                CachedChannel::Forum(c) => c.parent_id,
+                CachedChannel::Voice(c) => c.parent_id,
+                CachedChannel::Thread(c) => Some(c.parent_id),
+            }
+        }
+
+        /// The permission overwrites of the channel. Always empty for threads.
+        #[must_use]
+        pub fn permission_overwrites(&self) -> &[PermissionOverwrite] {
+            match self {
+                CachedChannel::Category(c) => &c.permission_overwrites,
+                CachedChannel::Text(c) => &c.permission_overwrites,
+                CachedChannel::Forum(c) => &c.permission_overwrites,
+                CachedChannel::Voice(c) => &c.permission_overwrites,
+                CachedChannel::Thread(_) => &[],
+            }
+        }
+
+        /// Whether messages can be sent in the channel, which is what log channels need.
+        #[must_use]
+        pub fn is_messageable(&self) -> bool {
+            match self {
+                CachedChannel::Category(_) | CachedChannel::Forum(_) => false,
+                CachedChannel::Text(_) | CachedChannel::Voice(_) => true,
+                CachedChannel::Thread(c) => !c.locked,
             }
         }
     }
@@ -85,6 +215,19 @@ pub mod cache {
             format!("discord:guilds:{id}")
         }
 
+        /// The counter kept alongside the guild so that joins and leaves don't have to rewrite
+        /// it. Takes precedence over [`CachedGuild::member_count`].
+        #[must_use]
+        pub fn member_count_key(id: GuildId) -> String {
+            format!("discord:guilds:{id}:member_count")
+        }
+
+        /// The set of the IDs of the cached members of the guild.
+        #[must_use]
+        pub fn members_key(id: GuildId) -> String {
+            format!("discord:m:{id}")
+        }
+
         #[must_use]
         pub fn from_guild(guild: &Guild) -> Self {
             Self {
@@ -92,9 +235,17 @@ pub mod cache {
                 name: guild.name.clone(),
                 owner_id: UserId::new(guild.owner_id.get()),
                 icon: guild.icon,
-                member_count: guild.member_count.unwrap_or_default(),
+                member_count: guild
+                    .member_count
+                    .or(guild.approximate_member_count)
+                    .unwrap_or_default(),
                 roles: guild.roles.iter().map(|r| RoleId(r.id)).collect(),
-                channels: guild.channels.iter().map(|c| ChannelId(c.id)).collect(),
+                channels: guild
+                    .channels
+                    .iter()
+                    .chain(&guild.threads)
+                    .map(|c| ChannelId(c.id))
+                    .collect(),
             }
         }
     }
@@ -104,6 +255,16 @@ pub mod cache {
         pub fn key(guild_id: GuildId, user_id: UserId) -> String {
             format!("discord:m:{guild_id}:{user_id}")
         }
+
+        #[must_use]
+        pub fn from_member(member: &Member) -> Self {
+            Self {
+                id: UserId(member.user.id),
+                roles: member.roles.iter().map(|r| RoleId(*r)).collect(),
+                nickname: member.nick.clone(),
+                avatar: member.avatar.map(|a| a.to_string()),
+            }
+        }
     }
 
     impl CachedRole {
@@ -111,6 +272,18 @@ pub mod cache {
         pub fn key(id: RoleId) -> String {
             format!("discord:roles:{id}")
         }
+
+        #[must_use]
+        pub fn from_role(role: &Role) -> Self {
+            Self {
+                id: RoleId(role.id),
+                name: role.name.clone(),
+                permissions: role.permissions,
+                managed: role.managed,
+                position: role.position,
+                color: role.color,
+            }
+        }
     }
 
     impl CachedUser {
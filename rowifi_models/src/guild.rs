@@ -9,7 +9,7 @@ use crate::{
     deny_list::DenyList,
     events::EventType,
     id::{ChannelId, GuildId, RoleId},
-    roblox::id::GroupId,
+    roblox::id::{GroupId, UniverseId},
 };
 
 #[derive(Debug)]
@@ -24,11 +24,6 @@ pub struct PartialRoGuild {
     pub bypass_roles: Vec<BypassRole>,
     pub unverified_roles: Vec<RoleId>,
     pub verified_roles: Vec<RoleId>,
-    pub rankbinds: Vec<Rankbind>,
-    pub groupbinds: Vec<Groupbind>,
-    pub assetbinds: Vec<Assetbind>,
-    pub custombinds: Vec<Custombind>,
-    pub deny_lists: Vec<DenyList>,
     pub default_template: Option<Template>,
     pub update_on_join: Option<bool>,
     pub event_types: Vec<EventType>,
@@ -38,6 +33,22 @@ pub struct PartialRoGuild {
     pub registered_groups: Vec<GroupId>,
     pub sticky_roles: Vec<RoleId>,
     pub log_channel: Option<ChannelId>,
+    /// The universe that ban-type denylists and `/gameban` restrict users from.
+    pub linked_universe: Option<UniverseId>,
+    /// Bumped on every change to the configuration. Changes based on this configuration are
+    /// rejected if it is no longer the latest version.
+    pub config_version: i64,
+}
+
+/// The binds and deny lists of a server. They are stored in their own tables and loaded separately
+/// from the [`PartialRoGuild`] they belong to.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct GuildBinds {
+    pub rankbinds: Vec<Rankbind>,
+    pub groupbinds: Vec<Groupbind>,
+    pub assetbinds: Vec<Assetbind>,
+    pub custombinds: Vec<Custombind>,
+    pub deny_lists: Vec<DenyList>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
@@ -73,11 +84,6 @@ impl PartialRoGuild {
             bypass_roles: Vec::new(),
             unverified_roles: Vec::new(),
             verified_roles: Vec::new(),
-            rankbinds: Vec::new(),
-            groupbinds: Vec::new(),
-            assetbinds: Vec::new(),
-            custombinds: Vec::new(),
-            deny_lists: Vec::new(),
             default_template: None,
             update_on_join: None,
             event_types: Vec::new(),
@@ -87,6 +93,8 @@ impl PartialRoGuild {
             registered_groups: Vec::new(),
             sticky_roles: Vec::new(),
             log_channel: None,
+            linked_universe: None,
+            config_version: 0,
         }
     }
 }
@@ -102,21 +110,6 @@ impl TryFrom<tokio_postgres::Row> for PartialRoGuild {
             .unwrap_or_else(|_| Json(Vec::new()));
         let unverified_roles = row.try_get("unverified_roles").unwrap_or_default();
         let verified_roles = row.try_get("verified_roles").unwrap_or_default();
-        let rankbinds = row
-            .try_get("rankbinds")
-            .unwrap_or_else(|_| Json(Vec::new()));
-        let groupbinds = row
-            .try_get("groupbinds")
-            .unwrap_or_else(|_| Json(Vec::new()));
-        let assetbinds = row
-            .try_get("assetbinds")
-            .unwrap_or_else(|_| Json(Vec::new()));
-        let custombinds = row
-            .try_get("custombinds")
-            .unwrap_or_else(|_| Json(Vec::new()));
-        let deny_lists = row
-            .try_get("deny_lists")
-            .unwrap_or_else(|_| Json(Vec::new()));
         let default_template = row.try_get("default_template").unwrap_or_default();
         let update_on_join = row.try_get("update_on_join").unwrap_or_default();
         let event_types = row
@@ -130,6 +123,8 @@ impl TryFrom<tokio_postgres::Row> for PartialRoGuild {
             .unwrap_or_else(|_| Vec::new());
         let sticky_roles = row.try_get("sticky_roles").unwrap_or_default();
         let log_channel = row.try_get("log_channel").ok();
+        let linked_universe = row.try_get("linked_universe").ok();
+        let config_version = row.try_get("config_version").unwrap_or_default();
 
         Ok(Self {
             guild_id,
@@ -137,11 +132,6 @@ impl TryFrom<tokio_postgres::Row> for PartialRoGuild {
             bypass_roles: bypass_roles.0,
             unverified_roles,
             verified_roles,
-            rankbinds: rankbinds.0,
-            groupbinds: groupbinds.0,
-            assetbinds: assetbinds.0,
-            custombinds: custombinds.0,
-            deny_lists: deny_lists.0,
             default_template,
             update_on_join,
             event_types: event_types.0,
@@ -151,6 +141,8 @@ impl TryFrom<tokio_postgres::Row> for PartialRoGuild {
             registered_groups,
             sticky_roles,
             log_channel,
+            linked_universe,
+            config_version,
         })
     }
 }
@@ -1,7 +1,14 @@
-use hyper::StatusCode;
+use hyper::{
+    header::{HeaderMap, RETRY_AFTER},
+    http::response::Parts,
+    StatusCode,
+};
+use serde::Deserialize;
+use serde_json::Value;
 use std::{
     error::Error as StdError,
     fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
 };
 
 #[derive(Debug)]
@@ -9,14 +16,56 @@ pub enum ErrorKind {
     BuildingRequest,
     Sending,
     ChunkingResponse,
-    Response {
-        route: String,
-        status: StatusCode,
-        bytes: Vec<u8>,
-    },
+    Response { route: String, error: ApiError },
     Deserialize,
 }
 
+/// An error returned by a Roblox API, parsed from the status of the response and its body.
+#[derive(Clone, Debug)]
+pub struct ApiError {
+    pub kind: ApiErrorKind,
+    pub status: StatusCode,
+    /// The error code in the body, such as `NOT_FOUND` for Open Cloud APIs.
+    pub code: Option<String>,
+    pub message: Option<String>,
+    /// The API key the request was made with.
+    pub api_key: ApiKeySource,
+}
+
+/// Which Open Cloud API key authenticated a request.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ApiKeySource {
+    /// The key the client was created with.
+    #[default]
+    Bot,
+    /// A key registered by a server. See [`RobloxClient::with_api_key`](crate::RobloxClient::with_api_key).
+    Server,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ApiErrorKind {
+    NotFound,
+    /// Too many requests were made. `retry_after` is the time to wait, if Roblox sent one.
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    /// The API key or the OAuth token is missing, invalid or expired.
+    Unauthorized,
+    /// The credentials are valid but lack access to the resource. `scope` is the missing scope,
+    /// if Roblox named one.
+    PermissionDenied {
+        scope: Option<String>,
+    },
+    /// The request was malformed. `field` is the offending field, if Roblox named one.
+    InvalidArgument {
+        field: Option<String>,
+    },
+    /// Roblox failed to handle the request.
+    Upstream,
+    /// Any other status.
+    Other,
+}
+
 #[derive(Debug)]
 pub struct RobloxError {
     pub(super) source: Option<Box<dyn StdError + Send + Sync>>,
@@ -35,6 +84,21 @@ impl RobloxError {
         &self.kind
     }
 
+    /// The error returned by the API, if the request made it there.
+    #[must_use]
+    pub const fn api_error(&self) -> Option<&ApiError> {
+        match &self.kind {
+            ErrorKind::Response { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.api_error()
+            .is_some_and(|e| e.kind == ApiErrorKind::NotFound)
+    }
+
     #[must_use]
     pub fn into_source(self) -> Option<Box<dyn StdError + Send + Sync>> {
         self.source
@@ -57,11 +121,16 @@ impl Display for RobloxError {
             ErrorKind::BuildingRequest => write!(f, "failed to build the request"),
             ErrorKind::Sending => write!(f, "sending the request failed"),
             ErrorKind::ChunkingResponse => write!(f, "chunking the response failed"),
-            ErrorKind::Response {
-                route,
-                status,
-                bytes: _,
-            } => write!(f, "failed with {status} on {route}"),
+            ErrorKind::Response { route, error } => {
+                write!(f, "failed with {} on {route}", error.status)?;
+                if let Some(code) = &error.code {
+                    write!(f, ": {code}")?;
+                }
+                if let Some(message) = &error.message {
+                    write!(f, ": {message}")?;
+                }
+                Ok(())
+            }
             ErrorKind::Deserialize => write!(f, "error deserializing"),
         }
     }
@@ -89,3 +158,218 @@ impl StdError for DeserializeBodyError {
             .map(|source| &**source as &(dyn StdError + 'static))
     }
 }
+
+/// The error bodies of the APIs. Open Cloud v2 uses `code`, Open Cloud v1 uses `error` and the
+/// legacy APIs wrap a list of errors.
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    code: Option<Value>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    details: Vec<Value>,
+    #[serde(default)]
+    errors: Vec<LegacyError>,
+}
+
+#[derive(Deserialize)]
+struct LegacyError {
+    #[serde(default)]
+    code: Option<Value>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl ApiError {
+    #[must_use]
+    pub fn new(status: StatusCode) -> Self {
+        Self::from_parts(status, &HeaderMap::new(), &[])
+    }
+
+    #[must_use]
+    pub fn from_response(parts: &Parts, bytes: &[u8]) -> Self {
+        Self {
+            api_key: parts.extensions.get().copied().unwrap_or_default(),
+            ..Self::from_parts(parts.status, &parts.headers, bytes)
+        }
+    }
+
+    fn from_parts(status: StatusCode, headers: &HeaderMap, bytes: &[u8]) -> Self {
+        let body = serde_json::from_slice::<ErrorBody>(bytes).ok();
+        let (code, message, details) = match body {
+            Some(mut body) => {
+                let legacy = (!body.errors.is_empty()).then(|| body.errors.swap_remove(0));
+                let code = body
+                    .code
+                    .or_else(|| body.error.map(Value::String))
+                    .or_else(|| legacy.as_ref().and_then(|l| l.code.clone()))
+                    .map(|c| match c {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    });
+                let message = body.message.or_else(|| legacy.and_then(|l| l.message));
+                (code, message, body.details)
+            }
+            None => (None, None, Vec::new()),
+        };
+
+        let kind = match (status, code.as_deref()) {
+            (StatusCode::NOT_FOUND, _) | (_, Some("NOT_FOUND")) => ApiErrorKind::NotFound,
+            (StatusCode::TOO_MANY_REQUESTS, _) | (_, Some("RESOURCE_EXHAUSTED")) => {
+                ApiErrorKind::RateLimited {
+                    retry_after: retry_after(headers),
+                }
+            }
+            (StatusCode::UNAUTHORIZED, _) | (_, Some("UNAUTHENTICATED")) => {
+                ApiErrorKind::Unauthorized
+            }
+            (StatusCode::FORBIDDEN, _) | (_, Some("PERMISSION_DENIED" | "INSUFFICIENT_SCOPE")) => {
+                ApiErrorKind::PermissionDenied {
+                    scope: message.as_deref().and_then(scope),
+                }
+            }
+            (StatusCode::BAD_REQUEST, _) | (_, Some("INVALID_ARGUMENT")) => {
+                ApiErrorKind::InvalidArgument {
+                    field: field(&details),
+                }
+            }
+            (status, _) if status.is_server_error() => ApiErrorKind::Upstream,
+            _ => ApiErrorKind::Other,
+        };
+
+        Self {
+            kind,
+            status,
+            code,
+            message,
+            api_key: ApiKeySource::Bot,
+        }
+    }
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(RETRY_AFTER)
+        .or_else(|| headers.get("x-ratelimit-reset"))?;
+    value
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Roblox names the missing scope in the message, for example
+/// `Insufficient scope. Required: universe.user-restriction:write`.
+fn scope(message: &str) -> Option<String> {
+    message
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '\'' || c == '"')
+        .map(|word| word.trim_end_matches('.'))
+        .find(|word| {
+            word.split_once(':')
+                .is_some_and(|(resource, action)| !resource.is_empty() && !action.is_empty())
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':'))
+        })
+        .map(ToString::to_string)
+}
+
+/// The first field violation of a `google.rpc.BadRequest` detail.
+fn field(details: &[Value]) -> Option<String> {
+    details.iter().find_map(|detail| {
+        detail
+            .get("fieldViolations")?
+            .as_array()?
+            .first()?
+            .get("field")?
+            .as_str()
+            .map(ToString::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_cloud_not_found() {
+        let error = ApiError::from_parts(
+            StatusCode::NOT_FOUND,
+            &HeaderMap::new(),
+            br#"{"code":"NOT_FOUND","message":"User not found."}"#,
+        );
+        assert_eq!(error.kind, ApiErrorKind::NotFound);
+        assert_eq!(error.code.as_deref(), Some("NOT_FOUND"));
+        assert_eq!(error.message.as_deref(), Some("User not found."));
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "30".parse().unwrap());
+        let error = ApiError::from_parts(StatusCode::TOO_MANY_REQUESTS, &headers, b"");
+        assert_eq!(
+            error.kind,
+            ApiErrorKind::RateLimited {
+                retry_after: Some(Duration::from_secs(30))
+            }
+        );
+    }
+
+    #[test]
+    fn test_permission_denied_scope() {
+        let error = ApiError::from_parts(
+            StatusCode::FORBIDDEN,
+            &HeaderMap::new(),
+            br#"{"code":"PERMISSION_DENIED","message":"Insufficient scope. Required: universe.user-restriction:write."}"#,
+        );
+        assert_eq!(
+            error.kind,
+            ApiErrorKind::PermissionDenied {
+                scope: Some("universe.user-restriction:write".into())
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_argument_field() {
+        let error = ApiError::from_parts(
+            StatusCode::BAD_REQUEST,
+            &HeaderMap::new(),
+            br#"{"code":"INVALID_ARGUMENT","message":"Invalid filter.","details":[{"@type":"type.googleapis.com/google.rpc.BadRequest","fieldViolations":[{"field":"filter","description":"Unknown field."}]}]}"#,
+        );
+        assert_eq!(
+            error.kind,
+            ApiErrorKind::InvalidArgument {
+                field: Some("filter".into())
+            }
+        );
+    }
+
+    #[test]
+    fn test_legacy_errors() {
+        let error = ApiError::from_parts(
+            StatusCode::BAD_REQUEST,
+            &HeaderMap::new(),
+            br#"{"errors":[{"code":1,"message":"The group is invalid or does not exist."}]}"#,
+        );
+        assert_eq!(error.kind, ApiErrorKind::InvalidArgument { field: None });
+        assert_eq!(error.code.as_deref(), Some("1"));
+        assert_eq!(
+            error.message.as_deref(),
+            Some("The group is invalid or does not exist.")
+        );
+    }
+
+    #[test]
+    fn test_upstream() {
+        let error = ApiError::from_parts(StatusCode::BAD_GATEWAY, &HeaderMap::new(), b"<html>");
+        assert_eq!(error.kind, ApiErrorKind::Upstream);
+        assert!(error.code.is_none());
+    }
+}
@@ -0,0 +1,23 @@
+use futures_util::{
+    stream::{self, Stream},
+    StreamExt,
+};
+use std::future::Future;
+
+use crate::MAX_CONCURRENT_REQUESTS;
+
+/// Calls `f` with each of the `items`, keeping up to [`MAX_CONCURRENT_REQUESTS`] of the returned
+/// futures in flight at once. The outputs are yielded as they complete, in no particular order.
+///
+/// The items are taken as an owned [`Vec`] so that the stream does not borrow from a local of the
+/// caller. Such a borrow held across the `await` on the stream keeps the caller's future from being
+/// `Send`, which the command handlers and spawned tasks awaiting it need.
+pub fn fan_out<T, F, Fut>(items: Vec<T>, f: F) -> impl Stream<Item = Fut::Output>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future,
+{
+    stream::iter(items)
+        .map(f)
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+}
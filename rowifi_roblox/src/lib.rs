@@ -7,11 +7,19 @@
 )]
 
 pub mod error;
+mod fan_out;
 pub mod filter;
+pub mod metrics;
+mod pagination;
 pub mod request;
 mod route;
 
+pub use fan_out::fan_out;
+pub use metrics::RobloxMetrics;
+pub use route::BaseUrls;
+
 use filter::AssetFilterBuilder;
+use futures_util::{Stream, TryStreamExt};
 use http_body_util::{BodyExt, Full};
 use hyper::{
     body::Bytes,
@@ -24,31 +32,67 @@ use hyper_util::{
     client::legacy::{connect::HttpConnector, Client as HyperClient},
     rt::TokioExecutor,
 };
+use itertools::Itertools;
 use rowifi_roblox_models::{
-    datastore::{Datastore, DatastoreEntry, PartialDatastoreEntry},
-    group::{Group, GroupRole, GroupUserRole},
-    id::{GroupId, UniverseId, UserId},
+    api_key::ApiKeyInfo,
+    asset::{AssetDetails, Badge, GamePass, IconKind},
+    datastore::{
+        Datastore, DatastoreEntry, OrderedDatastoreEntry, OrderedDatastoreOrder,
+        PartialDatastoreEntry,
+    },
+    group::{Group, GroupJoinRequest, GroupMembership, GroupRole, GroupUserRole},
+    id::{AssetId, GroupId, PlaceId, RoleId, UniverseId, UserId},
     inventory::InventoryItem,
+    memory_store::{MemoryStoreQueueItem, MemoryStoreQueueRead, MemoryStoreSortedMapItem, SortKey},
+    resource::{RolePath, UserPath},
+    thumbnail::{AvatarThumbnailKind, Thumbnail, ThumbnailOptions},
     universe::Universe,
     user::{OAuthUser, PartialUser},
+    user_restriction::{GameJoinRestriction, UserRestriction},
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::Write;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tracing::{field, Instrument};
 
 use error::DeserializeBodyError;
+use pagination::paginate;
 use request::Request;
 use serde_json::Value;
 
 use crate::{
-    error::{ErrorKind, RobloxError},
+    error::{ApiError, ApiErrorKind, ApiKeySource, ErrorKind, RobloxError},
     route::Route,
 };
 
+/// The largest page sizes accepted by the Open Cloud list endpoints.
+const MAX_DATASTORES_PAGE_SIZE: u32 = 100;
+const MAX_DATASTORE_ENTRIES_PAGE_SIZE: u32 = 256;
+const MAX_GROUP_JOIN_REQUESTS_PAGE_SIZE: u32 = 20;
+const MAX_GROUP_MEMBERSHIPS_PAGE_SIZE: u32 = 100;
+const MAX_GROUP_RANKS_PAGE_SIZE: u32 = 20;
+const MAX_ICONS_PER_REQUEST: usize = 100;
+const MAX_INVENTORY_ITEMS_PAGE_SIZE: u32 = 100;
+const MAX_MEMORY_STORE_QUEUE_READ_COUNT: u32 = 200;
+const MAX_MEMORY_STORE_SORTED_MAP_ITEMS_PAGE_SIZE: u32 = 100;
+const MAX_ORDERED_DATASTORE_ENTRIES_PAGE_SIZE: u32 = 100;
+const MAX_THUMBNAILS_PER_REQUEST: usize = 100;
+const MAX_USER_RESTRICTIONS_PAGE_SIZE: u32 = 100;
+const MAX_USERS_PER_REQUEST: usize = 100;
+
+/// How many requests [`fan_out`] keeps in flight at once.
+pub const MAX_CONCURRENT_REQUESTS: usize = 4;
+
 #[derive(Clone)]
 pub struct RobloxClient {
     client: HyperClient<HttpsConnector<HttpConnector>, Full<Bytes>>,
     open_cloud_auth: String,
     proxy_url: Option<String>,
+    base_urls: BaseUrls,
+    metrics: Option<RobloxMetrics>,
+    api_key_source: ApiKeySource,
 }
 
 /// Represents a long-running operation
@@ -80,6 +124,22 @@ pub struct GroupRanks {
     pub next_page_token: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct GroupMemberships {
+    #[serde(rename = "groupMemberships")]
+    pub memberships: Vec<GroupMembership>,
+    #[serde(rename = "nextPageToken", default)]
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GroupJoinRequests {
+    #[serde(rename = "groupJoinRequests")]
+    pub join_requests: Vec<GroupJoinRequest>,
+    #[serde(rename = "nextPageToken", default)]
+    pub next_page_token: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct ThumbnailResponse {
     #[serde(rename = "imageUri")]
@@ -102,12 +162,43 @@ pub struct DatastoreEntriesResponse {
     pub next_page_token: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OrderedDatastoreEntriesResponse {
+    #[serde(rename = "orderedDataStoreEntries", default)]
+    pub entries: Vec<OrderedDatastoreEntry>,
+    #[serde(rename = "nextPageToken", default)]
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryStoreSortedMapItemsResponse {
+    #[serde(rename = "memoryStoreSortedMapItems", default)]
+    pub items: Vec<MemoryStoreSortedMapItem>,
+    #[serde(rename = "nextPageToken", default)]
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserRestrictionsResponse {
+    #[serde(rename = "userRestrictions", default)]
+    pub user_restrictions: Vec<UserRestriction>,
+    #[serde(rename = "nextPageToken", default)]
+    pub next_page_token: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
     pub next_page_token: Option<String>,
 }
 
+/// A user along with their avatar thumbnail, if it was requested and has been generated.
+#[derive(Clone, Debug)]
+pub struct ResolvedUser {
+    pub user: PartialUser,
+    pub thumbnail: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct UpdateDatastoreEntryArgs {
     pub value: Value,
@@ -115,6 +206,24 @@ pub struct UpdateDatastoreEntryArgs {
     pub attributes: Option<Value>,
 }
 
+/// The value of an item of a memory store sorted map, along with how long to keep it and how to
+/// sort it.
+#[derive(Debug)]
+pub struct MemoryStoreSortedMapItemArgs {
+    pub value: Value,
+    pub ttl: Duration,
+    pub sort_key: Option<SortKey>,
+}
+
+/// The order and the bounds on the values of the entries returned when listing an ordered
+/// datastore. Both bounds are inclusive.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedDatastoreQuery {
+    pub order: OrderedDatastoreOrder,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+}
+
 impl RobloxClient {
     #[must_use]
     pub fn new(open_cloud_auth: &str, proxy_url: Option<String>) -> Self {
@@ -128,6 +237,35 @@ impl RobloxClient {
             client,
             open_cloud_auth: open_cloud_auth.to_string(),
             proxy_url,
+            base_urls: BaseUrls::default(),
+            metrics: None,
+            api_key_source: ApiKeySource::Bot,
+        }
+    }
+
+    /// Record the requests made by this client, and any client derived from it, in `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: RobloxMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Send the requests to the given hosts instead of Roblox.
+    #[must_use]
+    pub fn with_base_urls(mut self, base_urls: BaseUrls) -> Self {
+        self.base_urls = base_urls;
+        self
+    }
+
+    /// A client that authenticates Open Cloud requests with the API key registered by a server
+    /// instead of the one it was created with. The connection pool is shared with this client,
+    /// and its errors report [`ApiKeySource::Server`].
+    #[must_use]
+    pub fn with_api_key(&self, api_key: &str) -> Self {
+        Self {
+            open_cloud_auth: api_key.to_string(),
+            api_key_source: ApiKeySource::Server,
+            ..self.clone()
         }
     }
 
@@ -145,7 +283,7 @@ impl RobloxClient {
         let route = Route::GetUserGroupRoles { user_id: user_id.0 };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .proxy_uri(self.proxy_url.clone())
             .body(Full::default())
@@ -155,15 +293,14 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -191,7 +328,7 @@ impl RobloxClient {
         let route = Route::GetUser { user_id: user_id.0 };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -205,15 +342,14 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -229,7 +365,7 @@ impl RobloxClient {
         Ok(json)
     }
 
-    /// Get multiple users.
+    /// Get multiple users. Users that do not exist are left out.
     ///
     /// # Errors
     ///
@@ -238,9 +374,60 @@ impl RobloxClient {
         &self,
         user_ids: impl Iterator<Item = UserId>,
     ) -> Result<Vec<PartialUser>, RobloxError> {
+        let user_ids = user_ids.collect::<Vec<_>>();
+        let mut users = Vec::with_capacity(user_ids.len());
+        for user_ids in user_ids.chunks(MAX_USERS_PER_REQUEST) {
+            users.extend(self.get_users_chunk(user_ids).await?);
+        }
+        Ok(users)
+    }
+
+    /// Get the users along with their avatar thumbnails, if `thumbnails` is set. Up to
+    /// [`MAX_CONCURRENT_REQUESTS`] requests are made at once.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn resolve_users(
+        &self,
+        user_ids: impl IntoIterator<Item = UserId>,
+        thumbnails: Option<(AvatarThumbnailKind, ThumbnailOptions)>,
+    ) -> Result<HashMap<UserId, ResolvedUser>, RobloxError> {
+        let chunks = owned_chunks(user_ids.into_iter().unique(), MAX_USERS_PER_REQUEST);
+        let users = fan_out(chunks, |user_ids| async move {
+            self.get_users_chunk(&user_ids).await
+        })
+        .try_concat()
+        .await?;
+
+        let mut images = HashMap::new();
+        if let Some((kind, options)) = thumbnails {
+            let chunks = owned_chunks(users.iter().map(|u| u.id), MAX_THUMBNAILS_PER_REQUEST);
+            let thumbnails = fan_out(chunks, |user_ids| async move {
+                self.get_avatar_thumbnails_chunk(kind, &user_ids, options)
+                    .await
+            })
+            .try_concat()
+            .await?;
+            for thumbnail in thumbnails {
+                if let Some(image_url) = thumbnail.image_url {
+                    images.insert(UserId(thumbnail.target_id), image_url);
+                }
+            }
+        }
+
+        Ok(users
+            .into_iter()
+            .map(|user| {
+                let thumbnail = images.remove(&user.id);
+                (user.id, ResolvedUser { user, thumbnail })
+            })
+            .collect())
+    }
+
+    async fn get_users_chunk(&self, user_ids: &[UserId]) -> Result<Vec<PartialUser>, RobloxError> {
         let route = Route::GetUsers;
 
-        let user_ids = user_ids.collect::<Vec<_>>();
         let json = serde_json::json!({"userIds": user_ids});
         let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
             source: Some(Box::new(source)),
@@ -248,7 +435,7 @@ impl RobloxClient {
         })?;
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::POST)
             .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
             .header(CONTENT_LENGTH, body.len())
@@ -260,15 +447,14 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -304,7 +490,7 @@ impl RobloxClient {
         })?;
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::POST)
             .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
             .header(CONTENT_LENGTH, body.len())
@@ -316,15 +502,14 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -341,7 +526,7 @@ impl RobloxClient {
         Ok(json.data)
     }
 
-    /// Get the items in an user's inventory.
+    /// Get the items in an user's inventory. Follows the pagination if necessary.
     ///
     /// # Errors
     ///
@@ -351,19 +536,58 @@ impl RobloxClient {
         user_id: UserId,
         asset_filter: AssetFilterBuilder,
     ) -> Result<Vec<InventoryItem>, RobloxError> {
+        self.stream_inventory_items(user_id, asset_filter, MAX_INVENTORY_ITEMS_PAGE_SIZE)
+            .try_collect()
+            .await
+    }
+
+    /// Lazily iterate over the items in an user's inventory, requesting at most `page_size`
+    /// items at a time.
+    pub fn stream_inventory_items(
+        &self,
+        user_id: UserId,
+        asset_filter: AssetFilterBuilder,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<InventoryItem, RobloxError>> + '_ {
         // We request inventory items indivdually specifically, so if the filter is empty, it means
         // we do not want anything.
-        if asset_filter.is_empty() {
-            return Ok(Vec::new());
-        }
+        let filter = (!asset_filter.is_empty()).then(|| asset_filter.build());
+        paginate(move |page_token| {
+            let filter = filter.clone();
+            async move {
+                let Some(filter) = filter else {
+                    return Ok(PaginatedResponse {
+                        data: Vec::new(),
+                        next_page_token: None,
+                    });
+                };
+                self.list_inventory_items(user_id, &filter, page_token.as_deref(), page_size)
+                    .await
+            }
+        })
+    }
 
+    /// Get a single page of the items in an user's inventory.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_inventory_items(
+        &self,
+        user_id: UserId,
+        filter: &str,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<InventoryItem>, RobloxError> {
         let route = Route::ListInventoryItems {
             user_id: user_id.0,
-            filter: &asset_filter.build(),
+            filter,
+            page_token,
+            page_size: page_size.clamp(1, MAX_INVENTORY_ITEMS_PAGE_SIZE),
         };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -377,18 +601,21 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
+            // Private inventories are treated as empty ones.
             if parts.status == StatusCode::FORBIDDEN {
-                return Ok(Vec::new());
+                return Ok(PaginatedResponse {
+                    data: Vec::new(),
+                    next_page_token: None,
+                });
             }
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -402,7 +629,10 @@ impl RobloxClient {
                 kind: ErrorKind::Deserialize,
             })?;
 
-        Ok(json.inventory_items)
+        Ok(PaginatedResponse {
+            data: json.inventory_items,
+            next_page_token: json.next_page_token,
+        })
     }
 
     /// Get the ranks of a Roblox Group. Follows the pagination if necessary.
@@ -414,88 +644,58 @@ impl RobloxClient {
         &self,
         group_id: GroupId,
     ) -> Result<Option<Vec<GroupRole>>, RobloxError> {
-        let route = Route::ListGroupRanks {
-            group_id: group_id.0,
-        };
-        let mut ranks = Vec::new();
-        let mut next_page_token = None;
-
-        loop {
-            let mut route = route.to_string();
-            if let Some(next_page_token) = next_page_token {
-                let _ = write!(route, "&pageToken={next_page_token}");
-            }
-            let request = Request::new()
-                .uri(&route)
-                .method(Method::GET)
-                .header(
-                    HeaderName::from_static("x-api-key"),
-                    HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
-                )
-                .body(Full::default())
-                .proxy_uri(self.proxy_url.clone())
-                .build()
-                .map_err(|source| RobloxError {
-                    source: Some(Box::new(source)),
-                    kind: ErrorKind::BuildingRequest,
-                })?;
-
-            let (parts, bytes) = self.request(request).await?;
-
-            if parts.status == StatusCode::BAD_REQUEST {
-                return Ok(None);
-            }
-
-            if !parts.status.is_success() {
-                if parts.status == StatusCode::NOT_FOUND {
-                    return Ok(None);
-                }
-
-                return Err(RobloxError {
-                    source: None,
-                    kind: ErrorKind::Response {
-                        route,
-                        status: parts.status,
-                        bytes,
-                    },
-                });
-            }
-
-            let json =
-                serde_json::from_slice::<GroupRanks>(&bytes).map_err(|source| RobloxError {
-                    source: Some(Box::new(DeserializeBodyError {
-                        source: Some(Box::new(source)),
-                        bytes,
-                    })),
-                    kind: ErrorKind::Deserialize,
-                })?;
-            tracing::trace!(?json);
-            ranks.extend(json.ranks.into_iter());
-            if let Some(npt) = json.next_page_token {
-                if npt.is_empty() {
-                    break;
+        match self
+            .stream_group_ranks(group_id, MAX_GROUP_RANKS_PAGE_SIZE)
+            .try_collect()
+            .await
+        {
+            Ok(ranks) => Ok(Some(ranks)),
+            Err(err) => {
+                if let Some(error) = err.api_error() {
+                    if matches!(
+                        error.kind,
+                        ApiErrorKind::NotFound | ApiErrorKind::InvalidArgument { .. }
+                    ) {
+                        return Ok(None);
+                    }
                 }
-                next_page_token = Some(npt);
-            } else {
-                break;
+                Err(err)
             }
         }
+    }
 
-        Ok(Some(ranks))
+    /// Lazily iterate over the ranks of a Roblox Group, requesting at most `page_size` ranks at
+    /// a time.
+    pub fn stream_group_ranks(
+        &self,
+        group_id: GroupId,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<GroupRole, RobloxError>> + '_ {
+        paginate(move |page_token| async move {
+            self.list_group_ranks(group_id, page_token.as_deref(), page_size)
+                .await
+        })
     }
 
-    /// Get a Roblox Group
+    /// Get a single page of the ranks of a Roblox Group.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn get_group(&self, group_id: GroupId) -> Result<Option<Group>, RobloxError> {
-        let route = Route::GetGroup {
+    pub async fn list_group_ranks(
+        &self,
+        group_id: GroupId,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<GroupRole>, RobloxError> {
+        let route = Route::ListGroupRanks {
             group_id: group_id.0,
+            page_token,
+            page_size: page_size.clamp(1, MAX_GROUP_RANKS_PAGE_SIZE),
         };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -509,44 +709,86 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
-
-        if parts.status == StatusCode::NOT_FOUND {
-            return Ok(None);
-        }
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
-        let json = serde_json::from_slice::<Group>(&bytes).map_err(|source| RobloxError {
+        let json = serde_json::from_slice::<GroupRanks>(&bytes).map_err(|source| RobloxError {
             source: Some(Box::new(DeserializeBodyError {
                 source: Some(Box::new(source)),
                 bytes,
             })),
             kind: ErrorKind::Deserialize,
         })?;
+        tracing::trace!(?json);
 
-        Ok(Some(json))
+        Ok(PaginatedResponse {
+            data: json.ranks,
+            next_page_token: json.next_page_token,
+        })
     }
 
-    /// Get a user's thumbnail.
+    /// Get the membership of a user in a Roblox Group. Returns [`None`] if the user is not in the
+    /// group.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn get_user_thumbnail(&self, user_id: UserId) -> Result<String, RobloxError> {
-        let route = Route::GetUserThumbail { user_id: user_id.0 };
+    pub async fn get_group_membership(
+        &self,
+        group_id: GroupId,
+        user_id: UserId,
+    ) -> Result<Option<GroupMembership>, RobloxError> {
+        let filter = format!("user == '{}'", UserPath::new(user_id));
+        let page = self
+            .list_group_memberships(group_id, Some(&filter), None, 1)
+            .await?;
+        Ok(page.data.into_iter().next())
+    }
+
+    /// Lazily iterate over the members of a Roblox Group, requesting at most `page_size` members
+    /// at a time. The `filter` follows the Open Cloud filtering syntax.
+    pub fn stream_group_memberships<'a>(
+        &'a self,
+        group_id: GroupId,
+        filter: Option<&'a str>,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<GroupMembership, RobloxError>> + 'a {
+        paginate(move |page_token| async move {
+            self.list_group_memberships(group_id, filter, page_token.as_deref(), page_size)
+                .await
+        })
+    }
+
+    /// Get a single page of the members of a Roblox Group.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_group_memberships(
+        &self,
+        group_id: GroupId,
+        filter: Option<&str>,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<GroupMembership>, RobloxError> {
+        let route = Route::ListGroupMemberships {
+            group_id: group_id.0,
+            filter,
+            page_token,
+            page_size: page_size.clamp(1, MAX_GROUP_MEMBERSHIPS_PAGE_SIZE),
+        };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -560,66 +802,86 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
         let json =
-            serde_json::from_slice::<Operation<ThumbnailResponse>>(&bytes).map_err(|source| {
-                RobloxError {
-                    source: Some(Box::new(DeserializeBodyError {
-                        source: Some(Box::new(source)),
-                        bytes,
-                    })),
-                    kind: ErrorKind::Deserialize,
-                }
+            serde_json::from_slice::<GroupMemberships>(&bytes).map_err(|source| RobloxError {
+                source: Some(Box::new(DeserializeBodyError {
+                    source: Some(Box::new(source)),
+                    bytes,
+                })),
+                kind: ErrorKind::Deserialize,
             })?;
 
-        Ok(json.response.image_uri)
+        Ok(PaginatedResponse {
+            data: json.memberships,
+            next_page_token: json.next_page_token,
+        })
     }
 
-    /// Get an user from the Open Cloud API using the OAuth workflow.
+    /// Change the role of a member of a Roblox Group. The `membership_id` is the last segment of
+    /// the membership's path.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn get_oauth_userinfo(&self, authorization: &str) -> Result<OAuthUser, RobloxError> {
-        let route = Route::OAuthUserInfo;
+    pub async fn update_group_membership(
+        &self,
+        group_id: GroupId,
+        membership_id: &str,
+        role_id: RoleId,
+    ) -> Result<GroupMembership, RobloxError> {
+        let route = Route::UpdateGroupMembership {
+            group_id: group_id.0,
+            membership_id,
+        };
+
+        let json = serde_json::json!({ "role": RolePath::new(group_id, role_id) });
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::GET)
-            .header(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap())
+            .uri(self.url(&route))
+            .method(Method::PATCH)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
             .proxy_uri(self.proxy_url.clone())
-            .body(Full::default())
+            .body(Full::new(Bytes::from(body)))
             .build()
             .map_err(|source| RobloxError {
                 source: Some(Box::new(source)),
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
-        let json = serde_json::from_slice::<OAuthUser>(&bytes).map_err(|source| RobloxError {
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
             source: Some(Box::new(DeserializeBodyError {
                 source: Some(Box::new(source)),
                 bytes,
@@ -630,19 +892,25 @@ impl RobloxClient {
         Ok(json)
     }
 
-    /// Get the universe object.
+    /// Remove a user from a Roblox Group. Open Cloud has no endpoint for this, so it goes through
+    /// the legacy groups API and needs the `legacy-group:manage` scope on the API key.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn get_universe(&self, universe_id: UniverseId) -> Result<Universe, RobloxError> {
-        let route = Route::GetUniverse {
-            universe_id: universe_id.0,
+    pub async fn remove_group_member(
+        &self,
+        group_id: GroupId,
+        user_id: UserId,
+    ) -> Result<(), RobloxError> {
+        let route = Route::RemoveGroupMember {
+            group_id: group_id.0,
+            user_id: user_id.0,
         };
 
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::GET)
+            .uri(self.url(&route))
+            .method(Method::DELETE)
             .header(
                 HeaderName::from_static("x-api-key"),
                 HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
@@ -655,46 +923,1567 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
-        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
-            source: Some(Box::new(DeserializeBodyError {
-                source: Some(Box::new(source)),
-                bytes,
-            })),
-            kind: ErrorKind::Deserialize,
-        })?;
+        Ok(())
+    }
 
-        Ok(json)
+    /// Lazily iterate over the pending join requests of a Roblox Group, requesting at most
+    /// `page_size` requests at a time.
+    pub fn stream_group_join_requests<'a>(
+        &'a self,
+        group_id: GroupId,
+        filter: Option<&'a str>,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<GroupJoinRequest, RobloxError>> + 'a {
+        paginate(move |page_token| async move {
+            self.list_group_join_requests(group_id, filter, page_token.as_deref(), page_size)
+                .await
+        })
     }
 
-    /// Get a list of datastores of an universe.
+    /// Get a single page of the pending join requests of a Roblox Group.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn list_datastores(
+    pub async fn list_group_join_requests(
         &self,
-        universe_id: UniverseId,
-    ) -> Result<PaginatedResponse<Datastore>, RobloxError> {
-        let route = Route::ListDatastores {
-            universe_id: universe_id.0,
-            query: "",
-        };
-
+        group_id: GroupId,
+        filter: Option<&str>,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<GroupJoinRequest>, RobloxError> {
+        let route = Route::ListGroupJoinRequests {
+            group_id: group_id.0,
+            filter,
+            page_token,
+            page_size: page_size.clamp(1, MAX_GROUP_JOIN_REQUESTS_PAGE_SIZE),
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json =
+            serde_json::from_slice::<GroupJoinRequests>(&bytes).map_err(|source| RobloxError {
+                source: Some(Box::new(DeserializeBodyError {
+                    source: Some(Box::new(source)),
+                    bytes,
+                })),
+                kind: ErrorKind::Deserialize,
+            })?;
+
+        Ok(PaginatedResponse {
+            data: json.join_requests,
+            next_page_token: json.next_page_token,
+        })
+    }
+
+    /// Accept the request of a user to join a Roblox Group.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn accept_group_join_request(
+        &self,
+        group_id: GroupId,
+        user_id: UserId,
+    ) -> Result<(), RobloxError> {
+        let route = Route::AcceptGroupJoinRequest {
+            group_id: group_id.0,
+            user_id: user_id.0,
+        };
+        self.resolve_group_join_request(route).await
+    }
+
+    /// Decline the request of a user to join a Roblox Group.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn decline_group_join_request(
+        &self,
+        group_id: GroupId,
+        user_id: UserId,
+    ) -> Result<(), RobloxError> {
+        let route = Route::DeclineGroupJoinRequest {
+            group_id: group_id.0,
+            user_id: user_id.0,
+        };
+        self.resolve_group_join_request(route).await
+    }
+
+    async fn resolve_group_join_request(&self, route: Route<'_>) -> Result<(), RobloxError> {
+        let body = b"{}";
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::POST)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from_static(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get the catalog details of an asset.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_asset_details(
+        &self,
+        asset_id: AssetId,
+    ) -> Result<Option<AssetDetails>, RobloxError> {
+        let route = Route::GetAssetDetails {
+            asset_id: asset_id.0,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        // These legacy endpoints answer with a 400 rather than a 404 for ids that do not exist.
+        if parts.status == StatusCode::NOT_FOUND || parts.status == StatusCode::BAD_REQUEST {
+            return Ok(None);
+        }
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json =
+            serde_json::from_slice::<AssetDetails>(&bytes).map_err(|source| RobloxError {
+                source: Some(Box::new(DeserializeBodyError {
+                    source: Some(Box::new(source)),
+                    bytes,
+                })),
+                kind: ErrorKind::Deserialize,
+            })?;
+
+        Ok(Some(json))
+    }
+
+    /// Get a badge.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_badge(&self, badge_id: u64) -> Result<Option<Badge>, RobloxError> {
+        let route = Route::GetBadge { badge_id };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        // These legacy endpoints answer with a 400 rather than a 404 for ids that do not exist.
+        if parts.status == StatusCode::NOT_FOUND || parts.status == StatusCode::BAD_REQUEST {
+            return Ok(None);
+        }
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice::<Badge>(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(Some(json))
+    }
+
+    /// Get the product info of a gamepass.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_game_pass(&self, game_pass_id: u64) -> Result<Option<GamePass>, RobloxError> {
+        let route = Route::GetGamePass { game_pass_id };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        // These legacy endpoints answer with a 400 rather than a 404 for ids that do not exist.
+        if parts.status == StatusCode::NOT_FOUND || parts.status == StatusCode::BAD_REQUEST {
+            return Ok(None);
+        }
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice::<GamePass>(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(Some(json))
+    }
+
+    /// Get the icons of assets, badges or gamepasses. Icons that are still being generated are
+    /// returned without an image url.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_icons(
+        &self,
+        kind: IconKind,
+        ids: &[u64],
+    ) -> Result<Vec<Thumbnail>, RobloxError> {
+        let mut thumbnails = Vec::with_capacity(ids.len());
+        for ids in ids.chunks(MAX_ICONS_PER_REQUEST) {
+            let route = Route::GetIcons { kind, ids };
+
+            let request = Request::new()
+                .uri(self.url(&route))
+                .method(Method::GET)
+                .proxy_uri(self.proxy_url.clone())
+                .body(Full::default())
+                .build()
+                .map_err(|source| RobloxError {
+                    source: Some(Box::new(source)),
+                    kind: ErrorKind::BuildingRequest,
+                })?;
+
+            let (parts, bytes) = self.request(&route, request).await?;
+
+            if !parts.status.is_success() {
+                return Err(RobloxError {
+                    source: None,
+                    kind: ErrorKind::Response {
+                        route: self.url(&route),
+                        error: ApiError::from_response(&parts, &bytes),
+                    },
+                });
+            }
+
+            let json =
+                serde_json::from_slice::<VecWrapper<Thumbnail>>(&bytes).map_err(|source| {
+                    RobloxError {
+                        source: Some(Box::new(DeserializeBodyError {
+                            source: Some(Box::new(source)),
+                            bytes,
+                        })),
+                        kind: ErrorKind::Deserialize,
+                    }
+                })?;
+            thumbnails.extend(json.data);
+        }
+
+        Ok(thumbnails)
+    }
+
+    /// Get a Roblox Group
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_group(&self, group_id: GroupId) -> Result<Option<Group>, RobloxError> {
+        let route = Route::GetGroup {
+            group_id: group_id.0,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if parts.status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice::<Group>(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(Some(json))
+    }
+
+    /// Get the avatar thumbnails of many users. Thumbnails that are still being generated are
+    /// returned without an image url.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_avatar_thumbnails(
+        &self,
+        kind: AvatarThumbnailKind,
+        user_ids: &[UserId],
+        options: ThumbnailOptions,
+    ) -> Result<Vec<Thumbnail>, RobloxError> {
+        let mut thumbnails = Vec::with_capacity(user_ids.len());
+        for user_ids in user_ids.chunks(MAX_THUMBNAILS_PER_REQUEST) {
+            thumbnails.extend(
+                self.get_avatar_thumbnails_chunk(kind, user_ids, options)
+                    .await?,
+            );
+        }
+        Ok(thumbnails)
+    }
+
+    async fn get_avatar_thumbnails_chunk(
+        &self,
+        kind: AvatarThumbnailKind,
+        user_ids: &[UserId],
+        options: ThumbnailOptions,
+    ) -> Result<Vec<Thumbnail>, RobloxError> {
+        let user_ids = user_ids.iter().map(|u| u.0).collect::<Vec<_>>();
+        let route = Route::GetAvatarThumbnails {
+            kind,
+            user_ids: &user_ids,
+            options,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice::<VecWrapper<Thumbnail>>(&bytes).map_err(|source| {
+            RobloxError {
+                source: Some(Box::new(DeserializeBodyError {
+                    source: Some(Box::new(source)),
+                    bytes,
+                })),
+                kind: ErrorKind::Deserialize,
+            }
+        })?;
+
+        Ok(json.data)
+    }
+
+    /// Get a user's thumbnail.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_user_thumbnail(&self, user_id: UserId) -> Result<String, RobloxError> {
+        let route = Route::GetUserThumbail { user_id: user_id.0 };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json =
+            serde_json::from_slice::<Operation<ThumbnailResponse>>(&bytes).map_err(|source| {
+                RobloxError {
+                    source: Some(Box::new(DeserializeBodyError {
+                        source: Some(Box::new(source)),
+                        bytes,
+                    })),
+                    kind: ErrorKind::Deserialize,
+                }
+            })?;
+
+        Ok(json.response.image_uri)
+    }
+
+    /// Get an user from the Open Cloud API using the OAuth workflow.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_oauth_userinfo(&self, authorization: &str) -> Result<OAuthUser, RobloxError> {
+        let route = Route::OAuthUserInfo;
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice::<OAuthUser>(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Get the universe object.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_universe(&self, universe_id: UniverseId) -> Result<Universe, RobloxError> {
+        let route = Route::GetUniverse {
+            universe_id: universe_id.0,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Get a single page of the datastores of an universe.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_datastores(
+        &self,
+        universe_id: UniverseId,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<Datastore>, RobloxError> {
+        let route = Route::ListDatastores {
+            universe_id: universe_id.0,
+            page_token,
+            page_size: page_size.clamp(1, MAX_DATASTORES_PAGE_SIZE),
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json =
+            serde_json::from_slice::<DatastoresResponse>(&bytes).map_err(|source| RobloxError {
+                source: Some(Box::new(DeserializeBodyError {
+                    source: Some(Box::new(source)),
+                    bytes,
+                })),
+                kind: ErrorKind::Deserialize,
+            })?;
+
+        Ok(PaginatedResponse {
+            data: json.datastores,
+            next_page_token: json.next_page_token,
+        })
+    }
+
+    /// Lazily iterate over the datastores of an universe, requesting at most `page_size`
+    /// datastores at a time.
+    pub fn stream_datastores(
+        &self,
+        universe_id: UniverseId,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Datastore, RobloxError>> + '_ {
+        paginate(move |page_token| async move {
+            self.list_datastores(universe_id, page_token.as_deref(), page_size)
+                .await
+        })
+    }
+
+    /// Lists the entries of a datastore. Supports filtering based on entry IDs.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_datastore_entries(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        page_token: Option<&str>,
+        page_size: u32,
+        filter: Option<&str>,
+    ) -> Result<PaginatedResponse<PartialDatastoreEntry>, RobloxError> {
+        let route = Route::ListDatastoreEntries {
+            universe_id: universe_id.0,
+            datastore_id,
+            page_token,
+            page_size: page_size.clamp(1, MAX_DATASTORE_ENTRIES_PAGE_SIZE),
+            filter,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json =
+            serde_json::from_slice::<DatastoreEntriesResponse>(&bytes).map_err(|source| {
+                RobloxError {
+                    source: Some(Box::new(DeserializeBodyError {
+                        source: Some(Box::new(source)),
+                        bytes,
+                    })),
+                    kind: ErrorKind::Deserialize,
+                }
+            })?;
+
+        Ok(PaginatedResponse {
+            data: json.datastore_entries,
+            next_page_token: json.next_page_token,
+        })
+    }
+
+    /// Lazily iterate over the entries of a datastore, requesting at most `page_size` entries at
+    /// a time. Supports filtering based on entry IDs.
+    pub fn stream_datastore_entries<'a>(
+        &'a self,
+        universe_id: UniverseId,
+        datastore_id: &'a str,
+        page_size: u32,
+        filter: Option<&'a str>,
+    ) -> impl Stream<Item = Result<PartialDatastoreEntry, RobloxError>> + 'a {
+        paginate(move |page_token| async move {
+            self.list_datastore_entries(
+                universe_id,
+                datastore_id,
+                page_token.as_deref(),
+                page_size,
+                filter,
+            )
+            .await
+        })
+    }
+
+    /// Get a datastore entry.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        entry_id: &str,
+        revision_id: Option<&str>,
+    ) -> Result<DatastoreEntry, RobloxError> {
+        let route = Route::GetDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            entry_id,
+            revision_id,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Update a datastore entry.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn update_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        entry_id: &str,
+        args: UpdateDatastoreEntryArgs,
+    ) -> Result<DatastoreEntry, RobloxError> {
+        let route = Route::UpdateDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            entry_id,
+        };
+
+        let users = args
+            .users
+            .into_iter()
+            .map(UserPath::new)
+            .collect::<Vec<_>>();
+        let json =
+            serde_json::json!({"value": args.value, "users": users, "attributes": args.attributes});
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::PATCH)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Delete a datastore entry.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn delete_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        entry_id: &str,
+    ) -> Result<(), RobloxError> {
+        let route = Route::DeleteDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            entry_id,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::DELETE)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Creates a datastore entry.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn create_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        entry_id: &str,
+        args: UpdateDatastoreEntryArgs,
+    ) -> Result<DatastoreEntry, RobloxError> {
+        let route = Route::CreateDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            entry_id,
+        };
+
+        let users = args
+            .users
+            .into_iter()
+            .map(UserPath::new)
+            .collect::<Vec<_>>();
+        let json =
+            serde_json::json!({"value": args.value, "users": users, "attributes": args.attributes});
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::POST)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Lists the revisions of an entry.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_datastore_entry_revisions(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        entry_id: &str,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<PartialDatastoreEntry>, RobloxError> {
+        let route = Route::ListDatastoreEntryRevisions {
+            universe_id: universe_id.0,
+            datastore_id,
+            entry_id,
+            page_token,
+            page_size: page_size.clamp(1, MAX_DATASTORE_ENTRIES_PAGE_SIZE),
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json =
+            serde_json::from_slice::<DatastoreEntriesResponse>(&bytes).map_err(|source| {
+                RobloxError {
+                    source: Some(Box::new(DeserializeBodyError {
+                        source: Some(Box::new(source)),
+                        bytes,
+                    })),
+                    kind: ErrorKind::Deserialize,
+                }
+            })?;
+
+        Ok(PaginatedResponse {
+            data: json.datastore_entries,
+            next_page_token: json.next_page_token,
+        })
+    }
+
+    /// Lazily iterate over the revisions of an entry, requesting at most `page_size` revisions
+    /// at a time.
+    pub fn stream_datastore_entry_revisions<'a>(
+        &'a self,
+        universe_id: UniverseId,
+        datastore_id: &'a str,
+        entry_id: &'a str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<PartialDatastoreEntry, RobloxError>> + 'a {
+        paginate(move |page_token| async move {
+            self.list_datastore_entry_revisions(
+                universe_id,
+                datastore_id,
+                entry_id,
+                page_token.as_deref(),
+                page_size,
+            )
+            .await
+        })
+    }
+
+    /// Lazily iterate over the entries of an ordered datastore, requesting at most `page_size`
+    /// entries at a time.
+    pub fn stream_ordered_datastore_entries<'a>(
+        &'a self,
+        universe_id: UniverseId,
+        datastore_id: &'a str,
+        scope: &'a str,
+        query: OrderedDatastoreQuery,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<OrderedDatastoreEntry, RobloxError>> + 'a {
+        paginate(move |page_token| async move {
+            self.list_ordered_datastore_entries(
+                universe_id,
+                datastore_id,
+                scope,
+                query,
+                page_token.as_deref(),
+                page_size,
+            )
+            .await
+        })
+    }
+
+    /// Get a single page of the entries of an ordered datastore, sorted by their values.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_ordered_datastore_entries(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        scope: &str,
+        query: OrderedDatastoreQuery,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<OrderedDatastoreEntry>, RobloxError> {
+        let route = Route::ListOrderedDatastoreEntries {
+            universe_id: universe_id.0,
+            datastore_id,
+            scope,
+            descending: query.order == OrderedDatastoreOrder::Descending,
+            min_value: query.min_value,
+            max_value: query.max_value,
+            page_token,
+            page_size: page_size.clamp(1, MAX_ORDERED_DATASTORE_ENTRIES_PAGE_SIZE),
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice::<OrderedDatastoreEntriesResponse>(&bytes).map_err(
+            |source| RobloxError {
+                source: Some(Box::new(DeserializeBodyError {
+                    source: Some(Box::new(source)),
+                    bytes,
+                })),
+                kind: ErrorKind::Deserialize,
+            },
+        )?;
+
+        Ok(PaginatedResponse {
+            data: json.entries,
+            next_page_token: json.next_page_token,
+        })
+    }
+
+    /// Get an entry of an ordered datastore. Returns [`None`] if the entry does not exist.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn get_ordered_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+    ) -> Result<Option<OrderedDatastoreEntry>, RobloxError> {
+        let route = Route::GetOrderedDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            scope,
+            entry_id,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::GET)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if parts.status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(Some(json))
+    }
+
+    /// Create an entry in an ordered datastore.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn create_ordered_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+        value: i64,
+    ) -> Result<OrderedDatastoreEntry, RobloxError> {
+        let route = Route::CreateOrderedDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            scope,
+            entry_id,
+        };
+
+        let json = serde_json::json!({"value": value});
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::POST)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Set the value of an entry in an ordered datastore. If `allow_missing` is set, the entry is
+    /// created when it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn update_ordered_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+        value: i64,
+        allow_missing: bool,
+    ) -> Result<OrderedDatastoreEntry, RobloxError> {
+        let route = Route::UpdateOrderedDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            scope,
+            entry_id,
+            allow_missing,
+        };
+
+        let json = serde_json::json!({"value": value});
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::PATCH)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Atomically add `amount` to the value of an entry in an ordered datastore. The entry is
+    /// created if it does not exist. `amount` may be negative.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn increment_ordered_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+        amount: i64,
+    ) -> Result<OrderedDatastoreEntry, RobloxError> {
+        let route = Route::IncrementOrderedDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            scope,
+            entry_id,
+        };
+
+        let json = serde_json::json!({"amount": amount});
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::POST)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Delete an entry of an ordered datastore.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn delete_ordered_datastore_entry(
+        &self,
+        universe_id: UniverseId,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+    ) -> Result<(), RobloxError> {
+        let route = Route::DeleteOrderedDatastoreEntry {
+            universe_id: universe_id.0,
+            datastore_id,
+            scope,
+            entry_id,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::DELETE)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Lazily iterate over the items of a memory store sorted map, requesting at most `page_size`
+    /// items at a time.
+    pub fn stream_memory_store_sorted_map_items<'a>(
+        &'a self,
+        universe_id: UniverseId,
+        map_id: &'a str,
+        descending: bool,
+        filter: Option<&'a str>,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<MemoryStoreSortedMapItem, RobloxError>> + 'a {
+        paginate(move |page_token| async move {
+            self.list_memory_store_sorted_map_items(
+                universe_id,
+                map_id,
+                descending,
+                filter,
+                page_token.as_deref(),
+                page_size,
+            )
+            .await
+        })
+    }
+
+    /// Get a single page of the items of a memory store sorted map, sorted by their sort keys.
+    /// `filter` follows the Open Cloud filter syntax, such as `id > "abc"`.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn list_memory_store_sorted_map_items(
+        &self,
+        universe_id: UniverseId,
+        map_id: &str,
+        descending: bool,
+        filter: Option<&str>,
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<PaginatedResponse<MemoryStoreSortedMapItem>, RobloxError> {
+        let route = Route::ListMemoryStoreSortedMapItems {
+            universe_id: universe_id.0,
+            map_id,
+            descending,
+            filter,
+            page_token,
+            page_size: page_size.clamp(1, MAX_MEMORY_STORE_SORTED_MAP_ITEMS_PAGE_SIZE),
+        };
+
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -708,57 +2497,53 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
-        let json =
-            serde_json::from_slice::<DatastoresResponse>(&bytes).map_err(|source| RobloxError {
+        let json = serde_json::from_slice::<MemoryStoreSortedMapItemsResponse>(&bytes).map_err(
+            |source| RobloxError {
                 source: Some(Box::new(DeserializeBodyError {
                     source: Some(Box::new(source)),
                     bytes,
                 })),
                 kind: ErrorKind::Deserialize,
-            })?;
+            },
+        )?;
 
         Ok(PaginatedResponse {
-            data: json.datastores,
+            data: json.items,
             next_page_token: json.next_page_token,
         })
     }
 
-    /// Lists the entries of a datastore. Supports filtering based on entry IDs.
+    /// Get an item of a memory store sorted map. Returns [`None`] if the item does not exist.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn list_datastore_entries(
+    pub async fn get_memory_store_sorted_map_item(
         &self,
         universe_id: UniverseId,
-        datastore_id: &str,
-        page_token: &str,
-        page_size: u32,
-        filter: Option<&str>,
-    ) -> Result<PaginatedResponse<PartialDatastoreEntry>, RobloxError> {
-        let route = Route::ListDatastoreEntries {
+        map_id: &str,
+        item_id: &str,
+    ) -> Result<Option<MemoryStoreSortedMapItem>, RobloxError> {
+        let route = Route::GetMemoryStoreSortedMapItem {
             universe_id: universe_id.0,
-            datastore_id,
-            page_token,
-            page_size,
-            filter,
+            map_id,
+            item_id,
         };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -772,79 +2557,261 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if parts.status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
-        let json =
-            serde_json::from_slice::<DatastoreEntriesResponse>(&bytes).map_err(|source| {
-                RobloxError {
-                    source: Some(Box::new(DeserializeBodyError {
-                        source: Some(Box::new(source)),
-                        bytes,
-                    })),
-                    kind: ErrorKind::Deserialize,
-                }
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(Some(json))
+    }
+
+    /// Create an item in a memory store sorted map.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn create_memory_store_sorted_map_item(
+        &self,
+        universe_id: UniverseId,
+        map_id: &str,
+        item_id: &str,
+        args: &MemoryStoreSortedMapItemArgs,
+    ) -> Result<MemoryStoreSortedMapItem, RobloxError> {
+        let route = Route::CreateMemoryStoreSortedMapItem {
+            universe_id: universe_id.0,
+            map_id,
+            item_id,
+        };
+
+        let body = serde_json::to_vec(&args.to_json()).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::POST)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Update an item of a memory store sorted map. If `allow_missing` is set, the item is
+    /// created when it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn update_memory_store_sorted_map_item(
+        &self,
+        universe_id: UniverseId,
+        map_id: &str,
+        item_id: &str,
+        args: &MemoryStoreSortedMapItemArgs,
+        allow_missing: bool,
+    ) -> Result<MemoryStoreSortedMapItem, RobloxError> {
+        let route = Route::UpdateMemoryStoreSortedMapItem {
+            universe_id: universe_id.0,
+            map_id,
+            item_id,
+            allow_missing,
+        };
+
+        let body = serde_json::to_vec(&args.to_json()).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::PATCH)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Delete an item of a memory store sorted map.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn delete_memory_store_sorted_map_item(
+        &self,
+        universe_id: UniverseId,
+        map_id: &str,
+        item_id: &str,
+    ) -> Result<(), RobloxError> {
+        let route = Route::DeleteMemoryStoreSortedMapItem {
+            universe_id: universe_id.0,
+            map_id,
+            item_id,
+        };
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::DELETE)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::default())
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
             })?;
 
-        Ok(PaginatedResponse {
-            data: json.datastore_entries,
-            next_page_token: json.next_page_token,
-        })
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        Ok(())
     }
 
-    /// Get a datastore entry.
+    /// Add an item to a memory store queue. Items with a higher `priority` are read first.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn get_datastore_entry(
+    pub async fn enqueue_memory_store_queue_item(
         &self,
         universe_id: UniverseId,
-        datastore_id: &str,
-        entry_id: &str,
-        revision_id: Option<&str>,
-    ) -> Result<DatastoreEntry, RobloxError> {
-        let route = Route::GetDatastoreEntry {
+        queue_id: &str,
+        data: Value,
+        priority: Option<f64>,
+        ttl: Duration,
+    ) -> Result<MemoryStoreQueueItem, RobloxError> {
+        let route = Route::EnqueueMemoryStoreQueueItem {
             universe_id: universe_id.0,
-            datastore_id,
-            entry_id,
-            revision_id,
+            queue_id,
         };
 
+        let mut json = serde_json::json!({ "data": data, "ttl": format!("{}s", ttl.as_secs()) });
+        if let Some(priority) = priority {
+            json["priority"] = serde_json::json!(priority);
+        }
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::GET)
+            .uri(self.url(&route))
+            .method(Method::POST)
             .header(
                 HeaderName::from_static("x-api-key"),
                 HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
             )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
             .proxy_uri(self.proxy_url.clone())
-            .body(Full::default())
+            .body(Full::new(Bytes::from(body)))
             .build()
             .map_err(|source| RobloxError {
                 source: Some(Box::new(source)),
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -860,56 +2827,53 @@ impl RobloxClient {
         Ok(json)
     }
 
-    /// Update a datastore entry.
+    /// Read up to `count` items from the front of a memory store queue. The items are hidden from
+    /// other readers for `invisibility_window` and should be removed with
+    /// [`discard_memory_store_queue_items`](Self::discard_memory_store_queue_items) once handled.
+    /// If `all_or_nothing` is set, no items are returned unless `count` of them are available.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn update_datastore_entry(
+    pub async fn read_memory_store_queue_items(
         &self,
         universe_id: UniverseId,
-        datastore_id: &str,
-        entry_id: &str,
-        args: UpdateDatastoreEntryArgs,
-    ) -> Result<DatastoreEntry, RobloxError> {
-        let route = Route::UpdateDatastoreEntry {
+        queue_id: &str,
+        count: u32,
+        all_or_nothing: bool,
+        invisibility_window: Duration,
+    ) -> Result<MemoryStoreQueueRead, RobloxError> {
+        let route = Route::ReadMemoryStoreQueueItems {
             universe_id: universe_id.0,
-            datastore_id,
-            entry_id,
+            queue_id,
+            count: count.clamp(1, MAX_MEMORY_STORE_QUEUE_READ_COUNT),
+            all_or_nothing,
+            invisibility_window_secs: invisibility_window.as_secs(),
         };
 
-        let json = serde_json::json!({"value": args.value, "users": args.users, "attributes": args.attributes});
-        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
-            source: Some(Box::new(source)),
-            kind: ErrorKind::BuildingRequest,
-        })?;
-
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::PATCH)
+            .uri(self.url(&route))
+            .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
                 HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
             )
-            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
-            .header(CONTENT_LENGTH, body.len())
             .proxy_uri(self.proxy_url.clone())
-            .body(Full::new(Bytes::from(body)))
+            .body(Full::default())
             .build()
             .map_err(|source| RobloxError {
                 source: Some(Box::new(source)),
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -925,47 +2889,55 @@ impl RobloxClient {
         Ok(json)
     }
 
-    /// Delete a datastore entry.
+    /// Remove the items returned by a read of a memory store queue.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn delete_datastore_entry(
+    pub async fn discard_memory_store_queue_items(
         &self,
         universe_id: UniverseId,
-        datastore_id: &str,
-        entry_id: &str,
+        queue_id: &str,
+        read_id: &str,
     ) -> Result<(), RobloxError> {
-        let route = Route::DeleteDatastoreEntry {
+        let route = Route::DiscardMemoryStoreQueueItems {
             universe_id: universe_id.0,
-            datastore_id,
-            entry_id,
+            queue_id,
         };
 
+        let body =
+            serde_json::to_vec(&serde_json::json!({ "readId": read_id })).map_err(|source| {
+                RobloxError {
+                    source: Some(Box::new(source)),
+                    kind: ErrorKind::BuildingRequest,
+                }
+            })?;
+
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::DELETE)
+            .uri(self.url(&route))
+            .method(Method::POST)
             .header(
                 HeaderName::from_static("x-api-key"),
                 HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
             )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
             .proxy_uri(self.proxy_url.clone())
-            .body(Full::default())
+            .body(Full::new(Bytes::from(body)))
             .build()
             .map_err(|source| RobloxError {
                 source: Some(Box::new(source)),
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -973,56 +2945,47 @@ impl RobloxClient {
         Ok(())
     }
 
-    /// Creates a datastore entry.
+    /// Get the restrictions placed on a user in a universe, or in a place of the universe if
+    /// `place_id` is set.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn create_datastore_entry(
+    pub async fn get_user_restriction(
         &self,
         universe_id: UniverseId,
-        datastore_id: &str,
-        entry_id: &str,
-        args: UpdateDatastoreEntryArgs,
-    ) -> Result<DatastoreEntry, RobloxError> {
-        let route = Route::CreateDatastoreEntry {
+        place_id: Option<PlaceId>,
+        user_id: UserId,
+    ) -> Result<UserRestriction, RobloxError> {
+        let route = Route::GetUserRestriction {
             universe_id: universe_id.0,
-            datastore_id,
-            entry_id,
+            place_id: place_id.map(|p| p.0),
+            user_id: user_id.0,
         };
 
-        let json = serde_json::json!({"value": args.value, "users": args.users, "attributes": args.attributes});
-        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
-            source: Some(Box::new(source)),
-            kind: ErrorKind::BuildingRequest,
-        })?;
-
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::POST)
+            .uri(self.url(&route))
+            .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
                 HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
             )
-            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
-            .header(CONTENT_LENGTH, body.len())
             .proxy_uri(self.proxy_url.clone())
-            .body(Full::new(Bytes::from(body)))
+            .body(Full::default())
             .build()
             .map_err(|source| RobloxError {
                 source: Some(Box::new(source)),
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -1038,29 +3001,41 @@ impl RobloxClient {
         Ok(json)
     }
 
-    /// Lists the revisions of an entry.
+    /// Lazily iterate over the user restrictions of a universe or a place, requesting at most
+    /// `page_size` restrictions at a time.
+    pub fn stream_user_restrictions(
+        &self,
+        universe_id: UniverseId,
+        place_id: Option<PlaceId>,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<UserRestriction, RobloxError>> + '_ {
+        paginate(move |page_token| async move {
+            self.list_user_restrictions(universe_id, place_id, page_token.as_deref(), page_size)
+                .await
+        })
+    }
+
+    /// Get a single page of the user restrictions of a universe or a place.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn list_datastore_entry_revisions(
+    pub async fn list_user_restrictions(
         &self,
         universe_id: UniverseId,
-        datastore_id: &str,
-        entry_id: &str,
-        page_token: &str,
+        place_id: Option<PlaceId>,
+        page_token: Option<&str>,
         page_size: u32,
-    ) -> Result<PaginatedResponse<PartialDatastoreEntry>, RobloxError> {
-        let route = Route::ListDatastoreEntryRevisions {
+    ) -> Result<PaginatedResponse<UserRestriction>, RobloxError> {
+        let route = Route::ListUserRestrictions {
             universe_id: universe_id.0,
-            datastore_id,
-            entry_id,
+            place_id: place_id.map(|p| p.0),
             page_token,
-            page_size,
+            page_size: page_size.clamp(1, MAX_USER_RESTRICTIONS_PAGE_SIZE),
         };
 
         let request = Request::new()
-            .uri(route.to_string())
+            .uri(self.url(&route))
             .method(Method::GET)
             .header(
                 HeaderName::from_static("x-api-key"),
@@ -1074,21 +3049,20 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
 
         let json =
-            serde_json::from_slice::<DatastoreEntriesResponse>(&bytes).map_err(|source| {
+            serde_json::from_slice::<UserRestrictionsResponse>(&bytes).map_err(|source| {
                 RobloxError {
                     source: Some(Box::new(DeserializeBodyError {
                         source: Some(Box::new(source)),
@@ -1099,11 +3073,120 @@ impl RobloxClient {
             })?;
 
         Ok(PaginatedResponse {
-            data: json.datastore_entries,
+            data: json.user_restrictions,
             next_page_token: json.next_page_token,
         })
     }
 
+    /// Replace the game join restriction of a user in a universe or a place.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn update_user_restriction(
+        &self,
+        universe_id: UniverseId,
+        place_id: Option<PlaceId>,
+        user_id: UserId,
+        restriction: &GameJoinRestriction,
+    ) -> Result<UserRestriction, RobloxError> {
+        let route = Route::UpdateUserRestriction {
+            universe_id: universe_id.0,
+            place_id: place_id.map(|p| p.0),
+            user_id: user_id.0,
+        };
+
+        let json = serde_json::json!({"gameJoinRestriction": restriction});
+        let body = serde_json::to_vec(&json).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::PATCH)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
+            )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::new(Bytes::from(body)))
+            .build()
+            .map_err(|source| RobloxError {
+                source: Some(Box::new(source)),
+                kind: ErrorKind::BuildingRequest,
+            })?;
+
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    /// Ban a user from joining a universe or a place. The ban is permanent if `duration` is
+    /// [`None`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn ban_user(
+        &self,
+        universe_id: UniverseId,
+        place_id: Option<PlaceId>,
+        user_id: UserId,
+        reason: &str,
+        duration: Option<Duration>,
+    ) -> Result<UserRestriction, RobloxError> {
+        let restriction = GameJoinRestriction {
+            active: true,
+            duration: duration.map(|d| format!("{}s", d.as_secs())),
+            private_reason: reason.to_string(),
+            display_reason: reason.to_string(),
+            ..GameJoinRestriction::default()
+        };
+        self.update_user_restriction(universe_id, place_id, user_id, &restriction)
+            .await
+    }
+
+    /// Lift the ban of a user in a universe or a place.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub async fn unban_user(
+        &self,
+        universe_id: UniverseId,
+        place_id: Option<PlaceId>,
+        user_id: UserId,
+    ) -> Result<UserRestriction, RobloxError> {
+        self.update_user_restriction(
+            universe_id,
+            place_id,
+            user_id,
+            &GameJoinRestriction::default(),
+        )
+        .await
+    }
+
     /// Publishes a message to a topic of an universe.
     ///
     /// # Errors
@@ -1126,12 +3209,14 @@ impl RobloxClient {
         })?;
 
         let request = Request::new()
-            .uri(route.to_string())
-            .method(Method::GET)
+            .uri(self.url(&route))
+            .method(Method::POST)
             .header(
                 HeaderName::from_static("x-api-key"),
                 HeaderValue::from_str(&self.open_cloud_auth).unwrap(),
             )
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
             .proxy_uri(self.proxy_url.clone())
             .body(Full::from(Bytes::from(body)))
             .build()
@@ -1140,15 +3225,14 @@ impl RobloxClient {
                 kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, bytes) = self.request(request).await?;
+        let (parts, bytes) = self.request(&route, request).await?;
 
         if !parts.status.is_success() {
             return Err(RobloxError {
                 source: None,
                 kind: ErrorKind::Response {
-                    route: route.to_string(),
-                    status: parts.status,
-                    bytes,
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
                 },
             });
         }
@@ -1156,25 +3240,112 @@ impl RobloxClient {
         Ok(())
     }
 
-    /// Make a request to the Roblox API.
+    /// Get the name, status and scopes of an Open Cloud API key.
     ///
     /// # Errors
     ///
     /// See [`RobloxError`] for details.
-    pub async fn request(
-        &self,
-        request: HyperRequest<Full<Bytes>>,
-    ) -> Result<(Parts, Vec<u8>), RobloxError> {
-        let res = self
-            .client
-            .request(request)
-            .await
+    pub async fn introspect_api_key(&self, api_key: &str) -> Result<ApiKeyInfo, RobloxError> {
+        let route = Route::IntrospectApiKey;
+
+        let body = serde_json::json!({ "apiKey": api_key });
+        let body = serde_json::to_vec(&body).map_err(|source| RobloxError {
+            source: Some(Box::new(source)),
+            kind: ErrorKind::BuildingRequest,
+        })?;
+
+        let request = Request::new()
+            .uri(self.url(&route))
+            .method(Method::POST)
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(CONTENT_LENGTH, body.len())
+            .proxy_uri(self.proxy_url.clone())
+            .body(Full::from(Bytes::from(body)))
+            .build()
             .map_err(|source| RobloxError {
                 source: Some(Box::new(source)),
-                kind: ErrorKind::Sending,
+                kind: ErrorKind::BuildingRequest,
             })?;
 
-        let (parts, body) = res.into_parts();
+        let (parts, bytes) = self.request(&route, request).await?;
+
+        if !parts.status.is_success() {
+            return Err(RobloxError {
+                source: None,
+                kind: ErrorKind::Response {
+                    route: self.url(&route),
+                    error: ApiError::from_response(&parts, &bytes),
+                },
+            });
+        }
+
+        let json = serde_json::from_slice(&bytes).map_err(|source| RobloxError {
+            source: Some(Box::new(DeserializeBodyError {
+                source: Some(Box::new(source)),
+                bytes,
+            })),
+            kind: ErrorKind::Deserialize,
+        })?;
+
+        Ok(json)
+    }
+
+    fn url(&self, route: &Route<'_>) -> String {
+        route.url(&self.base_urls)
+    }
+
+    /// Make a request to the Roblox API, recording it in the metrics of the client and in a span.
+    ///
+    /// # Errors
+    ///
+    /// See [`RobloxError`] for details.
+    pub(crate) async fn request(
+        &self,
+        route: &Route<'_>,
+        request: HyperRequest<Full<Bytes>>,
+    ) -> Result<(Parts, Vec<u8>), RobloxError> {
+        let kind = route.kind();
+        // The span is named after the kind of route since the url contains ids.
+        let span = tracing::debug_span!(
+            "roblox_request",
+            otel.name = kind,
+            route = kind,
+            method = %request.method(),
+            status = field::Empty,
+        );
+
+        async move {
+            let (parts, bytes) = self.send(kind, request).await?;
+            tracing::Span::current().record("status", parts.status.as_u16());
+            Ok((parts, bytes))
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn send(
+        &self,
+        kind: &str,
+        request: HyperRequest<Full<Bytes>>,
+    ) -> Result<(Parts, Vec<u8>), RobloxError> {
+        let start = Instant::now();
+        let res = self.client.request(request).await;
+        let res = match res {
+            Ok(res) => res,
+            Err(source) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.observe(kind, None, start.elapsed());
+                }
+                return Err(RobloxError {
+                    source: Some(Box::new(source)),
+                    kind: ErrorKind::Sending,
+                });
+            }
+        };
+
+        let (mut parts, body) = res.into_parts();
+        // Read back by `ApiError::from_response` to tell which key a failed request used.
+        parts.extensions.insert(self.api_key_source);
         let bytes = body
             .collect()
             .await
@@ -1184,6 +3355,34 @@ impl RobloxClient {
             })?
             .to_bytes();
 
+        if let Some(metrics) = &self.metrics {
+            metrics.observe(kind, Some(parts.status), start.elapsed());
+        }
+
         Ok((parts, bytes.into()))
     }
 }
+
+impl MemoryStoreSortedMapItemArgs {
+    fn to_json(&self) -> Value {
+        let mut json = serde_json::json!({
+            "value": self.value,
+            "ttl": format!("{}s", self.ttl.as_secs()),
+        });
+        match &self.sort_key {
+            Some(SortKey::Numeric(key)) => json["numericSortKey"] = serde_json::json!(key),
+            Some(SortKey::String(key)) => json["stringSortKey"] = serde_json::json!(key),
+            None => {}
+        }
+        json
+    }
+}
+
+/// Splits `items` into [`fan_out`]'s owned input, one [`Vec`] of at most `size` items per request.
+fn owned_chunks<T>(items: impl Iterator<Item = T>, size: usize) -> Vec<Vec<T>> {
+    items
+        .chunks(size)
+        .into_iter()
+        .map(Iterator::collect)
+        .collect()
+}
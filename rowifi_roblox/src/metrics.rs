@@ -0,0 +1,110 @@
+use hyper::StatusCode;
+use prometheus::{exponential_buckets, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+use std::time::Duration;
+
+/// Request metrics of a [`RobloxClient`](crate::RobloxClient), labelled by the kind of route so
+/// that no ids end up in the label values.
+#[derive(Clone, Debug)]
+pub struct RobloxMetrics {
+    requests: IntCounterVec,
+    latency: HistogramVec,
+}
+
+impl RobloxMetrics {
+    /// Create the metrics and register them in `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if metrics with the same names are already registered.
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let requests = IntCounterVec::new(
+            Opts::new(
+                "roblox_requests_total",
+                "Requests made to Roblox, by route and status class",
+            ),
+            &["route", "status"],
+        )?;
+        // 25ms up to ~13s
+        let latency = HistogramVec::new(
+            HistogramOpts::new(
+                "roblox_request_duration_seconds",
+                "Time taken by a request to Roblox",
+            )
+            .buckets(exponential_buckets(0.025, 2.0, 10)?),
+            &["route"],
+        )?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(latency.clone()))?;
+
+        Ok(Self { requests, latency })
+    }
+
+    pub(crate) fn observe(&self, route: &str, status: Option<StatusCode>, elapsed: Duration) {
+        self.requests
+            .with_label_values(&[route, status_class(status)])
+            .inc();
+        self.latency
+            .with_label_values(&[route])
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+/// `None` is for requests that never got a response.
+fn status_class(status: Option<StatusCode>) -> &'static str {
+    match status.map(|s| s.as_u16()) {
+        Some(100..=199) => "1xx",
+        Some(200..=299) => "2xx",
+        Some(300..=399) => "3xx",
+        Some(400..=499) => "4xx",
+        Some(_) => "5xx",
+        None => "error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe() {
+        let registry = Registry::new();
+        let metrics = RobloxMetrics::new(&registry).unwrap();
+        metrics.observe("GetUser", Some(StatusCode::OK), Duration::from_millis(30));
+        metrics.observe(
+            "GetUser",
+            Some(StatusCode::NOT_FOUND),
+            Duration::from_millis(30),
+        );
+        metrics.observe("GetUser", None, Duration::from_millis(30));
+
+        assert_eq!(
+            metrics
+                .requests
+                .with_label_values(&["GetUser", "2xx"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .requests
+                .with_label_values(&["GetUser", "error"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .latency
+                .with_label_values(&["GetUser"])
+                .get_sample_count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_register_twice() {
+        let registry = Registry::new();
+        RobloxMetrics::new(&registry).unwrap();
+        assert!(RobloxMetrics::new(&registry).is_err());
+    }
+}
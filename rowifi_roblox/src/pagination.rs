@@ -0,0 +1,46 @@
+use futures_util::{
+    stream::{self, Stream},
+    TryStreamExt,
+};
+use std::future::Future;
+
+use crate::{error::RobloxError, PaginatedResponse};
+
+enum PageState {
+    Start,
+    Next(String),
+    Done,
+}
+
+/// Lazily follows the `nextPageToken` of an Open Cloud list endpoint, yielding the items of
+/// every page. `fetch` is called with the token of the page to request, which is `None` for the
+/// first one. The stream ends on the first page without a token or on the first error.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    mut fetch: F,
+) -> impl Stream<Item = Result<T, RobloxError>> + 'a
+where
+    T: 'a,
+    F: FnMut(Option<String>) -> Fut + 'a,
+    Fut: Future<Output = Result<PaginatedResponse<T>, RobloxError>> + 'a,
+{
+    stream::try_unfold(PageState::Start, move |state| {
+        let page = match state {
+            PageState::Start => Some(fetch(None)),
+            PageState::Next(page_token) => Some(fetch(Some(page_token))),
+            PageState::Done => None,
+        };
+        async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let page = page.await?;
+            let state = match page.next_page_token {
+                Some(page_token) if !page_token.is_empty() => PageState::Next(page_token),
+                _ => PageState::Done,
+            };
+            Ok(Some((page.data, state)))
+        }
+    })
+    .map_ok(|data| stream::iter(data.into_iter().map(Ok)))
+    .try_flatten()
+}
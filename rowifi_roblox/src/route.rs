@@ -1,28 +1,131 @@
+use rowifi_roblox_models::{
+    asset::IconKind,
+    thumbnail::{AvatarThumbnailKind, ThumbnailOptions},
+};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
+/// The hosts that the Roblox APIs are served from. Overriding these allows pointing the client
+/// at a fake Roblox during tests.
+#[derive(Clone, Debug)]
+pub struct BaseUrls {
+    /// The host of the Open Cloud and OAuth APIs.
+    pub apis: String,
+    /// The host of the legacy badges API.
+    pub badges: String,
+    /// The host of the legacy economy API.
+    pub economy: String,
+    /// The host of the legacy groups API.
+    pub groups: String,
+    /// The host of the thumbnails API.
+    pub thumbnails: String,
+    /// The host of the legacy users API.
+    pub users: String,
+}
+
+enum Host {
+    Apis,
+    Badges,
+    Economy,
+    Groups,
+    Thumbnails,
+    Users,
+}
+
 pub enum Route<'a> {
+    AcceptGroupJoinRequest {
+        group_id: u64,
+        user_id: u64,
+    },
     CreateDatastoreEntry {
         universe_id: u64,
         datastore_id: &'a str,
         entry_id: &'a str,
     },
+    CreateMemoryStoreSortedMapItem {
+        universe_id: u64,
+        map_id: &'a str,
+        item_id: &'a str,
+    },
+    CreateOrderedDatastoreEntry {
+        universe_id: u64,
+        datastore_id: &'a str,
+        scope: &'a str,
+        entry_id: &'a str,
+    },
+    DeclineGroupJoinRequest {
+        group_id: u64,
+        user_id: u64,
+    },
     DeleteDatastoreEntry {
         universe_id: u64,
         datastore_id: &'a str,
         entry_id: &'a str,
     },
+    DeleteMemoryStoreSortedMapItem {
+        universe_id: u64,
+        map_id: &'a str,
+        item_id: &'a str,
+    },
     GetDatastoreEntry {
         universe_id: u64,
         datastore_id: &'a str,
         entry_id: &'a str,
         revision_id: Option<&'a str>,
     },
+    DeleteOrderedDatastoreEntry {
+        universe_id: u64,
+        datastore_id: &'a str,
+        scope: &'a str,
+        entry_id: &'a str,
+    },
+    DiscardMemoryStoreQueueItems {
+        universe_id: u64,
+        queue_id: &'a str,
+    },
+    EnqueueMemoryStoreQueueItem {
+        universe_id: u64,
+        queue_id: &'a str,
+    },
+    GetAssetDetails {
+        asset_id: u64,
+    },
+    GetAvatarThumbnails {
+        kind: AvatarThumbnailKind,
+        user_ids: &'a [u64],
+        options: ThumbnailOptions,
+    },
+    GetBadge {
+        badge_id: u64,
+    },
+    GetGamePass {
+        game_pass_id: u64,
+    },
     GetGroup {
         group_id: u64,
     },
+    GetIcons {
+        kind: IconKind,
+        ids: &'a [u64],
+    },
+    GetMemoryStoreSortedMapItem {
+        universe_id: u64,
+        map_id: &'a str,
+        item_id: &'a str,
+    },
+    GetOrderedDatastoreEntry {
+        universe_id: u64,
+        datastore_id: &'a str,
+        scope: &'a str,
+        entry_id: &'a str,
+    },
     GetUniverse {
         universe_id: u64,
     },
+    GetUserRestriction {
+        universe_id: u64,
+        place_id: Option<u64>,
+        user_id: u64,
+    },
     GetUserGroupRoles {
         user_id: u64,
     },
@@ -36,12 +139,20 @@ pub enum Route<'a> {
     },
     ListDatastores {
         universe_id: u64,
-        query: &'a str,
+        page_token: Option<&'a str>,
+        page_size: u32,
     },
+    IncrementOrderedDatastoreEntry {
+        universe_id: u64,
+        datastore_id: &'a str,
+        scope: &'a str,
+        entry_id: &'a str,
+    },
+    IntrospectApiKey,
     ListDatastoreEntries {
         universe_id: u64,
         datastore_id: &'a str,
-        page_token: &'a str,
+        page_token: Option<&'a str>,
         page_size: u32,
         filter: Option<&'a str>,
     },
@@ -49,61 +160,369 @@ pub enum Route<'a> {
         universe_id: u64,
         datastore_id: &'a str,
         entry_id: &'a str,
-        page_token: &'a str,
+        page_token: Option<&'a str>,
         page_size: u32,
     },
     ListInventoryItems {
         user_id: u64,
         filter: &'a str,
+        page_token: Option<&'a str>,
+        page_size: u32,
+    },
+    ListGroupJoinRequests {
+        group_id: u64,
+        filter: Option<&'a str>,
+        page_token: Option<&'a str>,
+        page_size: u32,
+    },
+    ListGroupMemberships {
+        group_id: u64,
+        filter: Option<&'a str>,
+        page_token: Option<&'a str>,
+        page_size: u32,
     },
     ListGroupRanks {
         group_id: u64,
+        page_token: Option<&'a str>,
+        page_size: u32,
+    },
+    ListMemoryStoreSortedMapItems {
+        universe_id: u64,
+        map_id: &'a str,
+        descending: bool,
+        filter: Option<&'a str>,
+        page_token: Option<&'a str>,
+        page_size: u32,
+    },
+    ListOrderedDatastoreEntries {
+        universe_id: u64,
+        datastore_id: &'a str,
+        scope: &'a str,
+        descending: bool,
+        min_value: Option<i64>,
+        max_value: Option<i64>,
+        page_token: Option<&'a str>,
+        page_size: u32,
+    },
+    ListUserRestrictions {
+        universe_id: u64,
+        place_id: Option<u64>,
+        page_token: Option<&'a str>,
+        page_size: u32,
     },
     OAuthUserInfo,
     PublishUniverseMessage {
         universe_id: u64,
     },
+    ReadMemoryStoreQueueItems {
+        universe_id: u64,
+        queue_id: &'a str,
+        count: u32,
+        all_or_nothing: bool,
+        invisibility_window_secs: u64,
+    },
+    /// Open Cloud can list and update group memberships but not delete them, so this is the
+    /// legacy groups endpoint, which accepts API keys with the `legacy-group:manage` scope.
+    RemoveGroupMember {
+        group_id: u64,
+        user_id: u64,
+    },
     UpdateDatastoreEntry {
         universe_id: u64,
         datastore_id: &'a str,
         entry_id: &'a str,
     },
+    UpdateGroupMembership {
+        group_id: u64,
+        membership_id: &'a str,
+    },
+    UpdateMemoryStoreSortedMapItem {
+        universe_id: u64,
+        map_id: &'a str,
+        item_id: &'a str,
+        allow_missing: bool,
+    },
+    UpdateOrderedDatastoreEntry {
+        universe_id: u64,
+        datastore_id: &'a str,
+        scope: &'a str,
+        entry_id: &'a str,
+        allow_missing: bool,
+    },
+    UpdateUserRestriction {
+        universe_id: u64,
+        place_id: Option<u64>,
+        user_id: u64,
+    },
 }
 
+impl Default for BaseUrls {
+    fn default() -> Self {
+        Self {
+            apis: "https://apis.roblox.com".into(),
+            badges: "https://badges.roblox.com".into(),
+            economy: "https://economy.roblox.com".into(),
+            groups: "https://groups.roblox.com".into(),
+            thumbnails: "https://thumbnails.roblox.com".into(),
+            users: "https://users.roblox.com".into(),
+        }
+    }
+}
+
+impl Route<'_> {
+    /// The full URL of the route on the given hosts.
+    pub fn url(&self, base_urls: &BaseUrls) -> String {
+        let base = match self.host() {
+            Host::Apis => &base_urls.apis,
+            Host::Badges => &base_urls.badges,
+            Host::Economy => &base_urls.economy,
+            Host::Groups => &base_urls.groups,
+            Host::Thumbnails => &base_urls.thumbnails,
+            Host::Users => &base_urls.users,
+        };
+        format!("{}{self}", base.trim_end_matches('/'))
+    }
+
+    /// The name of the route, without any of the ids in its path. Used to label metrics and
+    /// spans.
+    #[allow(clippy::too_many_lines)]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Route::AcceptGroupJoinRequest { .. } => "AcceptGroupJoinRequest",
+            Route::CreateDatastoreEntry { .. } => "CreateDatastoreEntry",
+            Route::CreateMemoryStoreSortedMapItem { .. } => "CreateMemoryStoreSortedMapItem",
+            Route::CreateOrderedDatastoreEntry { .. } => "CreateOrderedDatastoreEntry",
+            Route::DeclineGroupJoinRequest { .. } => "DeclineGroupJoinRequest",
+            Route::DeleteDatastoreEntry { .. } => "DeleteDatastoreEntry",
+            Route::DeleteMemoryStoreSortedMapItem { .. } => "DeleteMemoryStoreSortedMapItem",
+            Route::DeleteOrderedDatastoreEntry { .. } => "DeleteOrderedDatastoreEntry",
+            Route::DiscardMemoryStoreQueueItems { .. } => "DiscardMemoryStoreQueueItems",
+            Route::EnqueueMemoryStoreQueueItem { .. } => "EnqueueMemoryStoreQueueItem",
+            Route::GetAssetDetails { .. } => "GetAssetDetails",
+            Route::GetAvatarThumbnails { .. } => "GetAvatarThumbnails",
+            Route::GetBadge { .. } => "GetBadge",
+            Route::GetDatastoreEntry { .. } => "GetDatastoreEntry",
+            Route::GetGamePass { .. } => "GetGamePass",
+            Route::GetGroup { .. } => "GetGroup",
+            Route::GetIcons { .. } => "GetIcons",
+            Route::GetMemoryStoreSortedMapItem { .. } => "GetMemoryStoreSortedMapItem",
+            Route::GetOrderedDatastoreEntry { .. } => "GetOrderedDatastoreEntry",
+            Route::GetUniverse { .. } => "GetUniverse",
+            Route::GetUser { .. } => "GetUser",
+            Route::GetUserByUsernames => "GetUserByUsernames",
+            Route::GetUserGroupRoles { .. } => "GetUserGroupRoles",
+            Route::GetUserRestriction { .. } => "GetUserRestriction",
+            Route::GetUserThumbail { .. } => "GetUserThumbnail",
+            Route::GetUsers => "GetUsers",
+            Route::IncrementOrderedDatastoreEntry { .. } => "IncrementOrderedDatastoreEntry",
+            Route::IntrospectApiKey => "IntrospectApiKey",
+            Route::ListDatastoreEntries { .. } => "ListDatastoreEntries",
+            Route::ListDatastoreEntryRevisions { .. } => "ListDatastoreEntryRevisions",
+            Route::ListDatastores { .. } => "ListDatastores",
+            Route::ListGroupJoinRequests { .. } => "ListGroupJoinRequests",
+            Route::ListGroupMemberships { .. } => "ListGroupMemberships",
+            Route::ListGroupRanks { .. } => "ListGroupRanks",
+            Route::ListInventoryItems { .. } => "ListInventoryItems",
+            Route::ListMemoryStoreSortedMapItems { .. } => "ListMemoryStoreSortedMapItems",
+            Route::ListOrderedDatastoreEntries { .. } => "ListOrderedDatastoreEntries",
+            Route::ListUserRestrictions { .. } => "ListUserRestrictions",
+            Route::OAuthUserInfo => "OAuthUserInfo",
+            Route::PublishUniverseMessage { .. } => "PublishUniverseMessage",
+            Route::ReadMemoryStoreQueueItems { .. } => "ReadMemoryStoreQueueItems",
+            Route::RemoveGroupMember { .. } => "RemoveGroupMember",
+            Route::UpdateDatastoreEntry { .. } => "UpdateDatastoreEntry",
+            Route::UpdateGroupMembership { .. } => "UpdateGroupMembership",
+            Route::UpdateMemoryStoreSortedMapItem { .. } => "UpdateMemoryStoreSortedMapItem",
+            Route::UpdateOrderedDatastoreEntry { .. } => "UpdateOrderedDatastoreEntry",
+            Route::UpdateUserRestriction { .. } => "UpdateUserRestriction",
+        }
+    }
+
+    fn host(&self) -> Host {
+        match self {
+            Route::GetAssetDetails { .. } => Host::Economy,
+            Route::GetBadge { .. } => Host::Badges,
+            Route::GetAvatarThumbnails { .. } | Route::GetIcons { .. } => Host::Thumbnails,
+            Route::GetUserGroupRoles { .. } | Route::RemoveGroupMember { .. } => Host::Groups,
+            Route::GetUsers | Route::GetUserByUsernames => Host::Users,
+            _ => Host::Apis,
+        }
+    }
+}
+
+/// Writes the path and query of the route.
 impl Display for Route<'_> {
+    #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Route::CreateDatastoreEntry { universe_id, datastore_id, entry_id } => write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries?id={entry_id}"),
-            Route::DeleteDatastoreEntry { universe_id, datastore_id, entry_id } => write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}"), 
+            Route::AcceptGroupJoinRequest { group_id, user_id } => write!(f, "/cloud/v2/groups/{group_id}/join-requests/{user_id}:accept"),
+            Route::CreateDatastoreEntry { universe_id, datastore_id, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries?id={entry_id}"),
+            Route::CreateMemoryStoreSortedMapItem { universe_id, map_id, item_id } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items?id={item_id}"),
+            Route::CreateOrderedDatastoreEntry { universe_id, datastore_id, scope, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries?id={entry_id}"),
+            Route::DeclineGroupJoinRequest { group_id, user_id } => write!(f, "/cloud/v2/groups/{group_id}/join-requests/{user_id}:decline"),
+            Route::DeleteDatastoreEntry { universe_id, datastore_id, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}"), 
+            Route::DeleteMemoryStoreSortedMapItem { universe_id, map_id, item_id } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items/{item_id}"),
             Route::GetDatastoreEntry { universe_id, datastore_id, entry_id, revision_id } => write!(
                 f,
-                "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}@{}",
+                "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}@{}",
                 revision_id.unwrap_or("latest")
             ),
-            Route::GetGroup { group_id } => write!(f, "https://apis.roblox.com/cloud/v2/groups/{group_id}"),
-            Route::GetUniverse { universe_id } => write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}"),
+            Route::DeleteOrderedDatastoreEntry { universe_id, datastore_id, scope, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}"),
+            Route::DiscardMemoryStoreQueueItems { universe_id, queue_id } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/queues/{queue_id}/items:discard"),
+            Route::EnqueueMemoryStoreQueueItem { universe_id, queue_id } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/queues/{queue_id}/items"),
+            Route::GetAssetDetails { asset_id } => write!(f, "/v2/assets/{asset_id}/details"),
+            Route::GetAvatarThumbnails { kind, user_ids, options } => {
+                let path = match kind {
+                    AvatarThumbnailKind::Headshot => "avatar-headshot",
+                    AvatarThumbnailKind::Bust => "avatar-bust",
+                };
+                write!(f, "/v1/users/{path}?userIds=")?;
+                write_ids(f, user_ids)?;
+                write!(
+                    f,
+                    "&size={size}x{size}&format={}&isCircular={}",
+                    options.format,
+                    options.circular,
+                    size = options.size
+                )
+            },
+            Route::GetBadge { badge_id } => write!(f, "/v1/badges/{badge_id}"),
+            Route::GetGamePass { game_pass_id } => write!(f, "/game-passes/v1/game-passes/{game_pass_id}/product-info"),
+            Route::GetGroup { group_id } => write!(f, "/cloud/v2/groups/{group_id}"),
+            Route::GetIcons { kind, ids } => {
+                let (path, param) = match kind {
+                    IconKind::Asset => ("/v1/assets", "assetIds"),
+                    IconKind::Badge => ("/v1/badges/icons", "badgeIds"),
+                    IconKind::GamePass => ("/v1/game-passes", "gamePassIds"),
+                };
+                write!(f, "{path}?{param}=")?;
+                write_ids(f, ids)?;
+                f.write_str("&size=150x150&format=Png")
+            },
+            Route::GetMemoryStoreSortedMapItem { universe_id, map_id, item_id } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items/{item_id}"),
+            Route::GetOrderedDatastoreEntry { universe_id, datastore_id, scope, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}"),
+            Route::GetUniverse { universe_id } => write!(f, "/cloud/v2/universes/{universe_id}"),
+            Route::GetUserRestriction { universe_id, place_id, user_id } => {
+                write_user_restrictions(f, *universe_id, *place_id)?;
+                write!(f, "/{user_id}")
+            },
             Route::GetUserGroupRoles { user_id } => write!(
                 f,
-                "https://groups.roblox.com/v2/users/{user_id}/groups/roles"
+                "/v2/users/{user_id}/groups/roles"
             ),
-            Route::GetUser { user_id } => write!(f, "https://apis.roblox.com/cloud/v2/users/{user_id}"),
-            Route::GetUsers => write!(f, "https://users.roblox.com/v1/users"),
-            Route::GetUserByUsernames => write!(f, "https://users.roblox.com/v1/usernames/users"),
-            Route::GetUserThumbail { user_id } => write!(f, "https://apis.roblox.com/cloud/v2/users/{user_id}:generateThumbnail?size=420&format=PNG"),
-            Route::ListDatastores { universe_id, query } => write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores?maxPageSize=100&{query}"),
+            Route::GetUser { user_id } => write!(f, "/cloud/v2/users/{user_id}"),
+            Route::GetUsers => write!(f, "/v1/users"),
+            Route::GetUserByUsernames => write!(f, "/v1/usernames/users"),
+            Route::GetUserThumbail { user_id } => write!(f, "/cloud/v2/users/{user_id}:generateThumbnail?size=420&format=PNG"),
+            Route::ListDatastores { universe_id, page_token, page_size } => {
+                write!(f, "/cloud/v2/universes/{universe_id}/data-stores?maxPageSize={page_size}")?;
+                write_page_token(f, *page_token)
+            },
+            Route::IncrementOrderedDatastoreEntry { universe_id, datastore_id, scope, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}:increment"),
+            Route::IntrospectApiKey => write!(f, "/api-keys/v1/introspect"),
             Route::ListDatastoreEntries { universe_id, datastore_id, page_token, page_size,filter } => {
                 // Encoding the entire filter causes it to give an invalid filter. So, just pre-encode the quotes.
                 let filter = filter.map(|f| format!("&filter=id.startsWith(%22{f}%22)")).unwrap_or_default();
-                write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries?maxPageSize={page_size}&pageToken={page_token}{filter}")
+                write!(f, "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries?maxPageSize={page_size}{filter}")?;
+                write_page_token(f, *page_token)
             },
             Route::ListDatastoreEntryRevisions { universe_id, datastore_id, entry_id, page_token, page_size } => {
-                write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}:listRevisions?maxPageSize={page_size}&pageToken={page_token}")
+                write!(f, "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}:listRevisions?maxPageSize={page_size}")?;
+                write_page_token(f, *page_token)
+            },
+            Route::ListInventoryItems { user_id, filter, page_token, page_size } => {
+                write!(f, "/cloud/v2/users/{user_id}/inventory-items?maxPageSize={page_size}&{filter}")?;
+                write_page_token(f, *page_token)
+            },
+            Route::ListGroupJoinRequests { group_id, filter, page_token, page_size } => {
+                write!(f, "/cloud/v2/groups/{group_id}/join-requests?maxPageSize={page_size}")?;
+                write_filter(f, *filter)?;
+                write_page_token(f, *page_token)
+            },
+            Route::ListGroupMemberships { group_id, filter, page_token, page_size } => {
+                write!(f, "/cloud/v2/groups/{group_id}/memberships?maxPageSize={page_size}")?;
+                write_filter(f, *filter)?;
+                write_page_token(f, *page_token)
+            },
+            Route::ListGroupRanks { group_id, page_token, page_size } => {
+                write!(f, "/cloud/v2/groups/{group_id}/roles?maxPageSize={page_size}")?;
+                write_page_token(f, *page_token)
+            },
+            Route::ListMemoryStoreSortedMapItems { universe_id, map_id, descending, filter, page_token, page_size } => {
+                write!(f, "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items?maxPageSize={page_size}")?;
+                if *descending {
+                    f.write_str("&orderBy=id%20desc")?;
+                }
+                write_filter(f, *filter)?;
+                write_page_token(f, *page_token)
+            },
+            Route::ListOrderedDatastoreEntries { universe_id, datastore_id, scope, descending, min_value, max_value, page_token, page_size } => {
+                write!(f, "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries?maxPageSize={page_size}")?;
+                if *descending {
+                    f.write_str("&orderBy=value%20desc")?;
+                }
+                let bounds = [min_value.map(|v| format!("entry >= {v}")), max_value.map(|v| format!("entry <= {v}"))]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                if !bounds.is_empty() {
+                    write_filter(f, Some(&bounds.join(" && ")))?;
+                }
+                write_page_token(f, *page_token)
+            },
+            Route::ListUserRestrictions { universe_id, place_id, page_token, page_size } => {
+                write_user_restrictions(f, *universe_id, *place_id)?;
+                write!(f, "?maxPageSize={page_size}")?;
+                write_page_token(f, *page_token)
+            },
+            Route::OAuthUserInfo => write!(f, "/oauth/v1/userinfo"),
+            Route::PublishUniverseMessage { universe_id } => write!(f, "/cloud/v2/universes/{universe_id}:publishMessage"),
+            Route::ReadMemoryStoreQueueItems { universe_id, queue_id, count, all_or_nothing, invisibility_window_secs } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/queues/{queue_id}/items:read?count={count}&allOrNothing={all_or_nothing}&invisibilityWindow={invisibility_window_secs}s"),
+            Route::RemoveGroupMember { group_id, user_id } => write!(f, "/v1/groups/{group_id}/users/{user_id}"),
+            Route::UpdateDatastoreEntry { universe_id, datastore_id, entry_id } => write!(f, "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}"),
+            Route::UpdateGroupMembership { group_id, membership_id } => write!(f, "/cloud/v2/groups/{group_id}/memberships/{membership_id}"),
+            Route::UpdateMemoryStoreSortedMapItem { universe_id, map_id, item_id, allow_missing } => write!(f, "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items/{item_id}?allowMissing={allow_missing}"),
+            Route::UpdateOrderedDatastoreEntry { universe_id, datastore_id, scope, entry_id, allow_missing } => write!(f, "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}?allowMissing={allow_missing}"),
+            Route::UpdateUserRestriction { universe_id, place_id, user_id } => {
+                write_user_restrictions(f, *universe_id, *place_id)?;
+                write!(f, "/{user_id}?updateMask=gameJoinRestriction")
             },
-            Route::ListInventoryItems { user_id, filter } => write!(f, "https://apis.roblox.com/cloud/v2/users/{user_id}/inventory-items?maxPageSize=100&{filter}"),
-            Route::ListGroupRanks { group_id } => write!(f, "https://apis.roblox.com/cloud/v2/groups/{group_id}/roles?maxPageSize=20"),
-            Route::OAuthUserInfo => write!(f, "https://apis.roblox.com/oauth/v1/userinfo"),
-            Route::PublishUniverseMessage { universe_id } => write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}:publishMessage"),
-            Route::UpdateDatastoreEntry { universe_id, datastore_id, entry_id } => write!(f, "https://apis.roblox.com/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}")
         }
     }
 }
+
+fn write_user_restrictions(
+    f: &mut Formatter<'_>,
+    universe_id: u64,
+    place_id: Option<u64>,
+) -> FmtResult {
+    write!(f, "/cloud/v2/universes/{universe_id}")?;
+    if let Some(place_id) = place_id {
+        write!(f, "/places/{place_id}")?;
+    }
+    f.write_str("/user-restrictions")
+}
+
+fn write_filter(f: &mut Formatter<'_>, filter: Option<&str>) -> FmtResult {
+    match filter {
+        Some(filter) => write!(f, "&filter={}", urlencoding::encode(filter)),
+        None => Ok(()),
+    }
+}
+
+fn write_ids(f: &mut Formatter<'_>, ids: &[u64]) -> FmtResult {
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{id}")?;
+    }
+    Ok(())
+}
+
+fn write_page_token(f: &mut Formatter<'_>, page_token: Option<&str>) -> FmtResult {
+    match page_token {
+        Some(page_token) => write!(f, "&pageToken={}", urlencoding::encode(page_token)),
+        None => Ok(()),
+    }
+}
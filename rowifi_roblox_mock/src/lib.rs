@@ -0,0 +1,472 @@
+//! An in-process fake of the Roblox APIs the bot talks to, backed by fixture data.
+//!
+//! [`MockRoblox::start`] binds the fake to a random local port. Point a [`RobloxClient`] at it
+//! with [`MockRoblox::client`], or at its [`MockRoblox::base_urls`] when the client is built
+//! elsewhere. The fake also answers the couple of Discord endpoints the core operations call so
+//! that a twilight client proxied to [`MockRoblox::address`] can be used alongside it.
+
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions, clippy::missing_panics_doc)]
+
+mod routes;
+
+use chrono::Utc;
+use rowifi_roblox::{BaseUrls, RobloxClient};
+use rowifi_roblox_models::{
+    api_key::{ApiKeyInfo, ApiKeyScope},
+    datastore::{DatastoreEntry, DatastoreEntryState},
+    group::{GroupRole, GroupUserRole},
+    id::{GroupId, RoleId, UniverseId, UserId},
+    memory_store::{MemoryStoreQueueItem, MemoryStoreSortedMapItem, SortKey},
+    resource::{DatastoreEntryPath, UserPath},
+    user::PartialUser,
+};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+/// The data served by the fake.
+#[derive(Clone, Debug, Default)]
+pub struct Fixtures {
+    users: HashMap<UserId, PartialUser>,
+    user_roles: HashMap<UserId, Vec<GroupUserRole>>,
+    group_ranks: HashMap<GroupId, Vec<GroupRole>>,
+    group_members: HashMap<GroupId, BTreeMap<u64, RoleId>>,
+    inventory: HashMap<UserId, Vec<InventoryFixture>>,
+    datastores: BTreeMap<(UniverseId, String), BTreeMap<String, Vec<DatastoreEntry>>>,
+    ordered_datastores: BTreeMap<(UniverseId, String, String), BTreeMap<String, i64>>,
+    sorted_maps: BTreeMap<(UniverseId, String), BTreeMap<String, MemoryStoreSortedMapItem>>,
+    queues: BTreeMap<(UniverseId, String), Vec<MemoryStoreQueueItem>>,
+    api_keys: HashMap<String, ApiKeyInfo>,
+    catalog: HashMap<InventoryFixture, String>,
+}
+
+/// An item in the inventory of a user or in the catalog.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InventoryFixture {
+    Asset(u64),
+    Badge(u64),
+    Gamepass(u64),
+}
+
+/// A message published through the messaging service.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublishedMessage {
+    pub universe_id: UniverseId,
+    pub topic: String,
+    pub message: String,
+}
+
+/// A request received on one of the Discord endpoints.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiscordRequest {
+    UpdateMember {
+        guild_id: u64,
+        user_id: u64,
+        body: Value,
+    },
+    CreateMessage {
+        channel_id: u64,
+        body: Value,
+    },
+}
+
+/// A running fake. The server is shut down when this is dropped.
+pub struct MockRoblox {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    handle: JoinHandle<()>,
+}
+
+#[derive(Default)]
+pub(crate) struct State {
+    pub fixtures: Fixtures,
+    pub published_messages: Vec<PublishedMessage>,
+    pub discord_requests: Vec<DiscordRequest>,
+    pub api_keys_used: Vec<String>,
+    pub requests: Vec<String>,
+    pub next_revision: u64,
+    /// Queue items that were read but not discarded yet, by read id. They never become visible
+    /// again since the fake does not track invisibility windows.
+    pub queue_reads: HashMap<String, Vec<MemoryStoreQueueItem>>,
+}
+
+impl Fixtures {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn user(mut self, id: u64, name: &str) -> Self {
+        self.users.insert(
+            UserId(id),
+            PartialUser {
+                create_time: None,
+                id: UserId(id),
+                name: name.to_string(),
+                display_name: Some(name.to_string()),
+            },
+        );
+        self
+    }
+
+    /// The ranks returned for the user by the groups API.
+    #[must_use]
+    pub fn user_roles(mut self, id: u64, roles: Vec<GroupUserRole>) -> Self {
+        self.user_roles.insert(UserId(id), roles);
+        self
+    }
+
+    #[must_use]
+    pub fn group_ranks(mut self, group_id: u64, ranks: Vec<GroupRole>) -> Self {
+        self.group_ranks.insert(GroupId(group_id), ranks);
+        self
+    }
+
+    /// Makes the user a member of the group with the given role.
+    #[must_use]
+    pub fn group_member(mut self, group_id: u64, user_id: u64, role_id: u64) -> Self {
+        self.group_members
+            .entry(GroupId(group_id))
+            .or_default()
+            .insert(user_id, RoleId(role_id));
+        self
+    }
+
+    #[must_use]
+    pub fn inventory(mut self, id: u64, items: Vec<InventoryFixture>) -> Self {
+        self.inventory.insert(UserId(id), items);
+        self
+    }
+
+    /// Adds an entry to a datastore, creating the datastore if needed.
+    #[must_use]
+    pub fn datastore_entry(
+        mut self,
+        universe_id: u64,
+        datastore_id: &str,
+        entry_id: &str,
+        value: Value,
+    ) -> Self {
+        let entry = new_entry(
+            UniverseId(universe_id),
+            datastore_id,
+            entry_id,
+            value,
+            Vec::new(),
+            None,
+            "0".into(),
+        );
+        self.datastores
+            .entry((UniverseId(universe_id), datastore_id.to_string()))
+            .or_default()
+            .insert(entry_id.to_string(), vec![entry]);
+        self
+    }
+
+    /// Adds an entry to a scope of an ordered datastore, creating the datastore if needed.
+    #[must_use]
+    pub fn ordered_datastore_entry(
+        mut self,
+        universe_id: u64,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+        value: i64,
+    ) -> Self {
+        self.ordered_datastores
+            .entry((
+                UniverseId(universe_id),
+                datastore_id.to_string(),
+                scope.to_string(),
+            ))
+            .or_default()
+            .insert(entry_id.to_string(), value);
+        self
+    }
+
+    /// Adds an item without a sort key to a memory store sorted map.
+    #[must_use]
+    pub fn sorted_map_item(
+        mut self,
+        universe_id: u64,
+        map_id: &str,
+        item_id: &str,
+        value: Value,
+    ) -> Self {
+        let item = new_sorted_map_item(UniverseId(universe_id), map_id, item_id, value, None, None);
+        self.sorted_maps
+            .entry((UniverseId(universe_id), map_id.to_string()))
+            .or_default()
+            .insert(item_id.to_string(), item);
+        self
+    }
+
+    /// Adds an item to the back of a memory store queue.
+    #[must_use]
+    pub fn queue_item(mut self, universe_id: u64, queue_id: &str, data: Value) -> Self {
+        self.queues
+            .entry((UniverseId(universe_id), queue_id.to_string()))
+            .or_default()
+            .push(MemoryStoreQueueItem {
+                path: None,
+                data,
+                priority: None,
+                expire_time: None,
+            });
+        self
+    }
+
+    /// An API key known to the introspection endpoint, with the given `(scope, operation)`
+    /// pairs.
+    #[must_use]
+    pub fn api_key(mut self, api_key: &str, scopes: &[(&str, &str)]) -> Self {
+        let mut info = ApiKeyInfo {
+            name: api_key.to_string(),
+            authorized_user_id: None,
+            scopes: Vec::new(),
+            enabled: true,
+            expired: false,
+        };
+        for (name, operation) in scopes {
+            match info.scopes.iter_mut().find(|s| s.name == *name) {
+                Some(scope) => scope.operations.push((*operation).to_string()),
+                None => info.scopes.push(ApiKeyScope {
+                    name: (*name).to_string(),
+                    operations: vec![(*operation).to_string()],
+                    universe_ids: Vec::new(),
+                }),
+            }
+        }
+        self.api_keys.insert(api_key.to_string(), info);
+        self
+    }
+
+    /// An asset, badge or gamepass known to the catalog and thumbnails endpoints.
+    #[must_use]
+    pub fn catalog_item(mut self, item: InventoryFixture, name: &str) -> Self {
+        self.catalog.insert(item, name.to_string());
+        self
+    }
+}
+
+impl MockRoblox {
+    /// Serve the fixtures on a random local port.
+    pub async fn start(fixtures: Fixtures) -> Self {
+        let state = Arc::new(Mutex::new(State {
+            fixtures,
+            next_revision: 1,
+            ..State::default()
+        }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = routes::router(state.clone());
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        Self {
+            address,
+            state,
+            handle,
+        }
+    }
+
+    #[must_use]
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The hosts to give to [`RobloxClient::with_base_urls`].
+    #[must_use]
+    pub fn base_urls(&self) -> BaseUrls {
+        let url = format!("http://{}", self.address);
+        BaseUrls {
+            apis: url.clone(),
+            badges: url.clone(),
+            economy: url.clone(),
+            groups: url.clone(),
+            thumbnails: url.clone(),
+            users: url,
+        }
+    }
+
+    /// A Roblox client that talks to this fake.
+    #[must_use]
+    pub fn client(&self) -> RobloxClient {
+        RobloxClient::new("mock-open-cloud-key", None).with_base_urls(self.base_urls())
+    }
+
+    /// The latest revision of a datastore entry, if it exists.
+    #[must_use]
+    pub fn datastore_entry(
+        &self,
+        universe_id: u64,
+        datastore_id: &str,
+        entry_id: &str,
+    ) -> Option<DatastoreEntry> {
+        self.state()
+            .fixtures
+            .datastores
+            .get(&(UniverseId(universe_id), datastore_id.to_string()))
+            .and_then(|entries| entries.get(entry_id))
+            .and_then(|revisions| revisions.last().cloned())
+    }
+
+    /// The value of an entry of an ordered datastore, if it exists.
+    #[must_use]
+    pub fn ordered_datastore_entry(
+        &self,
+        universe_id: u64,
+        datastore_id: &str,
+        scope: &str,
+        entry_id: &str,
+    ) -> Option<i64> {
+        self.state()
+            .fixtures
+            .ordered_datastores
+            .get(&(
+                UniverseId(universe_id),
+                datastore_id.to_string(),
+                scope.to_string(),
+            ))
+            .and_then(|entries| entries.get(entry_id))
+            .copied()
+    }
+
+    /// An item of a memory store sorted map, if it exists.
+    #[must_use]
+    pub fn sorted_map_item(
+        &self,
+        universe_id: u64,
+        map_id: &str,
+        item_id: &str,
+    ) -> Option<MemoryStoreSortedMapItem> {
+        self.state()
+            .fixtures
+            .sorted_maps
+            .get(&(UniverseId(universe_id), map_id.to_string()))
+            .and_then(|items| items.get(item_id))
+            .cloned()
+    }
+
+    /// The items of a memory store queue that can still be read, front first.
+    #[must_use]
+    pub fn queue_items(&self, universe_id: u64, queue_id: &str) -> Vec<MemoryStoreQueueItem> {
+        self.state()
+            .fixtures
+            .queues
+            .get(&(UniverseId(universe_id), queue_id.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The number of queue reads that were not discarded.
+    #[must_use]
+    pub fn pending_queue_reads(&self) -> usize {
+        self.state().queue_reads.len()
+    }
+
+    /// The role of the user in the group, if they are a member.
+    #[must_use]
+    pub fn group_member_role(&self, group_id: u64, user_id: u64) -> Option<RoleId> {
+        self.state()
+            .fixtures
+            .group_members
+            .get(&GroupId(group_id))
+            .and_then(|members| members.get(&user_id))
+            .copied()
+    }
+
+    #[must_use]
+    pub fn published_messages(&self) -> Vec<PublishedMessage> {
+        self.state().published_messages.clone()
+    }
+
+    #[must_use]
+    pub fn discord_requests(&self) -> Vec<DiscordRequest> {
+        self.state().discord_requests.clone()
+    }
+
+    /// The `x-api-key` header of every Open Cloud request received, in order.
+    #[must_use]
+    pub fn api_keys_used(&self) -> Vec<String> {
+        self.state().api_keys_used.clone()
+    }
+
+    /// The method, path and query of every request received, in order, e.g.
+    /// `GET /cloud/v2/users/1`.
+    #[must_use]
+    pub fn requests(&self) -> Vec<String> {
+        self.state().requests.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockRoblox {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+pub(crate) fn new_entry(
+    universe_id: UniverseId,
+    datastore_id: &str,
+    entry_id: &str,
+    value: Value,
+    users: Vec<UserPath>,
+    attributes: Option<Value>,
+    revision_id: String,
+) -> DatastoreEntry {
+    let now = Utc::now();
+    DatastoreEntry {
+        path: Some(DatastoreEntryPath {
+            universe_id,
+            datastore_id: datastore_id.to_string(),
+            scope: None,
+            entry_id: entry_id.to_string(),
+        }),
+        create_time: now,
+        revision_id: revision_id.clone(),
+        revision_create_time: now,
+        state: DatastoreEntryState::Active,
+        etag: revision_id,
+        value,
+        id: entry_id.to_string(),
+        users,
+        attributes: attributes.unwrap_or(Value::Null),
+    }
+}
+
+pub(crate) fn new_sorted_map_item(
+    universe_id: UniverseId,
+    map_id: &str,
+    item_id: &str,
+    value: Value,
+    ttl: Option<Duration>,
+    sort_key: Option<SortKey>,
+) -> MemoryStoreSortedMapItem {
+    let (numeric_sort_key, string_sort_key) = match sort_key {
+        Some(SortKey::Numeric(key)) => (Some(key), None),
+        Some(SortKey::String(key)) => (None, Some(key)),
+        None => (None, None),
+    };
+    MemoryStoreSortedMapItem {
+        path: format!("universes/{universe_id}/memory-store/sorted-maps/{map_id}/items/{item_id}"),
+        value,
+        etag: Some(Utc::now().timestamp_micros().to_string()),
+        expire_time: ttl.map(|ttl| Utc::now() + ttl),
+        id: item_id.to_string(),
+        string_sort_key,
+        numeric_sort_key,
+    }
+}
@@ -0,0 +1,1385 @@
+use axum::{
+    extract::{Path, Query, Request, State as AxumState},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch, post},
+    Json, Router,
+};
+use chrono::Utc;
+use rowifi_roblox_models::{
+    datastore::{Datastore, OrderedDatastoreEntry, PartialDatastoreEntry},
+    group::{Group, GroupMembership},
+    id::{GroupId, RoleId, UniverseId, UserId},
+    memory_store::{MemoryStoreQueueItem, MemoryStoreQueueRead, MemoryStoreSortedMapItem, SortKey},
+    resource::{DatastoreEntryPath, DatastorePath, GroupPath, RolePath, UserPath},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use crate::{
+    new_entry, new_sorted_map_item, DiscordRequest, InventoryFixture, PublishedMessage, State,
+};
+
+type SharedState = Arc<Mutex<State>>;
+
+#[derive(Deserialize)]
+struct PageQuery {
+    #[serde(rename = "maxPageSize", default)]
+    max_page_size: Option<usize>,
+    #[serde(rename = "pageToken", default)]
+    page_token: Option<String>,
+    #[serde(default)]
+    filter: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(rename = "orderBy", default)]
+    order_by: Option<String>,
+    #[serde(rename = "allowMissing", default)]
+    allow_missing: bool,
+}
+
+#[derive(Deserialize)]
+struct EntryBody {
+    value: Value,
+    #[serde(default)]
+    users: Vec<UserPath>,
+    #[serde(default)]
+    attributes: Option<Value>,
+}
+
+pub(crate) fn router(state: SharedState) -> Router {
+    let open_cloud = Router::new()
+        .route("/cloud/v2/users/{user_id}", get(get_user))
+        .route(
+            "/cloud/v2/users/{user_id}/inventory-items",
+            get(list_inventory_items),
+        )
+        .route("/cloud/v2/groups/{group_id}", get(get_group))
+        .route("/cloud/v2/groups/{group_id}/roles", get(list_group_ranks))
+        .route(
+            "/cloud/v2/groups/{group_id}/memberships",
+            get(list_group_memberships),
+        )
+        .route(
+            "/cloud/v2/groups/{group_id}/memberships/{membership_id}",
+            patch(update_group_membership),
+        )
+        // A legacy endpoint, but one that is called with an API key.
+        .route(
+            "/v1/groups/{group_id}/users/{user_id}",
+            delete(remove_group_member),
+        )
+        .route("/cloud/v2/universes/{universe_id}", post(publish_message))
+        .route(
+            "/cloud/v2/universes/{universe_id}/data-stores",
+            get(list_datastores),
+        )
+        .route(
+            "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries",
+            get(list_entries).post(create_entry),
+        )
+        .route(
+            "/cloud/v2/universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}",
+            get(get_entry).patch(update_entry).delete(delete_entry),
+        )
+        .route(
+            "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries",
+            get(list_ordered_entries).post(create_ordered_entry),
+        )
+        .route(
+            "/cloud/v2/universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}",
+            get(get_ordered_entry)
+                .patch(update_ordered_entry)
+                .post(increment_ordered_entry)
+                .delete(delete_ordered_entry),
+        )
+        .route(
+            "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items",
+            get(list_sorted_map_items).post(create_sorted_map_item),
+        )
+        .route(
+            "/cloud/v2/universes/{universe_id}/memory-store/sorted-maps/{map_id}/items/{item_id}",
+            get(get_sorted_map_item)
+                .patch(update_sorted_map_item)
+                .delete(delete_sorted_map_item),
+        )
+        // `items`, `items:read` and `items:discard` share the last segment.
+        .route(
+            "/cloud/v2/universes/{universe_id}/memory-store/queues/{queue_id}/{method}",
+            get(read_queue_items).post(write_queue_items),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ));
+
+    Router::new()
+        .merge(open_cloud)
+        .route("/v2/users/{user_id}/groups/roles", get(get_user_roles))
+        .route("/v1/users", post(get_users))
+        .route("/v1/usernames/users", post(get_users_from_usernames))
+        .route("/api-keys/v1/introspect", post(introspect_api_key))
+        .route("/v2/assets/{asset_id}/details", get(get_asset_details))
+        .route("/v1/badges/{badge_id}", get(get_badge))
+        .route(
+            "/game-passes/v1/game-passes/{game_pass_id}/product-info",
+            get(get_game_pass),
+        )
+        .route("/v1/assets", get(get_asset_icons))
+        .route("/v1/badges/icons", get(get_badge_icons))
+        .route("/v1/game-passes", get(get_game_pass_icons))
+        .route("/v1/users/{kind}", get(get_avatar_thumbnails))
+        .route(
+            "/api/v10/guilds/{guild_id}/members/{user_id}",
+            patch(update_member),
+        )
+        .route(
+            "/api/v10/channels/{channel_id}/messages",
+            post(create_message),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            record_request,
+        ))
+        .with_state(state)
+}
+
+async fn record_request(
+    AxumState(state): AxumState<SharedState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let uri = request
+        .uri()
+        .path_and_query()
+        .map_or_else(|| request.uri().path().to_string(), ToString::to_string);
+    lock(&state)
+        .requests
+        .push(format!("{} {uri}", request.method()));
+    next.run(request).await
+}
+
+async fn require_api_key(
+    AxumState(state): AxumState<SharedState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(api_key) = request
+        .headers()
+        .get("x-api-key")
+        .and_then(|k| k.to_str().ok())
+    else {
+        return error(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHENTICATED",
+            "missing api key",
+        );
+    };
+    lock(&state).api_keys_used.push(api_key.to_string());
+    next.run(request).await
+}
+
+fn error(status: StatusCode, code: &str, message: &str) -> Response {
+    (status, Json(json!({ "code": code, "message": message }))).into_response()
+}
+
+fn not_found() -> Response {
+    error(StatusCode::NOT_FOUND, "NOT_FOUND", "resource not found")
+}
+
+/// The legacy catalog endpoints answer unknown ids with a 400.
+fn invalid_item() -> Response {
+    error(
+        StatusCode::BAD_REQUEST,
+        "INVALID_ARGUMENT",
+        "the item is invalid or does not exist",
+    )
+}
+
+fn lock(state: &SharedState) -> MutexGuard<'_, State> {
+    state.lock().unwrap()
+}
+
+/// Returns the requested page of `items` along with the token of the next one, or `None` if the
+/// page token is invalid. Page tokens are the offset into the items, wrapped in characters that
+/// have to be percent-encoded in a query, so that a client which forgets to encode them gets a
+/// bad request.
+fn paginate<T: Clone>(items: &[T], query: &PageQuery) -> Option<(Vec<T>, Option<String>)> {
+    let start = match query.page_token.as_deref() {
+        None => 0,
+        Some(page_token) => page_token
+            .strip_prefix("offset+")
+            .and_then(|t| t.strip_suffix('='))
+            .and_then(|t| t.parse::<usize>().ok())?
+            .min(items.len()),
+    };
+    let size = query.max_page_size.unwrap_or(10).max(1);
+    let end = (start + size).min(items.len());
+    let next_page_token = (end < items.len()).then(|| format!("offset+{end}="));
+    Some((items[start..end].to_vec(), next_page_token))
+}
+
+fn invalid_page_token() -> Response {
+    error(
+        StatusCode::BAD_REQUEST,
+        "INVALID_ARGUMENT",
+        "invalid page token",
+    )
+}
+
+async fn get_user(
+    AxumState(state): AxumState<SharedState>,
+    Path(user_id): Path<String>,
+) -> Response {
+    let (user_id, method) = user_id
+        .split_once(':')
+        .map_or((user_id.as_str(), None), |(id, method)| (id, Some(method)));
+    let Ok(user_id) = user_id.parse() else {
+        return not_found();
+    };
+    let state = lock(&state);
+    let Some(user) = state.fixtures.users.get(&UserId(user_id)) else {
+        return not_found();
+    };
+
+    match method {
+        None => Json(user).into_response(),
+        Some("generateThumbnail") => Json(json!({
+            "done": true,
+            "response": { "imageUri": format!("https://tr.rbxcdn.com/{}/420/420/AvatarHeadshot/Png", user.id) }
+        }))
+        .into_response(),
+        Some(_) => not_found(),
+    }
+}
+
+async fn get_users(AxumState(state): AxumState<SharedState>, Json(body): Json<Value>) -> Response {
+    let state = lock(&state);
+    let user_ids = body["userIds"].as_array().cloned().unwrap_or_default();
+    if user_ids.len() > 100 {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "too many user ids",
+        );
+    }
+    let users = user_ids
+        .iter()
+        .filter_map(Value::as_u64)
+        .filter_map(|id| state.fixtures.users.get(&UserId(id)))
+        .collect::<Vec<_>>();
+    Json(json!({ "data": users })).into_response()
+}
+
+#[derive(Deserialize)]
+struct AvatarThumbnailQuery {
+    #[serde(rename = "userIds")]
+    user_ids: String,
+    size: String,
+    format: String,
+}
+
+async fn get_avatar_thumbnails(
+    AxumState(state): AxumState<SharedState>,
+    Path(kind): Path<String>,
+    Query(query): Query<AvatarThumbnailQuery>,
+) -> Response {
+    let kind = match kind.as_str() {
+        "avatar-headshot" => "AvatarHeadshot",
+        "avatar-bust" => "AvatarBust",
+        _ => return not_found(),
+    };
+    let user_ids = query.user_ids.split(',').collect::<Vec<_>>();
+    if user_ids.len() > 100 {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "too many user ids",
+        );
+    }
+
+    let state = lock(&state);
+    let data = user_ids
+        .iter()
+        .filter_map(|id| id.parse::<u64>().ok())
+        .map(|id| {
+            if state.fixtures.users.contains_key(&UserId(id)) {
+                json!({
+                    "targetId": id,
+                    "state": "Completed",
+                    "imageUrl": format!("https://tr.rbxcdn.com/{id}/{}/{kind}/{}", query.size, query.format),
+                })
+            } else {
+                json!({ "targetId": id, "state": "Error", "imageUrl": null })
+            }
+        })
+        .collect::<Vec<_>>();
+    Json(json!({ "data": data })).into_response()
+}
+
+async fn get_users_from_usernames(
+    AxumState(state): AxumState<SharedState>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    let state = lock(&state);
+    let users = body["usernames"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|name| {
+            state
+                .fixtures
+                .users
+                .values()
+                .find(|u| u.name.eq_ignore_ascii_case(name))
+        })
+        .collect::<Vec<_>>();
+    Json(json!({ "data": users }))
+}
+
+async fn get_user_roles(
+    AxumState(state): AxumState<SharedState>,
+    Path(user_id): Path<u64>,
+) -> Json<Value> {
+    let state = lock(&state);
+    let roles = state
+        .fixtures
+        .user_roles
+        .get(&UserId(user_id))
+        .cloned()
+        .unwrap_or_default();
+    Json(json!({ "data": roles }))
+}
+
+async fn list_inventory_items(
+    AxumState(state): AxumState<SharedState>,
+    Path(user_id): Path<u64>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let state = lock(&state);
+    if !state.fixtures.users.contains_key(&UserId(user_id)) {
+        return not_found();
+    }
+
+    // The filter looks like `assetIds=1,2;badgeIds=3;gamePassIds=4`.
+    let mut wanted = Vec::new();
+    for filter in query.filter.as_deref().unwrap_or_default().split(';') {
+        let Some((kind, ids)) = filter.split_once('=') else {
+            continue;
+        };
+        for id in ids.split(',').filter_map(|id| id.parse().ok()) {
+            match kind {
+                "assetIds" => wanted.push(InventoryFixture::Asset(id)),
+                "badgeIds" => wanted.push(InventoryFixture::Badge(id)),
+                "gamePassIds" => wanted.push(InventoryFixture::Gamepass(id)),
+                _ => return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad filter"),
+            }
+        }
+    }
+
+    let items = state
+        .fixtures
+        .inventory
+        .get(&UserId(user_id))
+        .into_iter()
+        .flatten()
+        .filter(|item| wanted.contains(item))
+        .map(|item| match item {
+            InventoryFixture::Asset(id) => json!({ "assetDetails": { "assetId": id.to_string() } }),
+            InventoryFixture::Badge(id) => json!({ "badgeDetails": { "badgeId": id.to_string() } }),
+            InventoryFixture::Gamepass(id) => {
+                json!({ "gamePassDetails": { "gamePassId": id.to_string() } })
+            }
+        })
+        .collect::<Vec<_>>();
+    let Some((items, next_page_token)) = paginate(&items, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "inventoryItems": items, "nextPageToken": next_page_token })).into_response()
+}
+
+async fn get_group(
+    AxumState(state): AxumState<SharedState>,
+    Path(group_id): Path<u64>,
+) -> Response {
+    let state = lock(&state);
+    if !state.fixtures.group_ranks.keys().any(|g| g.0 == group_id) {
+        return not_found();
+    }
+    Json(Group {
+        path: GroupPath::new(GroupId(group_id)),
+        id: GroupId(group_id),
+        display_name: format!("Group {group_id}"),
+    })
+    .into_response()
+}
+
+async fn list_group_ranks(
+    AxumState(state): AxumState<SharedState>,
+    Path(group_id): Path<u64>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let state = lock(&state);
+    let Some(ranks) = state
+        .fixtures
+        .group_ranks
+        .iter()
+        .find_map(|(g, r)| (g.0 == group_id).then_some(r))
+    else {
+        return not_found();
+    };
+    let Some((ranks, next_page_token)) = paginate(ranks, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "groupRoles": ranks, "nextPageToken": next_page_token })).into_response()
+}
+
+fn membership(group_id: GroupId, user_id: UserId, role_id: RoleId) -> GroupMembership {
+    GroupMembership {
+        path: format!("groups/{group_id}/memberships/m{user_id}"),
+        create_time: None,
+        update_time: None,
+        user: UserPath::new(user_id),
+        role: RolePath::new(group_id, role_id),
+    }
+}
+
+async fn list_group_memberships(
+    AxumState(state): AxumState<SharedState>,
+    Path(group_id): Path<u64>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let group_id = GroupId(group_id);
+    let state = lock(&state);
+    if !state.fixtures.group_ranks.contains_key(&group_id) {
+        return not_found();
+    }
+
+    // The only filter supported by Roblox is `user == 'users/{user_id}'`.
+    let user_id = match query.filter.as_deref() {
+        None => None,
+        Some(filter) => match filter
+            .strip_prefix("user == 'users/")
+            .and_then(|f| f.strip_suffix('\''))
+            .and_then(|id| id.parse().ok())
+        {
+            Some(user_id) => Some(user_id),
+            None => return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad filter"),
+        },
+    };
+    let memberships = state
+        .fixtures
+        .group_members
+        .get(&group_id)
+        .into_iter()
+        .flatten()
+        .filter(|(member, _)| user_id.is_none_or(|u: u64| u == **member))
+        .map(|(member, role_id)| membership(group_id, UserId(*member), *role_id))
+        .collect::<Vec<_>>();
+    let Some((memberships, next_page_token)) = paginate(&memberships, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "groupMemberships": memberships, "nextPageToken": next_page_token }))
+        .into_response()
+}
+
+#[derive(Deserialize)]
+struct MembershipBody {
+    role: RolePath,
+}
+
+async fn update_group_membership(
+    AxumState(state): AxumState<SharedState>,
+    Path((group_id, membership_id)): Path<(u64, String)>,
+    Json(body): Json<MembershipBody>,
+) -> Response {
+    let group_id = GroupId(group_id);
+    let mut state = lock(&state);
+    let role_exists = state
+        .fixtures
+        .group_ranks
+        .get(&group_id)
+        .is_some_and(|ranks| ranks.iter().any(|r| r.id == body.role.role_id));
+    if body.role.group_id != group_id || !role_exists {
+        return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "invalid role");
+    }
+    let Some(user_id) = membership_id
+        .strip_prefix('m')
+        .and_then(|id| id.parse::<u64>().ok())
+    else {
+        return not_found();
+    };
+    let Some(role_id) = state
+        .fixtures
+        .group_members
+        .get_mut(&group_id)
+        .and_then(|members| members.get_mut(&user_id))
+    else {
+        return not_found();
+    };
+    *role_id = body.role.role_id;
+    Json(membership(group_id, UserId(user_id), body.role.role_id)).into_response()
+}
+
+async fn remove_group_member(
+    AxumState(state): AxumState<SharedState>,
+    Path((group_id, user_id)): Path<(u64, u64)>,
+) -> Response {
+    let removed = lock(&state)
+        .fixtures
+        .group_members
+        .get_mut(&GroupId(group_id))
+        .and_then(|members| members.remove(&user_id));
+    match removed {
+        Some(_) => Json(json!({})).into_response(),
+        None => error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "the user is not a member of the group",
+        ),
+    }
+}
+
+async fn introspect_api_key(
+    AxumState(state): AxumState<SharedState>,
+    Json(body): Json<Value>,
+) -> Response {
+    let state = lock(&state);
+    match body["apiKey"]
+        .as_str()
+        .and_then(|k| state.fixtures.api_keys.get(k))
+    {
+        Some(info) => Json(info).into_response(),
+        None => error(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHENTICATED",
+            "invalid api key",
+        ),
+    }
+}
+
+async fn publish_message(
+    AxumState(state): AxumState<SharedState>,
+    Path(universe_id): Path<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    let Some(universe_id) = universe_id
+        .strip_suffix(":publishMessage")
+        .and_then(|id| id.parse().ok())
+    else {
+        return not_found();
+    };
+    let (Some(topic), Some(message)) = (body["topic"].as_str(), body["message"].as_str()) else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "topic and message are required",
+        );
+    };
+    lock(&state).published_messages.push(PublishedMessage {
+        universe_id: UniverseId(universe_id),
+        topic: topic.to_string(),
+        message: message.to_string(),
+    });
+    Json(json!({})).into_response()
+}
+
+async fn list_datastores(
+    AxumState(state): AxumState<SharedState>,
+    Path(universe_id): Path<u64>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let state = lock(&state);
+    let datastores = state
+        .fixtures
+        .datastores
+        .keys()
+        .filter(|(u, _)| u.0 == universe_id)
+        .map(|(universe_id, id)| Datastore {
+            path: Some(DatastorePath {
+                universe_id: *universe_id,
+                datastore_id: id.clone(),
+            }),
+            id: id.clone(),
+        })
+        .collect::<Vec<_>>();
+    let Some((datastores, next_page_token)) = paginate(&datastores, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "dataStores": datastores, "nextPageToken": next_page_token })).into_response()
+}
+
+async fn list_entries(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id)): Path<(u64, String)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let state = lock(&state);
+    let Some(entries) = state
+        .fixtures
+        .datastores
+        .get(&(UniverseId(universe_id), datastore_id.clone()))
+    else {
+        return not_found();
+    };
+
+    let prefix = query.filter.as_deref().and_then(|f| {
+        f.strip_prefix("id.startsWith(\"")
+            .and_then(|f| f.strip_suffix("\")"))
+    });
+    let entries = entries
+        .keys()
+        .filter(|id| prefix.is_none_or(|prefix| id.starts_with(prefix)))
+        .map(|id| PartialDatastoreEntry {
+            path: Some(DatastoreEntryPath {
+                universe_id: UniverseId(universe_id),
+                datastore_id: datastore_id.clone(),
+                scope: None,
+                entry_id: id.clone(),
+            }),
+            create_time: None,
+            revision_id: None,
+            revision_create_time: None,
+            state: None,
+            etag: None,
+            value: None,
+            id: id.clone(),
+            users: None,
+            attributes: None,
+        })
+        .collect::<Vec<_>>();
+    let Some((entries, next_page_token)) = paginate(&entries, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "dataStoreEntries": entries, "nextPageToken": next_page_token })).into_response()
+}
+
+async fn create_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id)): Path<(u64, String)>,
+    Query(query): Query<PageQuery>,
+    Json(body): Json<EntryBody>,
+) -> Response {
+    let Some(entry_id) = query.id else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "id is required",
+        );
+    };
+    let mut state = lock(&state);
+    let revision = next_revision(&mut state);
+    let entries = state
+        .fixtures
+        .datastores
+        .entry((UniverseId(universe_id), datastore_id.clone()))
+        .or_default();
+    if entries.contains_key(&entry_id) {
+        return error(
+            StatusCode::CONFLICT,
+            "ALREADY_EXISTS",
+            "entry already exists",
+        );
+    }
+
+    let entry = new_entry(
+        UniverseId(universe_id),
+        &datastore_id,
+        &entry_id,
+        body.value,
+        body.users,
+        body.attributes,
+        revision,
+    );
+    entries.insert(entry_id, vec![entry.clone()]);
+    Json(entry).into_response()
+}
+
+async fn get_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, entry_id)): Path<(u64, String, String)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let state = lock(&state);
+    let entries = state
+        .fixtures
+        .datastores
+        .get(&(UniverseId(universe_id), datastore_id));
+
+    if let Some(entry_id) = entry_id.strip_suffix(":listRevisions") {
+        let Some(revisions) = entries.and_then(|e| e.get(entry_id)) else {
+            return not_found();
+        };
+        let revisions = revisions
+            .iter()
+            .rev()
+            .map(|r| {
+                serde_json::from_value::<PartialDatastoreEntry>(json!(r))
+                    .expect("a datastore entry is a valid partial entry")
+            })
+            .collect::<Vec<_>>();
+        let Some((revisions, next_page_token)) = paginate(&revisions, &query) else {
+            return invalid_page_token();
+        };
+        return Json(json!({ "dataStoreEntries": revisions, "nextPageToken": next_page_token }))
+            .into_response();
+    }
+
+    let (entry_id, revision_id) = entry_id.split_once('@').unwrap_or((&entry_id, "latest"));
+    let Some(revisions) = entries.and_then(|e| e.get(entry_id)) else {
+        return not_found();
+    };
+    let revision = if revision_id == "latest" {
+        revisions.last()
+    } else {
+        revisions.iter().find(|r| r.revision_id == revision_id)
+    };
+    match revision {
+        Some(revision) => Json(revision).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn update_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, entry_id)): Path<(u64, String, String)>,
+    Json(body): Json<EntryBody>,
+) -> Response {
+    let mut state = lock(&state);
+    let revision = next_revision(&mut state);
+    let Some(revisions) = state
+        .fixtures
+        .datastores
+        .get_mut(&(UniverseId(universe_id), datastore_id.clone()))
+        .and_then(|e| e.get_mut(&entry_id))
+    else {
+        return not_found();
+    };
+
+    let mut entry = new_entry(
+        UniverseId(universe_id),
+        &datastore_id,
+        &entry_id,
+        body.value,
+        body.users,
+        body.attributes,
+        revision,
+    );
+    if let Some(first) = revisions.first() {
+        entry.create_time = first.create_time;
+    }
+    revisions.push(entry.clone());
+    Json(entry).into_response()
+}
+
+async fn delete_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, entry_id)): Path<(u64, String, String)>,
+) -> Response {
+    let mut state = lock(&state);
+    let removed = state
+        .fixtures
+        .datastores
+        .get_mut(&(UniverseId(universe_id), datastore_id))
+        .and_then(|e| e.remove(&entry_id));
+    match removed {
+        Some(_) => Json(json!({})).into_response(),
+        None => not_found(),
+    }
+}
+
+type OrderedDatastoreKey = (UniverseId, String, String);
+
+#[derive(Deserialize)]
+struct OrderedEntryBody {
+    value: i64,
+}
+
+#[derive(Deserialize)]
+struct IncrementBody {
+    amount: i64,
+}
+
+fn ordered_entry(key: &OrderedDatastoreKey, entry_id: &str, value: i64) -> OrderedDatastoreEntry {
+    let (universe_id, datastore_id, scope) = key;
+    OrderedDatastoreEntry {
+        path: format!(
+            "universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}"
+        ),
+        value,
+        id: entry_id.to_string(),
+    }
+}
+
+/// Parses the `entry >= {min} && entry <= {max}` filter of ordered datastores, where either bound
+/// may be missing. Returns `None` if the filter is malformed.
+fn ordered_bounds(filter: Option<&str>) -> Option<(i64, i64)> {
+    let mut bounds = (i64::MIN, i64::MAX);
+    for condition in filter.into_iter().flat_map(|f| f.split(" && ")) {
+        if let Some(min) = condition.strip_prefix("entry >= ") {
+            bounds.0 = min.parse().ok()?;
+        } else if let Some(max) = condition.strip_prefix("entry <= ") {
+            bounds.1 = max.parse().ok()?;
+        } else {
+            return None;
+        }
+    }
+    Some(bounds)
+}
+
+async fn list_ordered_entries(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, scope)): Path<(u64, String, String)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let descending = match query.order_by.as_deref() {
+        None | Some("value") => false,
+        Some("value desc") => true,
+        Some(_) => return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad orderBy"),
+    };
+    let Some((min, max)) = ordered_bounds(query.filter.as_deref()) else {
+        return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad filter");
+    };
+    let key = (UniverseId(universe_id), datastore_id, scope);
+    let state = lock(&state);
+    let mut entries = state
+        .fixtures
+        .ordered_datastores
+        .get(&key)
+        .into_iter()
+        .flatten()
+        .filter(|(_, value)| (min..=max).contains(*value))
+        .map(|(id, value)| ordered_entry(&key, id, *value))
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|e| e.value);
+    if descending {
+        entries.reverse();
+    }
+    let Some((entries, next_page_token)) = paginate(&entries, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "orderedDataStoreEntries": entries, "nextPageToken": next_page_token }))
+        .into_response()
+}
+
+async fn create_ordered_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, scope)): Path<(u64, String, String)>,
+    Query(query): Query<PageQuery>,
+    Json(body): Json<OrderedEntryBody>,
+) -> Response {
+    let Some(entry_id) = query.id else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "id is required",
+        );
+    };
+    let key = (UniverseId(universe_id), datastore_id, scope);
+    let mut state = lock(&state);
+    let entries = state
+        .fixtures
+        .ordered_datastores
+        .entry(key.clone())
+        .or_default();
+    if entries.contains_key(&entry_id) {
+        return error(
+            StatusCode::CONFLICT,
+            "ALREADY_EXISTS",
+            "entry already exists",
+        );
+    }
+    entries.insert(entry_id.clone(), body.value);
+    Json(ordered_entry(&key, &entry_id, body.value)).into_response()
+}
+
+async fn get_ordered_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, scope, entry_id)): Path<(u64, String, String, String)>,
+) -> Response {
+    let key = (UniverseId(universe_id), datastore_id, scope);
+    let state = lock(&state);
+    match state
+        .fixtures
+        .ordered_datastores
+        .get(&key)
+        .and_then(|e| e.get(&entry_id))
+    {
+        Some(value) => Json(ordered_entry(&key, &entry_id, *value)).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn update_ordered_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, scope, entry_id)): Path<(u64, String, String, String)>,
+    Query(query): Query<PageQuery>,
+    Json(body): Json<OrderedEntryBody>,
+) -> Response {
+    let key = (UniverseId(universe_id), datastore_id, scope);
+    let mut state = lock(&state);
+    let entries = state
+        .fixtures
+        .ordered_datastores
+        .entry(key.clone())
+        .or_default();
+    if !query.allow_missing && !entries.contains_key(&entry_id) {
+        return not_found();
+    }
+    entries.insert(entry_id.clone(), body.value);
+    Json(ordered_entry(&key, &entry_id, body.value)).into_response()
+}
+
+async fn increment_ordered_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, scope, entry_id)): Path<(u64, String, String, String)>,
+    Json(body): Json<IncrementBody>,
+) -> Response {
+    let Some(entry_id) = entry_id.strip_suffix(":increment") else {
+        return not_found();
+    };
+    let key = (UniverseId(universe_id), datastore_id, scope);
+    let mut state = lock(&state);
+    let value = state
+        .fixtures
+        .ordered_datastores
+        .entry(key.clone())
+        .or_default()
+        .entry(entry_id.to_string())
+        .or_default();
+    *value += body.amount;
+    Json(ordered_entry(&key, entry_id, *value)).into_response()
+}
+
+async fn delete_ordered_entry(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, datastore_id, scope, entry_id)): Path<(u64, String, String, String)>,
+) -> Response {
+    let removed = lock(&state)
+        .fixtures
+        .ordered_datastores
+        .get_mut(&(UniverseId(universe_id), datastore_id, scope))
+        .and_then(|e| e.remove(&entry_id));
+    match removed {
+        Some(_) => Json(json!({})).into_response(),
+        None => not_found(),
+    }
+}
+
+type MemoryStoreKey = (UniverseId, String);
+
+#[derive(Deserialize)]
+struct SortedMapItemBody {
+    value: Value,
+    ttl: String,
+    #[serde(rename = "numericSortKey", default)]
+    numeric_sort_key: Option<f64>,
+    #[serde(rename = "stringSortKey", default)]
+    string_sort_key: Option<String>,
+}
+
+impl SortedMapItemBody {
+    fn into_item(self, key: &MemoryStoreKey, item_id: &str) -> Option<MemoryStoreSortedMapItem> {
+        let ttl = parse_duration(&self.ttl)?;
+        let sort_key = match (self.string_sort_key, self.numeric_sort_key) {
+            (Some(key), _) => Some(SortKey::String(key)),
+            (None, Some(key)) => Some(SortKey::Numeric(key)),
+            (None, None) => None,
+        };
+        Some(new_sorted_map_item(
+            key.0,
+            &key.1,
+            item_id,
+            self.value,
+            Some(ttl),
+            sort_key,
+        ))
+    }
+}
+
+/// Parses durations written as a number of seconds followed by `s`, such as `3600s`.
+fn parse_duration(duration: &str) -> Option<Duration> {
+    duration
+        .strip_suffix('s')?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn invalid_ttl() -> Response {
+    error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "invalid ttl")
+}
+
+/// Items are listed by id. The fake ignores sort keys when listing and only supports filters on
+/// the id, such as `id > "a" && id < "c"`.
+async fn list_sorted_map_items(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, map_id)): Path<(u64, String)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let descending = match query.order_by.as_deref() {
+        None | Some("id") => false,
+        Some("id desc") => true,
+        Some(_) => return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad orderBy"),
+    };
+    let mut bounds = (None, None);
+    for condition in query.filter.iter().flat_map(|f| f.split(" && ")) {
+        let parsed = condition
+            .strip_prefix("id > \"")
+            .map(|id| (true, id))
+            .or_else(|| condition.strip_prefix("id < \"").map(|id| (false, id)))
+            .and_then(|(lower, id)| Some((lower, id.strip_suffix('"')?)));
+        match parsed {
+            Some((true, id)) => bounds.0 = Some(id.to_string()),
+            Some((false, id)) => bounds.1 = Some(id.to_string()),
+            None => return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad filter"),
+        }
+    }
+
+    let state = lock(&state);
+    let mut items = state
+        .fixtures
+        .sorted_maps
+        .get(&(UniverseId(universe_id), map_id))
+        .into_iter()
+        .flat_map(BTreeMap::values)
+        .filter(|item| bounds.0.as_ref().is_none_or(|after| item.id > *after))
+        .filter(|item| bounds.1.as_ref().is_none_or(|before| item.id < *before))
+        .cloned()
+        .collect::<Vec<_>>();
+    if descending {
+        items.reverse();
+    }
+    let Some((items, next_page_token)) = paginate(&items, &query) else {
+        return invalid_page_token();
+    };
+    Json(json!({ "memoryStoreSortedMapItems": items, "nextPageToken": next_page_token }))
+        .into_response()
+}
+
+async fn create_sorted_map_item(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, map_id)): Path<(u64, String)>,
+    Query(query): Query<PageQuery>,
+    Json(body): Json<SortedMapItemBody>,
+) -> Response {
+    let Some(item_id) = query.id else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "id is required",
+        );
+    };
+    let key = (UniverseId(universe_id), map_id);
+    let Some(item) = body.into_item(&key, &item_id) else {
+        return invalid_ttl();
+    };
+    let mut state = lock(&state);
+    let items = state.fixtures.sorted_maps.entry(key).or_default();
+    if items.contains_key(&item_id) {
+        return error(
+            StatusCode::CONFLICT,
+            "ALREADY_EXISTS",
+            "item already exists",
+        );
+    }
+    items.insert(item_id, item.clone());
+    Json(item).into_response()
+}
+
+async fn get_sorted_map_item(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, map_id, item_id)): Path<(u64, String, String)>,
+) -> Response {
+    let state = lock(&state);
+    match state
+        .fixtures
+        .sorted_maps
+        .get(&(UniverseId(universe_id), map_id))
+        .and_then(|items| items.get(&item_id))
+    {
+        Some(item) => Json(item).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn update_sorted_map_item(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, map_id, item_id)): Path<(u64, String, String)>,
+    Query(query): Query<PageQuery>,
+    Json(body): Json<SortedMapItemBody>,
+) -> Response {
+    let key = (UniverseId(universe_id), map_id);
+    let Some(item) = body.into_item(&key, &item_id) else {
+        return invalid_ttl();
+    };
+    let mut state = lock(&state);
+    let items = state.fixtures.sorted_maps.entry(key).or_default();
+    if !query.allow_missing && !items.contains_key(&item_id) {
+        return not_found();
+    }
+    items.insert(item_id, item.clone());
+    Json(item).into_response()
+}
+
+async fn delete_sorted_map_item(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, map_id, item_id)): Path<(u64, String, String)>,
+) -> Response {
+    let removed = lock(&state)
+        .fixtures
+        .sorted_maps
+        .get_mut(&(UniverseId(universe_id), map_id))
+        .and_then(|items| items.remove(&item_id));
+    match removed {
+        Some(_) => Json(json!({})).into_response(),
+        None => not_found(),
+    }
+}
+
+#[derive(Deserialize)]
+struct ReadQueueQuery {
+    count: usize,
+    #[serde(rename = "allOrNothing")]
+    all_or_nothing: bool,
+    #[serde(rename = "invisibilityWindow")]
+    invisibility_window: String,
+}
+
+#[derive(Deserialize)]
+struct QueueItemBody {
+    data: Value,
+    ttl: String,
+    #[serde(default)]
+    priority: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct DiscardBody {
+    #[serde(rename = "readId")]
+    read_id: String,
+}
+
+/// Items are taken from the front of the queue and kept aside until the read is discarded.
+async fn read_queue_items(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, queue_id, method)): Path<(u64, String, String)>,
+    Query(query): Query<ReadQueueQuery>,
+) -> Response {
+    if method != "items:read" {
+        return not_found();
+    }
+    if parse_duration(&query.invisibility_window).is_none() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_ARGUMENT",
+            "invalid invisibility window",
+        );
+    }
+
+    let mut state = lock(&state);
+    let read_id = format!("read-{}", next_revision(&mut state));
+    let queue = state
+        .fixtures
+        .queues
+        .entry((UniverseId(universe_id), queue_id))
+        .or_default();
+    if queue.is_empty() || (query.all_or_nothing && queue.len() < query.count) {
+        return Json(json!({ "items": [] })).into_response();
+    }
+    let items = queue
+        .drain(..query.count.min(queue.len()))
+        .collect::<Vec<_>>();
+    state.queue_reads.insert(read_id.clone(), items.clone());
+    Json(MemoryStoreQueueRead {
+        read_id: Some(read_id),
+        items,
+    })
+    .into_response()
+}
+
+async fn write_queue_items(
+    AxumState(state): AxumState<SharedState>,
+    Path((universe_id, queue_id, method)): Path<(u64, String, String)>,
+    Json(body): Json<Value>,
+) -> Response {
+    match method.as_str() {
+        "items" => {
+            let Ok(body) = serde_json::from_value::<QueueItemBody>(body) else {
+                return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad item");
+            };
+            let Some(ttl) = parse_duration(&body.ttl) else {
+                return invalid_ttl();
+            };
+            let item = MemoryStoreQueueItem {
+                path: Some(format!(
+                    "universes/{universe_id}/memory-store/queues/{queue_id}/items/{}",
+                    Utc::now().timestamp_micros()
+                )),
+                data: body.data,
+                priority: body.priority,
+                expire_time: Some(Utc::now() + ttl),
+            };
+            let mut state = lock(&state);
+            let queue = state
+                .fixtures
+                .queues
+                .entry((UniverseId(universe_id), queue_id))
+                .or_default();
+            // Higher priorities are read first, and items of the same priority in order.
+            let priority = item.priority.unwrap_or_default();
+            let position = queue
+                .iter()
+                .position(|i| i.priority.unwrap_or_default() < priority)
+                .unwrap_or(queue.len());
+            queue.insert(position, item.clone());
+            Json(item).into_response()
+        }
+        "items:discard" => {
+            let Ok(body) = serde_json::from_value::<DiscardBody>(body) else {
+                return error(StatusCode::BAD_REQUEST, "INVALID_ARGUMENT", "bad read id");
+            };
+            match lock(&state).queue_reads.remove(&body.read_id) {
+                Some(_) => Json(json!({})).into_response(),
+                None => error(
+                    StatusCode::BAD_REQUEST,
+                    "INVALID_ARGUMENT",
+                    "unknown read id",
+                ),
+            }
+        }
+        _ => not_found(),
+    }
+}
+
+async fn get_asset_details(
+    AxumState(state): AxumState<SharedState>,
+    Path(asset_id): Path<u64>,
+) -> Response {
+    let state = lock(&state);
+    let Some(name) = state
+        .fixtures
+        .catalog
+        .get(&InventoryFixture::Asset(asset_id))
+    else {
+        return invalid_item();
+    };
+    Json(json!({ "AssetId": asset_id, "Name": name, "AssetTypeId": 8 })).into_response()
+}
+
+async fn get_badge(
+    AxumState(state): AxumState<SharedState>,
+    Path(badge_id): Path<u64>,
+) -> Response {
+    let state = lock(&state);
+    let Some(name) = state
+        .fixtures
+        .catalog
+        .get(&InventoryFixture::Badge(badge_id))
+    else {
+        return not_found();
+    };
+    Json(json!({ "id": badge_id, "name": name, "enabled": true, "iconImageId": badge_id }))
+        .into_response()
+}
+
+async fn get_game_pass(
+    AxumState(state): AxumState<SharedState>,
+    Path(game_pass_id): Path<u64>,
+) -> Response {
+    let state = lock(&state);
+    let Some(name) = state
+        .fixtures
+        .catalog
+        .get(&InventoryFixture::Gamepass(game_pass_id))
+    else {
+        return invalid_item();
+    };
+    Json(json!({ "TargetId": game_pass_id, "Name": name, "IconImageAssetId": game_pass_id }))
+        .into_response()
+}
+
+#[derive(Deserialize)]
+struct IconQuery {
+    #[serde(alias = "badgeIds", alias = "gamePassIds", rename = "assetIds")]
+    ids: String,
+}
+
+async fn get_asset_icons(
+    AxumState(state): AxumState<SharedState>,
+    Query(query): Query<IconQuery>,
+) -> Json<Value> {
+    icons(&state, &query, InventoryFixture::Asset)
+}
+
+async fn get_badge_icons(
+    AxumState(state): AxumState<SharedState>,
+    Query(query): Query<IconQuery>,
+) -> Json<Value> {
+    icons(&state, &query, InventoryFixture::Badge)
+}
+
+async fn get_game_pass_icons(
+    AxumState(state): AxumState<SharedState>,
+    Query(query): Query<IconQuery>,
+) -> Json<Value> {
+    icons(&state, &query, InventoryFixture::Gamepass)
+}
+
+/// Known items get an icon on the fake CDN, the rest are reported as blocked like moderated
+/// items are.
+fn icons(state: &SharedState, query: &IconQuery, item: fn(u64) -> InventoryFixture) -> Json<Value> {
+    let state = lock(state);
+    let data = query
+        .ids
+        .split(',')
+        .filter_map(|id| id.parse::<u64>().ok())
+        .map(|id| {
+            if state.fixtures.catalog.contains_key(&item(id)) {
+                json!({ "targetId": id, "state": "Completed", "imageUrl": format!("https://tr.rbxcdn.com/{id}.png") })
+            } else {
+                json!({ "targetId": id, "state": "Blocked", "imageUrl": null })
+            }
+        })
+        .collect::<Vec<_>>();
+    Json(json!({ "data": data }))
+}
+
+async fn update_member(
+    AxumState(state): AxumState<SharedState>,
+    Path((guild_id, user_id)): Path<(u64, u64)>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    lock(&state)
+        .discord_requests
+        .push(DiscordRequest::UpdateMember {
+            guild_id,
+            user_id,
+            body,
+        });
+    Json(json!({}))
+}
+
+async fn create_message(
+    AxumState(state): AxumState<SharedState>,
+    Path(channel_id): Path<u64>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    lock(&state)
+        .discord_requests
+        .push(DiscordRequest::CreateMessage { channel_id, body });
+    Json(json!({}))
+}
+
+fn next_revision(state: &mut State) -> String {
+    let revision = state.next_revision;
+    state.next_revision += 1;
+    revision.to_string()
+}
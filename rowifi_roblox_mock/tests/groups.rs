@@ -0,0 +1,139 @@
+use futures_util::TryStreamExt;
+use rowifi_roblox::error::ApiKeySource;
+use rowifi_roblox_mock::{Fixtures, MockRoblox};
+use rowifi_roblox_models::{
+    group::GroupRole,
+    id::{GroupId, RoleId, UserId},
+    resource::RolePath,
+};
+
+const GROUP_ID: u64 = 10;
+const MEMBER_ROLE: u64 = 1;
+const OFFICER_ROLE: u64 = 2;
+
+fn rank(id: u64, rank: u32, name: &str) -> GroupRole {
+    GroupRole {
+        path: RolePath::new(GroupId(GROUP_ID), RoleId(id)),
+        id: RoleId(id),
+        rank,
+        display_name: name.into(),
+        member_count: None,
+    }
+}
+
+fn fixtures() -> Fixtures {
+    (1..=5).fold(
+        Fixtures::new().group_ranks(
+            GROUP_ID,
+            vec![
+                rank(MEMBER_ROLE, 1, "Member"),
+                rank(OFFICER_ROLE, 100, "Officer"),
+            ],
+        ),
+        |fixtures, user_id| fixtures.group_member(GROUP_ID, user_id, MEMBER_ROLE),
+    )
+}
+
+#[tokio::test]
+async fn get_group_membership_filters_by_user() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let membership = roblox
+        .get_group_membership(GroupId(GROUP_ID), UserId(3))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(membership.user_id(), UserId(3));
+    assert_eq!(membership.role_id(), RoleId(MEMBER_ROLE));
+    assert!(mock.requests().contains(
+        &"GET /cloud/v2/groups/10/memberships?maxPageSize=1&filter=user%20%3D%3D%20%27users%2F3%27"
+            .to_string()
+    ));
+
+    let missing = roblox
+        .get_group_membership(GroupId(GROUP_ID), UserId(99))
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn stream_group_memberships_reads_every_page() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let members = roblox
+        .stream_group_memberships(GroupId(GROUP_ID), None, 2)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(members.len(), 5);
+}
+
+#[tokio::test]
+async fn update_group_membership_changes_the_role() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let membership = roblox
+        .get_group_membership(GroupId(GROUP_ID), UserId(2))
+        .await
+        .unwrap()
+        .unwrap();
+    let updated = roblox
+        .update_group_membership(GroupId(GROUP_ID), membership.id(), RoleId(OFFICER_ROLE))
+        .await
+        .unwrap();
+    assert_eq!(updated.role_id(), RoleId(OFFICER_ROLE));
+    assert_eq!(
+        mock.group_member_role(GROUP_ID, 2),
+        Some(RoleId(OFFICER_ROLE))
+    );
+
+    let err = roblox
+        .update_group_membership(GroupId(GROUP_ID), membership.id(), RoleId(77))
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_error().unwrap().status, 400);
+}
+
+#[tokio::test]
+async fn remove_group_member_exiles_the_user() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    roblox
+        .remove_group_member(GroupId(GROUP_ID), UserId(4))
+        .await
+        .unwrap();
+    assert_eq!(mock.group_member_role(GROUP_ID, 4), None);
+    assert!(mock
+        .requests()
+        .contains(&"DELETE /v1/groups/10/users/4".to_string()));
+    assert_eq!(mock.api_keys_used().last().unwrap(), "mock-open-cloud-key");
+
+    assert!(roblox
+        .remove_group_member(GroupId(GROUP_ID), UserId(4))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn errors_report_the_key_used() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+    let guild_roblox = roblox.with_api_key("guild-key");
+
+    let err = roblox
+        .remove_group_member(GroupId(GROUP_ID), UserId(99))
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_error().unwrap().api_key, ApiKeySource::Bot);
+    let err = guild_roblox
+        .remove_group_member(GroupId(GROUP_ID), UserId(99))
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_error().unwrap().api_key, ApiKeySource::Server);
+    assert_eq!(mock.api_keys_used().last().unwrap(), "guild-key");
+}
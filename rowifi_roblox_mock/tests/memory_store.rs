@@ -0,0 +1,224 @@
+use std::time::Duration;
+
+use futures_util::TryStreamExt;
+use rowifi_roblox::MemoryStoreSortedMapItemArgs;
+use rowifi_roblox_mock::{Fixtures, MockRoblox};
+use rowifi_roblox_models::{id::UniverseId, memory_store::SortKey};
+use serde_json::json;
+
+const UNIVERSE_ID: u64 = 1;
+const ITEMS: &str = "/cloud/v2/universes/1/memory-store/sorted-maps/scores/items";
+const QUEUE: &str = "/cloud/v2/universes/1/memory-store/queues/jobs/items";
+
+fn fixtures() -> Fixtures {
+    ["a", "b", "c", "d", "e"]
+        .into_iter()
+        .enumerate()
+        .fold(Fixtures::new(), |fixtures, (value, id)| {
+            fixtures.sorted_map_item(UNIVERSE_ID, "scores", id, json!(value))
+        })
+}
+
+fn args(value: i64) -> MemoryStoreSortedMapItemArgs {
+    MemoryStoreSortedMapItemArgs {
+        value: json!(value),
+        ttl: Duration::from_secs(60),
+        sort_key: Some(SortKey::Numeric(value as f64)),
+    }
+}
+
+#[tokio::test]
+async fn sorted_map_items_are_streamed_in_either_order() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let items = roblox
+        .stream_memory_store_sorted_map_items(UniverseId(UNIVERSE_ID), "scores", false, None, 2)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    let ids = items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids, ["a", "b", "c", "d", "e"]);
+    assert_eq!(
+        mock.requests()[..2],
+        [
+            format!("GET {ITEMS}?maxPageSize=2"),
+            format!("GET {ITEMS}?maxPageSize=2&pageToken=offset%2B2%3D"),
+        ]
+    );
+
+    let page = roblox
+        .list_memory_store_sorted_map_items(
+            UniverseId(UNIVERSE_ID),
+            "scores",
+            true,
+            Some("id > \"a\" && id < \"e\""),
+            None,
+            10,
+        )
+        .await
+        .unwrap();
+    let ids = page.data.iter().map(|i| i.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids, ["d", "c", "b"]);
+    assert!(page.next_page_token.is_none());
+    assert_eq!(
+        mock.requests().last().unwrap(),
+        &format!("GET {ITEMS}?maxPageSize=10&orderBy=id%20desc&filter=id%20%3E%20%22a%22%20%26%26%20id%20%3C%20%22e%22")
+    );
+}
+
+#[tokio::test]
+async fn sorted_map_items_are_read_by_id() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let item = roblox
+        .get_memory_store_sorted_map_item(UniverseId(UNIVERSE_ID), "scores", "c")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.value, json!(2));
+    assert_eq!(
+        item.path,
+        "universes/1/memory-store/sorted-maps/scores/items/c"
+    );
+
+    let missing = roblox
+        .get_memory_store_sorted_map_item(UniverseId(UNIVERSE_ID), "other", "c")
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn sorted_map_items_are_created_updated_and_deleted() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let created = roblox
+        .create_memory_store_sorted_map_item(UniverseId(UNIVERSE_ID), "scores", "f", &args(5))
+        .await
+        .unwrap();
+    assert_eq!(created.id, "f");
+    assert_eq!(created.sort_key(), Some(SortKey::Numeric(5.0)));
+    assert!(created.expire_time.is_some());
+    let err = roblox
+        .create_memory_store_sorted_map_item(UniverseId(UNIVERSE_ID), "scores", "f", &args(6))
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_error().unwrap().status, 409);
+
+    let updated = roblox
+        .update_memory_store_sorted_map_item(
+            UniverseId(UNIVERSE_ID),
+            "scores",
+            "f",
+            &args(7),
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.value, json!(7));
+
+    let err = roblox
+        .update_memory_store_sorted_map_item(
+            UniverseId(UNIVERSE_ID),
+            "scores",
+            "g",
+            &args(1),
+            false,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+    roblox
+        .update_memory_store_sorted_map_item(UniverseId(UNIVERSE_ID), "scores", "g", &args(1), true)
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.sorted_map_item(UNIVERSE_ID, "scores", "g")
+            .unwrap()
+            .value,
+        json!(1)
+    );
+
+    roblox
+        .delete_memory_store_sorted_map_item(UniverseId(UNIVERSE_ID), "scores", "g")
+        .await
+        .unwrap();
+    assert!(mock.sorted_map_item(UNIVERSE_ID, "scores", "g").is_none());
+
+    let requests = mock.requests();
+    assert!(requests.contains(&format!("POST {ITEMS}?id=f")));
+    assert!(requests.contains(&format!("PATCH {ITEMS}/g?allowMissing=false")));
+    assert!(requests.contains(&format!("PATCH {ITEMS}/g?allowMissing=true")));
+    assert!(requests.contains(&format!("DELETE {ITEMS}/g")));
+}
+
+#[tokio::test]
+async fn queue_items_are_read_by_priority_and_discarded() {
+    let mock =
+        MockRoblox::start(Fixtures::new().queue_item(UNIVERSE_ID, "jobs", json!("first"))).await;
+    let roblox = mock.client();
+
+    let ttl = Duration::from_secs(60);
+    roblox
+        .enqueue_memory_store_queue_item(
+            UniverseId(UNIVERSE_ID),
+            "jobs",
+            json!("urgent"),
+            Some(10.0),
+            ttl,
+        )
+        .await
+        .unwrap();
+    let item = roblox
+        .enqueue_memory_store_queue_item(UniverseId(UNIVERSE_ID), "jobs", json!("last"), None, ttl)
+        .await
+        .unwrap();
+    assert_eq!(item.data, json!("last"));
+    assert!(item.expire_time.is_some());
+
+    let read = roblox
+        .read_memory_store_queue_items(UniverseId(UNIVERSE_ID), "jobs", 2, false, ttl)
+        .await
+        .unwrap();
+    let data = read
+        .items
+        .iter()
+        .map(|i| i.data.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(data, [json!("urgent"), json!("first")]);
+    assert_eq!(mock.queue_items(UNIVERSE_ID, "jobs").len(), 1);
+    assert_eq!(mock.pending_queue_reads(), 1);
+
+    // Too few items are left for an all or nothing read.
+    let empty = roblox
+        .read_memory_store_queue_items(UniverseId(UNIVERSE_ID), "jobs", 2, true, ttl)
+        .await
+        .unwrap();
+    assert!(empty.read_id.is_none());
+    assert!(empty.items.is_empty());
+
+    roblox
+        .discard_memory_store_queue_items(
+            UniverseId(UNIVERSE_ID),
+            "jobs",
+            read.read_id.as_deref().unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(mock.pending_queue_reads(), 0);
+    let err = roblox
+        .discard_memory_store_queue_items(UniverseId(UNIVERSE_ID), "jobs", "unknown")
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_error().unwrap().status, 400);
+
+    let requests = mock.requests();
+    assert!(requests.contains(&format!("POST {QUEUE}")));
+    assert!(requests.contains(&format!(
+        "GET {QUEUE}:read?count=2&allOrNothing=true&invisibilityWindow=60s"
+    )));
+    assert!(requests.contains(&format!("POST {QUEUE}:discard")));
+}
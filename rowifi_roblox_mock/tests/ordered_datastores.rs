@@ -0,0 +1,199 @@
+use futures_util::TryStreamExt;
+use rowifi_roblox::OrderedDatastoreQuery;
+use rowifi_roblox_mock::{Fixtures, MockRoblox};
+use rowifi_roblox_models::{datastore::OrderedDatastoreOrder, id::UniverseId};
+
+const UNIVERSE_ID: u64 = 1;
+const ENTRIES: &str = "/cloud/v2/universes/1/ordered-data-stores/xp/scopes/global/entries";
+
+fn fixtures() -> Fixtures {
+    [("a", 30), ("b", 10), ("c", 50), ("d", 20), ("e", 40)]
+        .into_iter()
+        .fold(Fixtures::new(), |fixtures, (id, value)| {
+            fixtures.ordered_datastore_entry(UNIVERSE_ID, "xp", "global", id, value)
+        })
+}
+
+fn ids(entries: &[rowifi_roblox_models::datastore::OrderedDatastoreEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn entries_are_listed_in_ascending_order_by_default() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let entries = roblox
+        .stream_ordered_datastore_entries(
+            UniverseId(UNIVERSE_ID),
+            "xp",
+            "global",
+            OrderedDatastoreQuery::default(),
+            2,
+        )
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(ids(&entries), ["b", "d", "a", "e", "c"]);
+    assert_eq!(entries[0].value, 10);
+    assert_eq!(
+        entries[0].path,
+        "universes/1/ordered-data-stores/xp/scopes/global/entries/b"
+    );
+    assert_eq!(
+        mock.requests()[..2],
+        [
+            format!("GET {ENTRIES}?maxPageSize=2"),
+            format!("GET {ENTRIES}?maxPageSize=2&pageToken=offset%2B2%3D"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn descending_order_and_bounds_are_encoded() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let query = OrderedDatastoreQuery {
+        order: OrderedDatastoreOrder::Descending,
+        min_value: Some(20),
+        max_value: Some(40),
+    };
+    let entries = roblox
+        .stream_ordered_datastore_entries(UniverseId(UNIVERSE_ID), "xp", "global", query, 2)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(ids(&entries), ["e", "a", "d"]);
+    assert_eq!(
+        mock.requests(),
+        [
+            format!("GET {ENTRIES}?maxPageSize=2&orderBy=value%20desc&filter=entry%20%3E%3D%2020%20%26%26%20entry%20%3C%3D%2040"),
+            format!("GET {ENTRIES}?maxPageSize=2&orderBy=value%20desc&filter=entry%20%3E%3D%2020%20%26%26%20entry%20%3C%3D%2040&pageToken=offset%2B2%3D"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn a_single_bound_is_encoded_on_its_own() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let query = OrderedDatastoreQuery {
+        max_value: Some(20),
+        ..OrderedDatastoreQuery::default()
+    };
+    let page = roblox
+        .list_ordered_datastore_entries(UniverseId(UNIVERSE_ID), "xp", "global", query, None, 1000)
+        .await
+        .unwrap();
+    assert_eq!(ids(&page.data), ["b", "d"]);
+    assert!(page.next_page_token.is_none());
+    assert_eq!(
+        mock.requests(),
+        [format!(
+            "GET {ENTRIES}?maxPageSize=100&filter=entry%20%3C%3D%2020"
+        )]
+    );
+}
+
+#[tokio::test]
+async fn entries_are_read_by_id() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let entry = roblox
+        .get_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "c")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(entry.value, 50);
+
+    let missing = roblox
+        .get_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "other", "c")
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn entries_are_created_and_updated() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let created = roblox
+        .create_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "f", 5)
+        .await
+        .unwrap();
+    assert_eq!(created.id, "f");
+    assert_eq!(
+        mock.ordered_datastore_entry(UNIVERSE_ID, "xp", "global", "f"),
+        Some(5)
+    );
+    let err = roblox
+        .create_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "f", 6)
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_error().unwrap().status, 409);
+
+    let updated = roblox
+        .update_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "f", 7, false)
+        .await
+        .unwrap();
+    assert_eq!(updated.value, 7);
+
+    let err = roblox
+        .update_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "g", 1, false)
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+    roblox
+        .update_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "g", 1, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.ordered_datastore_entry(UNIVERSE_ID, "xp", "global", "g"),
+        Some(1)
+    );
+
+    let requests = mock.requests();
+    assert!(requests.contains(&format!("POST {ENTRIES}?id=f")));
+    assert!(requests.contains(&format!("PATCH {ENTRIES}/g?allowMissing=false")));
+    assert!(requests.contains(&format!("PATCH {ENTRIES}/g?allowMissing=true")));
+}
+
+#[tokio::test]
+async fn entries_are_incremented() {
+    let mock = MockRoblox::start(fixtures()).await;
+    let roblox = mock.client();
+
+    let entry = roblox
+        .increment_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "a", 5)
+        .await
+        .unwrap();
+    assert_eq!(entry.value, 35);
+    let entry = roblox
+        .increment_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "a", -40)
+        .await
+        .unwrap();
+    assert_eq!(entry.value, -5);
+
+    // Incrementing a missing entry creates it.
+    let entry = roblox
+        .increment_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "z", 3)
+        .await
+        .unwrap();
+    assert_eq!(entry.value, 3);
+    assert!(mock
+        .requests()
+        .contains(&format!("POST {ENTRIES}/z:increment")));
+
+    roblox
+        .delete_ordered_datastore_entry(UniverseId(UNIVERSE_ID), "xp", "global", "z")
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.ordered_datastore_entry(UNIVERSE_ID, "xp", "global", "z"),
+        None
+    );
+}
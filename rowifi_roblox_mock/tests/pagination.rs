@@ -0,0 +1,162 @@
+use futures_util::{StreamExt, TryStreamExt};
+use rowifi_roblox::UpdateDatastoreEntryArgs;
+use rowifi_roblox_mock::{Fixtures, MockRoblox};
+use rowifi_roblox_models::id::UniverseId;
+use serde_json::json;
+
+const UNIVERSE_ID: u64 = 1;
+
+fn entries(count: usize) -> Fixtures {
+    (0..count).fold(Fixtures::new(), |fixtures, i| {
+        fixtures.datastore_entry(UNIVERSE_ID, "players", &format!("player_{i:02}"), json!(i))
+    })
+}
+
+fn list_requests(mock: &MockRoblox, path: &str) -> Vec<String> {
+    mock.requests()
+        .into_iter()
+        .filter(|r| r.starts_with(&format!("GET {path}")))
+        .collect()
+}
+
+#[tokio::test]
+async fn stream_follows_encoded_page_tokens() {
+    let mock = MockRoblox::start(entries(25)).await;
+    let roblox = mock.client();
+
+    let entries = roblox
+        .stream_datastore_entries(UniverseId(UNIVERSE_ID), "players", 10, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 25);
+    assert_eq!(entries[0].id, "player_00");
+    assert_eq!(entries[24].id, "player_24");
+
+    let requests = list_requests(&mock, "/cloud/v2/universes/1/data-stores/players/entries");
+    assert_eq!(
+        requests,
+        [
+            "GET /cloud/v2/universes/1/data-stores/players/entries?maxPageSize=10",
+            "GET /cloud/v2/universes/1/data-stores/players/entries?maxPageSize=10&pageToken=offset%2B10%3D",
+            "GET /cloud/v2/universes/1/data-stores/players/entries?maxPageSize=10&pageToken=offset%2B20%3D",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn stream_keeps_the_filter_on_every_page() {
+    let fixtures = entries(5).datastore_entry(UNIVERSE_ID, "players", "admin_1", json!(0));
+    let mock = MockRoblox::start(fixtures).await;
+    let roblox = mock.client();
+
+    let entries = roblox
+        .stream_datastore_entries(UniverseId(UNIVERSE_ID), "players", 2, Some("player_"))
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 5);
+    assert!(entries.iter().all(|e| e.id.starts_with("player_")));
+
+    let requests = list_requests(&mock, "/cloud/v2/universes/1/data-stores/players/entries");
+    assert_eq!(requests.len(), 3);
+    assert!(requests
+        .iter()
+        .all(|r| r.contains("filter=id.startsWith(%22player_%22)")));
+}
+
+#[tokio::test]
+async fn page_sizes_are_clamped() {
+    let mock = MockRoblox::start(entries(3)).await;
+    let roblox = mock.client();
+
+    let entries = roblox
+        .stream_datastore_entries(UniverseId(UNIVERSE_ID), "players", 0, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 3);
+    let datastores = roblox
+        .stream_datastores(UniverseId(UNIVERSE_ID), 1000)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(datastores.len(), 1);
+
+    let requests = mock.requests();
+    assert_eq!(
+        requests
+            .iter()
+            .filter(|r| r.contains("/entries?maxPageSize=1"))
+            .count(),
+        3
+    );
+    assert!(requests.contains(&"GET /cloud/v2/universes/1/data-stores?maxPageSize=100".to_string()));
+}
+
+#[tokio::test]
+async fn stream_ends_on_a_page_without_a_token() {
+    let mock = MockRoblox::start(entries(4)).await;
+    let roblox = mock.client();
+
+    // Exactly one full page: the mock sends no token, so no second request is made.
+    let entries = roblox
+        .stream_datastore_entries(UniverseId(UNIVERSE_ID), "players", 4, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(
+        list_requests(&mock, "/cloud/v2/universes/1/data-stores/players/entries").len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn stream_ends_on_the_first_error() {
+    let mock = MockRoblox::start(Fixtures::new()).await;
+    let roblox = mock.client();
+
+    let pages = roblox
+        .stream_datastore_entries(UniverseId(UNIVERSE_ID), "missing", 10, None)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(pages.len(), 1);
+    assert!(pages[0].as_ref().is_err_and(|e| e.is_not_found()));
+}
+
+#[tokio::test]
+async fn revisions_are_paginated() {
+    let mock = MockRoblox::start(entries(1)).await;
+    let roblox = mock.client();
+    for i in 0..4 {
+        roblox
+            .update_datastore_entry(
+                UniverseId(UNIVERSE_ID),
+                "players",
+                "player_00",
+                UpdateDatastoreEntryArgs {
+                    value: json!(i),
+                    users: Vec::new(),
+                    attributes: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    let revisions = roblox
+        .stream_datastore_entry_revisions(UniverseId(UNIVERSE_ID), "players", "player_00", 2)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(revisions.len(), 5);
+    assert_eq!(
+        list_requests(
+            &mock,
+            "/cloud/v2/universes/1/data-stores/players/entries/player_00:listRevisions"
+        )
+        .len(),
+        3
+    );
+}
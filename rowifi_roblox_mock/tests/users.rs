@@ -0,0 +1,57 @@
+use rowifi_roblox_mock::{Fixtures, MockRoblox};
+use rowifi_roblox_models::{
+    id::UserId,
+    thumbnail::{AvatarThumbnailKind, ThumbnailOptions},
+};
+
+fn fixtures(count: u64) -> Fixtures {
+    (1..=count).fold(Fixtures::new(), |fixtures, id| {
+        fixtures.user(id, &format!("user{id}"))
+    })
+}
+
+#[tokio::test]
+async fn get_users_chunks_large_requests() {
+    let mock = MockRoblox::start(fixtures(250)).await;
+    let roblox = mock.client();
+
+    let users = roblox.get_users((1..=260).map(UserId)).await.unwrap();
+    assert_eq!(users.len(), 250);
+}
+
+#[tokio::test]
+async fn resolve_users_with_thumbnails() {
+    let mock = MockRoblox::start(fixtures(150)).await;
+    let roblox = mock.client();
+
+    let user_ids = (1..=150).chain([1, 2, 999]).map(UserId);
+    let users = roblox
+        .resolve_users(
+            user_ids,
+            Some((AvatarThumbnailKind::Bust, ThumbnailOptions::default())),
+        )
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 150);
+    assert!(!users.contains_key(&UserId(999)));
+
+    let user = &users[&UserId(120)];
+    assert_eq!(user.user.name, "user120");
+    assert_eq!(
+        user.thumbnail.as_deref(),
+        Some("https://tr.rbxcdn.com/120/150x150/AvatarBust/Png")
+    );
+}
+
+#[tokio::test]
+async fn resolve_users_without_thumbnails() {
+    let mock = MockRoblox::start(fixtures(3)).await;
+    let roblox = mock.client();
+
+    let users = roblox
+        .resolve_users([UserId(1), UserId(3)], None)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 2);
+    assert!(users.values().all(|u| u.thumbnail.is_none()));
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// The details of an Open Cloud API key, as returned by the introspection endpoint.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiKeyInfo {
+    pub name: String,
+    #[serde(default)]
+    pub authorized_user_id: Option<u64>,
+    #[serde(default)]
+    pub scopes: Vec<ApiKeyScope>,
+    pub enabled: bool,
+    pub expired: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiKeyScope {
+    /// The name of the scope, such as `universe-datastores.objects`.
+    pub name: String,
+    #[serde(default)]
+    pub operations: Vec<String>,
+    /// The universes the scope is restricted to. Empty if the scope applies to all of them.
+    #[serde(default)]
+    pub universe_ids: Vec<String>,
+}
+
+impl ApiKeyInfo {
+    /// Whether the key is allowed to perform `operation` under the scope `name`.
+    #[must_use]
+    pub fn allows(&self, name: &str, operation: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| s.name == name && s.operations.iter().any(|o| o == operation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_introspection() {
+        let json = r#"{
+            "name": "RoWifi",
+            "authorized_user_id": 1,
+            "scopes": [
+                { "name": "universe-datastores.objects", "operations": ["read", "update"], "universe_ids": ["500"] },
+                { "name": "universe-messaging-service", "operations": ["publish"] }
+            ],
+            "enabled": true,
+            "expired": false,
+            "expiration_time": "2030-01-01T00:00:00Z"
+        }"#;
+        let info = serde_json::from_str::<ApiKeyInfo>(json).unwrap();
+        assert!(info.allows("universe-datastores.objects", "update"));
+        assert!(info.allows("universe-messaging-service", "publish"));
+        assert!(!info.allows("universe-datastores.objects", "delete"));
+        assert_eq!(info.scopes[0].universe_ids, vec!["500".to_string()]);
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::id::AssetId;
+
+/// The catalog details of an asset, as returned by the economy API.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AssetDetails {
+    #[serde(rename = "AssetId")]
+    pub id: AssetId,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Description", default)]
+    pub description: Option<String>,
+    #[serde(rename = "AssetTypeId")]
+    pub asset_type_id: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Badge {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Whether the badge can currently be awarded.
+    pub enabled: bool,
+    #[serde(rename = "iconImageId", default)]
+    pub icon_image_id: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GamePass {
+    #[serde(rename = "TargetId")]
+    pub id: u64,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Description", default)]
+    pub description: Option<String>,
+    #[serde(rename = "IconImageAssetId", default)]
+    pub icon_image_asset_id: Option<u64>,
+}
+
+/// The kinds of items the thumbnails API serves icons for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IconKind {
+    Asset,
+    Badge,
+    GamePass,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_game_pass() {
+        let json = r#"{
+            "TargetId": 123,
+            "ProductType": "Game Pass",
+            "AssetId": 0,
+            "ProductId": 456,
+            "Name": "VIP",
+            "Description": null,
+            "IconImageAssetId": 789
+        }"#;
+        let game_pass = serde_json::from_str::<GamePass>(json).unwrap();
+        assert_eq!(game_pass.id, 123);
+        assert_eq!(game_pass.name, "VIP");
+        assert_eq!(game_pass.icon_image_asset_id, Some(789));
+    }
+}
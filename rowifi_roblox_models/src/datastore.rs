@@ -2,13 +2,20 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::resource::{DatastoreEntryPath, DatastorePath, UserPath};
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Datastore {
+    #[serde(default)]
+    pub path: Option<DatastorePath>,
     pub id: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PartialDatastoreEntry {
+    /// The resource path of the entry.
+    #[serde(default)]
+    pub path: Option<DatastoreEntryPath>,
     /// The timestamp when the data store entry was created.
     #[serde(rename = "createTime", default)]
     pub create_time: Option<DateTime<Utc>>,
@@ -35,7 +42,7 @@ pub struct PartialDatastoreEntry {
     pub id: String,
     /// Users associated with the entry.
     #[serde(default)]
-    pub users: Option<Vec<String>>,
+    pub users: Option<Vec<UserPath>>,
     /// An arbitrary set of attributes associated with the entry.
     #[serde(default)]
     pub attributes: Option<Value>,
@@ -53,6 +60,9 @@ pub enum DatastoreEntryState {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DatastoreEntry {
+    /// The resource path of the entry.
+    #[serde(default)]
+    pub path: Option<DatastoreEntryPath>,
     /// The timestamp when the data store entry was created.
     #[serde(rename = "createTime")]
     pub create_time: DateTime<Utc>,
@@ -76,11 +86,33 @@ pub struct DatastoreEntry {
     pub id: String,
     /// Users associated with the entry.
     #[serde(default)]
-    pub users: Vec<String>,
+    pub users: Vec<UserPath>,
     /// An arbitrary set of attributes associated with the entry.
     pub attributes: Value,
 }
 
+/// An entry of an ordered datastore. Ordered datastores only hold integers, which are used to
+/// sort the entries of the datastore.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderedDatastoreEntry {
+    /// The resource path of the entry.
+    ///
+    /// Format: `universes/{universe_id}/ordered-data-stores/{datastore_id}/scopes/{scope}/entries/{entry_id}`.
+    pub path: String,
+    /// The value of the entry.
+    pub value: i64,
+    /// The resource ID of the entry.
+    pub id: String,
+}
+
+/// The order in which the entries of an ordered datastore are listed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OrderedDatastoreOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +133,19 @@ mod tests {
         assert_eq!(serialized, "\"STATE_UNSPECIFIED\"");
     }
 
+    #[test]
+    fn test_ordered_datastore_entry_deserialization() {
+        let json = r#"{
+            "path": "universes/1/ordered-data-stores/xp/scopes/global/entries/42",
+            "value": 1500,
+            "id": "42"
+        }"#;
+        let entry: OrderedDatastoreEntry =
+            serde_json::from_str(json).expect("Deserialization failed");
+        assert_eq!(entry.id, "42");
+        assert_eq!(entry.value, 1500);
+    }
+
     #[test]
     fn test_datastore_entry_state_deserialization() {
         let json = "\"ACTIVE\"";
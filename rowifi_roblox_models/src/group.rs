@@ -1,6 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::id::{GroupId, RoleId};
+use super::{
+    id::{GroupId, RoleId, UserId},
+    resource::{GroupPath, RolePath, UserPath},
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PartialGroup {
@@ -22,7 +26,8 @@ pub struct PartialRank {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Group {
-    pub id: String,
+    pub path: GroupPath,
+    pub id: GroupId,
     #[serde(rename = "displayName")]
     pub display_name: String,
 }
@@ -35,11 +40,85 @@ pub struct GroupUserRole {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GroupRole {
-    pub path: String,
-    pub id: String,
+    pub path: RolePath,
+    pub id: RoleId,
     pub rank: u32,
     #[serde(rename = "displayName")]
     pub display_name: String,
     #[serde(rename = "memberCount")]
     pub member_count: Option<i64>,
 }
+
+/// A user's membership in a group.
+///
+/// See [`GroupMembership`](https://create.roblox.com/docs/cloud/reference/GroupMembership) for details.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GroupMembership {
+    /// The resource path of the membership. Format: `groups/{group_id}/memberships/{membership_id}`.
+    pub path: String,
+    #[serde(rename = "createTime", default)]
+    pub create_time: Option<DateTime<Utc>>,
+    #[serde(rename = "updateTime", default)]
+    pub update_time: Option<DateTime<Utc>>,
+    /// The user that is a member of the group.
+    pub user: UserPath,
+    /// The role the user has in the group.
+    pub role: RolePath,
+}
+
+/// A pending request of a user to join a group.
+///
+/// See [`GroupJoinRequest`](https://create.roblox.com/docs/cloud/reference/GroupJoinRequest) for details.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GroupJoinRequest {
+    /// The resource path of the join request. Format: `groups/{group_id}/join-requests/{user_id}`.
+    pub path: String,
+    #[serde(rename = "createTime", default)]
+    pub create_time: Option<DateTime<Utc>>,
+    /// The user that requested to join the group.
+    pub user: UserPath,
+}
+
+impl GroupMembership {
+    /// The ID of the membership, which is the last segment of its path.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    #[must_use]
+    pub fn user_id(&self) -> UserId {
+        self.user.user_id
+    }
+
+    #[must_use]
+    pub fn role_id(&self) -> RoleId {
+        self.role.role_id
+    }
+}
+
+impl GroupJoinRequest {
+    #[must_use]
+    pub fn user_id(&self) -> UserId {
+        self.user.user_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_group_role() {
+        let json = r#"{
+            "path": "groups/7/roles/99513316",
+            "id": "99513316",
+            "displayName": "Member",
+            "rank": 1,
+            "memberCount": 12
+        }"#;
+        let role = serde_json::from_str::<GroupRole>(json).unwrap();
+        assert_eq!(role.id, RoleId(99_513_316));
+        assert_eq!(role.path, RolePath::new(GroupId(7), RoleId(99_513_316)));
+    }
+}
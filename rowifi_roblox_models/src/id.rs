@@ -17,12 +17,13 @@ use std::error::Error as StdError;
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct AssetId(pub u64);
 
-#[derive(
-    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
-)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct GroupId(pub u64);
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct PlaceId(pub u64);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub struct RoleId(pub u64);
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
@@ -43,6 +44,12 @@ impl Display for GroupId {
     }
 }
 
+impl Display for PlaceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
 impl Display for RoleId {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         Display::fmt(&self.0, f)
@@ -61,6 +68,18 @@ impl Display for UniverseId {
     }
 }
 
+impl From<u64> for GroupId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<u64> for RoleId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
 impl From<u64> for UserId {
     fn from(value: u64) -> Self {
         Self(value)
@@ -114,6 +133,18 @@ where
     }
 }
 
+impl<'de> Deserialize<'de> for GroupId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(IdVisitor { _p: PhantomData })
+    }
+}
+
+impl<'de> Deserialize<'de> for RoleId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(IdVisitor { _p: PhantomData })
+    }
+}
+
 impl<'de> Deserialize<'de> for UserId {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         deserializer.deserialize_any(IdVisitor { _p: PhantomData })
@@ -126,6 +157,37 @@ impl<'de> Deserialize<'de> for UniverseId {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl ToSql for AssetId {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        #[allow(clippy::cast_possible_wrap)]
+        i64::to_sql(&(self.0 as i64), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> FromSql<'a> for AssetId {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        let id = i64::from_sql(ty, raw)?;
+        #[allow(clippy::cast_sign_loss)]
+        Ok(Self(id as u64))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as FromSql>::accepts(ty)
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl ToSql for UserId {
     fn to_sql(
@@ -187,3 +249,65 @@ impl<'a> FromSql<'a> for GroupId {
         <i64 as FromSql>::accepts(ty)
     }
 }
+
+#[cfg(feature = "postgres")]
+impl ToSql for RoleId {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        #[allow(clippy::cast_possible_wrap)]
+        i64::to_sql(&(self.0 as i64), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> FromSql<'a> for RoleId {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        let id = i64::from_sql(ty, raw)?;
+        #[allow(clippy::cast_sign_loss)]
+        Ok(Self(id as u64))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ToSql for UniverseId {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        #[allow(clippy::cast_possible_wrap)]
+        i64::to_sql(&(self.0 as i64), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> FromSql<'a> for UniverseId {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        let id = i64::from_sql(ty, raw)?;
+        #[allow(clippy::cast_sign_loss)]
+        Ok(Self(id as u64))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as FromSql>::accepts(ty)
+    }
+}
@@ -1,6 +1,12 @@
+pub mod api_key;
+pub mod asset;
 pub mod datastore;
 pub mod group;
 pub mod id;
 pub mod inventory;
+pub mod memory_store;
+pub mod resource;
+pub mod thumbnail;
 pub mod universe;
 pub mod user;
+pub mod user_restriction;
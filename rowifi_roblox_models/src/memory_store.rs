@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An item of a memory store sorted map. Items are sorted by their sort key, then by their id.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MemoryStoreSortedMapItem {
+    /// The resource path of the item.
+    ///
+    /// Format: `universes/{universe_id}/memory-store/sorted-maps/{map_id}/items/{item_id}`.
+    pub path: String,
+    /// The value of the item.
+    pub value: Value,
+    /// Changes whenever the item is modified.
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// The timestamp when the item expires.
+    #[serde(rename = "expireTime", default)]
+    pub expire_time: Option<DateTime<Utc>>,
+    /// The resource ID of the item.
+    pub id: String,
+    #[serde(rename = "stringSortKey", default)]
+    pub string_sort_key: Option<String>,
+    #[serde(rename = "numericSortKey", default)]
+    pub numeric_sort_key: Option<f64>,
+}
+
+/// The key used to sort an item of a sorted map. Items without a sort key come first, followed by
+/// those with a numeric key and then those with a string key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SortKey {
+    Numeric(f64),
+    String(String),
+}
+
+/// An item of a memory store queue.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MemoryStoreQueueItem {
+    /// The resource path of the item.
+    ///
+    /// Format: `universes/{universe_id}/memory-store/queues/{queue_id}/items/{item_id}`.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// The data of the item.
+    pub data: Value,
+    /// Items with a higher priority are read first.
+    #[serde(default)]
+    pub priority: Option<f64>,
+    /// The timestamp when the item expires.
+    #[serde(rename = "expireTime", default)]
+    pub expire_time: Option<DateTime<Utc>>,
+}
+
+/// The items read from a memory store queue. The items stay in the queue, invisible to other
+/// readers, until they are discarded with the `read_id` or the invisibility window ends.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MemoryStoreQueueRead {
+    #[serde(rename = "readId", default)]
+    pub read_id: Option<String>,
+    #[serde(default)]
+    pub items: Vec<MemoryStoreQueueItem>,
+}
+
+impl MemoryStoreSortedMapItem {
+    #[must_use]
+    pub fn sort_key(&self) -> Option<SortKey> {
+        match (&self.string_sort_key, self.numeric_sort_key) {
+            (Some(key), _) => Some(SortKey::String(key.clone())),
+            (None, Some(key)) => Some(SortKey::Numeric(key)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_sorted_map_item() {
+        let json = r#"{
+            "path": "universes/1/memory-store/sorted-maps/lobbies/items/abc",
+            "value": { "players": 4 },
+            "etag": "1",
+            "expireTime": "2024-01-01T00:00:00Z",
+            "id": "abc",
+            "numericSortKey": 4
+        }"#;
+        let item = serde_json::from_str::<MemoryStoreSortedMapItem>(json).unwrap();
+        assert_eq!(item.id, "abc");
+        assert_eq!(item.value["players"], 4);
+        assert_eq!(item.sort_key(), Some(SortKey::Numeric(4.0)));
+    }
+
+    #[test]
+    fn test_deserialize_queue_read() {
+        let json = r#"{
+            "readId": "read-1",
+            "items": [{ "data": "match-1", "priority": 2 }]
+        }"#;
+        let read = serde_json::from_str::<MemoryStoreQueueRead>(json).unwrap();
+        assert_eq!(read.read_id.as_deref(), Some("read-1"));
+        assert_eq!(read.items[0].data, "match-1");
+    }
+}
@@ -0,0 +1,328 @@
+//! The resource paths used by the Open Cloud v2 APIs to refer to other resources, such as
+//! `groups/123/roles/456`.
+//!
+//! Each path is (de)serialized from and to its string form, so a malformed path in a response is
+//! a deserialization error rather than something every caller has to handle.
+
+use serde::{
+    de::{Deserializer, Error as DeError},
+    Deserialize, Serialize, Serializer,
+};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+use crate::id::{GroupId, RoleId, UniverseId, UserId};
+
+/// Format: `groups/{group_id}`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GroupPath {
+    pub group_id: GroupId,
+}
+
+/// Format: `groups/{group_id}/roles/{role_id}`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RolePath {
+    pub group_id: GroupId,
+    pub role_id: RoleId,
+}
+
+/// Format: `users/{user_id}`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UserPath {
+    pub user_id: UserId,
+}
+
+/// Format: `universes/{universe_id}`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UniversePath {
+    pub universe_id: UniverseId,
+}
+
+/// Format: `universes/{universe_id}/data-stores/{datastore_id}`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DatastorePath {
+    pub universe_id: UniverseId,
+    pub datastore_id: String,
+}
+
+/// Format: `universes/{universe_id}/data-stores/{datastore_id}/entries/{entry_id}`, with
+/// `/scopes/{scope}` before `/entries` for entries outside the default scope.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DatastoreEntryPath {
+    pub universe_id: UniverseId,
+    pub datastore_id: String,
+    pub scope: Option<String>,
+    pub entry_id: String,
+}
+
+/// A string that is not a valid resource path of the expected kind.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResourcePathError {
+    pub path: String,
+    pub expected: &'static str,
+}
+
+impl GroupPath {
+    #[must_use]
+    pub fn new(group_id: GroupId) -> Self {
+        Self { group_id }
+    }
+}
+
+impl RolePath {
+    #[must_use]
+    pub fn new(group_id: GroupId, role_id: RoleId) -> Self {
+        Self { group_id, role_id }
+    }
+}
+
+impl UserPath {
+    #[must_use]
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id }
+    }
+}
+
+impl UniversePath {
+    #[must_use]
+    pub fn new(universe_id: UniverseId) -> Self {
+        Self { universe_id }
+    }
+}
+
+impl Display for GroupPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "groups/{}", self.group_id)
+    }
+}
+
+impl Display for RolePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "groups/{}/roles/{}", self.group_id, self.role_id)
+    }
+}
+
+impl Display for UserPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "users/{}", self.user_id)
+    }
+}
+
+impl Display for UniversePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "universes/{}", self.universe_id)
+    }
+}
+
+impl Display for DatastorePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "universes/{}/data-stores/{}",
+            self.universe_id, self.datastore_id
+        )
+    }
+}
+
+impl Display for DatastoreEntryPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "universes/{}/data-stores/{}",
+            self.universe_id, self.datastore_id
+        )?;
+        if let Some(scope) = &self.scope {
+            write!(f, "/scopes/{scope}")?;
+        }
+        write!(f, "/entries/{}", self.entry_id)
+    }
+}
+
+impl FromStr for GroupPath {
+    type Err = ResourcePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ResourcePathError::new(s, "group");
+        match segments(s).as_slice() {
+            ["groups", group_id] => Ok(Self::new(GroupId(id(group_id).ok_or_else(err)?))),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl FromStr for RolePath {
+    type Err = ResourcePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ResourcePathError::new(s, "role");
+        match segments(s).as_slice() {
+            ["groups", group_id, "roles", role_id] => Ok(Self::new(
+                GroupId(id(group_id).ok_or_else(err)?),
+                RoleId(id(role_id).ok_or_else(err)?),
+            )),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl FromStr for UserPath {
+    type Err = ResourcePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ResourcePathError::new(s, "user");
+        match segments(s).as_slice() {
+            ["users", user_id] => Ok(Self::new(UserId(id(user_id).ok_or_else(err)?))),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl FromStr for UniversePath {
+    type Err = ResourcePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ResourcePathError::new(s, "universe");
+        match segments(s).as_slice() {
+            ["universes", universe_id] => {
+                Ok(Self::new(UniverseId(id(universe_id).ok_or_else(err)?)))
+            }
+            _ => Err(err()),
+        }
+    }
+}
+
+impl FromStr for DatastorePath {
+    type Err = ResourcePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ResourcePathError::new(s, "data store");
+        match segments(s).as_slice() {
+            ["universes", universe_id, "data-stores", datastore_id] => Ok(Self {
+                universe_id: UniverseId(id(universe_id).ok_or_else(err)?),
+                datastore_id: (*datastore_id).to_string(),
+            }),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl FromStr for DatastoreEntryPath {
+    type Err = ResourcePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ResourcePathError::new(s, "data store entry");
+        let (universe_id, datastore_id, scope, entry_id) = match segments(s).as_slice() {
+            ["universes", universe_id, "data-stores", datastore_id, "entries", entry_id] => {
+                (*universe_id, *datastore_id, None, *entry_id)
+            }
+            ["universes", universe_id, "data-stores", datastore_id, "scopes", scope, "entries", entry_id] => {
+                (*universe_id, *datastore_id, Some(*scope), *entry_id)
+            }
+            _ => return Err(err()),
+        };
+        Ok(Self {
+            universe_id: UniverseId(id(universe_id).ok_or_else(err)?),
+            datastore_id: datastore_id.to_string(),
+            scope: scope.map(ToString::to_string),
+            entry_id: entry_id.to_string(),
+        })
+    }
+}
+
+/// Implements serde for a path through its [`Display`] and [`FromStr`] implementations.
+macro_rules! impl_serde {
+    ($($path:ty),*) => {
+        $(
+            impl Serialize for $path {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $path {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let path = String::deserialize(deserializer)?;
+                    path.parse().map_err(DeError::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_serde!(
+    GroupPath,
+    RolePath,
+    UserPath,
+    UniversePath,
+    DatastorePath,
+    DatastoreEntryPath
+);
+
+impl ResourcePathError {
+    fn new(path: &str, expected: &'static str) -> Self {
+        Self {
+            path: path.to_string(),
+            expected,
+        }
+    }
+}
+
+impl Display for ResourcePathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "`{}` is not a valid {} path", self.path, self.expected)
+    }
+}
+
+impl StdError for ResourcePathError {}
+
+/// Empty segments are kept so that paths like `groups//roles/1` are rejected.
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/').collect()
+}
+
+fn id(segment: &str) -> Option<u64> {
+    segment.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let role = "groups/123/roles/456".parse::<RolePath>().unwrap();
+        assert_eq!(role, RolePath::new(GroupId(123), RoleId(456)));
+        assert_eq!(role.to_string(), "groups/123/roles/456");
+
+        let entry = "universes/1/data-stores/coins/scopes/global/entries/user_1"
+            .parse::<DatastoreEntryPath>()
+            .unwrap();
+        assert_eq!(entry.scope.as_deref(), Some("global"));
+        assert_eq!(entry.entry_id, "user_1");
+        assert_eq!(
+            entry.to_string(),
+            "universes/1/data-stores/coins/scopes/global/entries/user_1"
+        );
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        assert!("groups/abc".parse::<GroupPath>().is_err());
+        assert!("users/1/extra".parse::<UserPath>().is_err());
+        assert!("groups//roles/1".parse::<RolePath>().is_err());
+        assert!("universes/1/data-stores".parse::<DatastorePath>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let users = serde_json::from_str::<Vec<UserPath>>(r#"["users/1", "users/2"]"#).unwrap();
+        assert_eq!(users[1].user_id, UserId(2));
+        assert_eq!(serde_json::to_string(&users[0]).unwrap(), r#""users/1""#);
+
+        let err = serde_json::from_str::<UserPath>(r#""groups/1""#).unwrap_err();
+        assert!(err.to_string().contains("not a valid user path"));
+    }
+}
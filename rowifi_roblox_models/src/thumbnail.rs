@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Thumbnail {
+    #[serde(rename = "targetId")]
+    pub target_id: u64,
+    /// Either `Completed`, `Pending`, `Blocked` or `Error`.
+    pub state: String,
+    /// Only present once the thumbnail has been generated.
+    #[serde(rename = "imageUrl", default)]
+    pub image_url: Option<String>,
+}
+
+/// The framing of an avatar thumbnail.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum AvatarThumbnailKind {
+    #[default]
+    Headshot,
+    Bust,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ThumbnailFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+/// How the thumbnails are rendered. The thumbnails API only accepts some sizes for each kind of
+/// thumbnail, `150` (150x150) being one that all of them accept.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ThumbnailOptions {
+    /// The width and height of the thumbnail.
+    pub size: u32,
+    pub format: ThumbnailFormat,
+    pub circular: bool,
+}
+
+impl Default for ThumbnailOptions {
+    fn default() -> Self {
+        Self {
+            size: 150,
+            format: ThumbnailFormat::Png,
+            circular: false,
+        }
+    }
+}
+
+impl Display for ThumbnailFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Png => f.write_str("Png"),
+            Self::Jpeg => f.write_str("Jpeg"),
+            Self::Webp => f.write_str("Webp"),
+        }
+    }
+}
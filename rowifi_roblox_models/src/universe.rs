@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use crate::resource::{GroupPath, UniversePath, UserPath};
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Universe {
-    pub path: String,
+    pub path: UniversePath,
     #[serde(rename = "displayName")]
     pub display_name: String,
     pub description: String,
-    pub user: Option<String>,
-    pub group: Option<String>,
+    /// The user that owns the universe, if it is not owned by a group.
+    pub user: Option<UserPath>,
+    /// The group that owns the universe, if it is not owned by a user.
+    pub group: Option<GroupPath>,
 }
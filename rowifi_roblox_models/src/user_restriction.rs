@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{id::UserId, resource::UserPath};
+
+/// The restrictions placed on a user in a universe or in one of its places.
+///
+/// See [`UserRestriction`](https://create.roblox.com/docs/cloud/reference/UserRestriction) for details.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UserRestriction {
+    /// The resource path of the restriction.
+    ///
+    /// Format: `universes/{universe_id}/user-restrictions/{user_id}` or
+    /// `universes/{universe_id}/places/{place_id}/user-restrictions/{user_id}`.
+    pub path: String,
+    #[serde(rename = "updateTime", default)]
+    pub update_time: Option<DateTime<Utc>>,
+    /// The user the restriction applies to.
+    pub user: UserPath,
+    #[serde(rename = "gameJoinRestriction")]
+    pub game_join_restriction: GameJoinRestriction,
+}
+
+/// Whether a user is banned from joining, and why.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct GameJoinRestriction {
+    pub active: bool,
+    /// The time the restriction was applied. Set by Roblox.
+    #[serde(rename = "startTime", default, skip_serializing)]
+    pub start_time: Option<DateTime<Utc>>,
+    /// How long the restriction lasts, written as a number of seconds followed by `s`. Missing for
+    /// permanent restrictions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+    /// The reason shown to the experience's developers.
+    #[serde(rename = "privateReason", default)]
+    pub private_reason: String,
+    /// The reason shown to the user.
+    #[serde(rename = "displayReason", default)]
+    pub display_reason: String,
+    #[serde(rename = "excludeAltAccounts", default)]
+    pub exclude_alt_accounts: bool,
+    /// Whether the restriction comes from the universe rather than the place. Set by Roblox.
+    #[serde(default, skip_serializing)]
+    pub inherited: bool,
+}
+
+impl UserRestriction {
+    #[must_use]
+    pub fn user_id(&self) -> UserId {
+        self.user.user_id
+    }
+}
+
+impl GameJoinRestriction {
+    /// The duration of the restriction in seconds. Returns [`None`] for permanent restrictions.
+    #[must_use]
+    pub fn duration_secs(&self) -> Option<u64> {
+        self.duration.as_deref()?.strip_suffix('s')?.parse().ok()
+    }
+}